@@ -0,0 +1,272 @@
+use {
+	crate::{
+		Difficulty,
+		Game,
+		GameOutcome,
+		Guess,
+		GuessRange,
+		DIFFICULTY_RANGE,
+	},
+	rand::{
+		rngs::StdRng,
+		Rng,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		ops::RangeInclusive,
+	},
+};
+
+/// How many attempts a win must have to spare for AdaptivePolicy to widen the
+/// range.
+pub const SPARE_TO_WIDEN: u32 = 3;
+
+/// Adapts the range of each round of a Session to how the player is doing. See
+/// Session::adaptive.
+///
+/// A win with at least SPARE_TO_WIDEN attempts to spare doubles the span of the
+/// next round's range, and a loss or a timeout halves it, while any other win
+/// leaves it as it is. The span always stays from min_span through max_span,
+/// and the range keeps its lowest value, so 1..=1000 is widened to 1..=2000,
+/// unless that goes past RANGE. The attempts are left to the game.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		AdaptivePolicy,
+/// // 		Difficulty,
+/// // 		GameOutcome,
+/// // 	},
+/// // 	rand::{
+/// // 		rngs::StdRng,
+/// // 		SeedableRng,
+/// // 	},
+/// //};
+/// // let mut policy = AdaptivePolicy::for_difficulty(Difficulty::Normal, StdRng::from_entropy());
+/// // policy.record(&GameOutcome::Won { attempts_used: 4 }, Some(6));
+/// // assert_eq!(policy.range().size(), 2000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptivePolicy<const RANGE: RangeInclusive<i32>>
+{
+	range: GuessRange<RANGE>,
+	min_span: u64,
+	max_span: u64,
+	rounds: usize,
+	rng: StdRng,
+}
+
+impl<const RANGE: RangeInclusive<i32>> AdaptivePolicy<RANGE>
+{
+	/// Creates a policy whose first round is played in range, that keeps the span
+	/// from min_span through max_span and picks the secrets with rng. A min_span
+	/// of 0 is taken as 1, and a max_span below min_span as min_span.
+	pub fn new(
+		range: GuessRange<RANGE>,
+		min_span: u64,
+		max_span: u64,
+		rng: StdRng,
+	) -> Self
+	{
+		let min_span = min_span.max(1_u64);
+		AdaptivePolicy { range, min_span, max_span: max_span.max(min_span), rounds: 0, rng }
+	}
+
+	/// Returns the range the next round is played in.
+	pub const fn range(&self) -> GuessRange<RANGE>
+	{
+		self.range
+	}
+
+	/// Moves game to the range of the next round with a new secret from it. A
+	/// game that was already started, such as one that is resumed, keeps its
+	/// range and secret.
+	pub fn apply(
+		&mut self,
+		game: &mut Game<RANGE>,
+	)
+	{
+		if game.attempts_used() > 0 || game.is_finished()
+		{
+			return;
+		}
+		let secret = Guess { value: self.rng.gen_range(self.range.lo().value()..=self.range.hi().value()) };
+		game.rerange(self.range, secret);
+	}
+
+	/// Adapts the range to a round that ended with outcome and attempts_left,
+	/// which is None in practice, and returns the Adjustment if the range
+	/// changed. A practice win never widens the range.
+	pub fn record(
+		&mut self,
+		outcome: &GameOutcome<RANGE>,
+		attempts_left: Option<u32>,
+	) -> Option<Adjustment<RANGE>>
+	{
+		self.rounds += 1;
+		let span = match outcome
+		{
+			GameOutcome::Won { .. } if attempts_left.is_some_and(|left| left >= SPARE_TO_WIDEN) => self.range.size().saturating_mul(2_u64),
+			GameOutcome::Lost { .. } | GameOutcome::TimedOut { .. } => self.range.size() / 2_u64,
+			GameOutcome::Won { .. } | GameOutcome::Aborted => return None,
+		};
+		let from = self.range;
+		self.range = self.resized(span);
+		(self.range != from).then_some(Adjustment { after_round: self.rounds, from, to: self.range })
+	}
+
+	/// Returns the range from the same lowest value with span values, or fewer
+	/// if that goes past RANGE, after clamping span to the configured spans.
+	fn resized(
+		&self,
+		span: u64,
+	) -> GuessRange<RANGE>
+	{
+		let span = span.clamp(self.min_span, self.max_span);
+		let lo = self.range.lo().value();
+		let hi = i64::from(lo).saturating_add(i64::try_from(span - 1_u64).unwrap_or(i64::MAX)).min(i64::from(Guess::<RANGE>::max_value()));
+		GuessRange { lo: self.range.lo(), hi: Guess { value: hi as i32 } }
+	}
+}
+
+impl AdaptivePolicy<DIFFICULTY_RANGE>
+{
+	/// Creates a policy that starts with the range of difficulty and keeps the
+	/// span from that of Difficulty::Easy through that of Difficulty::Insane,
+	/// picking the secrets with rng.
+	pub fn for_difficulty(
+		difficulty: Difficulty,
+		rng: StdRng,
+	) -> Self
+	{
+		let range = difficulty.range();
+		let range = GuessRange { lo: Guess { value: *range.start() }, hi: Guess { value: *range.end() } };
+		AdaptivePolicy::new(range, Difficulty::Easy.span(), Difficulty::Insane.span(), rng)
+	}
+}
+
+/// A change AdaptivePolicy made to the range after a round.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Adjustment<const RANGE: RangeInclusive<i32>>
+{
+	/// Which round the change was made after, starting from 1.
+	pub after_round: usize,
+	/// The range before the change.
+	pub from: GuessRange<RANGE>,
+	/// The range after the change.
+	pub to: GuessRange<RANGE>,
+}
+
+/// Formats Adjustment as a sentence, as in "After round 1 the range widened
+/// from 1..=1000 to 1..=2000."
+impl<const RANGE: RangeInclusive<i32>> Display for Adjustment<RANGE>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> std::fmt::Result
+	{
+		let change = if self.to.size() > self.from.size() { "widened" } else { "narrowed" };
+		write!(f, "After round {} the range {change} from {}..={} to {}..={}.", self.after_round, self.from.lo(), self.from.hi(), self.to.lo(), self.to.hi())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		rand::SeedableRng,
+	};
+
+	fn won(attempts_used: u32) -> GameOutcome<DIFFICULTY_RANGE>
+	{
+		GameOutcome::Won { attempts_used }
+	}
+
+	fn lost() -> GameOutcome<DIFFICULTY_RANGE>
+	{
+		GameOutcome::Lost { answer: Guess::new(1).expect("answer failed to construct.") }
+	}
+
+	#[test]
+	fn progression()
+	{
+		let range = GuessRange { lo: Guess { value: 1 }, hi: Guess { value: 1000 } };
+		let mut policy = AdaptivePolicy::new(range, 100, 8000, StdRng::seed_from_u64(1));
+		let timed_out = GameOutcome::TimedOut { answer: Guess::new(1).expect("answer failed to construct.") };
+		let script = [
+			(won(5), Some(5)),
+			(won(7), Some(3)),
+			// Two attempts to spare are not enough.
+			(won(8), Some(2)),
+			(won(1), None),
+			(won(2), Some(8)),
+			// The widest span is reached.
+			(won(1), Some(9)),
+			(lost(), Some(0)),
+			(timed_out, Some(4)),
+			(lost(), Some(0)),
+			(lost(), Some(0)),
+			(lost(), Some(0)),
+			(lost(), Some(0)),
+			// The narrowest span is reached, from 125 rather than down to 62.
+			(lost(), Some(0)),
+			(lost(), Some(0)),
+		];
+		let mut highs = Vec::new();
+		let mut adjusted = Vec::new();
+		for (round, (outcome, attempts_left)) in (1..).zip(script)
+		{
+			if policy.record(&outcome, attempts_left).is_some()
+			{
+				adjusted.push(round);
+			}
+			highs.push(policy.range().hi().value());
+			assert_eq!(policy.range().lo().value(), 1);
+		}
+		assert_eq!(highs, [2000, 4000, 4000, 4000, 8000, 8000, 4000, 2000, 1000, 500, 250, 125, 100, 100]);
+		assert_eq!(adjusted, [1, 2, 5, 7, 8, 9, 10, 11, 12, 13]);
+
+		// The range can not go past RANGE either.
+		let mut policy = AdaptivePolicy::<{ 0..=1024 }>::new(GuessRange::full(), 0, u64::MAX, StdRng::seed_from_u64(1));
+		assert_eq!(policy.record(&GameOutcome::Won { attempts_used: 1 }, Some(10)), None);
+		assert_eq!(policy.range(), GuessRange::full());
+	}
+
+	#[test]
+	fn adjustment()
+	{
+		let mut policy = AdaptivePolicy::for_difficulty(Difficulty::Normal, StdRng::seed_from_u64(1));
+		let widened = policy.record(&won(4), Some(6)).expect("the range was not widened.");
+		assert_eq!(widened.after_round, 1);
+		assert_eq!(widened.to_string(), "After round 1 the range widened from 1..=1000 to 1..=2000.");
+		let narrowed = policy.record(&lost(), Some(0)).expect("the range was not narrowed.");
+		assert_eq!(narrowed.to_string(), "After round 2 the range narrowed from 1..=2000 to 1..=1000.");
+	}
+
+	#[test]
+	fn apply()
+	{
+		let mut policy = AdaptivePolicy::for_difficulty(Difficulty::Easy, StdRng::seed_from_u64(1));
+		policy.record(&won(2), Some(6));
+		for _ in 0..100
+		{
+			let mut game = Game::with_secret(8, Guess::new(100000).expect("secret failed to construct."));
+			policy.apply(&mut game);
+			assert_eq!((game.range().lo().value(), game.range().hi().value()), (1, 100));
+			assert!(game.range().contains(game.secret().reveal()));
+		}
+
+		// A game that was started keeps its range.
+		let mut game = Game::with_secret(8, Guess::new(100000).expect("secret failed to construct."));
+		game.submit(Guess::new(5).expect("guess failed to construct.")).expect("submit failed.");
+		policy.apply(&mut game);
+		assert_eq!(game.range(), GuessRange::full());
+	}
+}
@@ -0,0 +1,184 @@
+use {
+	crate::{
+		BinarySearchStrategy,
+		Game,
+		Guess,
+		GuessRange,
+		HistoryEntry,
+		KnowledgeState,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		ops::RangeInclusive,
+		result::Result,
+	},
+};
+
+/// How the guesses of a game compare with optimal play. See analyze.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct Analysis
+{
+	/// How many valid guesses the player made.
+	pub guesses_used: u32,
+	/// The most guesses a binary search needs for the range, which is the best
+	/// any way of guessing can promise.
+	pub optimal: u32,
+	/// How many guesses a binary search would have needed after the player's
+	/// first guess, counting that one, or None if there were no guesses.
+	pub from_first_guess: Option<u32>,
+	/// How far each guess was from the middle of the values that were still
+	/// possible when it was made, oldest first. A guess off the middle rules out
+	/// less than it could have, so this is the information it wasted.
+	pub deviations: Vec<u32>,
+}
+
+/// Compares the guesses in history with a binary search for secret within
+/// range.
+///
+/// The history is replayed to find out what was still possible before each
+/// guess, and a BinarySearchStrategy is played from the first guess on to see
+/// how quickly it would have finished.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		analyze,
+/// // 		Game,
+/// // 		Guess,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let mut game = Game::<{ 0..=1024 }>::with_secret(10, Guess::new(300).expect(""));
+/// // game.play(&mut stdin().lock(), &mut stdout());
+/// // let analysis = analyze(game.range(), game.secret().reveal(), game.history());
+/// // println!("{analysis}");
+/// ```
+pub fn analyze<const RANGE: RangeInclusive<i32>>(
+	range: GuessRange<RANGE>,
+	secret: Guess<RANGE>,
+	history: &[HistoryEntry<RANGE>],
+) -> Analysis
+{
+	let mut knowledge = KnowledgeState::new(range);
+	let deviations = history
+		.iter()
+		.map(|entry| {
+			// Feedback that contradicts itself leaves the whole range possible.
+			let deviation = entry.guess.distance(knowledge.range().unwrap_or(range).midpoint());
+			knowledge.update(entry.guess, entry.feedback);
+			deviation
+		})
+		.collect();
+	let from_first_guess = history.first().map(|first| {
+		let mut replay = Game::with_secret(1, secret);
+		replay.set_range(range);
+		replay.set_attempts(None);
+		replay.submit(first.guess).expect("a new game is not finished.");
+		replay.play_with_strategy(&mut BinarySearchStrategy::new(range));
+		replay.attempts_used()
+	});
+	Analysis { guesses_used: history.len() as u32, optimal: range.size().ilog2() + 1_u32, from_first_guess, deviations }
+}
+
+/// Formats Analysis as lines that can be shown to the player, as in:
+///
+/// ```text
+/// You used 3 guesses; optimal play needs at most 11 for this range, and a perfect binary search from your first guess would have finished in 10.
+/// Distance from the middle of what was left: 0 155 6
+/// ```
+impl Display for Analysis
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		let guesses = if self.guesses_used == 1 { "guess" } else { "guesses" };
+		write!(f, "You used {} {guesses}; optimal play needs at most {} for this range", self.guesses_used, self.optimal)?;
+		let Some(from_first_guess) = self.from_first_guess
+		else
+		{
+			return writeln!(f, ".");
+		};
+		writeln!(f, ", and a perfect binary search from your first guess would have finished in {from_first_guess}.")?;
+		let deviations: Vec<_> = self.deviations.iter().map(ToString::to_string).collect();
+		writeln!(f, "Distance from the middle of what was left: {}", deviations.join(" "))
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Returns how the guesses so far compare with optimal play, as with
+	/// analyze. This uses the secret, so it is meant for after the game.
+	pub fn analysis(&self) -> Analysis
+	{
+		analyze(self.range, self.secret().reveal(), &self.history)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::Feedback,
+		std::time::Duration,
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Creates a history of guesses, with feedback about secret.
+	fn history(
+		secret: i32,
+		guesses: &[i32],
+	) -> Vec<HistoryEntry<{ 0..=1024 }>>
+	{
+		(1_u32..)
+			.zip(guesses)
+			.map(|(attempt, &value)| HistoryEntry { attempt, guess: guess(value), feedback: Feedback::from(value.cmp(&secret)), elapsed: Duration::ZERO })
+			.collect()
+	}
+
+	#[test]
+	fn lucky()
+	{
+		let analysis = analyze(GuessRange::full(), guess(300), &history(300, &[512, 100, 300]));
+		assert_eq!(analysis, Analysis { guesses_used: 3, optimal: 11, from_first_guess: Some(10), deviations: vec![0, 155, 6] });
+		assert_eq!(
+			analysis.to_string(),
+			"You used 3 guesses; optimal play needs at most 11 for this range, and a perfect binary search from your first guess would have finished in \
+			 10.\nDistance from the middle of what was left: 0 155 6\n"
+		);
+	}
+
+	#[test]
+	fn perfect()
+	{
+		// A binary search deviates from the middle by nothing at all.
+		let range = GuessRange::new(guess(1), guess(100)).expect("range failed to construct.");
+		let mut game = Game::with_secret(10, guess(1));
+		game.set_range(range);
+		game.play_with_strategy(&mut BinarySearchStrategy::new(range));
+		let analysis = game.analysis();
+		assert!(analysis.deviations.iter().all(|&deviation| deviation == 0), "{analysis:?}");
+		assert_eq!(analysis.from_first_guess, Some(analysis.guesses_used));
+		assert!(analysis.guesses_used <= analysis.optimal);
+		assert_eq!(analysis.optimal, 7);
+
+		let analysis = analyze(GuessRange::full(), guess(512), &history(512, &[512]));
+		assert_eq!((analysis.from_first_guess, analysis.deviations), (Some(1), vec![0]));
+		let analysis = analyze(GuessRange::full(), guess(512), &[]);
+		assert_eq!(analysis.to_string(), "You used 0 guesses; optimal play needs at most 11 for this range.\n");
+	}
+}
@@ -0,0 +1,247 @@
+use {
+	crate::{
+		check_line,
+		Feedback,
+		is_blank,
+		parse_lenient,
+		respond_to,
+		take_line_chunk,
+		Guess,
+		InputContext,
+		InputError,
+		InputOptions,
+		Prompt,
+		ResponseTheme,
+		Style,
+		Win,
+	},
+	std::{
+		io::ErrorKind,
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+	},
+	tokio::io::{
+		self,
+		AsyncBufRead,
+		AsyncBufReadExt,
+		AsyncWrite,
+		AsyncWriteExt,
+	},
+};
+
+/// Gets user input until it is valid and returns it as a Guess, without
+/// blocking the async runtime. This is the same as input, except that it reads
+/// and writes asynchronously.
+///
+/// # Cancel safety
+/// Dropping the future between lines loses nothing, since a line is only
+/// consumed from input once it is being read. Dropping it in the middle of a
+/// line discards the part of that line that was already read, so the next call
+/// starts with the rest of it, which is then rejected or accepted on its own.
+/// A prompt or message that was being written may be cut short. Neither input
+/// nor output is left in an unusable state.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_async,
+/// // 	tokio::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 		BufReader,
+/// // 	},
+/// //};
+/// // let input = input_async::<{ 0..=100000 }>(format!("Guess a number: ").yellow(), &mut BufReader::new(stdin()), &mut stdout()).await;
+/// ```
+pub async fn input_async<const RANGE: RangeInclusive<i32>>(
+	mut prompt: impl Prompt,
+	input: &mut (impl AsyncBufRead + Unpin),
+	output: &mut (impl AsyncWrite + Unpin),
+) -> Result<Guess<RANGE>, InputError>
+{
+	let options = InputOptions::default();
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	let parse = |text: &str| parse_lenient(text, min, max).map(|value| Guess { value });
+	let mut invalid = 0_usize;
+	// Avoids counting invalid guesses as used attempts.
+	loop
+	{
+		// Messages are rendered by the same code as input, then written at once.
+		let mut message = Vec::new();
+		options.write_prompt(&prompt.prompt(InputContext { invalid_tries: invalid }), &mut message)?;
+		output.write_all(&message).await?;
+		output.flush().await?;
+
+		let mut guess_input = Vec::new();
+		let read = read_line_limited(input, &mut guess_input).await?;
+		// Stop looping when there is nothing left to read.
+		if read == 0
+		{
+			return Err(InputError::Eof);
+		}
+		// Just ask again if nothing was entered.
+		if options.repeat_on_blank && is_blank(&guess_input, read)
+		{
+			continue;
+		}
+		let mut message = Vec::new();
+		let result = check_line(&options, min, max, &guess_input, read, &parse, &mut message)?;
+		output.write_all(&message).await?;
+		// Stop looping if everything checks out.
+		if let Ok(guess) = result
+		{
+			return Ok(guess);
+		}
+		invalid += 1;
+	}
+}
+
+/// Respond to a user's input with some console output, without blocking the
+/// async runtime. This is the same as respond, except that it writes
+/// asynchronously.
+///
+/// # Cancel safety
+/// Dropping the future may leave the response partially written.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_async,
+/// // 		Guess,
+/// // 	},
+/// // 	tokio::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // assert!(respond_async(example_guess, correct_guess, &mut stdout()).await.expect("").is_continue());
+/// ```
+pub async fn respond_async<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	output: &mut (impl AsyncWrite + Unpin),
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	let mut message = Vec::new();
+	let action = respond_to(guess, guess.cmp(&correct), &ResponseTheme::default(), Style::Fancy, &mut message)?;
+	output.write_all(&message).await?;
+	Ok(action)
+}
+
+/// Asynchronous counterpart of read_line_limited. Reads one line into line,
+/// keeping at most MAX_LINE_BYTES bytes of it, and returns how many bytes were
+/// read in total.
+async fn read_line_limited(
+	input: &mut (impl AsyncBufRead + Unpin),
+	line: &mut Vec<u8>,
+) -> io::Result<usize>
+{
+	let mut read = 0_usize;
+	loop
+	{
+		let available = match input.fill_buf().await
+		{
+			Ok(available) => available,
+			Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+			Err(error) => return Err(error),
+		};
+		let (len, done) = take_line_chunk(available, line);
+		input.consume(len);
+		read += len;
+		if done
+		{
+			return Ok(read);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		colored::Colorize,
+		tokio::io::{
+			duplex,
+			split,
+			AsyncReadExt,
+			BufReader,
+		},
+	};
+
+	#[tokio::test]
+	async fn input_async_test()
+	{
+		let (mut player, game) = duplex(4096);
+		let (game_input, mut game_output) = split(game);
+		let mut game_input = BufReader::new(game_input);
+
+		player.write_all(b"abc\n51\n42\n50\n").await.expect("write failed.");
+		let guess = input_async::<{ 0..=50 }>("dummy prompt: ".clear(), &mut game_input, &mut game_output).await.expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+
+		// The next line is left for the next call.
+		let guess = input_async::<{ 0..=50 }>("dummy prompt: ".clear(), &mut game_input, &mut game_output).await.expect("input failed.");
+		assert_eq!(guess.value(), 50_i32);
+
+		// Ends with an error once the player stops sending input.
+		player.shutdown().await.expect("shutdown failed.");
+		let result = input_async::<{ 0..=50 }>("dummy prompt: ".clear(), &mut game_input, &mut game_output).await;
+		assert!(matches!(result, Err(InputError::Eof)));
+
+		drop((game_input, game_output));
+		let mut output = String::new();
+		player.read_to_string(&mut output).await.expect("read failed.");
+		assert_eq!(output.matches("dummy prompt: ").count(), 5);
+		assert!(output.contains("\"abc\" is not an integer from 0 through 50."));
+		assert!(output.contains("51 is above the maximum."));
+	}
+
+	/// Both versions must give the same output for the same input.
+	#[tokio::test]
+	async fn input_async_matches_input()
+	{
+		let lines = "abc\n99999999999999\n-3\n7\n";
+
+		let mut sync_output = Vec::new();
+		let sync_guess: Guess<{ 0..=10 }> =
+			crate::input("dummy prompt: ".clear(), &mut lines.as_bytes(), &mut sync_output).expect("input failed.");
+
+		let mut async_output = Vec::new();
+		let async_guess: Guess<{ 0..=10 }> =
+			input_async("dummy prompt: ".clear(), &mut lines.as_bytes(), &mut async_output).await.expect("input failed.");
+
+		assert_eq!(sync_guess, async_guess);
+		assert_eq!(sync_output, async_output);
+	}
+
+	#[tokio::test]
+	async fn respond_async_test()
+	{
+		let (mut player, mut game) = duplex(4096);
+		let guess = Guess::<{ 0..=50 }>::new(40).expect("guess 1 failed to construct.");
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("guess 2 failed to construct.");
+		assert_eq!(respond_async(guess, correct, &mut game).await.expect("respond failed."), ControlFlow::Break(Win { guess }));
+
+		let guess = Guess::<{ 0..=50 }>::new(20).expect("guess 3 failed to construct.");
+		assert_eq!(respond_async(guess, correct, &mut game).await.expect("respond failed."), ControlFlow::Continue(Feedback::TooLow));
+
+		drop(game);
+		let mut output = String::new();
+		player.read_to_string(&mut output).await.expect("read failed.");
+		assert!(output.contains("You win!"));
+		assert!(output.contains("too low!"));
+	}
+}
@@ -0,0 +1,360 @@
+use {
+	crate::{
+		parse,
+		parse_lenient,
+		read_guess,
+		respond_to,
+		validate,
+		Feedback,
+		GuessError,
+		InputError,
+		InputOptions,
+		InputOutput,
+		Integer,
+		ParseGuessError,
+		Prompt,
+		ResponseTheme,
+		Style,
+		Win,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			BufRead,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+	},
+};
+
+/// Gets user input until it is valid and returns it as a BoundedGuess. This is
+/// the same as input, except that the range is a value rather than a const
+/// parameter, so it can come from the command line or a configuration file.
+///
+/// # Errors
+/// Returns InputError::Eof if the input ends before a valid guess is read, and
+/// InputError::Io if reading the input or writing to output fails.
+///
+/// # Panics
+/// Panics if range is empty, since no input could ever be valid.
+///
+/// # Examples
+///
+/// Prompting the user for a guess in a range chosen at runtime.
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_runtime,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let input = input_runtime(format!("Guess a number: ").yellow(), 0_u64..=100000_u64, &mut stdin().lock(), &mut stdout());
+/// ```
+pub fn input_runtime<T: Integer>(
+	prompt: impl Prompt,
+	range: RangeInclusive<T>,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<BoundedGuess<T>, InputError<T>>
+{
+	assert!(!range.is_empty(), "input_runtime requires a range that contains at least one value.");
+	let (min, max) = range.into_inner();
+	read_guess(prompt, &InputOptions::default(), min, max, input, output, |text| parse_lenient(text, min, max).map(|value| BoundedGuess { value, min, max })).map(|result| result.guess)
+}
+
+/// Respond to a user's input with some console output. This is the same as
+/// respond, except for guesses whose range is only known at runtime.
+///
+/// Only the values of the guesses are compared, so the guess and the correct
+/// value are expected to share a range.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_runtime,
+/// // 		BoundedGuess,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = BoundedGuess::new(42069_i32, 0..=100000).expect("");
+/// // let correct_guess = BoundedGuess::new(1660_i32, 0..=100000).expect("");
+/// // assert!(respond_runtime(example_guess, correct_guess, &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_runtime<T: Integer>(
+	guess: BoundedGuess<T>,
+	correct: BoundedGuess<T>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<BoundedGuess<T>>, Feedback>>
+{
+	respond_to(guess, guess.value.cmp(&correct.value), &ResponseTheme::default(), Style::Fancy, output)
+}
+
+/// A guess whose range is chosen at runtime.
+///
+/// This is the runtime counterpart of Guess, and it is validated the same way.
+/// It can hold any primitive integer type, not only i32. This struct must be
+/// created using BoundedGuess::new() or BoundedGuess::parse().
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct BoundedGuess<T = i32>
+{
+	value: T,
+	min: T,
+	max: T,
+}
+
+/// Constructors and accessors for BoundedGuess.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::BoundedGuess;
+/// // let example_guess = BoundedGuess::new(42_u8, 0..=100).expect("");
+/// // assert_eq!(example_guess.value(), 42_u8);
+/// ```
+impl<T: Integer> BoundedGuess<T>
+{
+	/// Creates a BoundedGuess if value lies within range, or returns an error
+	/// describing which side of the range it fell on.
+	pub fn new(
+		value: T,
+		range: RangeInclusive<T>,
+	) -> Result<Self, GuessError<T>>
+	{
+		let (min, max) = range.into_inner();
+		validate(value, min, max).map(|value| BoundedGuess { value, min, max })
+	}
+
+	/// Trims and parses text into a BoundedGuess within range.
+	pub fn parse(
+		text: &str,
+		range: RangeInclusive<T>,
+	) -> Result<Self, ParseGuessError<T>>
+	{
+		let (min, max) = range.into_inner();
+		parse(text, min, max).map(|value| BoundedGuess { value, min, max })
+	}
+
+	/// Trims and parses text into a BoundedGuess within range, accepting digits
+	/// grouped with underscores or commas the same way as Guess::parse_lenient().
+	pub fn parse_lenient(
+		text: &str,
+		range: RangeInclusive<T>,
+	) -> Result<Self, ParseGuessError<T>>
+	{
+		let (min, max) = range.into_inner();
+		parse_lenient(text, min, max).map(|value| BoundedGuess { value, min, max })
+	}
+
+	/// Returns the validated value of the guess.
+	pub fn value(&self) -> T
+	{
+		self.value
+	}
+
+	/// Returns the smallest value a guess in this range can have.
+	pub fn min_value(&self) -> T
+	{
+		self.min
+	}
+
+	/// Returns the largest value a guess in this range can have.
+	pub fn max_value(&self) -> T
+	{
+		self.max
+	}
+
+	/// Returns the range the guess was validated against.
+	pub fn range(&self) -> RangeInclusive<T>
+	{
+		self.min..=self.max
+	}
+}
+
+/// Formats BoundedGuess for displaying in console output.
+impl<T: Display> Display for BoundedGuess<T>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		self.value.fmt(f)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			input,
+			respond,
+			Guess,
+		},
+		colored::Colorize,
+		std::io::stdout,
+	};
+
+	#[test]
+	fn construction()
+	{
+		BoundedGuess::new(0_u8, 0..=0).expect("guess 1 failed to construct.");
+		BoundedGuess::new(255_u8, 0..=u8::MAX).expect("guess 2 failed to construct.");
+		BoundedGuess::new(1_u8, 0..=0).expect_err("guess 3 failed to fail to construct.");
+
+		BoundedGuess::new(-5_i32, -10..=10).expect("guess 4 failed to construct.");
+		BoundedGuess::new(11_i32, -10..=10).expect_err("guess 5 failed to fail to construct.");
+
+		BoundedGuess::new(i64::MAX, 0..=i64::MAX).expect("guess 6 failed to construct.");
+		BoundedGuess::new(i64::MIN, 0..=i64::MAX).expect_err("guess 7 failed to fail to construct.");
+
+		BoundedGuess::new(u64::MAX - 1, 1 << 63..=u64::MAX).expect("guess 8 failed to construct.");
+	}
+
+	#[test]
+	fn construction_error()
+	{
+		assert_eq!(BoundedGuess::new(11_u8, 0..=10), Err(GuessError::AboveRange { value: 11_u8, max: 10_u8 }));
+		assert_eq!(BoundedGuess::new(-11_i64, -10..=10), Err(GuessError::BelowRange { value: -11_i64, min: -10_i64 }));
+	}
+
+	#[test]
+	fn parse()
+	{
+		assert_eq!(BoundedGuess::parse(" 200\n", 0_u8..=255).map(|guess| guess.value()), Ok(200_u8));
+		assert_eq!(BoundedGuess::parse("256", 0_u8..=255), Err(ParseGuessError::Overflow));
+		assert_eq!(BoundedGuess::parse("-1", 0_u8..=255), Err(ParseGuessError::Negative { min: 0_u8 }));
+		assert_eq!(BoundedGuess::parse("-300", 5_u8..=10), Err(ParseGuessError::Negative { min: 5_u8 }));
+		assert_eq!(BoundedGuess::parse("-0", 0_u8..=255).map(|guess| guess.value()), Ok(0_u8));
+		assert_eq!(BoundedGuess::parse("-", 0_u8..=255), Err(ParseGuessError::NotANumber));
+		assert_eq!(BoundedGuess::parse("-1x", 0_u8..=255), Err(ParseGuessError::NotANumber));
+		assert_eq!(BoundedGuess::parse("-1", 5_u8..=10).map_err(|error| error.to_string()), Err(String::from("Negative numbers are too low to even be a guess. Guesses must be at least 5.")));
+		assert_eq!(BoundedGuess::parse("abc", 0_i64..=10), Err(ParseGuessError::NotANumber));
+		assert_eq!(
+			BoundedGuess::parse("3000000000", 0_i64..=10),
+			Err(ParseGuessError::OutOfRange(GuessError::AboveRange { value: 3000000000_i64, max: 10_i64 }))
+		);
+	}
+
+	#[test]
+	fn parse_lenient()
+	{
+		assert_eq!(BoundedGuess::parse_lenient("65_535", 0_u16..=u16::MAX).map(|guess| guess.value()), Ok(65535_u16));
+		assert_eq!(BoundedGuess::parse_lenient("65,536", 0_u16..=u16::MAX), Err(ParseGuessError::Overflow));
+		assert_eq!(BoundedGuess::parse_lenient("1,,0", 0_u16..=u16::MAX), Err(ParseGuessError::NotANumber));
+		assert_eq!(BoundedGuess::parse_lenient("-1,000", 0_u16..=u16::MAX), Err(ParseGuessError::Negative { min: 0_u16 }));
+		assert_eq!(BoundedGuess::parse_lenient("-0x1f", 0_u16..=u16::MAX), Err(ParseGuessError::Negative { min: 0_u16 }));
+	}
+
+	#[test]
+	fn accessors()
+	{
+		let guess = BoundedGuess::new(5_i32, -10..=10).expect("guess 1 failed to construct.");
+		assert_eq!(guess.value(), 5_i32);
+		assert_eq!(guess.min_value(), -10_i32);
+		assert_eq!(guess.max_value(), 10_i32);
+		assert_eq!(guess.range(), -10..=10);
+		assert_eq!(guess.to_string(), "5");
+	}
+
+	#[test]
+	fn ordering()
+	{
+		let low = BoundedGuess::new(3_u8, 0..=10).expect("guess 1 failed to construct.");
+		let high = BoundedGuess::new(7_u8, 0..=10).expect("guess 2 failed to construct.");
+		assert!(low < high);
+		assert_eq!(low, BoundedGuess::new(3_u8, 0..=10).expect("guess 3 failed to construct."));
+	}
+
+	#[test]
+	fn respond_runtime_test()
+	{
+		let guess = BoundedGuess::new(40_u8, 0..=50).expect("guess 1 failed to construct.");
+		let correct = BoundedGuess::new(40_u8, 0..=50).expect("guess 2 failed to construct.");
+		assert_eq!(respond_runtime(guess, correct, &mut stdout()).expect("respond failed."), ControlFlow::Break(Win { guess }));
+
+		let guess = BoundedGuess::new(20_i64, 0..=50).expect("guess 3 failed to construct.");
+		let correct = BoundedGuess::new(40_i64, 0..=50).expect("guess 4 failed to construct.");
+		assert_eq!(respond_runtime(guess, correct, &mut stdout()).expect("respond failed."), ControlFlow::Continue(Feedback::TooLow));
+	}
+
+	#[test]
+	fn input_runtime_test()
+	{
+		let guess = input_runtime("dummy prompt: ".clear(), 0_u8..=50, &mut "50".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess, BoundedGuess::new(50_u8, 0..=50).expect("guess 1 failed to construct."));
+
+		let guess = input_runtime("dummy prompt: ".clear(), -50_i64..=50, &mut "-40".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), -40_i64);
+	}
+
+	#[test]
+	#[should_panic(expected = "at least one value")]
+	fn input_runtime_empty_range()
+	{
+		#[expect(clippy::reversed_empty_ranges)]
+		let _ = input_runtime("dummy prompt: ".clear(), 10_i32..=0, &mut "5".as_bytes(), &mut stdout());
+	}
+
+	/// Reads a guess within range from lines and answers it against secret.
+	fn round<T: Integer>(
+		range: RangeInclusive<T>,
+		secret: T,
+		lines: &str,
+	) -> (T, Feedback)
+	{
+		let guess = input_runtime("dummy prompt: ".clear(), range.clone(), &mut lines.as_bytes(), &mut Vec::new()).expect("input failed.");
+		let correct = BoundedGuess::new(secret, range).expect("secret failed to construct.");
+		match respond_runtime(guess, correct, &mut Vec::new()).expect("respond failed.")
+		{
+			ControlFlow::Break(_) => (guess.value(), Feedback::Correct),
+			ControlFlow::Continue(feedback) => (guess.value(), feedback),
+		}
+	}
+
+	#[test]
+	fn integer_types()
+	{
+		assert_eq!(round(0_u8..=255, 200, "abc\n300\n255\n"), (255_u8, Feedback::TooHigh));
+		assert_eq!(round(1_u8..=10, 7, "7\n"), (7_u8, Feedback::Correct));
+		assert_eq!(round(-1000_i32..=1000, -7, "-8\n"), (-8_i32, Feedback::TooLow));
+		assert_eq!(round(i32::MIN..=i32::MAX, i32::MIN, "-2147483648\n"), (i32::MIN, Feedback::Correct));
+		assert_eq!(round(0_i64..=10_000_000_000, 5_000_000_000, "9999999999\n"), (9_999_999_999_i64, Feedback::TooHigh));
+		assert_eq!(round(i64::MIN..=0, i64::MIN, "-9223372036854775808\n"), (i64::MIN, Feedback::Correct));
+	}
+
+	/// Both kinds of guess must accept and reject exactly the same values.
+	#[test]
+	fn matches_guess()
+	{
+		for value in -5_i32..=15_i32
+		{
+			assert_eq!(Guess::<{ 0..=10 }>::new(value).map(i32::from), BoundedGuess::new(value, 0..=10).map(|guess| guess.value()));
+		}
+
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut "42".as_bytes(), &mut stdout()).expect("input failed.");
+		let bounded = input_runtime("dummy prompt: ".clear(), 0..=50, &mut "42".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), bounded.value());
+		assert_eq!(
+			respond(guess, guess, &mut stdout()).expect("respond failed.").map_break(|win| win.guess.value()),
+			respond_runtime(bounded, bounded, &mut stdout()).expect("respond failed.").map_break(|win| win.guess.value())
+		);
+	}
+}
@@ -0,0 +1,315 @@
+use {
+	crate::{
+		heat::respond_with_note,
+		Feedback,
+		Guess,
+		GuessRange,
+		ResponseTheme,
+		Style,
+		Win,
+	},
+	colored::Colorize,
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+	},
+};
+
+/// What the feedback so far implies about the correct value.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	Bounds,
+/// // 	Feedback,
+/// // 	Guess,
+/// //};
+/// // let mut bounds = Bounds::<{ 0..=1024 }>::new();
+/// // bounds.update(Guess::new(512).expect(""), Feedback::TooHigh);
+/// // bounds.update(Guess::new(256).expect(""), Feedback::TooLow);
+/// // assert_eq!(bounds.to_string(), "257..=511");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Bounds<const RANGE: RangeInclusive<i32>>
+{
+	/// The correct value lies somewhere within the interval.
+	Within(GuessRange<RANGE>),
+	/// The feedback contradicts itself, so no value is possible. This means
+	/// that the feedback was wrong at some point.
+	Contradiction,
+}
+
+impl<const RANGE: RangeInclusive<i32>> Bounds<RANGE>
+{
+	/// Creates bounds that allow every valid guess, as before the first guess.
+	pub const fn new() -> Self
+	{
+		Bounds::Within(GuessRange::full())
+	}
+
+	/// Narrows the bounds with feedback about guess. Once the bounds are a
+	/// contradiction, they stay one.
+	pub fn update(
+		&mut self,
+		guess: Guess<RANGE>,
+		feedback: Feedback,
+	)
+	{
+		let Bounds::Within(range) = *self
+		else
+		{
+			return;
+		};
+		let range = match feedback
+		{
+			Feedback::TooHigh => range.below(guess),
+			Feedback::TooLow => range.above(guess),
+			Feedback::Correct => range.contains(guess).then(|| GuessRange { lo: guess, hi: guess }),
+		};
+		*self = range.map_or(Bounds::Contradiction, Bounds::Within);
+	}
+
+	/// Returns the interval the correct value lies within, or None if the
+	/// feedback contradicts itself.
+	pub const fn range(&self) -> Option<GuessRange<RANGE>>
+	{
+		match self
+		{
+			Bounds::Within(range) => Some(*range),
+			Bounds::Contradiction => None,
+		}
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Default for Bounds<RANGE>
+{
+	fn default() -> Self
+	{
+		Bounds::new()
+	}
+}
+
+/// Formats Bounds as the interval it allows, as in `257..=511`, or as
+/// `nothing` for a contradiction.
+impl<const RANGE: RangeInclusive<i32>> Display for Bounds<RANGE>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			Bounds::Within(range) => write!(f, "{}..={}", range.lo(), range.hi()),
+			Bounds::Contradiction => write!(f, "nothing"),
+		}
+	}
+}
+
+/// Returns the chance of still winning with optimal play, from 0 through 1,
+/// given where the correct value can be and how many attempts are left.
+///
+/// Each attempt can at best halve the interval, so this is attempts_left
+/// doublings over the size of the interval, capped at 1. Without attempts left,
+/// or with bounds that contradict themselves, the game can not be won anymore.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	win_probability,
+/// // 	Bounds,
+/// //};
+/// // assert_eq!(win_probability(&Bounds::<{ 1..=100 }>::new(), 5), 0.32);
+/// ```
+pub fn win_probability<const RANGE: RangeInclusive<i32>>(
+	bounds: &Bounds<RANGE>,
+	attempts_left: u32,
+) -> f64
+{
+	let Some(range) = bounds.range().filter(|_| attempts_left > 0)
+	else
+	{
+		return 0.0;
+	};
+	// Past 127 doublings every interval is covered many times over.
+	let covered = 1_u128.checked_shl(attempts_left).unwrap_or(u128::MAX);
+	let size = u128::from(range.size());
+	if covered >= size { 1.0 } else { covered as f64 / size as f64 }
+}
+
+/// Respond to a user's input with some console output, including where the
+/// correct value can still be. Returns the correct action to take.
+///
+/// This is the same as respond, except that bounds is updated with the feedback
+/// about guess, and a guess that is too high or too low is followed by the
+/// interval that remains, as in "The answer is now somewhere in 257..=511." If
+/// bounds turns out to be a contradiction, that is reported instead.
+///
+/// # Errors
+/// Returns an error if writing to output fails. Bounds is updated either way.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_with_bounds,
+/// // 		Bounds,
+/// // 		Guess,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let correct_guess = Guess::<{ 0..=1024 }>::new(300_i32).expect("");
+/// // let mut bounds = Bounds::new();
+/// // respond_with_bounds(Guess::new(512_i32).expect(""), correct_guess, &mut bounds, &mut stdout()).expect("");
+/// // respond_with_bounds(Guess::new(256_i32).expect(""), correct_guess, &mut bounds, &mut stdout()).expect("");
+/// ```
+pub fn respond_with_bounds<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	bounds: &mut Bounds<RANGE>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	bounds.update(guess, Feedback::from(guess.cmp(&correct)));
+	let note = match bounds
+	{
+		Bounds::Within(_) => format!("The answer is now somewhere in {bounds}.").yellow(),
+		Bounds::Contradiction => "The hints contradict each other, so no answer is possible!".red().bold(),
+	};
+	respond_with_note(guess, correct, Some(note), &ResponseTheme::default(), Style::Fancy, output)
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	#[test]
+	fn update()
+	{
+		let mut bounds = Bounds::new();
+		assert_eq!(bounds.to_string(), "0..=1024");
+		bounds.update(guess(512), Feedback::TooHigh);
+		assert_eq!(bounds.to_string(), "0..=511");
+		bounds.update(guess(256), Feedback::TooLow);
+		assert_eq!(bounds.to_string(), "257..=511");
+
+		// Guesses outside of the bounds tell nothing new.
+		bounds.update(guess(600), Feedback::TooHigh);
+		assert_eq!(bounds.to_string(), "257..=511");
+
+		bounds.update(guess(300), Feedback::Correct);
+		assert_eq!(bounds.range(), GuessRange::new(guess(300), guess(300)));
+	}
+
+	#[test]
+	fn contradiction()
+	{
+		let mut bounds = Bounds::<{ 0..=1024 }>::new();
+		bounds.update(guess(10), Feedback::TooHigh);
+		bounds.update(guess(9), Feedback::TooLow);
+		assert_eq!(bounds, Bounds::Contradiction);
+		assert_eq!(bounds.range(), None);
+		assert_eq!(bounds.to_string(), "nothing");
+
+		// A contradiction can not be resolved by more feedback.
+		bounds.update(guess(5), Feedback::TooLow);
+		assert_eq!(bounds, Bounds::Contradiction);
+
+		let mut bounds = Bounds::<{ 0..=1024 }>::new();
+		bounds.update(guess(0), Feedback::TooHigh);
+		assert_eq!(bounds, Bounds::Contradiction);
+
+		let mut bounds = Bounds::<{ 0..=1024 }>::new();
+		bounds.update(guess(100), Feedback::TooLow);
+		bounds.update(guess(50), Feedback::Correct);
+		assert_eq!(bounds, Bounds::Contradiction);
+	}
+
+	#[test]
+	fn win_probability_test()
+	{
+		let mut bounds = Bounds::<{ 0..=1024 }>::new();
+		assert_eq!(win_probability(&bounds, 5), 32.0 / 1025.0);
+		assert_eq!(win_probability(&bounds, 10), 1024.0 / 1025.0);
+		assert_eq!(win_probability(&bounds, 11), 1.0);
+		assert_eq!(win_probability(&bounds, u32::MAX), 1.0);
+		assert_eq!(win_probability(&bounds, 0), 0.0);
+
+		bounds.update(guess(512), Feedback::TooHigh);
+		bounds.update(guess(256), Feedback::TooLow);
+		assert_eq!(win_probability(&bounds, 2), 4.0 / 255.0);
+		bounds.update(guess(300), Feedback::Correct);
+		assert_eq!(win_probability(&bounds, 1), 1.0);
+		assert_eq!(win_probability(&bounds, 0), 0.0);
+
+		bounds.update(guess(200), Feedback::Correct);
+		assert_eq!(win_probability(&bounds, 100), 0.0);
+
+		let widest = Bounds::<{ i32::MIN..=i32::MAX }>::new();
+		assert_eq!(win_probability(&widest, 31), 0.5);
+		assert_eq!(win_probability(&widest, 32), 1.0);
+	}
+
+	#[test]
+	fn respond_with_bounds_test()
+	{
+		let correct = guess(300);
+		let mut bounds = Bounds::new();
+		let steps = [
+			(512, Some("0..=511")),
+			(256, Some("257..=511")),
+			(400, Some("257..=399")),
+			(299, Some("300..=399")),
+			(300, None),
+		];
+		for (value, interval) in steps
+		{
+			let mut output = Vec::new();
+			let action = respond_with_bounds(guess(value), correct, &mut bounds, &mut output).expect("respond failed.");
+			let output = String::from_utf8(output).expect("output was not UTF-8.");
+			match interval
+			{
+				Some(interval) =>
+				{
+					assert!(action.is_continue());
+					assert!(output.contains(&format!("The answer is now somewhere in {interval}.")), "{output:?} after {value}.");
+				},
+				None =>
+				{
+					assert_eq!(action, ControlFlow::Break(Win { guess: correct }));
+					assert!(!output.contains("somewhere"), "{output:?} after {value}.");
+				},
+			}
+		}
+
+		// Bounds that disagree with the correct value are reported as such.
+		let mut bounds = Bounds::new();
+		bounds.update(guess(200), Feedback::TooHigh);
+		let mut output = Vec::new();
+		let action = respond_with_bounds(guess(250), correct, &mut bounds, &mut output).expect("respond failed.");
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooLow));
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("contradict"));
+		assert!(!output.contains("somewhere"));
+	}
+}
@@ -0,0 +1,614 @@
+use {
+	crate::{
+		fair_attempts,
+		game::FAIR_SLACK,
+		BonusRule,
+		Clock,
+		Difficulty,
+		Game,
+		GameObserver,
+		Guess,
+		GuessRange,
+		HeatThresholds,
+		Hints,
+		Style,
+	},
+	rand::{
+		rngs::StdRng,
+		thread_rng,
+		Rng,
+		SeedableRng,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		ops::RangeInclusive,
+		result::Result,
+		sync::{
+			atomic::AtomicBool,
+			Arc,
+		},
+		time::Duration,
+	},
+};
+
+/// Sets up a Game one setting at a time, checking that the settings fit
+/// together once the game is built. See Game::builder.
+///
+/// Every setting that is not given has a default:
+///
+/// - The range is that of the difficulty, or all of RANGE without one.
+/// - The hints are those of the difficulty, or Hints::default without one.
+/// - The attempts are Difficulty::attempts_with the hints, or fair_attempts for
+///   the range with a slack of 1 without a difficulty.
+/// - The secret is picked from the range, by the seed if there is one.
+/// - Everything else is as for Game::with_secret.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	Game,
+/// // 	Hints,
+/// //};
+/// // let game = Game::<{ 0..=1024 }>::builder().range(1, 100).attempts(7).hints(Hints::Direction).seed(42).build().expect("");
+/// // assert_eq!(game.range().size(), 100);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameBuilder<const RANGE: RangeInclusive<i32>>
+{
+	range: Option<(i32, i32)>,
+	difficulty: Option<Difficulty>,
+	attempts: Option<u32>,
+	practice: bool,
+	secret: Option<i32>,
+	seed: Option<u64>,
+	hints: Option<Hints>,
+	thresholds: HeatThresholds,
+	style: Style,
+	observers: Vec<Arc<dyn GameObserver<RANGE>>>,
+	bonus: Option<BonusRule>,
+	forgive_duplicates: bool,
+	time_limit: Option<Duration>,
+	clock: Option<Arc<dyn Clock>>,
+	liar: bool,
+	show_odds: bool,
+	speedrun: bool,
+	players: Vec<String>,
+	interrupt: Option<Arc<AtomicBool>>,
+}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Returns a GameBuilder with every setting at its default, which is the
+	/// easiest way to set up a game with more than a few settings.
+	pub fn builder() -> GameBuilder<RANGE>
+	{
+		GameBuilder::new()
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Default for GameBuilder<RANGE>
+{
+	fn default() -> Self
+	{
+		GameBuilder::new()
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> GameBuilder<RANGE>
+{
+	/// Creates a builder with every setting at its default. See Game::builder.
+	pub fn new() -> Self
+	{
+		GameBuilder {
+			range: None,
+			difficulty: None,
+			attempts: None,
+			practice: false,
+			secret: None,
+			seed: None,
+			hints: None,
+			thresholds: HeatThresholds::default(),
+			style: Style::default(),
+			observers: Vec::new(),
+			bonus: None,
+			forgive_duplicates: false,
+			time_limit: None,
+			clock: None,
+			liar: false,
+			show_odds: false,
+			speedrun: false,
+			players: Vec::new(),
+			interrupt: None,
+		}
+	}
+
+	/// Only accepts guesses from lo through hi, which have to lie within RANGE.
+	#[must_use]
+	pub const fn range(
+		mut self,
+		lo: i32,
+		hi: i32,
+	) -> Self
+	{
+		self.range = Some((lo, hi));
+		self
+	}
+
+	/// Takes the range, attempts and hints that are not set otherwise from
+	/// difficulty, whose range has to lie within RANGE.
+	#[must_use]
+	pub const fn difficulty(
+		mut self,
+		difficulty: Difficulty,
+	) -> Self
+	{
+		self.difficulty = Some(difficulty);
+		self
+	}
+
+	/// Gives the player attempts valid guesses, which has to be at least 1.
+	#[must_use]
+	pub const fn attempts(
+		mut self,
+		attempts: u32,
+	) -> Self
+	{
+		self.attempts = Some(attempts);
+		self
+	}
+
+	/// Makes the game practice, with as many attempts as it takes. See
+	/// Game::set_attempts.
+	#[must_use]
+	pub const fn practice(
+		mut self,
+		practice: bool,
+	) -> Self
+	{
+		self.practice = practice;
+		self
+	}
+
+	/// Makes secret the number to guess, which has to lie within the range.
+	#[must_use]
+	pub const fn secret(
+		mut self,
+		secret: i32,
+	) -> Self
+	{
+		self.secret = Some(secret);
+		self
+	}
+
+	/// Picks the secret, and the lie of Game::lie_once, with an rng seeded by
+	/// seed, so that they are the same every time. Only GameBuilder::build uses
+	/// it.
+	#[must_use]
+	pub const fn seed(
+		mut self,
+		seed: u64,
+	) -> Self
+	{
+		self.seed = Some(seed);
+		self
+	}
+
+	/// Sets how much a wrong guess tells the player. See Game::hints.
+	#[must_use]
+	pub const fn hints(
+		mut self,
+		hints: Hints,
+	) -> Self
+	{
+		self.hints = Some(hints);
+		self
+	}
+
+	/// Sets where the heat bands end, which have to increase from 0. See
+	/// Game::thresholds.
+	#[must_use]
+	pub const fn thresholds(
+		mut self,
+		thresholds: HeatThresholds,
+	) -> Self
+	{
+		self.thresholds = thresholds;
+		self
+	}
+
+	/// Sets how the game's messages look. See Game::style.
+	#[must_use]
+	pub const fn style(
+		mut self,
+		style: Style,
+	) -> Self
+	{
+		self.style = style;
+		self
+	}
+
+	/// Adds observer to the game. See Game::add_observer.
+	#[must_use]
+	pub fn observer(
+		mut self,
+		observer: Arc<dyn GameObserver<RANGE>>,
+	) -> Self
+	{
+		self.observers.push(observer);
+		self
+	}
+
+	/// Sets the rule for earning a bonus attempt. See Game::bonus.
+	#[must_use]
+	pub const fn bonus(
+		mut self,
+		bonus: BonusRule,
+	) -> Self
+	{
+		self.bonus = Some(bonus);
+		self
+	}
+
+	/// Sets whether a repeated guess is forgiven. See Game::forgive_duplicates.
+	#[must_use]
+	pub const fn forgive_duplicates(
+		mut self,
+		forgive_duplicates: bool,
+	) -> Self
+	{
+		self.forgive_duplicates = forgive_duplicates;
+		self
+	}
+
+	/// Gives the player time_limit for the whole game. See Game::time_limit.
+	#[must_use]
+	pub const fn time_limit(
+		mut self,
+		time_limit: Duration,
+	) -> Self
+	{
+		self.time_limit = Some(time_limit);
+		self
+	}
+
+	/// Times the game with clock. See Game::set_clock.
+	#[must_use]
+	pub fn clock(
+		mut self,
+		clock: Arc<dyn Clock>,
+	) -> Self
+	{
+		self.clock = Some(clock);
+		self
+	}
+
+	/// Sets whether the game lies once. See Game::lie_once.
+	#[must_use]
+	pub const fn liar(
+		mut self,
+		liar: bool,
+	) -> Self
+	{
+		self.liar = liar;
+		self
+	}
+
+	/// Sets whether play shows the chance of still winning. See
+	/// Game::show_odds.
+	#[must_use]
+	pub const fn show_odds(
+		mut self,
+		show_odds: bool,
+	) -> Self
+	{
+		self.show_odds = show_odds;
+		self
+	}
+
+	/// Sets whether play is a speedrun. See Game::speedrun.
+	#[must_use]
+	pub const fn speedrun(
+		mut self,
+		speedrun: bool,
+	) -> Self
+	{
+		self.speedrun = speedrun;
+		self
+	}
+
+	/// Makes the game co-op for players. See Game::set_players.
+	#[must_use]
+	pub fn players(
+		mut self,
+		players: Vec<String>,
+	) -> Self
+	{
+		self.players = players;
+		self
+	}
+
+	/// Ends play early once flag is set. See Game::set_interrupt.
+	#[must_use]
+	pub fn interrupt(
+		mut self,
+		flag: Arc<AtomicBool>,
+	) -> Self
+	{
+		self.interrupt = Some(flag);
+		self
+	}
+
+	/// Builds the game, picking the secret with an rng seeded by the seed, or
+	/// with thread_rng without one.
+	///
+	/// # Errors
+	/// Returns the first setting that does not fit, as a ConfigError.
+	pub fn build(self) -> Result<Game<RANGE>, ConfigError>
+	{
+		match self.seed
+		{
+			Some(seed) => self.build_with(&mut StdRng::seed_from_u64(seed)),
+			None => self.build_with(&mut thread_rng()),
+		}
+	}
+
+	/// Builds the game like GameBuilder::build, but picks the secret with rng
+	/// instead, ignoring the seed, such as to share one rng between games.
+	///
+	/// # Errors
+	/// Returns the first setting that does not fit, as a ConfigError.
+	pub fn build_with(
+		self,
+		rng: &mut impl Rng,
+	) -> Result<Game<RANGE>, ConfigError>
+	{
+		let (lo, hi) = match (self.range, self.difficulty)
+		{
+			(Some(range), _) => range,
+			(None, Some(difficulty)) => difficulty.range().into_inner(),
+			(None, None) => (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value()),
+		};
+		let range = checked_range(lo, hi)?;
+		let hints = self.hints.or(self.difficulty.map(Difficulty::hints)).unwrap_or_default();
+		let attempts = match (self.attempts, self.difficulty)
+		{
+			(Some(0_u32), _) => return Err(ConfigError::NoAttempts),
+			(Some(attempts), _) => attempts,
+			(None, Some(difficulty)) => difficulty.attempts_with(hints),
+			(None, None) => fair_attempts(range.size(), FAIR_SLACK),
+		};
+		let HeatThresholds { boiling, warm, cold } = self.thresholds;
+		// Written out, so that NaN is not ordered either.
+		let ordered = 0.0 <= boiling && boiling <= warm && warm <= cold;
+		if !ordered
+		{
+			return Err(ConfigError::UnorderedThresholds(self.thresholds));
+		}
+		let secret = match self.secret
+		{
+			Some(value) if (lo..=hi).contains(&value) => Guess { value },
+			Some(value) => return Err(ConfigError::SecretOutOfRange(value)),
+			None => Guess { value: rng.gen_range(lo..=hi) },
+		};
+		let mut game = Game::with_secret(attempts, secret);
+		game.set_range(range);
+		game.hints = hints;
+		game.thresholds = self.thresholds;
+		game.style = self.style;
+		game.bonus = self.bonus;
+		game.forgive_duplicates = self.forgive_duplicates;
+		game.time_limit = self.time_limit;
+		game.show_odds = self.show_odds;
+		game.speedrun = self.speedrun;
+		if let Some(clock) = self.clock
+		{
+			game.set_clock(clock);
+		}
+		for observer in self.observers
+		{
+			game.add_observer(observer);
+		}
+		game.set_players(self.players);
+		game.interrupt = self.interrupt;
+		if self.practice
+		{
+			game.set_attempts(None);
+		}
+		if self.liar
+		{
+			game.lie_once(rng);
+		}
+		Ok(game)
+	}
+}
+
+/// Returns the range from lo through hi, or the ConfigError if it is empty or
+/// does not lie within RANGE.
+pub(crate) const fn checked_range<const RANGE: RangeInclusive<i32>>(
+	lo: i32,
+	hi: i32,
+) -> Result<GuessRange<RANGE>, ConfigError>
+{
+	if lo > hi
+	{
+		return Err(ConfigError::EmptyRange { lo, hi });
+	}
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	if lo < min || hi > max
+	{
+		return Err(ConfigError::RangeOutOfBounds { lo, hi, min, max });
+	}
+	Ok(GuessRange { lo: Guess { value: lo }, hi: Guess { value: hi } })
+}
+
+/// Error returned when a GameBuilder is built with settings that do not fit
+/// together.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ConfigError
+{
+	/// The range ends before it starts, so it has no values.
+	EmptyRange
+	{
+		/// The lowest value of the range.
+		lo: i32,
+		/// The highest value of the range.
+		hi: i32,
+	},
+	/// The range does not lie within RANGE, the guesses the game can hold.
+	RangeOutOfBounds
+	{
+		/// The lowest value of the range.
+		lo: i32,
+		/// The highest value of the range.
+		hi: i32,
+		/// The lowest value of RANGE.
+		min: i32,
+		/// The highest value of RANGE.
+		max: i32,
+	},
+	/// The game was given no attempts, which can not be won. Practice is for
+	/// as many attempts as it takes.
+	NoAttempts,
+	/// The secret does not lie within the range.
+	SecretOutOfRange(i32),
+	/// The heat bands do not increase from 0 through boiling, warm and cold.
+	UnorderedThresholds(HeatThresholds),
+}
+
+/// Formats ConfigError as a message that can be shown to the user.
+impl Display for ConfigError
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			ConfigError::EmptyRange { lo, hi } => write!(f, "the range from {lo} through {hi} is empty"),
+			ConfigError::RangeOutOfBounds { lo, hi, min, max } => write!(f, "the range from {lo} through {hi} does not fit in {min} through {max}"),
+			ConfigError::NoAttempts => write!(f, "a game needs at least 1 attempt"),
+			ConfigError::SecretOutOfRange(secret) => write!(f, "the secret {secret} is not in the range"),
+			ConfigError::UnorderedThresholds(thresholds) =>
+			{
+				write!(f, "the heat thresholds {}, {} and {} do not increase from 0", thresholds.boiling, thresholds.warm, thresholds.cold)
+			},
+		}
+	}
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			clock::TestClock,
+			GameOutcome,
+			TranscriptObserver,
+			DIFFICULTY_RANGE,
+		},
+		std::num::NonZeroU32,
+	};
+
+	type Builder = GameBuilder<{ 0..=1024 }>;
+
+	#[test]
+	fn defaults()
+	{
+		let game = Game::<{ 0..=1024 }>::builder().build().expect("build failed.");
+		assert_eq!(game.range(), GuessRange::full());
+		assert_eq!(game.attempts(), NonZeroU32::new(12));
+		assert_eq!(game.hints, Hints::Heat);
+		assert_eq!(game.thresholds, HeatThresholds::default());
+		assert_eq!(game.style, Style::Fancy);
+		assert!(!game.is_practice() && !game.is_deceptive());
+		assert_eq!((game.bonus, game.forgive_duplicates, game.time_limit), (None, false, None));
+		assert!(game.players().is_empty());
+
+		// A difficulty fills in what is not set otherwise.
+		let game = GameBuilder::<DIFFICULTY_RANGE>::new().difficulty(Difficulty::Hard).build().expect("build failed.");
+		assert_eq!((game.range().lo().value(), game.range().hi().value()), (1, 10000));
+		assert_eq!((game.attempts(), game.hints), (NonZeroU32::new(12), Hints::Direction));
+		let game = GameBuilder::<DIFFICULTY_RANGE>::new().difficulty(Difficulty::Normal).hints(Hints::None).build().expect("build failed.");
+		assert_eq!(game.attempts(), NonZeroU32::new(30));
+		let game = GameBuilder::<DIFFICULTY_RANGE>::new().difficulty(Difficulty::Normal).attempts(3).range(5, 6).build().expect("build failed.");
+		assert_eq!((game.attempts(), game.range().size()), (NonZeroU32::new(3), 2));
+
+		// The same seed picks the same secret.
+		let secrets: Vec<_> = (0..2).map(|_| Builder::new().seed(42).build().expect("build failed.").secret()).collect();
+		assert_eq!(secrets[0], secrets[1]);
+	}
+
+	#[test]
+	fn validation()
+	{
+		assert_eq!(Builder::new().range(10, 9).build().err(), Some(ConfigError::EmptyRange { lo: 10, hi: 9 }));
+		assert_eq!(Builder::new().range(-1, 9).build().err(), Some(ConfigError::RangeOutOfBounds { lo: -1, hi: 9, min: 0, max: 1024 }));
+		assert_eq!(Builder::new().difficulty(Difficulty::Insane).build().err(), Some(ConfigError::RangeOutOfBounds { lo: 1, hi: 100000, min: 0, max: 1024 }));
+		assert_eq!(Builder::new().attempts(0).build().err(), Some(ConfigError::NoAttempts));
+		assert_eq!(Builder::new().attempts(0).practice(true).build().err(), Some(ConfigError::NoAttempts));
+		assert_eq!(Builder::new().range(1, 100).secret(101).build().err(), Some(ConfigError::SecretOutOfRange(101)));
+		for thresholds in [
+			HeatThresholds { boiling: 0.1, warm: 0.05, cold: 0.25 },
+			HeatThresholds { boiling: 0.01, warm: 0.5, cold: 0.25 },
+			HeatThresholds { boiling: -0.01, warm: 0.05, cold: 0.25 },
+			HeatThresholds { boiling: f64::NAN, warm: 0.05, cold: 0.25 },
+		]
+		{
+			assert!(matches!(Builder::new().thresholds(thresholds).build(), Err(ConfigError::UnorderedThresholds(_))), "{thresholds:?}");
+		}
+		assert!(Builder::new().thresholds(HeatThresholds { boiling: 0.0, warm: 0.0, cold: 1.0 }).build().is_ok());
+
+		assert_eq!(ConfigError::EmptyRange { lo: 10, hi: 9 }.to_string(), "the range from 10 through 9 is empty");
+		assert_eq!(ConfigError::RangeOutOfBounds { lo: -1, hi: 9, min: 0, max: 1024 }.to_string(), "the range from -1 through 9 does not fit in 0 through 1024");
+		assert_eq!(ConfigError::NoAttempts.to_string(), "a game needs at least 1 attempt");
+	}
+
+	#[test]
+	fn settings()
+	{
+		let clock = Arc::new(TestClock::new());
+		let transcript = Arc::new(TranscriptObserver::new());
+		let thresholds = HeatThresholds { boiling: 0.1, warm: 0.2, cold: 0.3 };
+		let mut game = Builder::new()
+			.range(100, 400)
+			.attempts(4)
+			.secret(300)
+			.hints(Hints::Direction)
+			.thresholds(thresholds)
+			.style(Style::Plain)
+			.observer(transcript.clone())
+			.bonus(BonusRule { within: 5 })
+			.forgive_duplicates(true)
+			.time_limit(Duration::from_secs(30))
+			.clock(clock.clone())
+			.show_odds(true)
+			.speedrun(true)
+			.players(vec![String::from("Ann"), String::from("Ben")])
+			.build()
+			.expect("build failed.");
+		assert_eq!((game.range().lo().value(), game.range().hi().value()), (100, 400));
+		assert_eq!(game.attempts(), NonZeroU32::new(4));
+		assert_eq!((game.hints, game.thresholds, game.style), (Hints::Direction, thresholds, Style::Plain));
+		assert_eq!((game.bonus, game.forgive_duplicates, game.time_limit), (Some(BonusRule { within: 5 }), true, Some(Duration::from_secs(30))));
+		assert!(game.show_odds && game.speedrun);
+		assert_eq!(game.current_player(), Some("Ann"));
+
+		clock.advance(Duration::from_secs(3));
+		assert_eq!(game.elapsed(), Duration::from_secs(3));
+		let outcome = game.play(&mut "298\n298\n300\n".as_bytes(), &mut Vec::new());
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		assert!(game.bonus_earned());
+		assert!(transcript.transcript().starts_with("Game started: from 100 through 400 with 4 attempts.\n"));
+
+		let practice = Builder::new().practice(true).liar(true).seed(7).build().expect("build failed.");
+		assert!(practice.is_practice() && practice.is_deceptive());
+	}
+}
@@ -0,0 +1,815 @@
+use {
+	crate::{
+		builder::checked_range,
+		fair_attempts,
+		game::FAIR_SLACK,
+		ColorChoice,
+		ConfigError,
+		Game,
+		GameBuilder,
+		GameOutcome,
+		GuessRange,
+		RangeSpec,
+		RangeSpecError,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			ErrorKind,
+		},
+		ops::RangeInclusive,
+		process::ExitCode,
+		result::Result,
+		str::FromStr,
+	},
+};
+
+/// What `guessing_game --help` shows.
+pub const USAGE: &str = "\
+Usage: guessing_game [COMMAND] [OPTIONS]
+
+Commands:
+  play                  Play rounds until you stop. This is the default.
+  stats                 Show your lifetime stats, as JSON with --json.
+  simulate              Let the computer play and report how it did.
+  daily                 Play today's challenge, which is the same for everyone.
+  coop                  Take turns with a friend against one secret.
+  golf                  Play nine holes of growing ranges, scored by strokes.
+  replay FILE           Watch a game recorded with --record.
+
+Options:
+  --min N               Guess from N instead of the difficulty's lowest value.
+  --max N               Guess up to N instead of the difficulty's highest value.
+  --range RANGE         Guess within RANGE, written like 1..=100, 1..101 or 100.
+  --attempts N          Give N attempts, at least 1, instead of the default.
+  --seed N              Pick the same secrets every time.
+  --difficulty NAME     Play on easy, normal, hard or insane.
+  --hints NAME          Hint with none, direction or heat.
+  --rounds N            Play N rounds without asking to play again.
+  --time-limit SECONDS  Lose a game that takes longer than SECONDS.
+  --practice            Take as many guesses as you like.
+  --speedrun            Race the clock, keeping the best time.
+  --forgive-duplicates  Do not count a guess that was already made.
+  --show-odds           Show the chance of still winning after each guess.
+  --bonus               Earn an extra attempt for a near miss.
+  --liar                Let every game lie once, for a few extra attempts.
+  --adaptive            Resize the range after each round to how you did.
+  --analyze             Compare every round with optimal play.
+  --ascii               Only write ASCII, without emoji.
+  --color WHEN          Color always, never or auto, only in a terminal.
+  --no-color            Do not color, the same as --color never.
+  --players A,B         Name the players of coop.
+  --games N             Have simulate play N games.
+  --strategy NAME       Have simulate guess with binary, linear or random.
+  --delay MILLIS        Wait MILLIS before each move of replay.
+  --record FILE         Keep the last game in FILE to replay it.
+  --save FILE           Keep a game that was quit in FILE.
+  --resume FILE         Continue the game saved in FILE.
+  --json                Have stats show JSON instead of a table.
+  -h, --help            Show this help.
+
+Exit status:
+  0  The game was won, or a command that plays no game is done.
+  1  The game was lost, or its time ran out.
+  2  The game was quit or aborted, or the input or output failed.
+  3  The command or options do not make sense.
+";
+
+/// Every option the binary knows, in the order `--help` lists them, and
+/// `--stats`, which still shows the stats as it did before there was a command
+/// for it.
+pub const FLAGS: [&str; 30] = [
+	"--min",
+	"--max",
+	"--range",
+	"--attempts",
+	"--seed",
+	"--difficulty",
+	"--hints",
+	"--rounds",
+	"--time-limit",
+	"--practice",
+	"--speedrun",
+	"--forgive-duplicates",
+	"--show-odds",
+	"--bonus",
+	"--liar",
+	"--adaptive",
+	"--analyze",
+	"--ascii",
+	"--color",
+	"--no-color",
+	"--players",
+	"--games",
+	"--strategy",
+	"--delay",
+	"--record",
+	"--save",
+	"--resume",
+	"--json",
+	"--help",
+	"--stats",
+];
+
+/// What the binary is asked to do, named by its first argument.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Command;
+/// // let args = [String::from("simulate"), String::from("--games"), String::from("10")];
+/// // assert_eq!(Command::parse(&args), Ok((Command::Simulate, &args[1..])));
+/// // assert_eq!(Command::parse(&args[1..]), Ok((Command::Play, &args[1..])));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Command
+{
+	/// Play rounds until the player stops, which is what no command does too.
+	#[default]
+	Play,
+	/// Show the lifetime stats.
+	Stats,
+	/// Let a strategy play many games and report how it did.
+	Simulate,
+	/// Play today's challenge.
+	Daily,
+	/// Let two players share the attempts of one game.
+	Coop,
+	/// Play nine holes of growing ranges.
+	Golf,
+	/// Watch a recorded game.
+	Replay,
+}
+
+impl Command
+{
+	/// Every command, in the order `--help` lists them.
+	pub const ALL: [Command; 7] = [Command::Play, Command::Stats, Command::Simulate, Command::Daily, Command::Coop, Command::Golf, Command::Replay];
+
+	/// Returns the name of the command, as in `simulate`.
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			Command::Play => "play",
+			Command::Stats => "stats",
+			Command::Simulate => "simulate",
+			Command::Daily => "daily",
+			Command::Coop => "coop",
+			Command::Golf => "golf",
+			Command::Replay => "replay",
+		}
+	}
+
+	/// Returns the command called name, or None if there is none. Unlike the
+	/// names of options, case matters, as for any command line program.
+	pub fn from_name(name: &str) -> Option<Self>
+	{
+		Command::ALL.into_iter().find(|command| command.name() == name)
+	}
+
+	/// Splits args into the command and the arguments that are left for it.
+	/// Args that are empty or start with an option are Command::Play, so that
+	/// running the binary without a command plays.
+	///
+	/// # Errors
+	/// Returns ArgsError::UnknownCommand if the first argument is neither a
+	/// command nor an option.
+	pub fn parse(args: &[String]) -> Result<(Self, &[String]), ArgsError>
+	{
+		match args.split_first()
+		{
+			None => Ok((Command::Play, args)),
+			Some((first, _)) if first.starts_with('-') => Ok((Command::Play, args)),
+			Some((first, rest)) => Command::from_name(first).map(|command| (command, rest)).ok_or_else(|| ArgsError::UnknownCommand(first.clone())),
+		}
+	}
+}
+
+/// How the binary exits, for scripts that run it. The codes are stable:
+///
+/// | ExitStatus | Code | When                                              |
+/// |------------|------|---------------------------------------------------|
+/// | Success    | 0    | A win, a command without a game, or a closed pipe |
+/// | Lost       | 1    | A loss, including running out of time             |
+/// | Aborted    | 2    | Quitting, or input or output that stopped working |
+/// | Usage      | 3    | A command or options that do not make sense       |
+///
+/// Output that nobody reads anymore, as with `guessing_game | head`, is a
+/// success, like it is for other command line tools.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		ExitStatus,
+/// // 		GameOutcome,
+/// // 	},
+/// // 	std::process::ExitCode,
+/// //};
+/// // let status = ExitStatus::of(&GameOutcome::<{ 0..=100 }>::Won { attempts_used: 3 });
+/// // assert_eq!(status.code(), 0);
+/// // let code = ExitCode::from(status);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum ExitStatus
+{
+	/// The game was won, or a command that plays no game is done.
+	Success,
+	/// The game was lost, or its time ran out.
+	Lost,
+	/// The game was quit or aborted, or the input or output failed.
+	Aborted,
+	/// The command or options do not make sense.
+	Usage,
+}
+
+impl ExitStatus
+{
+	/// Returns the code the process exits with.
+	pub const fn code(self) -> u8
+	{
+		match self
+		{
+			ExitStatus::Success => 0_u8,
+			ExitStatus::Lost => 1_u8,
+			ExitStatus::Aborted => 2_u8,
+			ExitStatus::Usage => 3_u8,
+		}
+	}
+
+	/// Returns the status after a game that ended with outcome.
+	pub const fn of<const RANGE: RangeInclusive<i32>>(outcome: &GameOutcome<RANGE>) -> Self
+	{
+		match outcome
+		{
+			GameOutcome::Won { .. } => ExitStatus::Success,
+			GameOutcome::Lost { .. } | GameOutcome::TimedOut { .. } => ExitStatus::Lost,
+			GameOutcome::Aborted => ExitStatus::Aborted,
+		}
+	}
+
+	/// Returns the status after game was played, which is Success rather than
+	/// Aborted if it was aborted because its output was closed.
+	pub fn of_game<const RANGE: RangeInclusive<i32>>(game: &Game<RANGE>) -> Self
+	{
+		match game.outcome()
+		{
+			Some(outcome) => ExitStatus::of(&outcome),
+			None if game.output_closed() => ExitStatus::Success,
+			None => ExitStatus::Aborted,
+		}
+	}
+
+	/// Returns the status after writing the output of a command that plays no
+	/// game, where a closed pipe is a success too.
+	pub fn of_output(result: &io::Result<()>) -> Self
+	{
+		match result
+		{
+			Ok(()) => ExitStatus::Success,
+			Err(error) if error.kind() == ErrorKind::BrokenPipe => ExitStatus::Success,
+			Err(_) => ExitStatus::Aborted,
+		}
+	}
+}
+
+impl From<ExitStatus> for ExitCode
+{
+	fn from(status: ExitStatus) -> Self
+	{
+		ExitCode::from(status.code())
+	}
+}
+
+/// Returns the value after flag in args, or an empty string if flag is last,
+/// or None if flag is not in args.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::flag_value;
+/// // let args = [String::from("--seed"), String::from("42"), String::from("--min")];
+/// // assert_eq!(flag_value(&args, "--seed"), Some("42"));
+/// // assert_eq!(flag_value(&args, "--min"), Some(""));
+/// // assert_eq!(flag_value(&args, "--max"), None);
+/// ```
+pub fn flag_value<'a>(
+	args: &'a [String],
+	flag: &str,
+) -> Option<&'a str>
+{
+	args.iter().position(|arg| arg == flag).map(|index| args.get(index + 1).map_or("", String::as_str))
+}
+
+/// The command line options that set up the range, attempts, seed and colors
+/// of the games the binary plays. Options that are not given are None, which leaves
+/// them to the difficulty.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		Difficulty,
+/// // 		Game,
+/// // 		GameArgs,
+/// // 		DIFFICULTY_RANGE,
+/// // 	},
+/// // 	std::env,
+/// //};
+/// // let args: Vec<String> = env::args().skip(1).collect();
+/// // let parsed = GameArgs::parse(&args).expect("");
+/// // let range = parsed.range::<DIFFICULTY_RANGE>(Difficulty::Normal.range()).expect("");
+/// // let game = parsed.apply(Game::builder().difficulty(Difficulty::Normal), range).build().expect("");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct GameArgs
+{
+	/// The lowest value to guess, from `--min` or `--range`.
+	pub min: Option<i32>,
+	/// The highest value to guess, from `--max` or `--range`.
+	pub max: Option<i32>,
+	/// How many attempts each game gives, from `--attempts`.
+	pub attempts: Option<u32>,
+	/// What the secrets are picked with, from `--seed`.
+	pub seed: Option<u64>,
+	/// Whether to color, from `--color` or `--no-color`. See ColorChoice::style
+	/// for how it is settled.
+	pub color: ColorChoice,
+}
+
+impl GameArgs
+{
+	/// Reads `--min`, `--max`, `--range`, `--attempts`, `--seed`, `--color` and
+	/// `--no-color` from args, leaving every other argument to the caller.
+	/// `--range` sets both the lowest and the highest value, see RangeSpec.
+	/// `--color` may be written as `--color never` or as `--color=never`.
+	///
+	/// # Errors
+	/// Returns an ArgsError if an argument that starts with `--` is not in FLAGS,
+	/// if a value is not a whole number that fits, if `--attempts` is 0, if
+	/// `--min` is above `--max`, if `--range` is not a RangeSpec, if `--range` is
+	/// given along with `--min` or `--max` or if `--color` is not a ColorChoice.
+	pub fn parse(args: &[String]) -> Result<Self, ArgsError>
+	{
+		if let Some(flag) = args.iter().find(|arg| arg.starts_with("--") && !arg.starts_with("--color=") && !FLAGS.contains(&arg.as_str()))
+		{
+			return Err(ArgsError::UnknownFlag(flag.clone()));
+		}
+		let mut parsed = GameArgs {
+			min: value(args, "--min", "a whole number", "--min 1")?,
+			max: value(args, "--max", "a whole number", "--max 100")?,
+			attempts: value(args, "--attempts", "a whole number of at least 1", "--attempts 10")?,
+			seed: value(args, "--seed", "a whole number of at least 0", "--seed 42")?,
+			color: color(args)?,
+		};
+		if let Some(spec) = flag_value(args, "--range")
+		{
+			let spec: RangeSpec = spec.parse().map_err(ArgsError::Range)?;
+			if let Some(flag) = [(parsed.min, "--min"), (parsed.max, "--max")].into_iter().find_map(|(value, flag)| value.map(|_| flag))
+			{
+				return Err(ArgsError::Conflict { flag });
+			}
+			(parsed.min, parsed.max) = (Some(spec.lo), Some(spec.hi));
+		}
+		if parsed.attempts == Some(0_u32)
+		{
+			return Err(ArgsError::Invalid { flag: "--attempts", expected: "a whole number of at least 1", example: "--attempts 10" });
+		}
+		if let (Some(min), Some(max)) = (parsed.min, parsed.max)
+			&& min > max
+		{
+			return Err(ArgsError::EmptyRange { min, max });
+		}
+		Ok(parsed)
+	}
+
+	/// Returns whether `--min` or `--max` was given, so that the games are not
+	/// played in the range of the difficulty.
+	pub const fn has_range(&self) -> bool
+	{
+		self.min.is_some() || self.max.is_some()
+	}
+
+	/// Returns the range the games are played in, where default fills in the
+	/// side that `--min` or `--max` leaves out.
+	///
+	/// # Errors
+	/// Returns a ConfigError if the range is empty or does not lie within
+	/// RANGE.
+	pub fn range<const RANGE: RangeInclusive<i32>>(
+		&self,
+		default: RangeInclusive<i32>,
+	) -> Result<GuessRange<RANGE>, ConfigError>
+	{
+		let (lo, hi) = default.into_inner();
+		checked_range(self.min.unwrap_or(lo), self.max.unwrap_or(hi))
+	}
+
+	/// Sets up builder with the options, playing in range if `--min` or
+	/// `--max` was given. A range of the player's own without `--attempts` gets
+	/// enough attempts for a binary search with one to spare, like
+	/// Game::new_fair, since the difficulty's would not fit it.
+	pub fn apply<const RANGE: RangeInclusive<i32>>(
+		&self,
+		mut builder: GameBuilder<RANGE>,
+		range: GuessRange<RANGE>,
+	) -> GameBuilder<RANGE>
+	{
+		if self.has_range()
+		{
+			builder = builder.range(range.lo().value(), range.hi().value()).attempts(fair_attempts(range.size(), FAIR_SLACK));
+		}
+		if let Some(attempts) = self.attempts
+		{
+			builder = builder.attempts(attempts);
+		}
+		builder
+	}
+}
+
+/// Returns the value of flag in args parsed as T, or None if flag is not in
+/// args.
+pub(crate) fn value<T: FromStr>(
+	args: &[String],
+	flag: &'static str,
+	expected: &'static str,
+	example: &'static str,
+) -> Result<Option<T>, ArgsError>
+{
+	flag_value(args, flag).map(str::parse).transpose().map_err(|_| ArgsError::Invalid { flag, expected, example })
+}
+
+/// Returns the number of single characters that must be added, removed or
+/// replaced to turn a into b.
+fn edit_distance(
+	a: &str,
+	b: &str,
+) -> usize
+{
+	let b: Vec<char> = b.chars().collect();
+	let mut previous: Vec<usize> = (0..=b.len()).collect();
+	for (i, a) in a.chars().enumerate()
+	{
+		let mut current = vec![i + 1];
+		for (j, &b) in b.iter().enumerate()
+		{
+			current.push((previous[j] + usize::from(a != b)).min(previous[j + 1] + 1).min(current[j] + 1));
+		}
+		previous = current;
+	}
+	previous[b.len()]
+}
+
+/// Returns the choice of `--no-color` or `--color`, or ColorChoice::Auto if
+/// neither is in args.
+fn color(args: &[String]) -> Result<ColorChoice, ArgsError>
+{
+	if args.iter().any(|arg| arg == "--no-color")
+	{
+		return Ok(ColorChoice::Never);
+	}
+	let name = args.iter().find_map(|arg| arg.strip_prefix("--color=")).or_else(|| flag_value(args, "--color"));
+	name.map_or(Ok(ColorChoice::Auto), |name| {
+		ColorChoice::from_name(name).ok_or(ArgsError::Invalid { flag: "--color", expected: "one of auto, always, never", example: "--color never" })
+	})
+}
+
+/// Error returned when the command line options of GameArgs do not make sense.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum ArgsError
+{
+	/// The value of flag is missing or not what it should be.
+	Invalid
+	{
+		/// The option with the wrong value, as in "--attempts".
+		flag: &'static str,
+		/// What the value should be, as in "a whole number of at least 1".
+		expected: &'static str,
+		/// The option with a value that would work, as in "--attempts 10".
+		example: &'static str,
+	},
+	/// `--min` is above `--max`, so there is nothing to guess.
+	EmptyRange
+	{
+		/// The value of `--min`.
+		min: i32,
+		/// The value of `--max`.
+		max: i32,
+	},
+	/// The value of `--range` is not a RangeSpec.
+	Range(RangeSpecError),
+	/// The first argument is not a Command, nor an option.
+	UnknownCommand(String),
+	/// An argument starts with `--` but is not in FLAGS, as in `--atempts`.
+	UnknownFlag(String),
+	/// `--range` is given along with flag, which it would override.
+	Conflict
+	{
+		/// `--min` or `--max`.
+		flag: &'static str,
+	},
+}
+
+/// Formats ArgsError as a message that can be shown to the user.
+impl Display for ArgsError
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			ArgsError::Invalid { flag, expected, example } => write!(f, "{flag} needs {expected}, like {example}"),
+			ArgsError::EmptyRange { min, max } => write!(f, "--min {min} is above --max {max}, so there is nothing to guess"),
+			ArgsError::Range(error) => write!(f, "--range needs a range, but {error}"),
+			ArgsError::UnknownCommand(name) =>
+			{
+				let names: Vec<_> = Command::ALL.iter().map(|command| command.name()).collect();
+				write!(f, "{name} is not a command, which is one of {}", names.join(", "))
+			},
+			ArgsError::UnknownFlag(flag) =>
+			{
+				// A flag that is only a letter or two off is most likely a typo.
+				match FLAGS.iter().map(|known| (edit_distance(flag, known), known)).filter(|&(distance, _)| distance <= 2).min()
+				{
+					Some((_, known)) => write!(f, "{flag} is not an option, so maybe {known} was meant"),
+					None => write!(f, "{flag} is not an option, which --help lists"),
+				}
+			},
+			ArgsError::Conflict { flag } => write!(f, "--range already sets what {flag} does, so only one of them can be given"),
+		}
+	}
+}
+
+impl std::error::Error for ArgsError
+{
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+	{
+		match self
+		{
+			ArgsError::Range(error) => Some(error),
+			ArgsError::Invalid { .. } | ArgsError::EmptyRange { .. } | ArgsError::UnknownCommand(_) | ArgsError::UnknownFlag(_) | ArgsError::Conflict { .. } => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Difficulty,
+			Guess,
+			Style,
+			WithErrors,
+			DIFFICULTY_RANGE,
+		},
+	};
+
+	fn args(line: &str) -> Vec<String>
+	{
+		line.split_whitespace().map(String::from).collect()
+	}
+
+	/// Sets up a game on difficulty with the options in line, as the binary
+	/// does, and returns what quitting it right away shows.
+	fn greeting(
+		line: &str,
+		difficulty: Difficulty,
+	) -> String
+	{
+		let parsed = GameArgs::parse(&args(line)).expect("parse failed.");
+		let range = parsed.range::<DIFFICULTY_RANGE>(difficulty.range()).expect("range failed.");
+		let mut game = parsed.apply(Game::builder().difficulty(difficulty).style(Style::Plain).seed(parsed.seed.unwrap_or(1)), range).build().expect("build failed.");
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		assert_eq!(game.play(&mut "quit\n".as_bytes(), &mut output), GameOutcome::Aborted);
+		String::from_utf8(output.output).expect("output was not UTF-8.")
+	}
+
+	#[test]
+	fn parse()
+	{
+		assert_eq!(GameArgs::parse(&args("golf --rounds 3")), Ok(GameArgs::default()));
+		let parsed = GameArgs::parse(&args("--min -5 --max 50 --attempts 7 --seed 42 --ascii")).expect("parse failed.");
+		assert_eq!(parsed, GameArgs { min: Some(-5), max: Some(50), attempts: Some(7), seed: Some(42), color: ColorChoice::Auto });
+		assert!(parsed.has_range());
+		assert_eq!(GameArgs::parse(&args("--min 5 --max 5")).map(|parsed| parsed.has_range()), Ok(true));
+		assert!(!GameArgs::parse(&args("--attempts 3")).expect("parse failed.").has_range());
+
+		for (line, color) in [("--color never", ColorChoice::Never), ("--color=always", ColorChoice::Always), ("--color=AUTO", ColorChoice::Auto), ("--no-color", ColorChoice::Never), ("--color always --no-color", ColorChoice::Never)]
+		{
+			assert_eq!(GameArgs::parse(&args(line)).map(|parsed| parsed.color), Ok(color), "{line}");
+		}
+
+		// `--range` sets both sides, in any spelling of RangeSpec.
+		for (line, min, max) in [("--range 1..=100", 1, 100), ("--range 1..101", 1, 100), ("--range 100", 0, 100), ("--range -5..=5 --seed 3", -5, 5)]
+		{
+			let parsed = GameArgs::parse(&args(line)).expect(line);
+			assert_eq!((parsed.min, parsed.max), (Some(min), Some(max)), "{line}");
+		}
+	}
+
+	#[test]
+	fn errors()
+	{
+		let cases = [
+			("--min abc", "--min needs a whole number, like --min 1"),
+			("--max", "--max needs a whole number, like --max 100"),
+			("--max 1.5", "--max needs a whole number, like --max 100"),
+			("--min 99999999999", "--min needs a whole number, like --min 1"),
+			("--attempts 0", "--attempts needs a whole number of at least 1, like --attempts 10"),
+			("--attempts -3", "--attempts needs a whole number of at least 1, like --attempts 10"),
+			("--seed -1", "--seed needs a whole number of at least 0, like --seed 42"),
+			("--min 10 --max 5", "--min 10 is above --max 5, so there is nothing to guess"),
+			("--color sometimes", "--color needs one of auto, always, never, like --color never"),
+			("--color=", "--color needs one of auto, always, never, like --color never"),
+			("--range", "--range needs a range, but a range is needed, like 1..=100"),
+			("--range 1.100", "--range needs a range, but 1.100 needs two dots between the start and the end, like 1..=100"),
+			("--range 100..=1", "--range needs a range, but the range starts at 100, above where it ends at 1, so maybe 1..=100 was meant"),
+			("--range 1..=100 --max 50", "--range already sets what --max does, so only one of them can be given"),
+			("--min 1 --range 1..=100", "--range already sets what --min does, so only one of them can be given"),
+			("--atempts 5", "--atempts is not an option, so maybe --attempts was meant"),
+			("--max 50 --Seed 3", "--Seed is not an option, so maybe --seed was meant"),
+			("--colour never", "--colour is not an option, so maybe --color was meant"),
+			("--frobnicate", "--frobnicate is not an option, which --help lists"),
+			("--", "-- is not an option, which --help lists"),
+		];
+		for (line, message) in cases
+		{
+			let error = GameArgs::parse(&args(line)).expect_err(line);
+			assert_eq!(error.to_string(), message);
+		}
+
+		// A side that is left to the difficulty is checked with the range.
+		let parsed = GameArgs::parse(&args("--min 2000")).expect("parse failed.");
+		assert_eq!(parsed.range::<DIFFICULTY_RANGE>(Difficulty::Normal.range()), Err(ConfigError::EmptyRange { lo: 2000, hi: 1000 }));
+		let parsed = GameArgs::parse(&args("--min -200000 --max 10")).expect("parse failed.");
+		assert_eq!(parsed.range::<DIFFICULTY_RANGE>(Difficulty::Normal.range()), Err(ConfigError::RangeOutOfBounds { lo: -200000, hi: 10, min: -100000, max: 100000 }));
+	}
+
+	#[test]
+	fn greetings()
+	{
+		let greeting_of = |line: &str| greeting(line, Difficulty::Normal);
+		assert!(greeting_of("").contains("from 1 through 1000. You have 10 attempts to guess it!"));
+		assert!(greeting_of("--min 5 --max 50 --attempts 3").contains("from 5 through 50. You have 3 attempts to guess it!"));
+		// A range of one's own gets fair attempts for it.
+		assert!(greeting_of("--min 5 --max 50").contains("from 5 through 50. You have 7 attempts to guess it!"));
+		assert!(greeting_of("--max 100").contains("from 1 through 100. You have 8 attempts to guess it!"));
+		assert!(greeting_of("--min 901").contains("from 901 through 1000. You have 8 attempts to guess it!"));
+		assert!(greeting_of("--attempts 4").contains("from 1 through 1000. You have 4 attempts to guess it!"));
+		assert!(greeting_of("--range 10..=20").contains("from 10 through 20. You have 5 attempts to guess it!"));
+		assert!(greeting_of("--range 10..21 --attempts 2").contains("from 10 through 20. You have 2 attempts to guess it!"));
+		// A range may start at or below 0, as `--range 100` does.
+		assert!(greeting_of("--range 100").contains("from 0 through 100. You have 8 attempts to guess it!"));
+		assert!(greeting_of("--range -5..=5").contains("from -5 through 5. You have 5 attempts to guess it!"));
+		assert!(greeting("--min 1 --max 100000", Difficulty::Easy).contains("from 1 through 100000. You have 18 attempts to guess it!"));
+
+		// The same seed picks the same secret.
+		let secret = |line: &str| {
+			let parsed = GameArgs::parse(&args(line)).expect("parse failed.");
+			let range = parsed.range::<DIFFICULTY_RANGE>(Difficulty::Normal.range()).expect("range failed.");
+			parsed.apply(Game::builder(), range).seed(parsed.seed.expect("no seed.")).build().expect("build failed.").secret()
+		};
+		assert_eq!(secret("--seed 7 --max 100"), secret("--max 100 --seed 7"));
+	}
+
+	#[test]
+	fn commands()
+	{
+		let cases = [
+			("", Command::Play, ""),
+			("--seed 4", Command::Play, "--seed 4"),
+			("play --rounds 2", Command::Play, "--rounds 2"),
+			("stats --json", Command::Stats, "--json"),
+			("simulate --games 10 --strategy linear", Command::Simulate, "--games 10 --strategy linear"),
+			("daily", Command::Daily, ""),
+			("coop --players Ann,Ben", Command::Coop, "--players Ann,Ben"),
+			("golf", Command::Golf, ""),
+			("replay game.json --delay 500", Command::Replay, "game.json --delay 500"),
+		];
+		for (line, command, rest) in cases
+		{
+			let line = args(line);
+			assert_eq!(Command::parse(&line), Ok((command, args(rest).as_slice())));
+		}
+		for command in Command::ALL
+		{
+			assert_eq!(Command::from_name(command.name()), Some(command));
+		}
+
+		// A command must come first, and is spelled exactly.
+		for line in ["simulat", "Stats", "5 play", "game.json"]
+		{
+			let error = Command::parse(&args(line)).expect_err(line);
+			assert_eq!(error, ArgsError::UnknownCommand(args(line).remove(0)));
+		}
+		assert_eq!(
+			ArgsError::UnknownCommand(String::from("simulat")).to_string(),
+			"simulat is not a command, which is one of play, stats, simulate, daily, coop, golf, replay"
+		);
+	}
+
+	#[test]
+	fn exit_status()
+	{
+		let answer = Guess::<{ 0..=100 }>::new(30).expect("answer failed to construct.");
+		let outcomes = [
+			(GameOutcome::Won { attempts_used: 1 }, ExitStatus::Success),
+			(GameOutcome::Won { attempts_used: 7 }, ExitStatus::Success),
+			(GameOutcome::Lost { answer }, ExitStatus::Lost),
+			(GameOutcome::TimedOut { answer }, ExitStatus::Lost),
+			(GameOutcome::Aborted, ExitStatus::Aborted),
+		];
+		for (outcome, status) in outcomes
+		{
+			assert_eq!(ExitStatus::of(&outcome), status, "{outcome:?}");
+		}
+		let codes = [ExitStatus::Success, ExitStatus::Lost, ExitStatus::Aborted, ExitStatus::Usage].map(ExitStatus::code);
+		assert_eq!(codes, [0, 1, 2, 3]);
+		for (code, line) in (0..).zip(USAGE.lines().skip_while(|line| *line != "Exit status:").skip(1))
+		{
+			assert!(line.starts_with(&format!("  {code}  ")), "{line}");
+		}
+
+		assert_eq!(ExitStatus::of_output(&Ok(())), ExitStatus::Success);
+		assert_eq!(ExitStatus::of_output(&Err(io::Error::from(ErrorKind::BrokenPipe))), ExitStatus::Success);
+		assert_eq!(ExitStatus::of_output(&Err(io::Error::from(ErrorKind::PermissionDenied))), ExitStatus::Aborted);
+	}
+
+	/// Writer for output that nobody reads anymore, such as a closed pipe.
+	struct Closed;
+
+	impl io::Write for Closed
+	{
+		fn write(
+			&mut self,
+			_buf: &[u8],
+		) -> io::Result<usize>
+		{
+			Err(io::Error::from(ErrorKind::BrokenPipe))
+		}
+
+		fn flush(&mut self) -> io::Result<()>
+		{
+			Ok(())
+		}
+	}
+
+	/// Plays a game of two attempts whose secret is 30 with input, and returns
+	/// how it ended with the status it exits with.
+	fn played(input: &str) -> (GameOutcome<{ 0..=100 }>, ExitStatus)
+	{
+		let mut game = Game::with_secret(2, Guess::new(30).expect("secret failed to construct."));
+		game.style = Style::Plain;
+		let outcome = game.play(&mut input.as_bytes(), &mut WithErrors { output: Vec::new(), errors: Vec::new() });
+		(outcome, ExitStatus::of_game(&game))
+	}
+
+	#[test]
+	fn exit_status_of_game()
+	{
+		assert_eq!(played("30\n"), (GameOutcome::Won { attempts_used: 1 }, ExitStatus::Success));
+		assert_eq!(played("10\n20\n").1, ExitStatus::Lost);
+		assert_eq!(played("10\nquit\n"), (GameOutcome::Aborted, ExitStatus::Aborted));
+		// Input that ends early aborts the game too.
+		assert_eq!(played("10\n"), (GameOutcome::Aborted, ExitStatus::Aborted));
+
+		// A game that can not write anymore is aborted, but the process succeeds.
+		let mut game = Game::with_secret(2, Guess::<{ 0..=100 }>::new(30).expect("secret failed to construct."));
+		assert_eq!(game.play(&mut "10\n".as_bytes(), &mut WithErrors { output: Closed, errors: Closed }), GameOutcome::Aborted);
+		assert!(game.output_closed());
+		assert_eq!(ExitStatus::of_game(&game), ExitStatus::Success);
+	}
+
+	#[test]
+	fn usage()
+	{
+		for command in Command::ALL
+		{
+			assert!(USAGE.contains(&format!("\n  {} ", command.name())), "{command:?}");
+		}
+		for flag in ["--min N", "--max N", "--range RANGE", "--attempts N", "--color WHEN", "--no-color", "--seed N", "-h, --help"]
+		{
+			assert!(USAGE.contains(flag), "{flag}");
+		}
+		assert!(USAGE.lines().all(|line| line.len() <= 80));
+		for word in USAGE.split(|c: char| c.is_whitespace() || c == ',' || c == '.').filter(|word| word.starts_with("--"))
+		{
+			assert!(FLAGS.contains(&word), "{word}");
+		}
+		for flag in FLAGS.iter().filter(|&&flag| flag != "--stats")
+		{
+			assert!(USAGE.contains(&format!("{flag} ")) || USAGE.contains(&format!("{flag}\n")), "{flag}");
+		}
+	}
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+use std::sync::Mutex;
+use std::{
+	fmt::Debug,
+	time::{
+		Duration,
+		Instant,
+	},
+};
+
+/// Where a Game gets the time from, so that timed games can be tested without
+/// waiting. See Game::set_clock.
+pub trait Clock: Debug + Send + Sync
+{
+	/// Returns the current time. It never goes backwards.
+	fn now(&self) -> Instant;
+}
+
+/// The clock of the system, as in Instant::now. Every game uses this one unless
+/// it is given another.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock
+{
+	fn now(&self) -> Instant
+	{
+		Instant::now()
+	}
+}
+
+/// Formats duration in seconds with one decimal, as in "3.2s".
+pub(crate) fn seconds(duration: Duration) -> String
+{
+	format!("{:.1}s", duration.as_secs_f64())
+}
+
+/// A clock that stands still until it is moved forward by hand, for tests.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct TestClock
+{
+	now: Mutex<Instant>,
+}
+
+#[cfg(test)]
+impl TestClock
+{
+	/// Creates a clock that stands at the time it was created.
+	pub(crate) fn new() -> Self
+	{
+		TestClock { now: Mutex::new(Instant::now()) }
+	}
+
+	/// Moves the clock forward by duration.
+	pub(crate) fn advance(
+		&self,
+		duration: Duration,
+	)
+	{
+		*self.now.lock().expect("clock was poisoned.") += duration;
+	}
+}
+
+#[cfg(test)]
+impl Clock for TestClock
+{
+	fn now(&self) -> Instant
+	{
+		*self.now.lock().expect("clock was poisoned.")
+	}
+}
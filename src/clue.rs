@@ -0,0 +1,216 @@
+use {
+	crate::{
+		Guess,
+		GuessRange,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		iter::FusedIterator,
+		ops::RangeInclusive,
+		result::Result,
+	},
+};
+
+/// Something true about a secret, given as a hint. See HintOracle.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Clue<const RANGE: RangeInclusive<i32>>
+{
+	/// Whether the secret is even.
+	Parity
+	{
+		/// True if the secret is even, false if it is odd.
+		even: bool,
+	},
+	/// How many digits the secret has, not counting a minus sign.
+	Digits
+	{
+		/// The number of digits, from 1 through 10.
+		count: u32,
+	},
+	/// Whether the secret is divisible by 5.
+	DivisibleByFive
+	{
+		/// True if the secret is divisible by 5.
+		divisible: bool,
+	},
+	/// Which half of the range the secret is in.
+	Half
+	{
+		/// The half the secret is in.
+		half: GuessRange<RANGE>,
+	},
+}
+
+/// Formats a Clue as a sentence that can be shown to the player.
+impl<const RANGE: RangeInclusive<i32>> Display for Clue<RANGE>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			Clue::Parity { even: true } => write!(f, "The number is even."),
+			Clue::Parity { even: false } => write!(f, "The number is odd."),
+			Clue::Digits { count: 1 } => write!(f, "The number has 1 digit."),
+			Clue::Digits { count } => write!(f, "The number has {count} digits."),
+			Clue::DivisibleByFive { divisible: true } => write!(f, "The number is divisible by 5."),
+			Clue::DivisibleByFive { divisible: false } => write!(f, "The number is not divisible by 5."),
+			Clue::Half { half } => write!(f, "The number is from {} through {}.", half.lo(), half.hi()),
+		}
+	}
+}
+
+/// Gives the clues about a secret one at a time, in a fixed order from least
+/// to most telling: parity, digit count, divisibility by 5 and the half of the
+/// range. Each clue is given once, after which the oracle runs dry.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	Clue,
+/// // 	Guess,
+/// // 	GuessRange,
+/// // 	HintOracle,
+/// //};
+/// // let secret = Guess::<{ 1..=1000 }>::new(300).expect("");
+/// // let mut oracle = HintOracle::new(secret, GuessRange::full());
+/// // assert_eq!(oracle.next(), Some(Clue::Parity { even: true }));
+/// // assert_eq!(oracle.next().expect("").to_string(), "The number has 3 digits.");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct HintOracle<const RANGE: RangeInclusive<i32>>
+{
+	secret: Guess<RANGE>,
+	range: GuessRange<RANGE>,
+	given: usize,
+}
+
+impl<const RANGE: RangeInclusive<i32>> HintOracle<RANGE>
+{
+	/// How many clues an oracle gives in total.
+	pub const CLUES: usize = 4;
+
+	/// Creates an oracle about secret, which lies within range, that has not
+	/// given any clues yet.
+	pub const fn new(
+		secret: Guess<RANGE>,
+		range: GuessRange<RANGE>,
+	) -> Self
+	{
+		HintOracle { secret, range, given: 0 }
+	}
+
+	/// Returns how many clues were given so far.
+	pub const fn given(&self) -> usize
+	{
+		self.given
+	}
+
+	/// Returns the clue at index in the order of the oracle, or None if there
+	/// are not that many.
+	fn clue(
+		&self,
+		index: usize,
+	) -> Option<Clue<RANGE>>
+	{
+		let value = self.secret.value();
+		match index
+		{
+			0 => Some(Clue::Parity { even: value % 2_i32 == 0_i32 }),
+			1 => Some(Clue::Digits { count: value.unsigned_abs().checked_ilog10().unwrap_or(0) + 1 }),
+			2 => Some(Clue::DivisibleByFive { divisible: value % 5_i32 == 0_i32 }),
+			3 =>
+			{
+				let midpoint = self.range.midpoint();
+				let half = if self.secret <= midpoint { GuessRange::new(self.range.lo(), midpoint) } else { self.range.above(midpoint) };
+				Some(Clue::Half { half: half.unwrap_or(self.range) })
+			},
+			_ => None,
+		}
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Iterator for HintOracle<RANGE>
+{
+	type Item = Clue<RANGE>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		let clue = self.clue(self.given)?;
+		self.given += 1;
+		Some(clue)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>)
+	{
+		let left = Self::CLUES.saturating_sub(self.given);
+		(left, Some(left))
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> ExactSizeIterator for HintOracle<RANGE> {}
+
+impl<const RANGE: RangeInclusive<i32>> FusedIterator for HintOracle<RANGE> {}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// Checks that clue is true of secret within range.
+	fn holds<const RANGE: RangeInclusive<i32>>(
+		clue: Clue<RANGE>,
+		secret: Guess<RANGE>,
+		range: GuessRange<RANGE>,
+	) -> bool
+	{
+		let value = secret.value();
+		match clue
+		{
+			Clue::Parity { even } => (value % 2 == 0) == even,
+			Clue::Digits { count } => value.abs().to_string().len() == count as usize,
+			Clue::DivisibleByFive { divisible } => (value % 5 == 0) == divisible,
+			Clue::Half { half } => half.contains(secret) && (half.lo() == range.lo() || half.hi() == range.hi()) && half.size() <= range.size() / 2 + 1,
+		}
+	}
+
+	#[test]
+	fn every_clue_holds()
+	{
+		let range = GuessRange::<{ -1000..=1000 }>::full();
+		for secret in Guess::<{ -1000..=1000 }>::iter()
+		{
+			let clues: Vec<_> = HintOracle::new(secret, range).collect();
+			assert_eq!(clues.len(), HintOracle::<{ -1000..=1000 }>::CLUES);
+			for clue in clues
+			{
+				assert!(holds(clue, secret, range), "{clue:?} about {secret}.");
+			}
+		}
+	}
+
+	#[test]
+	fn order()
+	{
+		let secret = Guess::<{ 1..=1000 }>::new(300).expect("secret failed to construct.");
+		let range = GuessRange::full();
+		let mut oracle = HintOracle::new(secret, range);
+		assert_eq!(oracle.len(), 4);
+		let sentences: Vec<_> = oracle.by_ref().map(|clue| clue.to_string()).collect();
+		assert_eq!(sentences, ["The number is even.", "The number has 3 digits.", "The number is divisible by 5.", "The number is from 1 through 500."]);
+		assert_eq!(oracle.given(), 4);
+		assert_eq!(oracle.next(), None);
+
+		let seven = Guess::<{ 1..=1000 }>::new(7).expect("secret failed to construct.");
+		let sentences: Vec<_> = HintOracle::new(seven, range).map(|clue| clue.to_string()).collect();
+		assert_eq!(sentences, ["The number is odd.", "The number has 1 digit.", "The number is not divisible by 5.", "The number is from 1 through 500."]);
+		let top = Guess::<{ 1..=1000 }>::new(1000).expect("secret failed to construct.");
+		assert_eq!(HintOracle::new(top, range).last().expect("no clues were given.").to_string(), "The number is from 501 through 1000.");
+	}
+}
@@ -0,0 +1,252 @@
+use {
+	crate::Style,
+	std::env,
+};
+
+/// Whether messages are written with colors.
+///
+/// The choice applies to everything the crate writes, whatever the writer is,
+/// since colors are added when a message is formatted rather than when it is
+/// written. Emoji are not affected; see Style::Plain for that.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum ColorChoice
+{
+	/// Colors unless NO_COLOR is set or stdout is not a terminal. CLICOLOR and
+	/// CLICOLOR_FORCE are respected as well.
+	#[default]
+	Auto,
+	/// Colors no matter where the output goes.
+	Always,
+	/// No colors at all.
+	Never,
+}
+
+impl ColorChoice
+{
+	/// Every choice, in the order `--help` lists them.
+	pub const ALL: [ColorChoice; 3] = [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never];
+
+	/// Returns the name of the choice in lowercase, as in `never`.
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			ColorChoice::Auto => "auto",
+			ColorChoice::Always => "always",
+			ColorChoice::Never => "never",
+		}
+	}
+
+	/// Returns the choice called name, ignoring case and surrounding whitespace,
+	/// or None if there is none.
+	pub fn from_name(name: &str) -> Option<Self>
+	{
+		let name = name.trim().to_lowercase();
+		ColorChoice::ALL.into_iter().find(|choice| choice.name() == name)
+	}
+
+	/// Picks Never if NO_COLOR is set to anything but an empty string or if the
+	/// output is not a terminal, and Always otherwise.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::ColorChoice,
+	/// // 	std::io::{
+	/// // 		stdout,
+	/// // 		IsTerminal,
+	/// // 	},
+	/// //};
+	/// // let choice = ColorChoice::detect(stdout().is_terminal());
+	/// ```
+	pub fn detect(terminal: bool) -> Self
+	{
+		let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+		if no_color || !terminal
+		{
+			ColorChoice::Never
+		}
+		else
+		{
+			ColorChoice::Always
+		}
+	}
+
+	/// Settles ColorChoice::Auto into Always or Never with ColorChoice::detect,
+	/// where terminal is whether the output is a terminal. Always and Never are
+	/// kept as they are.
+	pub fn resolve(
+		self,
+		terminal: bool,
+	) -> Self
+	{
+		match self
+		{
+			ColorChoice::Auto => ColorChoice::detect(terminal),
+			ColorChoice::Always | ColorChoice::Never => self,
+		}
+	}
+
+	/// Returns the Style that games should be played in, so that the choice is
+	/// made once rather than wherever a message is written. Without colors that
+	/// is Style::Plain, which leaves out emoji as well, since output that is
+	/// not shown by a terminal, such as a file, has no use for either. Otherwise
+	/// emoji is whether the terminal can show emoji, as for Style::detect.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::{
+	/// // 		ColorChoice,
+	/// // 		Style,
+	/// // 	},
+	/// // 	std::io::{
+	/// // 		stdout,
+	/// // 		IsTerminal,
+	/// // 	},
+	/// //};
+	/// // let style = ColorChoice::Auto.style(stdout().is_terminal(), Style::detect() == Style::Fancy);
+	/// ```
+	pub fn style(
+		self,
+		terminal: bool,
+		emoji: bool,
+	) -> Style
+	{
+		match self.resolve(terminal)
+		{
+			ColorChoice::Never => Style::Plain,
+			ColorChoice::Auto | ColorChoice::Always if emoji => Style::Fancy,
+			ColorChoice::Auto | ColorChoice::Always => Style::Ascii,
+		}
+	}
+}
+
+/// Sets whether messages are written with colors, overriding what colored
+/// detects from the environment. ColorChoice::Auto goes back to detecting it.
+///
+/// This is meant for library users who pass writers that are not stdout, such as
+/// a log file, and know better than the environment whether it can show colors.
+/// The choice is global, so it affects every thread.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	set_color_choice,
+/// // 	ColorChoice,
+/// //};
+/// // set_color_choice(ColorChoice::Never);
+/// ```
+pub fn set_color_choice(choice: ColorChoice)
+{
+	match choice
+	{
+		ColorChoice::Auto => colored::control::unset_override(),
+		ColorChoice::Always => colored::control::set_override(true),
+		ColorChoice::Never => colored::control::set_override(false),
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			input,
+			respond,
+			Game,
+			GameOutcome,
+			Guess,
+			WithErrors,
+		},
+		colored::Colorize,
+	};
+
+	#[test]
+	fn color_choice()
+	{
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		let escaped = |choice| {
+			set_color_choice(choice);
+			let mut output = Vec::new();
+			let guess: Guess<{ 0..=50 }> = input("Guess: ".yellow(), &mut "abc\n20\n".as_bytes(), &mut output).expect("input failed.");
+			let _ = respond(guess, correct, &mut output).expect("respond failed.");
+			let _ = respond(correct, correct, &mut output).expect("respond failed.");
+			output.windows(2).any(|bytes| bytes == b"\x1b[")
+		};
+		assert!(escaped(ColorChoice::Always));
+		assert!(!escaped(ColorChoice::Never));
+		assert!(escaped(ColorChoice::Always));
+		set_color_choice(ColorChoice::Auto);
+	}
+
+	#[test]
+	fn detect()
+	{
+		assert_eq!(ColorChoice::detect(false), ColorChoice::Never);
+		assert_eq!(ColorChoice::default(), ColorChoice::Auto);
+		assert_eq!(ColorChoice::Auto.resolve(false), ColorChoice::Never);
+		for choice in [ColorChoice::Always, ColorChoice::Never]
+		{
+			assert_eq!((choice.resolve(false), choice.resolve(true)), (choice, choice));
+		}
+	}
+
+	#[test]
+	fn names()
+	{
+		for choice in ColorChoice::ALL
+		{
+			assert_eq!(ColorChoice::from_name(choice.name()), Some(choice));
+		}
+		assert_eq!(ColorChoice::from_name(" NEVER "), Some(ColorChoice::Never));
+		assert_eq!(ColorChoice::from_name("sometimes"), None);
+	}
+
+	#[test]
+	fn style()
+	{
+		// Auto is only left to the environment in a terminal.
+		let cases = [
+			(ColorChoice::Auto, false, true, Style::Plain),
+			(ColorChoice::Auto, false, false, Style::Plain),
+			(ColorChoice::Always, false, true, Style::Fancy),
+			(ColorChoice::Always, true, true, Style::Fancy),
+			(ColorChoice::Always, true, false, Style::Ascii),
+			(ColorChoice::Always, false, false, Style::Ascii),
+			(ColorChoice::Never, true, true, Style::Plain),
+			(ColorChoice::Never, false, false, Style::Plain),
+		];
+		for (choice, terminal, emoji, expected) in cases
+		{
+			assert_eq!(choice.style(terminal, emoji), expected, "{choice:?} in a terminal: {terminal}, with emoji: {emoji}.");
+		}
+	}
+
+	#[test]
+	fn redirected()
+	{
+		// Output that is redirected gets neither escape codes nor emoji, whatever
+		// the global choice is.
+		let mut game = Game::with_secret(3, Guess::<{ 0..=100 }>::new(30).expect("secret failed to construct."));
+		game.style = ColorChoice::Auto.style(false, true);
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = game.play(&mut "50
+abc
+10
+40
+".as_bytes(), &mut output);
+		assert!(matches!(outcome, GameOutcome::Lost { .. }));
+		assert!(!output.output.is_empty());
+		for written in [output.output, output.errors]
+		{
+			let written = String::from_utf8(written).expect("output was not UTF-8.");
+			assert!(!written.contains('\x1b'), "{written}");
+			assert!(!written.chars().any(|character| matches!(character, '\u{1f000}'..='\u{1faff}' | '\u{2600}'..='\u{27bf}')), "{written}");
+		}
+	}
+}
@@ -0,0 +1,188 @@
+use {
+	crate::{
+		read_line_limited,
+		Game,
+		GameOutcome,
+		InputOutput,
+	},
+	colored::{
+		ColoredString,
+		Colorize,
+	},
+	std::{
+		io::{
+			self,
+			BufRead,
+			Write,
+		},
+		ops::RangeInclusive,
+	},
+};
+
+/// The names of the two players when they do not give one.
+const DEFAULT_NAMES: [&str; 2] = ["Player one", "Player two"];
+
+/// Plays game in co-op, where two players take turns guessing and share its
+/// attempts, and returns the outcome, which is the same for both of them. See
+/// Game::set_players.
+///
+/// The players are called by names, and each name that is missing is asked for
+/// first, where an empty answer keeps "Player one" or "Player two". Once the
+/// game is over a line tells who made which guess, as from Game::attribution,
+/// and another that the players won or lost together.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		play_coop,
+/// // 		Game,
+/// // 	},
+/// // 	rand::thread_rng,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let mut game = Game::<{ 0..=1024 }>::new(12, &mut thread_rng());
+/// // play_coop(&mut game, &[String::from("Ann")], &mut stdin().lock(), &mut stdout());
+/// ```
+pub fn play_coop<const RANGE: RangeInclusive<i32>>(
+	game: &mut Game<RANGE>,
+	names: &[String],
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> GameOutcome<RANGE>
+{
+	// A game that can not be shown any longer ends early.
+	run(game, names, input, output).unwrap_or(GameOutcome::Aborted)
+}
+
+/// Plays game in co-op, returning an error if reading or writing fails.
+fn run<const RANGE: RangeInclusive<i32>>(
+	game: &mut Game<RANGE>,
+	names: &[String],
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> io::Result<GameOutcome<RANGE>>
+{
+	let mut players = Vec::with_capacity(DEFAULT_NAMES.len());
+	for (index, default) in DEFAULT_NAMES.into_iter().enumerate()
+	{
+		let name = match names.get(index)
+		{
+			Some(name) => name.trim().to_owned(),
+			None =>
+			{
+				write!(output.prompts(), "{}", game.style.render(&format!("{default}, what's your name? ").yellow()))?;
+				output.prompts().flush()?;
+				let mut line = Vec::new();
+				if read_line_limited(input, &mut line)? == 0
+				{
+					return Ok(GameOutcome::Aborted);
+				}
+				String::from_utf8_lossy(&line).trim().to_owned()
+			},
+		};
+		players.push(if name.is_empty() { default.to_owned() } else { name });
+	}
+	let team = format!("{} and {}", players[0], players[1]);
+	let welcome = format!("\n{team}, you're in this together: take turns and share every attempt. {} goes first!", players[0]);
+	say(game, output.messages(), welcome.green())?;
+	game.set_players(players);
+	let outcome = game.play(input, output);
+	let verdict = match outcome
+	{
+		GameOutcome::Won { .. } => format!("{team} win together!").green(),
+		GameOutcome::Lost { .. } | GameOutcome::TimedOut { .. } => format!("{team} lose together. 😢").red(),
+		GameOutcome::Aborted => return Ok(outcome),
+	};
+	if let Some(attribution) = game.attribution()
+	{
+		say(game, output.messages(), attribution.normal())?;
+	}
+	say(game, output.messages(), verdict)?;
+	Ok(outcome)
+}
+
+/// Writes text on a line of its own, in the style of game.
+fn say<const RANGE: RangeInclusive<i32>>(
+	game: &Game<RANGE>,
+	output: &mut dyn Write,
+	text: ColoredString,
+) -> io::Result<()>
+{
+	writeln!(output, "{}", game.style.render(&text))
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Guess,
+			Style,
+			WithErrors,
+		},
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Creates a game of five attempts whose secret is 300, in plain style.
+	fn game() -> Game<{ 0..=1024 }>
+	{
+		Game { style: Style::Plain, ..Game::with_secret(5, guess(300)) }
+	}
+
+	#[test]
+	fn turns()
+	{
+		let mut game = game();
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = play_coop(&mut game, &[], &mut "Ann\nBen\n512\nabc\n256\n300\n".as_bytes(), &mut output);
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 3 });
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(transcript.starts_with("Player one, what's your name? Player two, what's your name? \nAnn and Ben, you're in this together"), "{transcript}");
+		let turns: Vec<_> = transcript.match_indices("'s turn. ").map(|(index, _)| &transcript[transcript[..index].rfind(['\n', ' ']).map_or(0, |start| start + 1)..index]).collect();
+		assert_eq!(turns, ["Ann", "Ben", "Ben", "Ann"]);
+		// The invalid guess does not pass the turn on.
+		assert!(transcript.contains("Ben's turn. That one didn't count, you still have 4 attempts remaining. Guess: "), "{transcript}");
+		assert!(transcript.contains("Ann's turn. You have 3 attempts remaining. Guess: "), "{transcript}");
+		assert!(transcript.ends_with("Who guessed what: Ann 512^, Ben 256v, Ann 300 (ok)\nAnn and Ben win together!\n"), "{transcript}");
+		assert_eq!([game.guesser(0), game.guesser(1), game.guesser(2)], [Some("Ann"), Some("Ben"), Some("Ann")]);
+	}
+
+	#[test]
+	fn shared_attempts()
+	{
+		let mut lost = game();
+		let names = [String::from("Ann"), String::from(" ")];
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = play_coop(&mut lost, &names, &mut "1\n2\n3\n4\n5\n".as_bytes(), &mut output);
+		assert_eq!(outcome, GameOutcome::Lost { answer: guess(300) });
+		assert_eq!(lost.attempts_left(), Some(0));
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(!transcript.contains("what's your name?"), "{transcript}");
+		assert!(transcript.contains("Player two's turn. You have 4 attempts remaining. Guess: "), "{transcript}");
+		assert!(transcript.contains("Ann's turn. You have 1 attempts remaining. Guess: "), "{transcript}");
+		assert!(transcript.ends_with("Who guessed what: Ann 1v, Player two 2v, Ann 3v, Player two 4v, Ann 5v\nAnn and Player two lose together. :(\n"), "{transcript}");
+
+		// A hint uses an attempt of both, but the turn stays.
+		let mut hinted = game();
+		hinted.set_players(vec![String::from("Ann"), String::from("Ben")]);
+		assert_eq!(hinted.current_player(), Some("Ann"));
+		hinted.submit(guess(512)).expect("submit failed.");
+		hinted.hint().expect("hint failed.");
+		assert_eq!(hinted.current_player(), Some("Ben"));
+		assert_eq!(hinted.attempts_left(), Some(3));
+
+		// One player is not co-op.
+		hinted.set_players(vec![String::from("Ann")]);
+		assert_eq!((hinted.current_player(), hinted.attribution()), (None, None));
+	}
+}
@@ -0,0 +1,159 @@
+use {
+	crate::{
+		Difficulty,
+		Feedback,
+		Game,
+		GameOutcome,
+		Guess,
+		DIFFICULTY_RANGE,
+	},
+	std::{
+		ops::RangeInclusive,
+		time::{
+			SystemTime,
+			UNIX_EPOCH,
+		},
+	},
+};
+
+/// Seconds in a day, ignoring leap seconds like Unix time does.
+const SECONDS_PER_DAY: u64 = 86400_u64;
+
+/// The day before the first daily challenge, 2024-01-01, counted in days since
+/// 1970-01-01.
+const DAY_BEFORE_FIRST: u64 = 19722_u64;
+
+/// Returns today's date in UTC as the number of days since 1970-01-01, which is
+/// the day Game::daily takes.
+pub fn today() -> u64
+{
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0_u64, |since| since.as_secs() / SECONDS_PER_DAY)
+}
+
+/// Returns the number of the daily challenge of day, counting 2024-01-01 as #1,
+/// or 0 for days before that.
+pub const fn daily_number(day: u64) -> u64
+{
+	day.saturating_sub(DAY_BEFORE_FIRST)
+}
+
+/// Mixes the bits of x with SplitMix64, so that neighbouring days get unrelated
+/// secrets.
+const fn split_mix(x: u64) -> u64
+{
+	let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15_u64);
+	z = (z ^ (z >> 30_u32)).wrapping_mul(0xbf58_476d_1ce4_e5b9_u64);
+	z = (z ^ (z >> 27_u32)).wrapping_mul(0x94d0_49bb_1331_11eb_u64);
+	z ^ (z >> 31_u32)
+}
+
+impl Game<DIFFICULTY_RANGE>
+{
+	/// Creates the daily challenge of day, a game of Difficulty::Normal whose
+	/// secret is the same for everyone who plays on that day. See today for the
+	/// day of today.
+	///
+	/// The secret is `lo + split_mix(day) % span`, where split_mix is the
+	/// SplitMix64 finalizer and lo and span are those of Difficulty::Normal. It is
+	/// not meant to be hard to work out, only the same everywhere.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	daily_number,
+	/// // 	today,
+	/// // 	Game,
+	/// //};
+	/// // let mut game = Game::daily(today());
+	/// // game.play(&mut std::io::stdin().lock(), &mut std::io::stdout());
+	/// // println!("{}", game.share(daily_number(today())));
+	/// ```
+	pub fn daily(day: u64) -> Self
+	{
+		let difficulty = Difficulty::Normal;
+		let offset = split_mix(day) % difficulty.span();
+		let value = i64::from(*difficulty.range().start()) + offset as i64;
+		let secret = i32::try_from(value).ok().and_then(|value| Guess::new(value).ok()).expect("the offset is less than the span.");
+		Game::with_difficulty_and_secret(difficulty, secret)
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Returns a line that shares how the daily challenge number went without
+	/// giving away the secret, as in "Guessing Game #812 — 4/10 🔼🔼🔽🎯".
+	///
+	/// Each guess is 🔼 if it was too high, 🔽 if it was too low and 🎯 if it was
+	/// correct. A game that was not won shows X instead of the attempts used, and
+	/// practice shows ∞ for the attempts it had.
+	pub fn share(
+		&self,
+		number: u64,
+	) -> String
+	{
+		let used = match self.outcome()
+		{
+			Some(GameOutcome::Won { attempts_used }) => attempts_used.to_string(),
+			_ => String::from("X"),
+		};
+		let marks: String = self
+			.history
+			.iter()
+			.map(|entry| match entry.feedback
+			{
+				Feedback::TooHigh => '🔼',
+				Feedback::TooLow => '🔽',
+				Feedback::Correct => '🎯',
+			})
+			.collect();
+		let attempts = self.attempts().map_or_else(|| String::from("∞"), |attempts| attempts.to_string());
+		format!("Guessing Game #{number} — {used}/{attempts} {marks}")
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// 2026-03-22, counted in days since 1970-01-01.
+	const DAY: u64 = 20534_u64;
+
+	fn guess(value: i32) -> Guess<DIFFICULTY_RANGE>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	#[test]
+	fn daily_secret()
+	{
+		assert_eq!(daily_number(DAY), 812);
+		assert_eq!(daily_number(DAY_BEFORE_FIRST + 1), 1);
+		assert_eq!(Game::daily(DAY).secret().reveal(), guess(302));
+		assert_eq!(Game::daily(DAY_BEFORE_FIRST + 1).secret().reveal(), guess(798));
+		assert_eq!(Game::daily(DAY).range(), Game::with_difficulty(Difficulty::Normal).range());
+		for day in 0..1000
+		{
+			assert!(Difficulty::Normal.range().contains(&Game::daily(day).secret().reveal().value()));
+		}
+	}
+
+	#[test]
+	fn share()
+	{
+		let mut game = Game::daily(DAY);
+		for value in [512, 400, 300, 302]
+		{
+			game.submit(guess(value)).expect("submit failed.");
+		}
+		assert_eq!(game.share(812), "Guessing Game #812 — 4/10 🔼🔼🔽🎯");
+
+		let mut lost = Game::daily(DAY);
+		for value in 1..=10
+		{
+			lost.submit(guess(value)).expect("submit failed.");
+		}
+		assert_eq!(lost.share(812), "Guessing Game #812 — X/10 🔽🔽🔽🔽🔽🔽🔽🔽🔽🔽");
+	}
+}
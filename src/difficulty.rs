@@ -0,0 +1,260 @@
+use std::ops::RangeInclusive;
+
+/// Range that contains the range of every Difficulty, so that games of any
+/// difficulty share a type. See Game::with_difficulty. It reaches as far below
+/// 0 as above it, so that a range of the player's own, like `--range 100` for 0
+/// through 100, may start at or below 0.
+pub const DIFFICULTY_RANGE: RangeInclusive<i32> = -100000_i32..=100000_i32;
+
+/// How much a wrong guess tells the player.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Hints
+{
+	/// Only that the guess is wrong, so the game is about luck and memory.
+	None,
+	/// Only whether the guess is too high or too low.
+	Direction,
+	/// The direction and how close the guess is, as a Heat.
+	#[default]
+	Heat,
+}
+
+impl Hints
+{
+	/// Every kind of hints, from least to most telling.
+	pub const ALL: [Hints; 3] = [Hints::None, Hints::Direction, Hints::Heat];
+
+	/// Returns the name of the hints in lowercase, as in `direction`.
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			Hints::None => "none",
+			Hints::Direction => "direction",
+			Hints::Heat => "heat",
+		}
+	}
+
+	/// Returns the hints called name, ignoring case and surrounding whitespace,
+	/// or None if there are none.
+	pub fn from_name(name: &str) -> Option<Self>
+	{
+		let name = name.trim().to_lowercase();
+		Hints::ALL.into_iter().find(|hints| hints.name() == name)
+	}
+}
+
+/// A preset for how hard a game is, made of a range, a number of attempts and
+/// how much the hints tell.
+///
+/// | Difficulty | Range       | Attempts | Hints     |
+/// |------------|-------------|----------|-----------|
+/// | Easy       | 1..=50      | 8        | Heat      |
+/// | Normal     | 1..=1000    | 10       | Heat      |
+/// | Hard       | 1..=10000   | 12       | Direction |
+/// | Insane     | 1..=100000  | 10       | Direction |
+///
+/// Easy and Normal can always be won by halving the range with each guess, while
+/// Hard and Insane give fewer attempts than that takes, so they also need luck.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Difficulty;
+/// // assert_eq!(Difficulty::Easy.range(), 1..=50);
+/// // assert_eq!(Difficulty::from_name("INSANE"), Some(Difficulty::Insane));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Difficulty
+{
+	/// 1 through 50, with 8 attempts and heat hints.
+	Easy,
+	/// 1 through 1000, with 10 attempts and heat hints.
+	#[default]
+	Normal,
+	/// 1 through 10000, with 12 attempts and direction hints.
+	Hard,
+	/// 1 through 100000, with 10 attempts and direction hints.
+	Insane,
+}
+
+impl Difficulty
+{
+	/// Every difficulty, from easiest to hardest.
+	pub const ALL: [Difficulty; 4] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Insane];
+
+	/// Returns the range the secret and the guesses lie within.
+	pub const fn range(self) -> RangeInclusive<i32>
+	{
+		match self
+		{
+			Difficulty::Easy => 1_i32..=50_i32,
+			Difficulty::Normal => 1_i32..=1000_i32,
+			Difficulty::Hard => 1_i32..=10000_i32,
+			Difficulty::Insane => 1_i32..=100000_i32,
+		}
+	}
+
+	/// Returns how many values are in the range.
+	pub const fn span(self) -> u64
+	{
+		let range = self.range();
+		range.end().abs_diff(*range.start()) as u64 + 1_u64
+	}
+
+	/// Returns how many valid guesses the player gets.
+	pub const fn attempts(self) -> u32
+	{
+		match self
+		{
+			Difficulty::Easy => 8_u32,
+			Difficulty::Normal | Difficulty::Insane => 10_u32,
+			Difficulty::Hard => 12_u32,
+		}
+	}
+
+	/// Returns how many valid guesses the player gets with hints instead of
+	/// Difficulty::hints. Without hints each wrong guess only rules out itself,
+	/// so Hints::None gets three times as many attempts.
+	pub const fn attempts_with(
+		self,
+		hints: Hints,
+	) -> u32
+	{
+		match hints
+		{
+			Hints::None => self.attempts().saturating_mul(3_u32),
+			Hints::Direction | Hints::Heat => self.attempts(),
+		}
+	}
+
+	/// Returns how much a wrong guess tells the player.
+	pub const fn hints(self) -> Hints
+	{
+		match self
+		{
+			Difficulty::Easy | Difficulty::Normal => Hints::Heat,
+			Difficulty::Hard | Difficulty::Insane => Hints::Direction,
+		}
+	}
+
+	/// Returns the name of the difficulty in lowercase, as in `easy`.
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			Difficulty::Easy => "easy",
+			Difficulty::Normal => "normal",
+			Difficulty::Hard => "hard",
+			Difficulty::Insane => "insane",
+		}
+	}
+
+	/// Returns the difficulty called name, ignoring case and surrounding
+	/// whitespace, or None if there is none.
+	pub fn from_name(name: &str) -> Option<Self>
+	{
+		let name = name.trim().to_lowercase();
+		Difficulty::ALL.into_iter().find(|difficulty| difficulty.name() == name)
+	}
+}
+
+/// Returns a fair number of attempts for a range of span values, which is
+/// `ceil(log2(span)) + slack`.
+///
+/// A binary search never needs more than `ceil(log2(span)) + 1` guesses, so a
+/// slack of 1 or more makes every game winnable with direction hints, while a
+/// slack of 0 leaves no room for a single mistake. A span of 0 or 1 needs no
+/// halving at all, and the result saturates instead of overflowing.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::fair_attempts;
+/// // assert_eq!(fair_attempts(1000, 1), 11);
+/// ```
+pub const fn fair_attempts(
+	span: u64,
+	slack: u32,
+) -> u32
+{
+	let halvings = if span <= 1_u64 { 0_u32 } else { (span - 1_u64).ilog2() + 1_u32 };
+	halvings.saturating_add(slack)
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// Returns how many guesses it takes to always find the secret by halving
+	/// the range with each guess.
+	fn guesses_needed(span: u64) -> u32
+	{
+		span.ilog2() + 1
+	}
+
+	#[test]
+	fn presets()
+	{
+		let presets = [
+			(Difficulty::Easy, 1..=50, 50, 8, Hints::Heat),
+			(Difficulty::Normal, 1..=1000, 1000, 10, Hints::Heat),
+			(Difficulty::Hard, 1..=10000, 10000, 12, Hints::Direction),
+			(Difficulty::Insane, 1..=100000, 100000, 10, Hints::Direction),
+		];
+		for (difficulty, range, span, attempts, hints) in presets
+		{
+			assert_eq!(difficulty.range(), range);
+			assert_eq!(difficulty.span(), span);
+			assert_eq!(difficulty.attempts(), attempts);
+			assert_eq!(difficulty.hints(), hints);
+			assert!(DIFFICULTY_RANGE.contains(difficulty.range().start()) && DIFFICULTY_RANGE.contains(difficulty.range().end()));
+		}
+		assert_eq!(Difficulty::default(), Difficulty::Normal);
+	}
+
+	#[test]
+	fn losable()
+	{
+		assert!(Difficulty::Easy.attempts() >= guesses_needed(Difficulty::Easy.span()));
+		assert!(Difficulty::Normal.attempts() >= guesses_needed(Difficulty::Normal.span()));
+		assert!(Difficulty::Hard.attempts() < guesses_needed(Difficulty::Hard.span()));
+		assert!(Difficulty::Insane.attempts() < guesses_needed(Difficulty::Insane.span()));
+		assert_eq!(guesses_needed(Difficulty::Insane.span()), 17);
+		assert_eq!(Difficulty::Easy.attempts_with(Hints::None), 24);
+		assert_eq!(Difficulty::Hard.attempts_with(Hints::Heat), Difficulty::Hard.attempts());
+	}
+
+	#[test]
+	fn fair()
+	{
+		let spans = [(0, 0), (1, 0), (2, 1), (3, 2), (1024, 10), (1025, 11), (u64::from(u32::MAX) + 1, 32), (u64::MAX, 64)];
+		for (span, halvings) in spans
+		{
+			assert_eq!(fair_attempts(span, 0), halvings, "span {span}.");
+			assert_eq!(fair_attempts(span, 3), halvings + 3, "span {span}.");
+			assert!(span == 0 || fair_attempts(span, 1) >= guesses_needed(span), "span {span}.");
+		}
+		assert_eq!(fair_attempts(u64::MAX, u32::MAX), u32::MAX);
+		assert_eq!(fair_attempts(Difficulty::Normal.span(), 0), Difficulty::Normal.attempts());
+	}
+
+	#[test]
+	fn from_name()
+	{
+		for difficulty in Difficulty::ALL
+		{
+			assert_eq!(Difficulty::from_name(difficulty.name()), Some(difficulty));
+		}
+		assert_eq!(Difficulty::from_name(" Hard\n"), Some(Difficulty::Hard));
+		assert_eq!(Difficulty::from_name("impossible"), None);
+
+		for hints in Hints::ALL
+		{
+			assert_eq!(Hints::from_name(hints.name()), Some(hints));
+		}
+		assert_eq!(Hints::from_name("NONE"), Some(Hints::None));
+		assert_eq!(Hints::from_name("psychic"), None);
+	}
+}
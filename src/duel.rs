@@ -0,0 +1,232 @@
+use {
+	crate::{
+		parse_lenient,
+		read_guess,
+		Game,
+		GameOutcome,
+		Guess,
+		InputError,
+		InputOptions,
+		InputOutput,
+		Style,
+	},
+	colored::{
+		ColoredString,
+		Colorize,
+	},
+	std::{
+		io::{
+			self,
+			BufRead,
+			ErrorKind,
+			Write,
+		},
+		ops::RangeInclusive,
+	},
+};
+
+/// Clears a terminal and moves the cursor to the top left, so that the secret
+/// is no longer on the screen when player two sits down.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// A game for two players, where player one picks the secret and player two
+/// guesses it with a limited number of attempts.
+///
+/// The secret is never written to the output, not even when it is invalid, so a
+/// transcript of the duel only reveals it once the game is over.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::DuelGame,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let duel = DuelGame::<{ 0..=1024 }>::new(10);
+/// // duel.play_shared(&mut stdin().lock(), &mut stdout());
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct DuelGame<const RANGE: RangeInclusive<i32>>
+{
+	attempts: u32,
+	/// How the duel's messages look.
+	pub style: Style,
+}
+
+impl<const RANGE: RangeInclusive<i32>> DuelGame<RANGE>
+{
+	/// Creates a duel where player two gets attempts valid guesses.
+	pub const fn new(attempts: u32) -> Self
+	{
+		DuelGame { attempts, style: Style::Fancy }
+	}
+
+	/// Returns how many valid guesses player two gets.
+	pub const fn attempts(&self) -> u32
+	{
+		self.attempts
+	}
+
+	/// Plays a duel where each player has an input and output of their own, so
+	/// player two never sees the secret being entered.
+	///
+	/// The outcome is the same as for Game::play, seen from player two.
+	pub fn play(
+		&self,
+		setter_input: &mut impl BufRead,
+		setter_output: &mut impl InputOutput,
+		guesser_input: &mut impl BufRead,
+		guesser_output: &mut impl InputOutput,
+	) -> GameOutcome<RANGE>
+	{
+		let Some(secret) = self.read_secret(setter_input, setter_output)
+		else
+		{
+			return GameOutcome::Aborted;
+		};
+		let _ = self.say(setter_output.messages(), "The secret is set. Now it's player two's turn!".green());
+		self.game(secret).play(guesser_input, guesser_output)
+	}
+
+	/// Plays a duel where both players share an input and output, such as one
+	/// terminal. Player two is asked to look away while the secret is entered,
+	/// and the screen is cleared afterwards.
+	///
+	/// The outcome is the same as for Game::play, seen from player two.
+	pub fn play_shared(
+		&self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> GameOutcome<RANGE>
+	{
+		if self.say(output.messages(), "\n\nPlayer two, look away!".yellow().bold()).is_err()
+		{
+			return GameOutcome::Aborted;
+		}
+		let Some(secret) = self.read_secret(input, output)
+		else
+		{
+			return GameOutcome::Aborted;
+		};
+		if write!(output.messages(), "{CLEAR_SCREEN}").is_err() || self.say(output.messages(), "Player two, it's your turn!".green()).is_err()
+		{
+			return GameOutcome::Aborted;
+		}
+		self.game(secret).play(input, output)
+	}
+
+	/// Asks player one for the secret until it is valid, without ever writing
+	/// what was entered. Returns None if the duel has to be aborted.
+	fn read_secret(
+		&self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> Option<Guess<RANGE>>
+	{
+		let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+		let prompt = format!("Player one, enter the secret number from {min} through {max}: ").yellow();
+		let options = InputOptions { style: self.style, redact: true, ..InputOptions::default() };
+		match read_guess(prompt, &options, min, max, input, output, |text| parse_lenient(text, min, max).map(|value| Guess { value }))
+		{
+			Ok(result) => Some(result.guess),
+			// Nobody is reading the output anymore, as with `guessing_game | head`.
+			Err(InputError::Io(error)) if error.kind() == ErrorKind::BrokenPipe => None,
+			Err(error) =>
+			{
+				let _ = self.say(output.errors(), format!("\n\nGame aborted because {error}. 👋\n\n").yellow());
+				None
+			},
+		}
+	}
+
+	/// Creates the game player two plays against secret.
+	fn game(
+		&self,
+		secret: Guess<RANGE>,
+	) -> Game<RANGE>
+	{
+		let mut game = Game::with_secret(self.attempts, secret);
+		game.style = self.style;
+		game
+	}
+
+	/// Writes text on a line of its own, in the style of the duel.
+	fn say(
+		&self,
+		output: &mut dyn Write,
+		text: ColoredString,
+	) -> io::Result<()>
+	{
+		writeln!(output, "{}", self.style.render(&text))
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::WithErrors,
+	};
+
+	fn duel() -> DuelGame<{ 0..=1024 }>
+	{
+		DuelGame { style: Style::Plain, ..DuelGame::new(5) }
+	}
+
+	fn text(bytes: Vec<u8>) -> String
+	{
+		String::from_utf8(bytes).expect("output was not UTF-8.")
+	}
+
+	#[test]
+	fn shared()
+	{
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = duel().play_shared(&mut "abc\n2000\n-300\n300\n512\n300\n".as_bytes(), &mut output);
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		let (transcript, errors) = (text(output.output), text(output.errors));
+
+		let win = transcript.find("You win!").expect("the duel was not won.");
+		assert!(!transcript[..win].contains("300"), "{transcript:?}");
+		assert!(transcript[win..].contains("You got 300 in 2 tries!"));
+		assert!(transcript.contains("Player two, look away!"));
+		assert!(transcript.contains(CLEAR_SCREEN));
+		assert!(transcript.contains("512 is too high!"));
+
+		// Invalid secrets are rejected without repeating them.
+		assert_eq!(errors.matches("\"***\" is not an integer from 0 through 1024.").count(), 3);
+		for secret in ["abc", "2000", "300"]
+		{
+			assert!(!errors.contains(secret), "{errors:?}");
+		}
+	}
+
+	#[test]
+	fn separate()
+	{
+		let mut setter = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let mut guesser = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = duel().play(&mut "777\n".as_bytes(), &mut setter, &mut "1\n2\n3\n4\n5\n".as_bytes(), &mut guesser);
+		assert_eq!(outcome, GameOutcome::Lost { answer: Guess::new(777).expect("answer failed to construct.") });
+
+		let (setter, guesser) = (text(setter.output), text(guesser.output));
+		assert!(setter.contains("Player one, enter the secret number from 0 through 1024:"));
+		assert!(setter.contains("The secret is set."));
+		assert!(!setter.contains("777"));
+		assert!(!guesser.contains("777"));
+		assert!(!guesser.contains("Player one"));
+		assert!(guesser.contains("You're out of guesses! Game over."));
+	}
+
+	#[test]
+	fn aborted()
+	{
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		assert_eq!(duel().play_shared(&mut "abc\n".as_bytes(), &mut output), GameOutcome::Aborted);
+		assert!(text(output.errors).contains("Game aborted because the input ended."));
+	}
+}
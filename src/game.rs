@@ -0,0 +1,2141 @@
+use {
+	crate::{
+		clock::seconds,
+		fair_attempts,
+		heat::respond_with_feedback,
+		input_or_command_within,
+		read_line_limited,
+		win_probability,
+		Bounds,
+		Clock,
+		Clue,
+		Difficulty,
+		Feedback,
+		GameObserver,
+		Guess,
+		GuessRange,
+		HeatThresholds,
+		HintOracle,
+		Hints,
+		InputContext,
+		InputError,
+		InputOptions,
+		InputOutput,
+		KnowledgeState,
+		PlayerAction,
+		Prompt,
+		ResponseTheme,
+		ScoreConfig,
+		Secret,
+		Strategy,
+		Style,
+		SystemClock,
+		Trend,
+		DIFFICULTY_RANGE,
+	},
+	colored::{
+		ColoredString,
+		Colorize,
+	},
+	rand::{
+		thread_rng,
+		Rng,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			BufRead,
+			ErrorKind,
+			Write,
+		},
+		num::NonZeroU32,
+		ops::RangeInclusive,
+		result::Result,
+		sync::{
+			atomic::{
+				AtomicBool,
+				Ordering,
+			},
+			Arc,
+		},
+		time::{
+			Duration,
+			Instant,
+		},
+	},
+};
+
+/// How many invalid guesses in a row earn the player a tip in the prompt.
+const TIP_AFTER_INVALID: usize = 3;
+
+/// The slack Game::new_fair gives, so that a binary search always wins.
+pub(crate) const FAIR_SLACK: u32 = 1_u32;
+
+/// A whole game of guessing a secret number in RANGE with a limited number of
+/// attempts, or as many as it takes in practice, from the greeting to the win
+/// or loss.
+///
+/// A game can either be played from start to end with play, which reads and
+/// writes everything itself, or be fed one guess at a time with submit, which
+/// leaves the input and output to the caller.
+///
+/// Game::builder is the easiest way to set up a game, checking that the
+/// settings fit together, while the constructors below cover the common cases.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		Game,
+/// // 		GameOutcome,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let mut game = Game::<{ 0..=1024 }>::builder().attempts(10).build().expect("");
+/// // if let GameOutcome::Won { attempts_used } = game.play(&mut stdin().lock(), &mut stdout())
+/// // {
+/// // 	println!("Won in {attempts_used}.");
+/// // }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Game<const RANGE: RangeInclusive<i32>>
+{
+	secret: Secret<Guess<RANGE>>,
+	attempts: Option<NonZeroU32>,
+	pub(crate) range: GuessRange<RANGE>,
+	pub(crate) history: Vec<HistoryEntry<RANGE>>,
+	/// What the feedback in the history tells about the secret.
+	pub(crate) knowledge: KnowledgeState<RANGE>,
+	/// The guess that came too late, if the game timed out.
+	pub(crate) late: Option<HistoryEntry<RANGE>>,
+	pub(crate) clues: Vec<Clue<RANGE>>,
+	pub(crate) gave_up: bool,
+	pub(crate) timed_out: bool,
+	pub(crate) bonus_earned: bool,
+	/// Which guess the game answers falsely, counting from 1, if it lies.
+	pub(crate) lie: Option<u32>,
+	pub(crate) clock: Arc<dyn Clock>,
+	pub(crate) started: Instant,
+	/// How long the game was paused, which does not count towards its time.
+	pub(crate) paused: Duration,
+	/// The flag that ends play early once it is set, such as by Ctrl-C.
+	pub(crate) interrupt: Option<Arc<AtomicBool>>,
+	/// Whether play was aborted because nobody reads the output anymore.
+	pub(crate) output_closed: bool,
+	/// When the first prompt of a speedrun was shown.
+	pub(crate) run_started: Option<Instant>,
+	/// When a speedrun was won.
+	pub(crate) run_stopped: Option<Instant>,
+	pub(crate) observers: Vec<Arc<dyn GameObserver<RANGE>>>,
+	/// The names of the players taking turns in co-op, or none for one player.
+	pub(crate) players: Vec<String>,
+	/// Whether the observers were told that the game started.
+	pub(crate) announced: bool,
+	/// How long the player has for the whole game, or None for as long as they
+	/// like. Once it runs out the game is lost, even if attempts are left.
+	///
+	/// A blocking read can not be interrupted portably, so the limit is only
+	/// checked when a guess is submitted. A player who stops typing is timed out
+	/// by their next guess, which does not count. Meanwhile play shows the time
+	/// left in every prompt.
+	pub time_limit: Option<Duration>,
+	/// How the game's messages look.
+	pub style: Style,
+	/// How much a wrong guess tells the player when playing.
+	pub hints: Hints,
+	/// How close a wrong guess must be to count as boiling, warm or cold.
+	pub thresholds: HeatThresholds,
+	/// Whether a guess that was already made is answered with its feedback
+	/// again without using up an attempt, rather than counting like any other.
+	/// See TurnResult::Duplicate.
+	pub forgive_duplicates: bool,
+	/// The rule for earning a bonus attempt with a near miss, or None for no
+	/// bonus attempts.
+	pub bonus: Option<BonusRule>,
+	/// Whether play follows each wrong guess with the chance of still winning.
+	/// See Game::win_probability.
+	pub show_odds: bool,
+	/// Whether play is a speedrun, timed from the first prompt to the correct
+	/// answer, which is usually played as practice since the time is the score.
+	/// See Game::run_time.
+	pub speedrun: bool,
+}
+
+/// How a game that was played ended.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum GameOutcome<const RANGE: RangeInclusive<i32>>
+{
+	/// The player guessed the secret.
+	Won
+	{
+		/// How many valid guesses it took, including the winning one.
+		attempts_used: u32,
+	},
+	/// The player ran out of attempts or gave up. A practice game is only lost
+	/// by giving up.
+	Lost
+	{
+		/// The secret the player did not guess.
+		answer: Guess<RANGE>,
+	},
+	/// The time limit ran out before the player guessed the secret. See
+	/// Game::time_limit.
+	TimedOut
+	{
+		/// The secret the player did not guess.
+		answer: Guess<RANGE>,
+	},
+	/// The game stopped early, because the player quit or because the input or
+	/// output stopped working.
+	Aborted,
+}
+
+/// A valid guess that was made in a game, and what it revealed.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct HistoryEntry<const RANGE: RangeInclusive<i32>>
+{
+	/// Which attempt the guess was, starting from 1.
+	pub attempt: u32,
+	/// The guess that was made.
+	pub guess: Guess<RANGE>,
+	/// Whether the guess was too high, too low or correct.
+	pub feedback: Feedback,
+	/// How long after the game was created the guess was made.
+	pub elapsed: Duration,
+}
+
+/// What happened to a guess that was submitted to a game.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum TurnResult<const RANGE: RangeInclusive<i32>>
+{
+	/// The guess is greater than the secret.
+	TooHigh
+	{
+		/// How many guesses the player has left, or None in practice.
+		attempts_left: Option<u32>,
+	},
+	/// The guess is less than the secret.
+	TooLow
+	{
+		/// How many guesses the player has left, or None in practice.
+		attempts_left: Option<u32>,
+	},
+	/// The guess is the secret, so the game is won.
+	Win
+	{
+		/// How many guesses it took, including this one.
+		attempts_used: u32,
+	},
+	/// The guess was wrong and it was the last one, so the game is lost.
+	Loss
+	{
+		/// The secret the player did not guess.
+		answer: Guess<RANGE>,
+	},
+	/// The guess came after the time limit ran out, so it does not count and the
+	/// game is lost.
+	TimedOut
+	{
+		/// The secret the player did not guess.
+		answer: Guess<RANGE>,
+	},
+	/// The guess was already made and Game::forgive_duplicates is set, so it
+	/// does not count. Its feedback is the same as the first time, so nothing
+	/// new is learned.
+	Duplicate
+	{
+		/// What the guess was the first time.
+		feedback: Feedback,
+	},
+}
+
+/// Returns the opposite of feedback, or Correct if it is correct, since that is
+/// never lied about. See Game::lie_once.
+pub(crate) const fn lie_about(feedback: Feedback) -> Feedback
+{
+	match feedback
+	{
+		Feedback::TooHigh => Feedback::TooLow,
+		Feedback::TooLow => Feedback::TooHigh,
+		Feedback::Correct => Feedback::Correct,
+	}
+}
+
+/// A rule that gives the player one extra attempt, at most once per game, for
+/// a wrong guess close to the secret. See Game::bonus.
+///
+/// The bonus can only be earned after an attempt was used, so the attempts left
+/// never add up to more than the game started with. A near miss on the last
+/// attempt earns one more try instead of losing the game.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct BonusRule
+{
+	/// How far from the secret a wrong guess may be to earn the bonus.
+	pub within: u32,
+}
+
+impl BonusRule
+{
+	/// Creates a rule for wrong guesses within percent of span of the secret,
+	/// rounded down but at least 1, as in 1% of a span of 1025 being 10.
+	pub fn percent_of_span(
+		percent: u32,
+		span: u64,
+	) -> Self
+	{
+		let within = span.saturating_mul(u64::from(percent)) / 100_u64;
+		BonusRule { within: u32::try_from(within).unwrap_or(u32::MAX).max(1_u32) }
+	}
+}
+
+/// Error returned when a guess is submitted to a game that is already won or
+/// lost.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct AlreadyFinished;
+
+/// Formats AlreadyFinished as a message that can be shown to the user.
+impl Display for AlreadyFinished
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		write!(f, "the game is already finished")
+	}
+}
+
+impl std::error::Error for AlreadyFinished {}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Creates a game with a secret picked by rng, where the player gets
+	/// attempts valid guesses.
+	///
+	/// Passing a seeded rng, such as StdRng::seed_from_u64, makes the secret the
+	/// same every time, which is useful for tests and recorded demos.
+	pub fn new(
+		attempts: u32,
+		rng: &mut impl Rng,
+	) -> Self
+	{
+		Game::with_secret(attempts, rng.gen())
+	}
+
+	/// Creates a game with a secret picked by rng, where the player gets as many
+	/// attempts as fair_attempts gives for RANGE with a slack of 1. That is just
+	/// enough to always win by halving what is left with each guess.
+	pub fn new_fair(rng: &mut impl Rng) -> Self
+	{
+		Game::new(fair_attempts(Guess::<RANGE>::span(), FAIR_SLACK), rng)
+	}
+
+	/// Creates a game with a secret picked by thread_rng, where the player gets
+	/// attempts valid guesses.
+	pub fn new_random(attempts: u32) -> Self
+	{
+		Game::new(attempts, &mut thread_rng())
+	}
+
+	/// Creates a game whose secret is secret, where the player gets attempts
+	/// valid guesses, or one if attempts is 0. See Game::set_attempts for
+	/// practice games.
+	pub fn with_secret(
+		attempts: u32,
+		secret: Guess<RANGE>,
+	) -> Self
+	{
+		Game {
+			secret: Secret::new(secret),
+			attempts: Some(NonZeroU32::new(attempts).unwrap_or(NonZeroU32::MIN)),
+			range: GuessRange::full(),
+			history: Vec::new(),
+			knowledge: KnowledgeState::new(GuessRange::full()),
+			late: None,
+			clues: Vec::new(),
+			gave_up: false,
+			timed_out: false,
+			bonus_earned: false,
+			lie: None,
+			clock: Arc::new(SystemClock),
+			started: SystemClock.now(),
+			paused: Duration::ZERO,
+			interrupt: None,
+			output_closed: false,
+			run_started: None,
+			run_stopped: None,
+			observers: Vec::new(),
+			players: Vec::new(),
+			announced: false,
+			time_limit: None,
+			style: Style::Fancy,
+			hints: Hints::Heat,
+			thresholds: HeatThresholds::default(),
+			forgive_duplicates: false,
+			bonus: None,
+			show_odds: false,
+			speedrun: false,
+		}
+	}
+
+	/// Returns the secret, still hidden, so that it can be compared with
+	/// another game's without spoiling it.
+	pub const fn secret(&self) -> Secret<Guess<RANGE>>
+	{
+		self.secret
+	}
+
+	/// Returns how many valid guesses the player gets, or None in practice.
+	pub const fn attempts(&self) -> Option<NonZeroU32>
+	{
+		self.attempts
+	}
+
+	/// Sets how many valid guesses the player gets, where None makes the game
+	/// practice: it takes as many guesses as the player needs and can only be
+	/// lost by giving up. The attempts already used still count.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::Game,
+	/// // 	rand::thread_rng,
+	/// //};
+	/// // let mut game = Game::<{ 0..=1024 }>::new(10, &mut thread_rng());
+	/// // game.set_attempts(None);
+	/// // assert_eq!(game.attempts_left(), None);
+	/// ```
+	pub fn set_attempts(
+		&mut self,
+		attempts: Option<NonZeroU32>,
+	)
+	{
+		self.attempts = attempts;
+	}
+
+	/// Returns whether the game is practice, with as many attempts as it takes.
+	pub const fn is_practice(&self) -> bool
+	{
+		self.attempts.is_none()
+	}
+
+	/// Returns the guesses that play accepts. This is all of RANGE, except for
+	/// games created with Game::with_difficulty.
+	pub const fn range(&self) -> GuessRange<RANGE>
+	{
+		self.range
+	}
+
+	/// Only accepts guesses within range from now on, which should hold the
+	/// secret, and works out what the history tells about it anew.
+	pub(crate) fn set_range(
+		&mut self,
+		range: GuessRange<RANGE>,
+	)
+	{
+		self.range = range;
+		self.knowledge = KnowledgeState::from_history(range, self.history.iter().map(|entry| (entry.guess, entry.feedback)));
+	}
+
+	/// Moves a game that was not started yet to range, with secret from it.
+	pub(crate) fn rerange(
+		&mut self,
+		range: GuessRange<RANGE>,
+		secret: Guess<RANGE>,
+	)
+	{
+		self.set_range(range);
+		self.secret = Secret::new(secret);
+	}
+
+	/// Returns how many attempts the player has used so far, on guesses and on
+	/// hints.
+	pub fn attempts_used(&self) -> u32
+	{
+		(self.history.len() + self.clues.len()) as u32
+	}
+
+	/// Returns how many attempts the player has left, counting the bonus
+	/// attempt if it was earned, or None in practice.
+	pub fn attempts_left(&self) -> Option<u32>
+	{
+		self.attempts.map(|attempts| (attempts.get() + u32::from(self.bonus_earned)).saturating_sub(self.attempts_used()))
+	}
+
+	/// Returns whether the player earned the bonus attempt of Game::bonus.
+	pub const fn bonus_earned(&self) -> bool
+	{
+		self.bonus_earned
+	}
+
+	/// Returns the ScoreConfig with the default points for this game's range
+	/// and attempts. A game with a time limit uses it as the par, and practice
+	/// earns nothing for unused attempts.
+	pub fn score_config(&self) -> ScoreConfig
+	{
+		let config = ScoreConfig::new(self.range.size(), self.attempts.map_or(0_u32, NonZeroU32::get));
+		ScoreConfig { par: self.time_limit.unwrap_or(config.par), ..config }
+	}
+
+	/// Replaces the clock that times the game, such as a clock that tests can
+	/// move by hand, and starts the time over on it. Call it before the first
+	/// guess, or the times in the history will not add up.
+	pub fn set_clock(
+		&mut self,
+		clock: Arc<dyn Clock>,
+	)
+	{
+		self.started = clock.now();
+		self.clock = clock;
+	}
+
+	/// Returns how long ago the game was created, leaving out the time it was
+	/// paused.
+	pub fn elapsed(&self) -> Duration
+	{
+		self.clock.now().saturating_duration_since(self.started).saturating_sub(self.paused)
+	}
+
+	/// Returns how long the game was paused in total. See PAUSE_COMMANDS.
+	pub const fn paused(&self) -> Duration
+	{
+		self.paused
+	}
+
+	/// Returns whether play aborted the game because nobody reads its output
+	/// anymore, as with `guessing_game | head`, rather than because the player
+	/// quit.
+	pub const fn output_closed(&self) -> bool
+	{
+		self.output_closed
+	}
+
+	/// Makes play end the game as aborted once flag is set, such as by a Ctrl-C
+	/// handler, showing how far the player got but not the answer.
+	///
+	/// A blocking read can not be interrupted portably, so the flag is checked
+	/// before each prompt and after each line that is read.
+	pub fn set_interrupt(
+		&mut self,
+		flag: Arc<AtomicBool>,
+	)
+	{
+		self.interrupt = Some(flag);
+	}
+
+	/// Returns how long a speedrun took from its first prompt to the correct
+	/// answer, or None if the game is not a speedrun or was not won. Time before
+	/// play showed the first prompt does not count, unlike for Game::elapsed, and
+	/// neither does the time it was paused.
+	pub fn run_time(&self) -> Option<Duration>
+	{
+		Some(self.run_stopped?.saturating_duration_since(self.run_started?).saturating_sub(self.paused))
+	}
+
+	/// Returns how much of the time limit is left, or None if there is no limit.
+	pub fn time_left(&self) -> Option<Duration>
+	{
+		self.time_limit.map(|limit| limit.saturating_sub(self.elapsed()))
+	}
+
+	/// Returns the valid guesses the player has made so far, oldest first.
+	/// Invalid guesses do not use up attempts, so they are not included.
+	pub fn history(&self) -> &[HistoryEntry<RANGE>]
+	{
+		&self.history
+	}
+
+	/// Returns what the feedback so far tells about the secret, within the
+	/// game's range. A game that lied may contradict itself.
+	pub const fn knowledge(&self) -> &KnowledgeState<RANGE>
+	{
+		&self.knowledge
+	}
+
+	/// Returns where the secret can be according to the feedback so far, as
+	/// Bounds. See Game::knowledge.
+	pub const fn bounds(&self) -> Bounds<RANGE>
+	{
+		self.knowledge.bounds()
+	}
+
+	/// Returns the chance of still winning with optimal play given the bounds
+	/// and attempts left, or None in practice. See win_probability.
+	pub fn win_probability(&self) -> Option<f64>
+	{
+		Some(win_probability(&self.bounds(), self.attempts_left()?))
+	}
+
+	/// Returns the clues the player has been given so far, oldest first. See
+	/// Game::hint.
+	pub fn clues(&self) -> &[Clue<RANGE>]
+	{
+		&self.clues
+	}
+
+	/// Returns whether the last guess was closer to the secret than the one
+	/// before it, or None if there have not been two guesses yet.
+	pub fn trend(&self) -> Option<Trend>
+	{
+		let [.., previous, last] = self.history.as_slice()
+		else
+		{
+			return None;
+		};
+		Some(Trend::of(last.guess, previous.guess, self.secret.reveal()))
+	}
+
+	/// Returns the guesses so far on one line, each marked with an arrow that
+	/// points the way it was off, as in "Your guesses: 512↑ 256↓ 384↑ 320 ✔".
+	pub fn recap(&self) -> String
+	{
+		let guesses: Vec<_> = self.history.iter().map(mark).collect();
+		format!("Your guesses: {}", guesses.join(" "))
+	}
+
+	/// Makes the game co-op, where players take turns in order and share the
+	/// attempts, so they win or lose together. The turn passes on with each
+	/// guess that is recorded, so an invalid guess, a hint or a forgiven
+	/// duplicate leaves it with the same player. No players, or one, is the usual
+	/// game for one player.
+	pub fn set_players(
+		&mut self,
+		players: Vec<String>,
+	)
+	{
+		self.players = players;
+	}
+
+	/// Returns the players taking turns in co-op, in order. See
+	/// Game::set_players.
+	pub fn players(&self) -> &[String]
+	{
+		&self.players
+	}
+
+	/// Returns the name of the player whose turn it is in co-op, or None for a
+	/// game for one player.
+	pub fn current_player(&self) -> Option<&str>
+	{
+		self.guesser(self.history.len())
+	}
+
+	/// Returns the name of the player who made the guess at index of the history
+	/// in co-op, or None for a game for one player.
+	pub fn guesser(
+		&self,
+		index: usize,
+	) -> Option<&str>
+	{
+		(self.players.len() > 1).then(|| self.players[index % self.players.len()].as_str())
+	}
+
+	/// Returns who made each guess so far on one line in co-op, marked as in
+	/// Game::recap, as in "Who guessed what: Ann 512↑, Ben 256↓, Ann 320 ✔", or
+	/// None for a game for one player.
+	pub fn attribution(&self) -> Option<String>
+	{
+		self.guesser(0)?;
+		let guesses: Vec<_> = self
+			.history
+			.iter()
+			.enumerate()
+			.filter_map(|(index, entry)| Some(format!("{} {}", self.guesser(index)?, mark(entry))))
+			.collect();
+		Some(format!("Who guessed what: {}", guesses.join(", ")))
+	}
+
+	/// Returns how long each guess so far took on one line, followed by how long
+	/// they took together, as in "Your times: 3.2s 1.5s 4.0s (8.7s in total)".
+	pub fn timings(&self) -> String
+	{
+		let mut previous = Duration::ZERO;
+		let times: Vec<_> = self
+			.history
+			.iter()
+			.map(|entry| {
+				let time = entry.elapsed.saturating_sub(previous);
+				previous = entry.elapsed;
+				seconds(time)
+			})
+			.collect();
+		format!("Your times: {} ({} in total)", times.join(" "), seconds(previous))
+	}
+
+	/// Returns whether the game is won or lost, so that it takes no more
+	/// guesses.
+	pub fn is_finished(&self) -> bool
+	{
+		self.outcome().is_some()
+	}
+
+	/// Takes the player's next guess and returns what happened to it.
+	///
+	/// A guess that comes after Game::time_limit ran out is not recorded, and
+	/// loses the game with TurnResult::TimedOut instead. With
+	/// Game::forgive_duplicates, a guess that was already made is not recorded
+	/// either, and gets TurnResult::Duplicate.
+	///
+	/// # Errors
+	/// Returns AlreadyFinished if the game is already won or lost. The guess is
+	/// not recorded then.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::{
+	/// // 		Game,
+	/// // 		Guess,
+	/// // 		TurnResult,
+	/// // 	},
+	/// // 	rand::thread_rng,
+	/// //};
+	/// // let mut game = Game::<{ 0..=1024 }>::new(10, &mut thread_rng());
+	/// // match game.submit(Guess::new(512).expect("")).expect("")
+	/// // {
+	/// // 	TurnResult::TooHigh { attempts_left } => println!("Lower! {attempts_left:?} left."),
+	/// // 	TurnResult::TooLow { attempts_left } => println!("Higher! {attempts_left:?} left."),
+	/// // 	TurnResult::Win { .. } => println!("Lucky!"),
+	/// // 	TurnResult::Loss { answer } | TurnResult::TimedOut { answer } => println!("It was {answer}."),
+	/// // 	TurnResult::Duplicate { .. } => println!("Again?"),
+	/// // }
+	/// ```
+	pub fn submit(
+		&mut self,
+		guess: Guess<RANGE>,
+	) -> Result<TurnResult<RANGE>, AlreadyFinished>
+	{
+		if self.is_finished()
+		{
+			return Err(AlreadyFinished);
+		}
+		self.announce();
+		let answer = self.secret.reveal();
+		let elapsed = self.elapsed();
+		let feedback = self.told(self.history.len() as u32 + 1, Feedback::from(guess.cmp(&answer)));
+		let entry = HistoryEntry { attempt: self.attempts_used() + 1, guess, feedback, elapsed };
+		if self.time_limit.is_some_and(|limit| elapsed > limit)
+		{
+			self.timed_out = true;
+			self.late = Some(entry);
+			self.end(&GameOutcome::TimedOut { answer });
+			return Ok(TurnResult::TimedOut { answer });
+		}
+		if self.forgive_duplicates
+			&& let Some(previous) = self.history.iter().find(|previous| previous.guess == guess)
+		{
+			return Ok(TurnResult::Duplicate { feedback: previous.feedback });
+		}
+		self.history.push(entry);
+		self.knowledge.update(guess, feedback);
+		if feedback.is_correct() && self.run_started.is_some()
+		{
+			self.run_stopped = Some(self.clock.now());
+		}
+		for observer in &self.observers
+		{
+			observer.on_guess(entry.attempt, guess, entry.feedback);
+		}
+		// Practice has no attempts to add one to.
+		if !self.bonus_earned
+			&& self.attempts.is_some()
+			&& !entry.feedback.is_correct()
+			&& self.bonus.is_some_and(|bonus| guess.distance(answer) <= bonus.within)
+		{
+			self.bonus_earned = true;
+		}
+		if let Some(outcome) = self.outcome()
+		{
+			self.end(&outcome);
+		}
+		let attempts_left = self.attempts_left();
+		Ok(match feedback
+		{
+			Feedback::Correct => TurnResult::Win { attempts_used: self.attempts_used() },
+			_ if attempts_left == Some(0_u32) => TurnResult::Loss { answer },
+			Feedback::TooHigh => TurnResult::TooHigh { attempts_left },
+			Feedback::TooLow => TurnResult::TooLow { attempts_left },
+		})
+	}
+
+	/// Makes the game lie about one guess, turning "too high" into "too low" or
+	/// the other way around, and gives the player ilog2 of the attempts plus one
+	/// extra attempts, enough to work out which answer was the lie. Which guess
+	/// gets the lie is picked by rng up front, among the first guesses a binary
+	/// search of the range would make. A correct guess is never lied about, so a
+	/// game won sooner has no lie at all.
+	///
+	/// Call it before the first guess. Practice games get no extra attempts, but
+	/// lie all the same.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::Game,
+	/// // 	rand::thread_rng,
+	/// //};
+	/// // let mut game = Game::<{ 0..=1024 }>::new(11, &mut thread_rng());
+	/// // game.lie_once(&mut thread_rng());
+	/// // assert_eq!(game.attempts_left(), Some(15));
+	/// ```
+	pub fn lie_once(
+		&mut self,
+		rng: &mut impl Rng,
+	)
+	{
+		self.attempts = self.attempts.map(|attempts| attempts.saturating_add(attempts.ilog2() + 1_u32));
+		self.lie = Some(rng.gen_range(1..=fair_attempts(self.range.size(), 0).max(1_u32)));
+	}
+
+	/// Returns whether the game lies once. See Game::lie_once.
+	pub const fn is_deceptive(&self) -> bool
+	{
+		self.lie.is_some()
+	}
+
+	/// Returns which guess the game lied about, counting from 1, or None if it
+	/// has not lied so far. This gives the lie away, so it is meant for after the
+	/// game.
+	pub fn lied_on(&self) -> Option<u32>
+	{
+		self.lie.filter(|&lie| self.history.get(lie as usize - 1).is_some_and(|entry| !entry.feedback.is_correct()))
+	}
+
+	/// Returns the line that owns up to the lie after a game that could lie, as
+	/// in "I lied on guess #3. 😈", or None if the game can not lie or is not
+	/// finished.
+	pub fn confession(&self) -> Option<String>
+	{
+		if !self.is_deceptive() || !self.is_finished()
+		{
+			return None;
+		}
+		Some(match self.lied_on()
+		{
+			Some(lie) => format!("I lied on guess #{lie}. 😈"),
+			None => String::from("I never got around to lying this time."),
+		})
+	}
+
+	/// Returns the feedback the game gives about guess number number, whose true
+	/// feedback is truth.
+	fn told(
+		&self,
+		number: u32,
+		truth: Feedback,
+	) -> Feedback
+	{
+		if self.lie == Some(number) { lie_about(truth) } else { truth }
+	}
+
+	/// Gives the player the next clue about the secret from HintOracle, which
+	/// costs an attempt. A hint on the last attempt loses the game, though the
+	/// clue is still returned.
+	///
+	/// Returns None without using an attempt once every clue has been given, so
+	/// a clue is never repeated.
+	///
+	/// # Errors
+	/// Returns AlreadyFinished if the game is already won or lost.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::Game,
+	/// // 	rand::thread_rng,
+	/// //};
+	/// // let mut game = Game::<{ 0..=1024 }>::new(10, &mut thread_rng());
+	/// // if let Some(clue) = game.hint().expect("")
+	/// // {
+	/// // 	println!("{clue} You have {:?} attempts left.", game.attempts_left());
+	/// // }
+	/// ```
+	pub fn hint(&mut self) -> Result<Option<Clue<RANGE>>, AlreadyFinished>
+	{
+		if self.is_finished()
+		{
+			return Err(AlreadyFinished);
+		}
+		self.announce();
+		let clue = HintOracle::new(self.secret.reveal(), self.range).nth(self.clues.len());
+		self.clues.extend(clue);
+		if let Some(outcome) = self.outcome()
+		{
+			self.end(&outcome);
+		}
+		Ok(clue)
+	}
+
+	/// Adds observer to the observers that are told about the game as it is
+	/// played, whether with play or submit. A clone of the game shares its
+	/// observers with the original.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::{
+	/// // 		Game,
+	/// // 		Guess,
+	/// // 		TranscriptObserver,
+	/// // 	},
+	/// // 	std::sync::Arc,
+	/// //};
+	/// // let transcript = Arc::new(TranscriptObserver::new());
+	/// // let mut game = Game::<{ 0..=1024 }>::new_random(10);
+	/// // game.add_observer(transcript.clone());
+	/// // game.submit(Guess::new(512).expect("")).expect("");
+	/// // print!("{}", transcript.transcript());
+	/// ```
+	pub fn add_observer(
+		&mut self,
+		observer: Arc<dyn GameObserver<RANGE>>,
+	)
+	{
+		self.observers.push(observer);
+	}
+
+	/// Tells the observers that the game started, unless they were told before.
+	fn announce(&mut self)
+	{
+		if !self.announced
+		{
+			self.announced = true;
+			for observer in &self.observers
+			{
+				observer.on_game_start(self.range, self.attempts);
+			}
+		}
+	}
+
+	/// Tells the observers that the game ended with outcome.
+	fn end(
+		&self,
+		outcome: &GameOutcome<RANGE>,
+	)
+	{
+		for observer in &self.observers
+		{
+			observer.on_game_end(outcome);
+		}
+	}
+
+	/// Lets strategy play the rest of the game without reading or writing
+	/// anything, and returns how it ended.
+	///
+	/// The strategy sees every guess so far with its feedback, including guesses
+	/// submitted before this was called. If the game is already finished, returns
+	/// how it ended without asking strategy for a guess. In practice, a strategy
+	/// that never finds the secret plays forever, and so does one that repeats a
+	/// guess forever with Game::forgive_duplicates.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::{
+	/// // 		BinarySearchStrategy,
+	/// // 		Game,
+	/// // 	},
+	/// // 	rand::thread_rng,
+	/// //};
+	/// // let mut game = Game::<{ 0..=1024 }>::new(11, &mut thread_rng());
+	/// // println!("{:?}", game.play_with_strategy(&mut BinarySearchStrategy::default()));
+	/// ```
+	pub fn play_with_strategy(
+		&mut self,
+		strategy: &mut impl Strategy<RANGE>,
+	) -> GameOutcome<RANGE>
+	{
+		loop
+		{
+			if let Some(outcome) = self.outcome()
+			{
+				return outcome;
+			}
+			let history: Vec<_> = self.history.iter().map(|entry| (entry.guess, entry.feedback)).collect();
+			let guess = strategy.next_guess(&history);
+			self.submit(guess).expect("the game is not finished while it is played.");
+		}
+	}
+
+	/// Returns how the game ended, or None if it is still going.
+	pub(crate) fn outcome(&self) -> Option<GameOutcome<RANGE>>
+	{
+		if self.history.last().is_some_and(|entry| entry.feedback.is_correct())
+		{
+			Some(GameOutcome::Won { attempts_used: self.attempts_used() })
+		}
+		else if self.timed_out
+		{
+			Some(GameOutcome::TimedOut { answer: self.secret.reveal() })
+		}
+		else if self.gave_up || self.attempts_left() == Some(0_u32)
+		{
+			Some(GameOutcome::Lost { answer: self.secret.reveal() })
+		}
+		else
+		{
+			None
+		}
+	}
+
+	/// Plays the game, reading guesses and commands from input and writing
+	/// everything to output, until it is won, lost or aborted.
+	///
+	/// Invalid guesses do not use up attempts. Typing a word in QUIT_COMMANDS
+	/// aborts the game, a word in GIVE_UP_COMMANDS loses it and a word in
+	/// HINT_COMMANDS trades an attempt for a clue, as with Game::hint. A word in
+	/// PAUSE_COMMANDS stops the clock until Enter is pressed, and a word in
+	/// ABORT_COMMANDS, or the flag of Game::set_interrupt, aborts the game with a
+	/// line on how far the player got. If the input ends or fails, the game is
+	/// aborted with an explanation, except when output is a closed pipe, which is
+	/// aborted silently. An aborted game can be played again to pick up where it
+	/// stopped, while a game that is already won or lost returns how it ended
+	/// without reading or writing anything.
+	pub fn play(
+		&mut self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> GameOutcome<RANGE>
+	{
+		if let Some(outcome) = self.outcome()
+		{
+			return outcome;
+		}
+		self.output_closed = false;
+		let outcome = match self.run(input, output)
+		{
+			Ok(outcome) => outcome,
+			// Nobody is reading the output anymore, as with `guessing_game | head`.
+			Err(error) if error.kind() == ErrorKind::BrokenPipe =>
+			{
+				self.output_closed = true;
+				GameOutcome::Aborted
+			},
+			Err(error) =>
+			{
+				// The output might be what failed, so the explanation can fail too.
+				let _ = self.say(output.errors(), format!("\n\nGame aborted because {error}. 👋\n\n").yellow());
+				GameOutcome::Aborted
+			},
+		};
+		// A finished game already told the observers when it finished.
+		if outcome == GameOutcome::Aborted
+		{
+			self.end(&outcome);
+		}
+		outcome
+	}
+
+	/// Plays the game, returning an error if reading or writing fails.
+	fn run(
+		&mut self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> io::Result<GameOutcome<RANGE>>
+	{
+		self.announce();
+		let (min, max) = (self.range.lo(), self.range.hi());
+		let answer = self.secret.reveal();
+		let greeting = match self.attempts
+		{
+			Some(attempts) => format!("\n\nI'm thinking of a number somwhere from {min} through {max}. You have {attempts} attempts to guess it! 😈"),
+			None => format!("\n\nI'm thinking of a number somwhere from {min} through {max}. Guess it! 😈"),
+		};
+		self.say(output.messages(), greeting.green())?;
+		self.say(output.messages(), "Type \"hint\" to trade an attempt for a clue, \"give up\" to see the answer or \"quit\" to leave.".yellow())?;
+		if self.is_practice()
+		{
+			self.say(output.messages(), "This is practice, so take as many guesses as you like.".yellow())?;
+		}
+		if self.is_deceptive()
+		{
+			self.say(output.messages(), "Careful: I may lie to you once, which is why you get a few extra attempts.".yellow())?;
+		}
+		if let Some(limit) = self.time_limit
+		{
+			self.say(output.messages(), format!("The clock is ticking: you have {} seconds. ⏱", limit.as_secs()).yellow())?;
+		}
+		if self.speedrun && self.run_started.is_none()
+		{
+			self.run_started = Some(self.clock.now());
+			self.say(output.messages(), "This is a speedrun, so the clock starts now. Go! ⏱".yellow())?;
+		}
+		if self.time_limit.is_some() || self.speedrun
+		{
+			self.say(output.messages(), "Type \"pause\" to stop the clock for a while.".yellow())?;
+		}
+		let options = InputOptions { style: self.style, ..InputOptions::default() };
+		let theme = ResponseTheme { hints: self.hints, ..ResponseTheme::default() };
+		loop
+		{
+			if self.is_interrupted()
+			{
+				return self.abort(output);
+			}
+			let prompt = |context: InputContext| {
+				let tip = format!("Tip: Enter a whole number from {min} through {max}, like {}, or type \"quit\" to leave.", self.range.midpoint());
+				// In co-op the same player keeps the turn until their guess counts.
+				let turn = self.current_player().map_or_else(String::new, |player| format!("{player}'s turn. "));
+				// The time left is counted down each time the prompt is shown.
+				let Some(remaining) = self.remaining()
+				else
+				{
+					// Practice counts attempts up instead, and an invalid guess does
+					// not move the count.
+					let attempt = self.attempt_number();
+					return match context.invalid_tries
+					{
+						TIP_AFTER_INVALID => format!("\n{tip}\n{turn}{attempt}. Guess: ").yellow(),
+						_ => format!("{turn}{attempt}. Guess: ").yellow(),
+					};
+				};
+				match context.invalid_tries
+				{
+					0 => format!("{turn}You have {remaining} remaining. Guess: ").yellow(),
+					// Offer help once the player has struggled for a while.
+					TIP_AFTER_INVALID => format!("\n{tip}\n{turn}You still have {remaining} remaining. Guess: ").yellow(),
+					_ => format!("{turn}That one didn't count, you still have {remaining} remaining. Guess: ").yellow(),
+				}
+			};
+			let prompt = ObservedPrompt { prompt, observers: &self.observers };
+			let action = input_or_command_within(prompt, &options, self.range, input, output);
+			// Ctrl-C while reading only shows once the line is in.
+			if self.is_interrupted()
+			{
+				return self.abort(output);
+			}
+			let guess = match action
+			{
+				Ok(PlayerAction::Guess(guess)) => guess,
+				Ok(PlayerAction::Quit) =>
+				{
+					self.say(output.messages(), "\n\nThanks for playing! 👋\n\n".yellow())?;
+					return Ok(GameOutcome::Aborted);
+				},
+				Ok(PlayerAction::GiveUp) =>
+				{
+					self.gave_up = true;
+					self.end(&GameOutcome::Lost { answer });
+					self.say(output.messages(), format!("\n\nThe number was {answer}. Better luck next time! 🙃\n\n").yellow())?;
+					return Ok(GameOutcome::Lost { answer });
+				},
+				Ok(PlayerAction::Hint) =>
+				{
+					match self.hint().expect("the game is not finished while it is played.")
+					{
+						Some(clue) => self.say(output.messages(), format!("\nHint: {clue}\n").cyan())?,
+						None => self.say(output.messages(), "\nThat's all the hints there are, so that one was free.\n".cyan())?,
+					}
+					if self.attempts_left() == Some(0_u32)
+					{
+						self.say(output.messages(), format!("You're out of guesses! The number was {answer}. Game over. 😢\n\n").red())?;
+						return Ok(GameOutcome::Lost { answer });
+					}
+					continue;
+				},
+				Ok(PlayerAction::Pause) =>
+				{
+					self.pause(input, output)?;
+					continue;
+				},
+				Ok(PlayerAction::Abort) => return self.abort(output),
+				Err(InputError::Io(error)) => return Err(error),
+				Err(error) =>
+				{
+					self.say(output.messages(), format!("\n\nGame aborted because {error}. 👋\n\n").yellow())?;
+					return Ok(GameOutcome::Aborted);
+				},
+			};
+			let had_bonus = self.bonus_earned;
+			let turn = self.submit(guess).expect("the game is not finished while it is played.");
+			if let TurnResult::TimedOut { answer } = turn
+			{
+				self.say(output.messages(), format!("\n\nTime's up! {guess} came too late, and the number was {answer}. ⏱\n\n").red())?;
+				return Ok(GameOutcome::TimedOut { answer });
+			}
+			if let TurnResult::Duplicate { feedback } = turn
+			{
+				// Without hints the direction is not given away the second time either.
+				let verdict = match (self.hints, feedback)
+				{
+					(Hints::None, _) => "wrong",
+					(_, Feedback::TooHigh) => "too high",
+					(_, Feedback::TooLow | Feedback::Correct) => "too low",
+				};
+				self.say(output.messages(), format!("You already tried {guess}, and it's still {verdict}. That one didn't count.").yellow())?;
+				continue;
+			}
+			let note = match self.hints
+			{
+				Hints::None | Hints::Direction => None,
+				Hints::Heat => Some(self.thresholds.heat(guess.distance(answer), self.range.size()).colored()),
+			};
+			let feedback = self.history.last().expect("the guess was recorded.").feedback;
+			// The odds would give away a lie, or the direction that Hints::None hides.
+			let odds = self.win_probability().filter(|_| self.show_odds && !self.is_deceptive() && self.hints != Hints::None);
+			let _ = respond_with_feedback(guess, feedback, note, odds, &theme, self.style, &mut output.messages())?;
+			if self.bonus_earned && !had_bonus
+			{
+				self.say(output.messages(), "So close - have an extra attempt! (+1)".green())?;
+			}
+			match turn
+			{
+				TurnResult::TooHigh { .. } | TurnResult::TooLow { .. } | TurnResult::TimedOut { .. } | TurnResult::Duplicate { .. } => (),
+				TurnResult::Win { attempts_used } =>
+				{
+					let tries = if attempts_used == 1 { "try" } else { "tries" };
+					let message = match self.run_time()
+					{
+						Some(time) => format!("You got {guess} in {attempts_used} {tries} and {}! ⏱\n\n", seconds(time)),
+						None => format!("You got {guess} in {attempts_used} {tries}!\n\n"),
+					};
+					self.say(output.messages(), message.green())?;
+					return Ok(GameOutcome::Won { attempts_used });
+				},
+				TurnResult::Loss { answer } =>
+				{
+					self.say(output.messages(), "\nYou're out of guesses! Game over. 😢\n\n".red())?;
+					return Ok(GameOutcome::Lost { answer });
+				},
+			}
+		}
+	}
+
+	/// Stops the clock until the player presses Enter, so that the wait counts
+	/// neither towards the time limit nor towards a speedrun.
+	fn pause(
+		&mut self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> io::Result<()>
+	{
+		let paused_at = self.clock.now();
+		write!(output.prompts(), "{}", self.style.render(&"Paused — press Enter to continue. ".yellow()))?;
+		output.prompts().flush()?;
+		// An input that ends here is noticed by the next prompt.
+		read_line_limited(input, &mut Vec::new())?;
+		self.paused += self.clock.now().saturating_duration_since(paused_at);
+		Ok(())
+	}
+
+	/// Returns whether the flag of Game::set_interrupt is set.
+	fn is_interrupted(&self) -> bool
+	{
+		self.interrupt.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+	}
+
+	/// Ends the game early, with a line on how far the player got that does not
+	/// give the answer away.
+	fn abort(
+		&self,
+		output: &mut impl InputOutput,
+	) -> io::Result<GameOutcome<RANGE>>
+	{
+		self.say(output.messages(), format!("\n\nGame aborted. {} 👋\n\n", self.standing()).yellow())?;
+		Ok(GameOutcome::Aborted)
+	}
+
+	/// Returns how far the player got, as in "So far you used 2 attempts in
+	/// 12.3s, and the number is somewhere in 257..=511." The interval is left
+	/// out without hints, which it would give away, and when the feedback
+	/// contradicts itself.
+	fn standing(&self) -> String
+	{
+		let used = self.attempts_used();
+		let attempts = if used == 1 { "attempt" } else { "attempts" };
+		let so_far = format!("So far you used {used} {attempts} in {}", seconds(self.elapsed()));
+		match self.knowledge.range().filter(|_| self.hints != Hints::None)
+		{
+			Some(range) => format!("{so_far}, and the number is somewhere in {}..={}.", range.lo(), range.hi()),
+			None => format!("{so_far}."),
+		}
+	}
+
+	/// Returns what the player has left to win with, as in "5 attempts" or "5
+	/// attempts and 42 seconds", or None in practice.
+	fn remaining(&self) -> Option<String>
+	{
+		let attempts = self.attempts_left()?;
+		Some(match self.time_left()
+		{
+			None => format!("{attempts} attempts"),
+			Some(left) => format!("{attempts} attempts and {} seconds", left.as_secs()),
+		})
+	}
+
+	/// Returns which attempt the player is on, as in "Attempt #3", "Attempt #3
+	/// with 42 seconds left" or "Attempt #3 at 12.3s" in a speedrun, for
+	/// practice.
+	fn attempt_number(&self) -> String
+	{
+		let attempt = self.attempts_used() + 1;
+		match (self.time_left(), self.run_started)
+		{
+			(Some(left), _) => format!("Attempt #{attempt} with {} seconds left", left.as_secs()),
+			(None, Some(started)) => format!("Attempt #{attempt} at {}", seconds(self.clock.now().saturating_duration_since(started).saturating_sub(self.paused))),
+			(None, None) => format!("Attempt #{attempt}"),
+		}
+	}
+
+	/// Writes text on a line of its own, in the style of the game.
+	fn say(
+		&self,
+		output: &mut dyn Write,
+		text: ColoredString,
+	) -> io::Result<()>
+	{
+		writeln!(output, "{}", self.style.render(&text))
+	}
+}
+
+/// Returns guess of entry marked with an arrow that points the way it was off,
+/// or with a check mark if it was correct, as in "512↑" or "320 ✔".
+fn mark<const RANGE: RangeInclusive<i32>>(entry: &HistoryEntry<RANGE>) -> String
+{
+	match entry.feedback
+	{
+		Feedback::TooHigh => format!("{}↑", entry.guess),
+		Feedback::TooLow => format!("{}↓", entry.guess),
+		Feedback::Correct => format!("{} ✔", entry.guess),
+	}
+}
+
+/// A Prompt that tells observers about every line it rejects.
+struct ObservedPrompt<'a, const RANGE: RangeInclusive<i32>, P>
+{
+	prompt: P,
+	observers: &'a [Arc<dyn GameObserver<RANGE>>],
+}
+
+impl<const RANGE: RangeInclusive<i32>, P: Prompt> Prompt for ObservedPrompt<'_, RANGE, P>
+{
+	fn prompt(
+		&mut self,
+		context: InputContext,
+	) -> ColoredString
+	{
+		self.prompt.prompt(context)
+	}
+
+	fn rejected(
+		&mut self,
+		line: &str,
+	)
+	{
+		self.prompt.rejected(line);
+		for observer in self.observers
+		{
+			observer.on_invalid_input(line);
+		}
+	}
+}
+
+impl Game<DIFFICULTY_RANGE>
+{
+	/// Creates a game set up for difficulty, with a secret picked by
+	/// thread_rng.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::{
+	/// // 		Difficulty,
+	/// // 		Game,
+	/// // 	},
+	/// // 	std::io::{
+	/// // 		stdin,
+	/// // 		stdout,
+	/// // 	},
+	/// //};
+	/// // let mut game = Game::with_difficulty(Difficulty::Hard);
+	/// // game.play(&mut stdin().lock(), &mut stdout());
+	/// ```
+	pub fn with_difficulty(difficulty: Difficulty) -> Self
+	{
+		Game::with_difficulty_and_rng(difficulty, &mut thread_rng())
+	}
+
+	/// Creates a game set up for difficulty, with a secret picked by rng.
+	///
+	/// The game only accepts guesses within Difficulty::range, gives
+	/// Difficulty::attempts attempts and hints as much as Difficulty::hints.
+	pub fn with_difficulty_and_rng(
+		difficulty: Difficulty,
+		rng: &mut impl Rng,
+	) -> Self
+	{
+		let secret = Guess { value: rng.gen_range(difficulty.range()) };
+		Game::with_difficulty_and_secret(difficulty, secret)
+	}
+
+	/// Creates a game set up for difficulty like Game::with_difficulty_and_rng,
+	/// but hinting as much as hints, with Difficulty::attempts_with attempts.
+	pub fn with_difficulty_and_hints(
+		difficulty: Difficulty,
+		hints: Hints,
+		rng: &mut impl Rng,
+	) -> Self
+	{
+		Game { attempts: NonZeroU32::new(difficulty.attempts_with(hints)), hints, ..Game::with_difficulty_and_rng(difficulty, rng) }
+	}
+
+	/// Creates a game set up for difficulty whose secret is secret, which has to
+	/// lie within Difficulty::range.
+	pub(crate) fn with_difficulty_and_secret(
+		difficulty: Difficulty,
+		secret: Guess<DIFFICULTY_RANGE>,
+	) -> Self
+	{
+		let (lo, hi) = difficulty.range().into_inner();
+		let mut game = Game { hints: difficulty.hints(), ..Game::with_secret(difficulty.attempts(), secret) };
+		game.set_range(GuessRange { lo: Guess { value: lo }, hi: Guess { value: hi } });
+		game
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			clock::TestClock,
+			BinarySearchStrategy,
+			LinearStrategy,
+			WithErrors,
+		},
+		rand::{
+			rngs::StdRng,
+			SeedableRng,
+		},
+		std::{
+			collections::VecDeque,
+			io::Read,
+		},
+	};
+
+	/// Input that takes the player a while to type each line, moving clock
+	/// forward by as much before the line can be read.
+	struct Typing<'a>
+	{
+		clock: &'a TestClock,
+		lines: VecDeque<(&'static str, Duration)>,
+		line: Vec<u8>,
+		position: usize,
+	}
+
+	impl<'a> Typing<'a>
+	{
+		fn new(
+			clock: &'a TestClock,
+			lines: impl IntoIterator<Item = (&'static str, Duration)>,
+		) -> Self
+		{
+			Typing { clock, lines: lines.into_iter().collect(), line: Vec::new(), position: 0 }
+		}
+	}
+
+	impl Read for Typing<'_>
+	{
+		fn read(
+			&mut self,
+			buf: &mut [u8],
+		) -> io::Result<usize>
+		{
+			let available = self.fill_buf()?;
+			let len = available.len().min(buf.len());
+			buf[..len].copy_from_slice(&available[..len]);
+			self.consume(len);
+			Ok(len)
+		}
+	}
+
+	impl BufRead for Typing<'_>
+	{
+		fn fill_buf(&mut self) -> io::Result<&[u8]>
+		{
+			if self.position == self.line.len()
+				&& let Some((line, typing)) = self.lines.pop_front()
+			{
+				self.clock.advance(typing);
+				self.line = format!("{line}\n").into_bytes();
+				self.position = 0;
+			}
+			Ok(&self.line[self.position..])
+		}
+
+		fn consume(
+			&mut self,
+			amount: usize,
+		)
+		{
+			self.position += amount;
+		}
+	}
+
+	/// Creates a game of five attempts whose secret is 300, in plain style so
+	/// that the transcript is easy to compare.
+	fn game() -> Game<{ 0..=1024 }>
+	{
+		Game { style: Style::Plain, ..Game::with_secret(5, Guess::new(300).expect("secret failed to construct.")) }
+	}
+
+	/// Plays game with lines as input, returning the outcome and what was
+	/// written to output and errors.
+	fn play<const RANGE: RangeInclusive<i32>>(
+		game: &mut Game<RANGE>,
+		lines: &str,
+	) -> (GameOutcome<RANGE>, String, String)
+	{
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = game.play(&mut lines.as_bytes(), &mut output);
+		let text = |bytes: Vec<u8>| String::from_utf8(bytes).expect("output was not UTF-8.");
+		(outcome, text(output.output), text(output.errors))
+	}
+
+	#[test]
+	fn won()
+	{
+		let (outcome, transcript, errors) = play(&mut game(), "512\nabc\n256\n\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 3 });
+		assert_eq!(
+			transcript,
+			"\n\nI'm thinking of a number somwhere from 0 through 1024. You have 5 attempts to guess it! >:)\n\
+			 Type \"hint\" to trade an attempt for a clue, \"give up\" to see the answer or \"quit\" to leave.\n\
+			 You have 5 attempts remaining. Guess: \n512 is too high! (hot) cold\n\
+			 You have 4 attempts remaining. Guess: That one didn't count, you still have 4 attempts remaining. Guess: \n256 is too low! (cold) warm\n\
+			 You have 3 attempts remaining. Guess: You have 3 attempts remaining. Guess: \nYou win! :)\n\
+			 You got 300 in 3 tries!\n\n\n"
+		);
+		assert!(errors.contains("\"abc\" is not an integer from 0 through 1024."));
+	}
+
+	#[test]
+	fn lost()
+	{
+		let (outcome, transcript, _) = play(&mut game(), "1\n2\n3\n4\n5\n300\n");
+		assert_eq!(outcome, GameOutcome::Lost { answer: Guess::new(300).expect("answer failed to construct.") });
+		assert_eq!(transcript.matches("is too low!").count(), 5);
+		assert!(transcript.ends_with("\nYou're out of guesses! Game over. :(\n\n\n"));
+		assert!(!transcript.contains("You win!"));
+	}
+
+	#[test]
+	fn gave_up()
+	{
+		let (outcome, transcript, _) = play(&mut game(), "1\ngive up\n");
+		assert_eq!(outcome, GameOutcome::Lost { answer: Guess::new(300).expect("answer failed to construct.") });
+		assert!(transcript.contains("The number was 300. Better luck next time!"));
+	}
+
+	#[test]
+	fn aborted()
+	{
+		let (outcome, transcript, _) = play(&mut game(), "1\nquit\n300\n");
+		assert_eq!(outcome, GameOutcome::Aborted);
+		assert!(transcript.contains("Thanks for playing!"));
+
+		let (outcome, transcript, _) = play(&mut game(), "1\n2\n");
+		assert_eq!(outcome, GameOutcome::Aborted);
+		assert!(transcript.ends_with("\n\nGame aborted because the input ended. \n\n\n"));
+	}
+
+	#[test]
+	fn no_hints()
+	{
+		let mut game = Game { hints: Hints::None, ..game() };
+		let (outcome, transcript, _) = play(&mut game, "512\n256\n299\n301\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 5 });
+		// The intro names the range, but nothing after it narrows it down.
+		let responses = &transcript[transcript.find("Guess: ").expect("no guess was asked for.")..];
+		for hint in ["high", "low", "hot", "warm", "cold", "close", "through", "somewhere in"]
+		{
+			assert!(!responses.contains(hint), "{hint:?} in {responses:?}");
+		}
+		assert_eq!(responses.matches("Nope, ").count(), 4);
+		assert!(responses.contains("\nNope, 299 is not it. (no)\n"));
+
+		let game = Game::with_difficulty_and_hints(Difficulty::Normal, Hints::None, &mut StdRng::seed_from_u64(1));
+		assert_eq!(game.attempts(), NonZeroU32::new(30));
+		assert_eq!(game.hints, Hints::None);
+	}
+
+	#[test]
+	fn time_limit()
+	{
+		let clock = Arc::new(TestClock::new());
+		let mut timed = game();
+		timed.set_clock(clock.clone());
+		timed.time_limit = Some(Duration::from_secs(60));
+		assert_eq!(timed.time_left(), Some(Duration::from_secs(60)));
+		assert_eq!(timed.score_config().par, Duration::from_secs(60));
+
+		clock.advance(Duration::from_secs(20));
+		assert_eq!(timed.submit(guess(512)), Ok(TurnResult::TooHigh { attempts_left: Some(4) }));
+		clock.advance(Duration::from_millis(1500));
+		assert_eq!(timed.submit(guess(256)), Ok(TurnResult::TooLow { attempts_left: Some(3) }));
+		assert_eq!(timed.history()[1].elapsed, Duration::from_millis(21500));
+		assert_eq!(timed.timings(), "Your times: 20.0s 1.5s (21.5s in total)");
+
+		// A guess after the limit does not count, even if it is right.
+		clock.advance(Duration::from_secs(40));
+		assert_eq!(timed.time_left(), Some(Duration::ZERO));
+		assert_eq!(timed.submit(guess(300)), Ok(TurnResult::TimedOut { answer: guess(300) }));
+		assert!(timed.is_finished());
+		assert_eq!(guesses(&timed), [512, 256]);
+		assert_eq!(timed.submit(guess(300)), Err(AlreadyFinished));
+		assert_eq!(timed.play_with_strategy(&mut LinearStrategy::default()), GameOutcome::TimedOut { answer: guess(300) });
+	}
+
+	#[test]
+	fn time_limit_play()
+	{
+		let clock = Arc::new(TestClock::new());
+		let mut timed = game();
+		timed.set_clock(clock.clone());
+		timed.time_limit = Some(Duration::from_secs(60));
+		let (outcome, transcript, _) = play(&mut timed, "512\nquit\n");
+		assert_eq!(outcome, GameOutcome::Aborted);
+		assert!(transcript.contains("The clock is ticking: you have 60 seconds."));
+		assert!(transcript.contains("You have 5 attempts and 60 seconds remaining. Guess: "));
+
+		clock.advance(Duration::from_secs(45));
+		let (_, transcript, _) = play(&mut timed, "256\nquit\n");
+		assert!(transcript.contains("You have 4 attempts and 15 seconds remaining. Guess: "));
+		assert!(transcript.contains("You have 3 attempts and 15 seconds remaining. Guess: "));
+
+		clock.advance(Duration::from_secs(16));
+		let (outcome, transcript, _) = play(&mut timed, "300\n");
+		assert_eq!(outcome, GameOutcome::TimedOut { answer: guess(300) });
+		assert!(transcript.contains("You have 3 attempts and 0 seconds remaining. Guess: "));
+		assert!(!transcript.contains("You win!"));
+		assert!(transcript.contains("Time's up! 300 came too late, and the number was 300."));
+	}
+
+	#[test]
+	fn pause()
+	{
+		let clock = Arc::new(TestClock::new());
+		let mut timed = game();
+		timed.set_clock(clock.clone());
+		timed.time_limit = Some(Duration::from_secs(30));
+		let seconds = Duration::from_secs;
+		let mut input = Typing::new(&clock, [("512", seconds(2)), ("pause", seconds(1)), ("", seconds(60)), ("300", seconds(3))]);
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		// Without the pause the minute would have run out the time limit.
+		assert_eq!(timed.play(&mut input, &mut output), GameOutcome::Won { attempts_used: 2 });
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(transcript.contains("Type \"pause\" to stop the clock for a while."), "{transcript}");
+		assert!(transcript.contains("You have 4 attempts and 28 seconds remaining. Guess: Paused — press Enter to continue. You have 4 attempts and 27 seconds remaining. Guess: "), "{transcript}");
+		assert_eq!(timed.paused(), seconds(60));
+		assert_eq!(timed.elapsed(), seconds(6));
+		assert_eq!(timed.history()[1].elapsed, seconds(6));
+
+		// A speedrun leaves the pause out of its time as well.
+		let clock = Arc::new(TestClock::new());
+		let mut speedrun = Game { speedrun: true, ..game() };
+		speedrun.set_attempts(None);
+		speedrun.set_clock(clock.clone());
+		let mut input = Typing::new(&clock, [("pause", seconds(1)), ("", seconds(10)), ("300", seconds(2))]);
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		assert_eq!(speedrun.play(&mut input, &mut output), GameOutcome::Won { attempts_used: 1 });
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(transcript.contains("Attempt #1 at 1.0s. Guess: "), "{transcript}");
+		assert!(transcript.contains("You got 300 in 1 try and 3.0s!"), "{transcript}");
+		assert_eq!(speedrun.run_time(), Some(seconds(3)));
+
+		// Games without a clock can pause too, even if it makes no difference.
+		let (outcome, transcript, _) = play(&mut game(), "pause\n\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 1 });
+		assert!(!transcript.contains("to stop the clock"), "{transcript}");
+	}
+
+	#[test]
+	fn abort()
+	{
+		let mut aborted = game();
+		aborted.set_clock(Arc::new(TestClock::new()));
+		let (outcome, transcript, _) = play(&mut aborted, "512\n256\nabort\n512\n");
+		assert_eq!(outcome, GameOutcome::Aborted);
+		assert!(transcript.contains("Game aborted. So far you used 2 attempts in 0.0s, and the number is somewhere in 257..=511."), "{transcript}");
+		assert!(!transcript.contains("300"), "{transcript}");
+		assert!(!aborted.is_finished());
+
+		// Without hints the interval would give the direction away.
+		let mut unhinted = Game { hints: Hints::None, ..game() };
+		unhinted.set_clock(Arc::new(TestClock::new()));
+		let (_, transcript, _) = play(&mut unhinted, "512\nABORT\n");
+		assert!(transcript.contains("Game aborted. So far you used 1 attempt in 0.0s."), "{transcript}");
+
+		// The interrupt flag aborts before the next prompt, and once it is cleared
+		// the game picks up where it stopped.
+		let flag = Arc::new(AtomicBool::new(true));
+		let mut interrupted = game();
+		interrupted.set_clock(Arc::new(TestClock::new()));
+		interrupted.set_interrupt(flag.clone());
+		let (outcome, transcript, _) = play(&mut interrupted, "300\n");
+		assert_eq!(outcome, GameOutcome::Aborted);
+		assert!(transcript.contains("Game aborted. So far you used 0 attempts in 0.0s, and the number is somewhere in 0..=1024."), "{transcript}");
+		assert!(!transcript.contains("Guess: "), "{transcript}");
+		flag.store(false, Ordering::Relaxed);
+		let (outcome, _, _) = play(&mut interrupted, "300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 1 });
+	}
+
+	#[test]
+	fn speedrun()
+	{
+		let clock = Arc::new(TestClock::new());
+		let mut speedrun = Game { speedrun: true, ..game() };
+		speedrun.set_attempts(None);
+		speedrun.set_clock(clock.clone());
+		// The run starts at the first prompt, not when the game is created.
+		clock.advance(Duration::from_secs(5));
+		let (_, transcript, _) = play(&mut speedrun, "512\nquit\n");
+		assert!(transcript.contains("This is a speedrun, so the clock starts now. Go!"), "{transcript}");
+		assert!(transcript.contains("Attempt #1 at 0.0s. Guess: "), "{transcript}");
+		assert_eq!(speedrun.run_time(), None);
+
+		clock.advance(Duration::from_millis(7300));
+		let (outcome, transcript, _) = play(&mut speedrun, "300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		assert!(!transcript.contains("the clock starts now"), "{transcript}");
+		assert!(transcript.contains("Attempt #2 at 7.3s. Guess: "), "{transcript}");
+		assert!(transcript.contains("You got 300 in 2 tries and 7.3s!"), "{transcript}");
+		assert_eq!(speedrun.run_time(), Some(Duration::from_millis(7300)));
+		assert_eq!(speedrun.elapsed(), Duration::from_millis(12300));
+
+		// Other games are not timed like this, even when won.
+		let mut normal = game();
+		normal.submit(guess(300)).expect("submit failed.");
+		assert_eq!(normal.run_time(), None);
+	}
+
+	#[test]
+	fn tip()
+	{
+		let (_, transcript, _) = play(&mut game(), "a\nb\nc\nd\n300\n");
+		assert_eq!(transcript.matches("Tip: Enter a whole number from 0 through 1024, like 512").count(), 1);
+	}
+
+	#[test]
+	fn practice()
+	{
+		let mut practice = game();
+		practice.set_attempts(None);
+		assert!(practice.is_practice());
+		let mut lines: String = (1..=29).map(|value| format!("{value}\n")).collect();
+		lines.insert_str(2, "abc\n");
+		lines.push_str("300\n");
+		let (outcome, transcript, _) = play(&mut practice, &lines);
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 30 });
+		assert!(transcript.contains("This is practice, so take as many guesses as you like.\nAttempt #1. Guess: "));
+		// The invalid guess after 1 asks for the second attempt again.
+		assert!(transcript.contains("Attempt #2. Guess: Attempt #2. Guess: "));
+		assert!(transcript.contains("Attempt #30. Guess: "));
+		assert_eq!(transcript.matches("is too low!").count(), 29);
+		assert!(!transcript.contains("remaining"));
+		assert!(!transcript.contains("out of guesses"));
+		assert!(transcript.ends_with("You got 300 in 30 tries!\n\n\n"));
+		assert_eq!(practice.attempts_left(), None);
+		assert_eq!(practice.score_config().attempts, 0);
+
+		let mut practice = game();
+		practice.set_attempts(None);
+		for value in 1..=100
+		{
+			assert_eq!(practice.submit(guess(value)), Ok(TurnResult::TooLow { attempts_left: None }));
+		}
+		assert!(!practice.is_finished());
+		let (outcome, _, _) = play(&mut practice, "give up\n");
+		assert_eq!(outcome, GameOutcome::Lost { answer: guess(300) });
+	}
+
+	#[test]
+	fn hints()
+	{
+		let mut hinted = Game { attempts: NonZeroU32::new(6), ..game() };
+		let (outcome, transcript, _) = play(&mut hinted, "hint\nh\nHint\nhint\nhint\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 5 });
+		assert_eq!(
+			transcript.lines().filter(|line| line.starts_with("Hint: ")).collect::<Vec<_>>(),
+			["Hint: The number is even.", "Hint: The number has 3 digits.", "Hint: The number is divisible by 5.", "Hint: The number is from 0 through 512."]
+		);
+		assert!(transcript.contains("That's all the hints there are, so that one was free."));
+		assert!(transcript.contains("You have 2 attempts remaining. Guess: "));
+		assert_eq!(hinted.clues().len(), 4);
+		assert_eq!(guesses(&hinted), [300]);
+		assert_eq!(hinted.history()[0].attempt, 5);
+
+		// A hint on the last attempt is still given, but loses the game.
+		let (outcome, transcript, _) = play(&mut game(), "1\n2\nhint\n3\nhint\n300\n");
+		assert_eq!(outcome, GameOutcome::Lost { answer: guess(300) });
+		assert!(transcript.ends_with("\nHint: The number has 3 digits.\n\nYou're out of guesses! The number was 300. Game over. :(\n\n\n"));
+
+		let mut last = game();
+		for value in 1..=4
+		{
+			last.submit(guess(value)).expect("submit failed.");
+		}
+		assert_eq!(last.hint(), Ok(Some(Clue::Parity { even: true })));
+		assert_eq!(last.outcome(), Some(GameOutcome::Lost { answer: guess(300) }));
+		assert_eq!(last.hint(), Err(AlreadyFinished));
+	}
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Returns the values of the guesses in the history of game.
+	fn guesses(game: &Game<{ 0..=1024 }>) -> Vec<i32>
+	{
+		game.history().iter().map(|entry| entry.guess.value()).collect()
+	}
+
+	#[test]
+	fn submit_win_on_last_attempt()
+	{
+		let mut game = game();
+		assert_eq!(game.submit(guess(512)), Ok(TurnResult::TooHigh { attempts_left: Some(4) }));
+		assert_eq!(game.submit(guess(100)), Ok(TurnResult::TooLow { attempts_left: Some(3) }));
+		assert_eq!(game.submit(guess(400)), Ok(TurnResult::TooHigh { attempts_left: Some(2) }));
+		assert_eq!(game.submit(guess(200)), Ok(TurnResult::TooLow { attempts_left: Some(1) }));
+		assert!(!game.is_finished());
+		assert_eq!(game.submit(guess(300)), Ok(TurnResult::Win { attempts_used: 5 }));
+		assert!(game.is_finished());
+		assert_eq!(game.attempts_used(), 5);
+		assert_eq!(game.attempts_left(), Some(0));
+		assert_eq!(guesses(&game), [512, 100, 400, 200, 300]);
+	}
+
+	#[test]
+	fn submit_loss()
+	{
+		let mut game = game();
+		for value in 1..=4
+		{
+			assert_eq!(game.submit(guess(value)), Ok(TurnResult::TooLow { attempts_left: Some(5 - value as u32) }));
+		}
+		assert_eq!(game.submit(guess(1000)), Ok(TurnResult::Loss { answer: guess(300) }));
+		assert!(game.is_finished());
+		assert_eq!(game.attempts_left(), Some(0));
+
+		// Playing a finished game only reports how it ended.
+		let (outcome, transcript, _) = play(&mut game, "300\n");
+		assert_eq!(outcome, GameOutcome::Lost { answer: guess(300) });
+		assert_eq!(transcript, "");
+	}
+
+	#[test]
+	fn submit_after_finished()
+	{
+		let mut won = game();
+		assert_eq!(won.submit(guess(300)), Ok(TurnResult::Win { attempts_used: 1 }));
+		assert_eq!(won.submit(guess(300)), Err(AlreadyFinished));
+		assert_eq!(won.submit(guess(0)), Err(AlreadyFinished));
+		assert_eq!(guesses(&won), [300]);
+		assert_eq!(AlreadyFinished.to_string(), "the game is already finished");
+
+		let mut given_up = game();
+		let (outcome, _, _) = play(&mut given_up, "give up\n");
+		assert_eq!(outcome, GameOutcome::Lost { answer: guess(300) });
+		assert_eq!(given_up.submit(guess(300)), Err(AlreadyFinished));
+		assert_eq!(given_up.attempts_used(), 0);
+	}
+
+	#[test]
+	fn duplicates()
+	{
+		// By default a repeated guess counts like any other.
+		let mut strict = game();
+		strict.submit(guess(512)).expect("submit failed.");
+		assert_eq!(strict.submit(guess(512)), Ok(TurnResult::TooHigh { attempts_left: Some(3) }));
+
+		let mut forgiving = Game { forgive_duplicates: true, ..game() };
+		forgiving.submit(guess(512)).expect("submit failed.");
+		forgiving.submit(guess(100)).expect("submit failed.");
+		assert_eq!(forgiving.submit(guess(512)), Ok(TurnResult::Duplicate { feedback: Feedback::TooHigh }));
+		assert_eq!(forgiving.submit(guess(100)), Ok(TurnResult::Duplicate { feedback: Feedback::TooLow }));
+		assert_eq!((forgiving.attempts_left(), forgiving.history().len()), (Some(3), 2));
+
+		let (outcome, transcript, _) = play(&mut Game { forgive_duplicates: true, ..game() }, "512\n512\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		assert!(transcript.contains("You already tried 512, and it's still too high. That one didn't count.\nYou have 4 attempts remaining. Guess: "), "{transcript}");
+
+		// Without hints a duplicate does not tell which way the guess was off.
+		let (_, transcript, _) = play(&mut Game { forgive_duplicates: true, hints: Hints::None, ..game() }, "512\n512\nquit\n");
+		let responses = &transcript[transcript.find("Guess: ").expect("no guess was asked for.")..];
+		assert!(responses.contains("You already tried 512, and it's still wrong."), "{responses}");
+		assert!(!responses.contains("high"), "{responses}");
+	}
+
+	#[test]
+	fn odds()
+	{
+		let mut started = game();
+		assert_eq!(started.win_probability(), Some(32.0 / 1025.0));
+		started.submit(guess(512)).expect("submit failed.");
+		assert_eq!(started.bounds().to_string(), "0..=511");
+		assert_eq!(started.win_probability(), Some(16.0 / 512.0));
+		started.set_attempts(None);
+		assert_eq!(started.win_probability(), None);
+
+		let (_, transcript, _) = play(&mut Game { show_odds: true, ..game() }, "512\n299\n300\n");
+		assert!(transcript.contains(" (3% chance to win)\n"), "{transcript}");
+		assert!(transcript.contains("299 is too low!"), "{transcript}");
+		assert!(transcript.contains(" (4% chance to win)\n"), "{transcript}");
+		assert_eq!(transcript.matches("chance to win").count(), 2, "{transcript}");
+
+		// Odds are only shown when asked for, and never without hints.
+		let (_, transcript, _) = play(&mut game(), "512\nquit\n");
+		assert!(!transcript.contains("chance to win"), "{transcript}");
+		let (_, transcript, _) = play(&mut Game { show_odds: true, hints: Hints::None, ..game() }, "512\nquit\n");
+		assert!(!transcript.contains("chance to win"), "{transcript}");
+	}
+
+	#[test]
+	fn bonus()
+	{
+		let near = Game { bonus: Some(BonusRule { within: 10 }), ..game() };
+
+		// A near miss on the last attempt earns one more instead of losing.
+		let mut last = near.clone();
+		for value in 1..=4
+		{
+			last.submit(guess(value)).expect("submit failed.");
+		}
+		assert_eq!(last.submit(guess(305)), Ok(TurnResult::TooHigh { attempts_left: Some(1) }));
+		assert!(last.bonus_earned() && !last.is_finished());
+		assert_eq!(last.submit(guess(299)), Ok(TurnResult::Loss { answer: guess(300) }));
+
+		// The bonus is earned once per game, and never adds up to more attempts
+		// than the game started with.
+		let mut once = near.clone();
+		assert_eq!(once.submit(guess(310)), Ok(TurnResult::TooHigh { attempts_left: Some(5) }));
+		assert_eq!(once.submit(guess(290)), Ok(TurnResult::TooLow { attempts_left: Some(4) }));
+
+		// Missing by more or winning earns nothing, and neither does a game without
+		// the rule.
+		let mut far = near.clone();
+		assert_eq!(far.submit(guess(311)), Ok(TurnResult::TooHigh { attempts_left: Some(4) }));
+		assert_eq!(far.submit(guess(300)), Ok(TurnResult::Win { attempts_used: 2 }));
+		assert!(!far.bonus_earned());
+		assert_eq!(game().submit(guess(301)), Ok(TurnResult::TooHigh { attempts_left: Some(4) }));
+
+		let (outcome, transcript, _) = play(&mut near.clone(), "305\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		assert!(transcript.contains("So close - have an extra attempt! (+1)\nYou have 5 attempts remaining. Guess: "), "{transcript}");
+
+		assert_eq!(BonusRule::percent_of_span(1, 1025), BonusRule { within: 10 });
+		assert_eq!(BonusRule::percent_of_span(1, 50), BonusRule { within: 1 });
+	}
+
+	#[test]
+	fn lies()
+	{
+		let mut rng = StdRng::seed_from_u64(3);
+		for _ in 0..100
+		{
+			let mut game = Game::<{ 0..=1024 }>::new(11, &mut rng);
+			game.lie_once(&mut rng);
+			assert_eq!(game.attempts(), NonZeroU32::new(15));
+			let secret = game.secret().reveal();
+			let misses: Vec<_> = Guess::<{ 0..=1024 }>::iter().filter(|&guess| guess != secret).take(11).collect();
+			for &guess in &misses
+			{
+				game.submit(guess).expect("submit failed.");
+			}
+			assert_eq!(game.confession(), None);
+			assert_eq!(game.submit(secret), Ok(TurnResult::Win { attempts_used: 12 }));
+
+			// Exactly one answer is false, and never the winning one.
+			let lies: Vec<_> = (1_u32..)
+				.zip(game.history())
+				.filter(|(_, entry)| entry.feedback != Feedback::from(entry.guess.cmp(&secret)))
+				.map(|(number, _)| number)
+				.collect();
+			assert_eq!(lies.len(), 1);
+			assert_eq!(game.lied_on(), Some(lies[0]));
+			assert_eq!(game.confession(), Some(format!("I lied on guess #{}. 😈", lies[0])));
+		}
+
+		// A correct guess is never lied about, so winning first means no lie.
+		let mut lucky = Game { lie: Some(1), ..game() };
+		assert_eq!(lucky.submit(guess(300)), Ok(TurnResult::Win { attempts_used: 1 }));
+		assert_eq!(lucky.lied_on(), None);
+		assert_eq!(lucky.confession().as_deref(), Some("I never got around to lying this time."));
+		assert_eq!(game().confession(), None);
+
+		let mut liar = Game { lie: Some(1), ..game() };
+		let (outcome, transcript, _) = play(&mut liar, "512\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		assert!(transcript.contains("Careful: I may lie to you once"), "{transcript}");
+		assert!(transcript.contains("512 is too low!"), "{transcript}");
+		assert_eq!(liar.recap(), "Your guesses: 512↓ 300 ✔");
+		assert_eq!(liar.confession().as_deref(), Some("I lied on guess #1. 😈"));
+	}
+
+	#[test]
+	fn knowledge()
+	{
+		let mut honest = game();
+		assert_eq!(honest.knowledge(), &KnowledgeState::new(GuessRange::full()));
+		honest.submit(guess(512)).expect("submit failed.");
+		honest.submit(guess(256)).expect("submit failed.");
+		let knowledge = honest.knowledge();
+		assert_eq!((knowledge.lo(), knowledge.hi(), knowledge.size()), (Some(guess(257)), Some(guess(511)), 255));
+		assert_eq!(honest.bounds().to_string(), "257..=511");
+		honest.submit(guess(300)).expect("submit failed.");
+		assert_eq!(honest.knowledge().size(), 1);
+
+		// The game's range is known before the first guess.
+		let mut hard = Game::with_difficulty_and_secret(Difficulty::Hard, Guess::new(5000).expect("secret failed to construct."));
+		assert_eq!(hard.knowledge().to_string(), "1..=10000");
+		hard.submit(Guess::new(6000).expect("guess failed to construct.")).expect("submit failed.");
+		assert_eq!(hard.knowledge().to_string(), "1..=5999");
+
+		// A lie can leave nothing possible, which the game takes in its stride.
+		let mut liar = Game { lie: Some(1), ..game() };
+		liar.submit(guess(512)).expect("submit failed.");
+		assert_eq!(liar.knowledge().to_string(), "513..=1024");
+		assert_eq!(liar.submit(guess(400)), Ok(TurnResult::TooHigh { attempts_left: Some(3) }));
+		assert!(liar.knowledge().is_contradictory());
+		assert_eq!(liar.win_probability(), Some(0.0));
+		assert_eq!(liar.submit(guess(300)), Ok(TurnResult::Win { attempts_used: 3 }));
+		assert!(liar.knowledge().is_contradictory());
+	}
+
+	#[test]
+	fn random_secret()
+	{
+		let game = Game::<{ 0..=1024 }>::new(10, &mut StdRng::seed_from_u64(7));
+		assert_eq!(game.attempts(), NonZeroU32::new(10));
+		assert!(format!("{game:?}").contains("secret: ***"));
+		assert_eq!(Game::<{ 0..=1024 }>::new_random(3).attempts_left(), Some(3));
+		assert_eq!(Game::<{ 0..=1024 }>::new_fair(&mut StdRng::seed_from_u64(7)).attempts(), NonZeroU32::new(12));
+		assert_eq!(Game::<{ 0..=1023 }>::new_fair(&mut StdRng::seed_from_u64(7)).attempts(), NonZeroU32::new(11));
+	}
+
+	#[test]
+	fn seeded_secret()
+	{
+		for seed in [0, 7, 42, u64::MAX]
+		{
+			let first = Game::<{ 0..=1024 }>::new(10, &mut StdRng::seed_from_u64(seed));
+			let second = Game::<{ 0..=1024 }>::new(10, &mut StdRng::seed_from_u64(seed));
+			assert_eq!(first.secret(), second.secret());
+		}
+		let secrets: Vec<_> = (0..20).map(|seed| Game::<{ 0..=1024 }>::new(10, &mut StdRng::seed_from_u64(seed)).secret()).collect();
+		assert!(secrets.iter().any(|secret| *secret != secrets[0]));
+
+		let game = Game::<{ 0..=1024 }>::with_secret(10, guess(300));
+		assert_eq!(game.secret().reveal(), guess(300));
+	}
+
+	#[test]
+	fn with_difficulty()
+	{
+		for difficulty in Difficulty::ALL
+		{
+			let game = Game::with_difficulty_and_rng(difficulty, &mut StdRng::seed_from_u64(1));
+			assert_eq!(game.attempts(), NonZeroU32::new(difficulty.attempts()));
+			assert_eq!(game.hints, difficulty.hints());
+			assert_eq!(game.range().lo().value()..=game.range().hi().value(), difficulty.range());
+			assert!(game.range().contains(game.secret().reveal()));
+		}
+
+		// Guesses outside of the difficulty's range are rejected without using up
+		// an attempt, and hard games do not tell how close a guess is.
+		let game = Game::with_difficulty_and_rng(Difficulty::Hard, &mut StdRng::seed_from_u64(1));
+		let mut game = Game { style: Style::Plain, ..game };
+		let answer = game.secret().reveal();
+		let wrong = if answer.value() == 1 { 2 } else { 1 };
+		let (outcome, transcript, errors) = play(&mut game, &format!("20000\n{wrong}\n{answer}\n"));
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 2 });
+		assert!(transcript.starts_with("\n\nI'm thinking of a number somwhere from 1 through 10000."));
+		let line = if wrong > answer.value() { format!("{wrong} is too high! (hot)") } else { format!("{wrong} is too low! (cold)") };
+		assert!(transcript.contains(&format!("\n{line}\n")), "{transcript:?}");
+		assert!(errors.contains("10000"));
+	}
+
+	#[test]
+	fn insane_is_losable()
+	{
+		// Even halving the range with every guess runs out of attempts when the
+		// secret is in the last place the search looks.
+		let (lo, hi) = Difficulty::Insane.range().into_inner();
+		let mut possible = GuessRange::<DIFFICULTY_RANGE> { lo: Guess { value: lo }, hi: Guess { value: hi } };
+		let mut game = Game::with_difficulty_and_secret(Difficulty::Insane, possible.hi());
+		let outcome = loop
+		{
+			let guess = possible.midpoint();
+			match game.submit(guess).expect("submit failed.")
+			{
+				TurnResult::TooHigh { .. } => possible = possible.below(guess).expect("the secret is below the guess."),
+				TurnResult::TooLow { .. } => possible = possible.above(guess).expect("the secret is above the guess."),
+				result => break result,
+			}
+		};
+		assert!(matches!(outcome, TurnResult::Loss { .. }), "{outcome:?}");
+	}
+
+	#[test]
+	fn play_with_strategy()
+	{
+		// The strategy picks up where the submitted guesses left off.
+		let mut linear = game();
+		assert_eq!(linear.submit(guess(290)), Ok(TurnResult::TooLow { attempts_left: Some(4) }));
+		let outcome = linear.play_with_strategy(&mut LinearStrategy::default());
+		assert_eq!(outcome, GameOutcome::Lost { answer: guess(300) });
+		assert_eq!(guesses(&linear), [290, 291, 292, 293, 294]);
+		assert_eq!(linear.play_with_strategy(&mut LinearStrategy::default()), outcome);
+
+		let mut binary = game();
+		assert_eq!(binary.play_with_strategy(&mut BinarySearchStrategy::default()), GameOutcome::Lost { answer: guess(300) });
+		assert_eq!(guesses(&binary), [512, 255, 383, 319, 287]);
+	}
+
+	#[test]
+	fn history()
+	{
+		let mut played = game();
+		let (outcome, _, _) = play(&mut played, "512\nabc\n\n2000\n256\n384\n300\n");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 4 });
+		let history = played.history();
+		assert_eq!(history.iter().map(|entry| entry.attempt).collect::<Vec<_>>(), [1, 2, 3, 4]);
+		assert_eq!(guesses(&played), [512, 256, 384, 300]);
+		assert_eq!(
+			history.iter().map(|entry| entry.feedback).collect::<Vec<_>>(),
+			[Feedback::TooHigh, Feedback::TooLow, Feedback::TooHigh, Feedback::Correct]
+		);
+		assert!(history.windows(2).all(|pair| pair[0].elapsed <= pair[1].elapsed));
+		assert_eq!(played.recap(), "Your guesses: 512↑ 256↓ 384↑ 300 ✔");
+		assert_eq!(played.trend(), Some(Trend::Warmer));
+
+		// Submitted guesses are recorded the same way.
+		let mut submitted = game();
+		assert_eq!(submitted.trend(), None);
+		submitted.submit(guess(0)).expect("submit failed.");
+		assert_eq!(submitted.trend(), None);
+		submitted.submit(guess(1000)).expect("submit failed.");
+		assert_eq!(submitted.trend(), Some(Trend::Colder));
+		assert_eq!(submitted.history()[1], HistoryEntry { attempt: 2, guess: guess(1000), feedback: Feedback::TooHigh, elapsed: submitted.history()[1].elapsed });
+		assert_eq!(submitted.recap(), "Your guesses: 0↓ 1000↑");
+	}
+}
@@ -0,0 +1,231 @@
+use {
+	crate::{
+		Game,
+		GameOutcome,
+		Guess,
+		GuessRange,
+		InputOutput,
+		Style,
+		DIFFICULTY_RANGE,
+	},
+	colored::Colorize,
+	rand::Rng,
+	std::{
+		fmt::Write as _,
+		io::{
+			self,
+			BufRead,
+		},
+		ops::RangeInclusive,
+	},
+};
+
+/// The highest number of each hole of a golf course, in order. Every hole
+/// starts at 1, so the holes get longer as the course goes on.
+pub const GOLF_HOLES: [i32; 9] = [10, 20, 50, 100, 200, 500, 1000, 10000, 100000];
+
+/// A hole of a golf course that was played. See play_golf.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Hole
+{
+	/// The range the secret of the hole was in.
+	pub range: GuessRange<DIFFICULTY_RANGE>,
+	/// How many strokes the hole takes with perfect play. See par.
+	pub par: u32,
+	/// How many strokes it took, which is twice the par if the secret was not
+	/// found.
+	pub strokes: u32,
+}
+
+impl Hole
+{
+	/// Returns how many strokes the hole took over its par, or under it if
+	/// negative.
+	pub fn to_par(&self) -> i64
+	{
+		i64::from(self.strokes) - i64::from(self.par)
+	}
+}
+
+/// Returns the par of a hole over range, which is the most guesses a binary
+/// search needs to find any secret in it, the same as the fair_attempts of its
+/// size apart from ranges whose size is a power of two, which need one more.
+pub const fn par<const RANGE: RangeInclusive<i32>>(range: GuessRange<RANGE>) -> u32
+{
+	range.size().ilog2() + 1_u32
+}
+
+/// Formats how many strokes over par as in "+3" or "-2", or "E" for even.
+fn format_to_par(to_par: i64) -> String
+{
+	match to_par
+	{
+		0 => String::from("E"),
+		1.. => format!("+{to_par}"),
+		_ => to_par.to_string(),
+	}
+}
+
+/// Plays a round of golf, which is one game for each hole in GOLF_HOLES with a
+/// secret picked by rng, in style, and returns the holes that were played.
+///
+/// Each stroke is a guess, and the player wants as few strokes as possible
+/// over the whole course. A hole that is not finished in twice its par is
+/// scored as twice its par. After each hole a line with its score is shown,
+/// and once the round is over the table from render_scorecard. A hole that is
+/// aborted, for example because the player quit, ends the round without being
+/// recorded.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		play_golf,
+/// // 		Style,
+/// // 	},
+/// // 	rand::thread_rng,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let holes = play_golf(&mut thread_rng(), Style::Fancy, &mut stdin().lock(), &mut stdout());
+/// // println!("{} over par.", holes.iter().map(|hole| hole.to_par()).sum::<i64>());
+/// ```
+pub fn play_golf(
+	rng: &mut impl Rng,
+	style: Style,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Vec<Hole>
+{
+	let mut holes = Vec::new();
+	// A round that can not be shown any longer ends early, like a game.
+	let _ = run(|range| Guess { value: rng.gen_range(range.lo().value()..=range.hi().value()) }, style, input, output, &mut holes);
+	holes
+}
+
+/// Plays a round of golf with the secrets from secret_for, recording each hole
+/// in holes, and returns an error if reading or writing fails.
+fn run(
+	mut secret_for: impl FnMut(GuessRange<DIFFICULTY_RANGE>) -> Guess<DIFFICULTY_RANGE>,
+	style: Style,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+	holes: &mut Vec<Hole>,
+) -> io::Result<()>
+{
+	for (number, hi) in (1_u32..).zip(GOLF_HOLES)
+	{
+		let range = GuessRange { lo: Guess { value: 1_i32 }, hi: Guess { value: hi } };
+		let par = par(range);
+		let mut game = Game::with_secret(2 * par, secret_for(range));
+		game.set_range(range);
+		game.style = style;
+		writeln!(output.messages(), "{}", style.render(&format!("\nHole {number}, par {par}.").bright_green().bold()))?;
+		let strokes = match game.play(input, output)
+		{
+			GameOutcome::Won { attempts_used } => attempts_used,
+			GameOutcome::Lost { .. } | GameOutcome::TimedOut { .. } => 2 * par,
+			GameOutcome::Aborted => break,
+		};
+		let hole = Hole { range, par, strokes };
+		holes.push(hole);
+		let line = format!("Hole {number}: {strokes} on a par {par} ({}).", format_to_par(hole.to_par()));
+		writeln!(output.messages(), "{}", style.render(&line.bright_green()))?;
+	}
+	if !holes.is_empty()
+	{
+		write!(output.messages(), "\n{}\n", render_scorecard(holes))?;
+	}
+	Ok(())
+}
+
+/// Renders holes as a scorecard with one row per hole and the totals at the
+/// bottom, as in:
+///
+/// ```text
+/// Hole  Range       Par  Strokes  +/-
+///    1  1-10          4        3   -1
+///    2  1-20          5        7   +2
+/// Total               9       10   +1
+/// ```
+pub fn render_scorecard(holes: &[Hole]) -> String
+{
+	let mut scorecard = String::from("Hole  Range       Par  Strokes  +/-\n");
+	for (number, hole) in (1_u32..).zip(holes)
+	{
+		let range = format!("{}-{}", hole.range.lo(), hole.range.hi());
+		let to_par = format_to_par(hole.to_par());
+		writeln!(scorecard, "{number:>4}  {range:<10}  {:>3}  {:>7}  {to_par:>3}", hole.par, hole.strokes).expect("writing to a String can not fail.");
+	}
+	let par = holes.iter().map(|hole| hole.par).sum::<u32>();
+	let strokes = holes.iter().map(|hole| hole.strokes).sum::<u32>();
+	let to_par = format_to_par(holes.iter().map(Hole::to_par).sum());
+	writeln!(scorecard, "Total {par:>13}  {strokes:>7}  {to_par:>3}").expect("writing to a String can not fail.");
+	scorecard
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::WithErrors,
+	};
+
+	#[test]
+	fn pars()
+	{
+		let pars: Vec<_> = GOLF_HOLES.into_iter().map(|hi| par(GuessRange::<DIFFICULTY_RANGE> { lo: Guess { value: 1 }, hi: Guess { value: hi } })).collect();
+		assert_eq!(pars, [4, 5, 6, 7, 8, 9, 10, 14, 17]);
+		let power_of_two = GuessRange::<{ 0..=1023 }>::full();
+		assert_eq!(par(power_of_two), 11);
+	}
+
+	#[test]
+	fn scorecard()
+	{
+		let range = |hi| GuessRange { lo: Guess { value: 1 }, hi: Guess { value: hi } };
+		let holes = [Hole { range: range(10), par: 4, strokes: 3 }, Hole { range: range(20), par: 5, strokes: 7 }, Hole { range: range(50), par: 6, strokes: 6 }];
+		assert_eq!(
+			render_scorecard(&holes),
+			"Hole  Range       Par  Strokes  +/-\n   1  1-10          4        3   -1\n   2  1-20          5        7   +2\n   3  1-50          6        6    E\nTotal              15       16   +1\n"
+		);
+		assert_eq!(render_scorecard(&[]), "Hole  Range       Par  Strokes  +/-\nTotal               0        0    E\n");
+	}
+
+	#[test]
+	fn nine_holes()
+	{
+		// Every secret is the top of its hole. The fourth hole is never found.
+		let mut script = String::from("10\n1\n20\n50\n");
+		for miss in 1..=14
+		{
+			script.push_str(&format!("{miss}\n"));
+		}
+		script.push_str("200\n500\n1000\n10000\n100000\n");
+		let mut holes = Vec::new();
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		run(|range| range.hi(), Style::Plain, &mut script.as_bytes(), &mut output, &mut holes).expect("golf failed.");
+		let strokes: Vec<_> = holes.iter().map(|hole| hole.strokes).collect();
+		assert_eq!(strokes, [1, 2, 1, 14, 1, 1, 1, 1, 1]);
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(transcript.contains("\nHole 4, par 7.\n"), "{transcript}");
+		assert!(transcript.contains("Hole 4: 14 on a par 7 (+7)."), "{transcript}");
+		assert!(
+			transcript.ends_with(
+				"\nHole  Range       Par  Strokes  +/-\n   1  1-10          4        1   -3\n   2  1-20          5        2   -3\n   3  1-50          6        1   -5\n   4  1-100         7       \
+				 14   +7\n   5  1-200         8        1   -7\n   6  1-500         9        1   -8\n   7  1-1000       10        1   -9\n   8  1-10000      14        1  -13\n   9  \
+				 1-100000     17        1  -16\nTotal              80       23  -57\n\n"
+			),
+			"{transcript}"
+		);
+
+		// Quitting ends the round without recording the hole.
+		let mut holes = Vec::new();
+		run(|range| range.hi(), Style::Plain, &mut "10\nquit\n".as_bytes(), &mut Vec::new(), &mut holes).expect("golf failed.");
+		assert_eq!(holes.len(), 1);
+	}
+}
@@ -0,0 +1,203 @@
+use {
+	crate::{
+		fair_attempts,
+		Game,
+		GameOutcome,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		ops::RangeInclusive,
+		result::Result,
+	},
+};
+
+/// How efficiently a game was won, from A for optimal play down to F, like a
+/// school grade. See grade.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Grade
+{
+	/// No more guesses than optimal play needs.
+	A,
+	/// At most a quarter more guesses than optimal.
+	B,
+	/// At most half again as many guesses as optimal.
+	C,
+	/// At most twice as many guesses as optimal.
+	D,
+	/// More than twice as many guesses as optimal.
+	F,
+}
+
+impl Grade
+{
+	/// Every grade, from best to worst.
+	pub const ALL: [Grade; 5] = [Grade::A, Grade::B, Grade::C, Grade::D, Grade::F];
+
+	/// Returns a short comment on the grade for the player.
+	pub const fn comment(self) -> &'static str
+	{
+		match self
+		{
+			Grade::A => "Flawless, no search could have done better.",
+			Grade::B => "Close to perfect, only a guess or two over.",
+			Grade::C => "Solid, but halving the range each time would be quicker.",
+			Grade::D => "You got there, the long way round.",
+			Grade::F => "Try guessing the middle of what is left next time.",
+		}
+	}
+}
+
+/// Formats Grade as its letter, as in "B".
+impl Display for Grade
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		let letter = match self
+		{
+			Grade::A => "A",
+			Grade::B => "B",
+			Grade::C => "C",
+			Grade::D => "D",
+			Grade::F => "F",
+		};
+		write!(f, "{letter}")
+	}
+}
+
+/// Grades a win that took attempts_used guesses against the optimal
+/// `ceil(log2(span))` for a range of span values, which is at least 1.
+///
+/// With optimal being that many guesses, the grade is:
+///
+/// - A for at most optimal.
+/// - B for at most `optimal * 5 / 4`.
+/// - C for at most `optimal * 3 / 2`.
+/// - D for at most `optimal * 2`.
+/// - F for more.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	grade,
+/// // 	Grade,
+/// //};
+/// // assert_eq!(grade(11, 1025), Grade::A);
+/// // assert_eq!(grade(14, 1025), Grade::C);
+/// ```
+pub const fn grade(
+	attempts_used: u32,
+	span: u64,
+) -> Grade
+{
+	let optimal = fair_attempts(span, 0) as u64;
+	let optimal = if optimal == 0 { 1_u64 } else { optimal };
+	let used = attempts_used as u64;
+	if used <= optimal
+	{
+		Grade::A
+	}
+	else if used * 4 <= optimal * 5
+	{
+		Grade::B
+	}
+	else if used * 2 <= optimal * 3
+	{
+		Grade::C
+	}
+	else if used <= optimal * 2
+	{
+		Grade::D
+	}
+	else
+	{
+		Grade::F
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Returns the grade of the game for its range, or None if it was not won.
+	/// See grade.
+	pub fn grade(&self) -> Option<Grade>
+	{
+		match self.outcome()
+		{
+			Some(GameOutcome::Won { attempts_used }) => Some(grade(attempts_used, self.range.size())),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Guess,
+			GuessRange,
+		},
+	};
+
+	#[test]
+	fn boundaries()
+	{
+		// 0..=1024 has 1025 values, so optimal play needs 11 guesses.
+		let span = GuessRange::<{ 0..=1024 }>::full().size();
+		let grades: Vec<_> = (1..=24).map(|attempts_used| grade(attempts_used, span)).collect();
+		let mut expected = vec![Grade::A; 11];
+		expected.extend([Grade::B; 2]);
+		expected.extend([Grade::C; 3]);
+		expected.extend([Grade::D; 6]);
+		expected.extend([Grade::F; 2]);
+		assert_eq!(grades, expected);
+
+		// A range of one value still takes a guess.
+		assert_eq!((grade(1, 1), grade(2, 1), grade(3, 1)), (Grade::A, Grade::D, Grade::F));
+		assert_eq!((grade(10, 1024), grade(11, 1024)), (Grade::A, Grade::B));
+		assert_eq!(grade(u32::MAX, u64::MAX), Grade::F);
+	}
+
+	#[test]
+	fn display()
+	{
+		let letters: Vec<_> = Grade::ALL.iter().map(ToString::to_string).collect();
+		assert_eq!(letters, ["A", "B", "C", "D", "F"]);
+		assert!(Grade::ALL.windows(2).all(|pair| pair[0] < pair[1] && pair[0].comment() != pair[1].comment()));
+	}
+
+	#[test]
+	fn game()
+	{
+		let secret = Guess::<{ 0..=1024 }>::new(300).expect("secret failed to construct.");
+		let mut game = Game::with_secret(20, secret);
+		assert_eq!(game.grade(), None);
+		for value in [0, 1, 2]
+		{
+			game.submit(Guess::new(value).expect("guess failed to construct.")).expect("submit failed.");
+		}
+		assert_eq!(game.grade(), None);
+		game.submit(secret).expect("submit failed.");
+		assert_eq!(game.grade(), Some(Grade::A));
+
+		// A narrower range needs fewer guesses for the same grade.
+		let mut narrow = Game::with_secret(20, secret);
+		narrow.set_range(GuessRange::new(Guess::new(297).expect("guess failed to construct."), Guess::new(300).expect("guess failed to construct.")).expect("range failed to construct."));
+		for value in [297, 298, 299, 300]
+		{
+			narrow.submit(Guess::new(value).expect("guess failed to construct.")).expect("submit failed.");
+		}
+		assert_eq!(narrow.grade(), Some(Grade::D));
+
+		let mut lost = Game::with_secret(1, secret);
+		lost.submit(Guess::new(1).expect("guess failed to construct.")).expect("submit failed.");
+		assert_eq!(lost.grade(), None);
+	}
+}
@@ -0,0 +1,514 @@
+use {
+	crate::{
+		Feedback,
+		Guess,
+		Hints,
+		ResponseTheme,
+		Style,
+		Win,
+	},
+	colored::{
+		ColoredString,
+		Colorize,
+	},
+	std::{
+		cmp::Ordering,
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+	},
+};
+
+/// How close a wrong guess is to the correct value, from closest to farthest.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Heat
+{
+	/// Within HeatThresholds::boiling.
+	Boiling,
+	/// Within HeatThresholds::warm.
+	Warm,
+	/// Within HeatThresholds::cold.
+	Cold,
+	/// Farther than HeatThresholds::cold.
+	Freezing,
+}
+
+impl Heat
+{
+	/// Returns the heat as text colored to match it.
+	pub(crate) fn colored(self) -> ColoredString
+	{
+		match self
+		{
+			Heat::Boiling => self.to_string().red().bold(),
+			Heat::Warm => self.to_string().yellow(),
+			Heat::Cold => self.to_string().blue(),
+			Heat::Freezing => self.to_string().bright_blue().bold(),
+		}
+	}
+}
+
+/// Formats Heat as a word that can be shown to the user.
+impl Display for Heat
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			Heat::Boiling => write!(f, "🔥 boiling"),
+			Heat::Warm => write!(f, "warm"),
+			Heat::Cold => write!(f, "cold"),
+			Heat::Freezing => write!(f, "🧊 freezing"),
+		}
+	}
+}
+
+/// Where the heat bands end, as fractions of the span of the range.
+///
+/// A guess is in the first band whose fraction its distance from the correct
+/// value does not exceed, so the ends are inclusive. The fractions are expected
+/// to increase from boiling to cold.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct HeatThresholds
+{
+	/// Largest fraction of the span that is still boiling. 1% by default.
+	pub boiling: f64,
+	/// Largest fraction of the span that is still warm. 5% by default.
+	pub warm: f64,
+	/// Largest fraction of the span that is still cold. 25% by default.
+	pub cold: f64,
+}
+
+impl Default for HeatThresholds
+{
+	fn default() -> Self
+	{
+		HeatThresholds { boiling: 0.01, warm: 0.05, cold: 0.25 }
+	}
+}
+
+impl HeatThresholds
+{
+	/// Returns the heat of a guess that is distance away from the correct value
+	/// in a range of span values.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Heat,
+	/// // 	HeatThresholds,
+	/// //};
+	/// // assert_eq!(HeatThresholds::default().heat(10, 1000), Heat::Boiling);
+	/// // assert_eq!(HeatThresholds::default().heat(11, 1000), Heat::Warm);
+	/// ```
+	pub fn heat(
+		&self,
+		distance: u32,
+		span: u64,
+	) -> Heat
+	{
+		// Dividing is rounded correctly, so a distance of exactly 1% of the span
+		// compares equal to 0.01.
+		let fraction = f64::from(distance) / span as f64;
+		if fraction <= self.boiling
+		{
+			Heat::Boiling
+		}
+		else if fraction <= self.warm
+		{
+			Heat::Warm
+		}
+		else if fraction <= self.cold
+		{
+			Heat::Cold
+		}
+		else
+		{
+			Heat::Freezing
+		}
+	}
+}
+
+/// Respond to a user's input with some console output, including how close a
+/// wrong guess is. Returns the correct action to take.
+///
+/// This is the same as respond, except that a guess that is too high or too low
+/// is followed by its Heat, using thresholds. The win message is unchanged.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_with_heat,
+/// // 		Guess,
+/// // 		HeatThresholds,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // assert!(respond_with_heat(example_guess, correct_guess, &HeatThresholds::default(), &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_with_heat<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	thresholds: &HeatThresholds,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_with_heat_and_style(guess, correct, thresholds, Style::Fancy, output)
+}
+
+/// Respond to a user's input with some console output in the given style,
+/// including how close a wrong guess is. Returns the correct action to take.
+///
+/// This is the same as respond_with_heat, which uses Style::Fancy.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_with_heat_and_style,
+/// // 		Guess,
+/// // 		HeatThresholds,
+/// // 		Style,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // let action = respond_with_heat_and_style(example_guess, correct_guess, &HeatThresholds::default(), Style::Ascii, &mut stdout());
+/// ```
+pub fn respond_with_heat_and_style<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	thresholds: &HeatThresholds,
+	style: Style,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	let heat = thresholds.heat(guess.distance(correct), Guess::<RANGE>::span());
+	respond_with_note(guess, correct, Some(heat.colored()), &ResponseTheme::default(), style, output)
+}
+
+/// Whether a guess is closer to the correct value than the previous guess.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Trend
+{
+	/// The guess is closer than the previous guess.
+	Warmer,
+	/// The guess is farther away than the previous guess.
+	Colder,
+	/// The guess is as far away as the previous guess, as when it is on the
+	/// other side of the correct value.
+	Same,
+}
+
+impl Trend
+{
+	/// Compares how far guess and previous are from correct.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Guess,
+	/// // 	Trend,
+	/// //};
+	/// // let correct = Guess::<{ 0..=100 }>::new(50).expect("");
+	/// // let previous = Guess::<{ 0..=100 }>::new(10).expect("");
+	/// // let guess = Guess::<{ 0..=100 }>::new(60).expect("");
+	/// // assert_eq!(Trend::of(guess, previous, correct), Trend::Warmer);
+	/// ```
+	pub fn of<const RANGE: RangeInclusive<i32>>(
+		guess: Guess<RANGE>,
+		previous: Guess<RANGE>,
+		correct: Guess<RANGE>,
+	) -> Self
+	{
+		match guess.distance(correct).cmp(&previous.distance(correct))
+		{
+			Ordering::Less => Trend::Warmer,
+			Ordering::Greater => Trend::Colder,
+			Ordering::Equal => Trend::Same,
+		}
+	}
+
+	/// Returns the trend as text colored to match it.
+	fn colored(self) -> ColoredString
+	{
+		match self
+		{
+			Trend::Warmer => self.to_string().red(),
+			Trend::Colder => self.to_string().blue(),
+			Trend::Same => self.to_string().yellow(),
+		}
+	}
+}
+
+/// Formats Trend as a sentence that can be shown to the user.
+impl Display for Trend
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			Trend::Warmer => write!(f, "Getting warmer."),
+			Trend::Colder => write!(f, "Getting colder."),
+			Trend::Same => write!(f, "Still the same distance."),
+		}
+	}
+}
+
+/// Respond to a user's input with some console output, including whether a
+/// wrong guess is closer than the previous guess. Returns the correct action to
+/// take.
+///
+/// This is the same as respond, except that a guess that is too high or too low
+/// is followed by its Trend compared to previous. Without a previous guess, as
+/// for the first guess, it is exactly the same as respond.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_trend,
+/// // 		Guess,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // let first_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let second_guess = Guess::<{ 0..=100000 }>::new(2000_i32).expect("");
+/// // respond_trend(first_guess, None, correct_guess, &mut stdout()).expect("");
+/// // respond_trend(second_guess, Some(first_guess), correct_guess, &mut stdout()).expect("");
+/// ```
+pub fn respond_trend<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	previous: Option<Guess<RANGE>>,
+	correct: Guess<RANGE>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	let trend = previous.map(|previous| Trend::of(guess, previous, correct).colored());
+	respond_with_note(guess, correct, trend, &ResponseTheme::default(), Style::Fancy, output)
+}
+
+/// Shared implementation of respond_with_heat, respond_trend and
+/// respond_with_bounds. Responds like respond_with_theme, adding note after the
+/// message unless the guess is correct or the theme gives Hints::None.
+pub(crate) fn respond_with_note<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	note: Option<ColoredString>,
+	theme: &ResponseTheme,
+	style: Style,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_with_feedback(guess, Feedback::from(guess.cmp(&correct)), note, None, theme, style, output)
+}
+
+/// Responds like respond_with_note with feedback about guess, which a game that
+/// lies does not take from the secret. Unless the guess is correct, the line
+/// ends with odds as a chance of winning, as in "(3% chance to win)", if given.
+pub(crate) fn respond_with_feedback<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	feedback: Feedback,
+	note: Option<ColoredString>,
+	odds: Option<f64>,
+	theme: &ResponseTheme,
+	style: Style,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	let mut line = style.render(&theme.render(guess, feedback));
+	if let Some(note) = note.filter(|_| !feedback.is_correct() && theme.hints != Hints::None)
+	{
+		line = format!("{line} {}", style.render(&note));
+	}
+	if let Some(odds) = odds.filter(|_| !feedback.is_correct())
+	{
+		line = format!("{line} {}", style.render(&format!("({} chance to win)", percent(odds)).dimmed()));
+	}
+	writeln!(output, "\n{line}")?;
+	Ok(feedback.into_control_flow(guess))
+}
+
+/// Formats a probability as a whole percentage, as in "42%". Only certainty is
+/// shown as 0% or 100%, so a slim chance still reads as one.
+fn percent(probability: f64) -> String
+{
+	let percent = (probability * 100.0).round();
+	let percent = if probability > 0.0 && probability < 1.0 { percent.clamp(1.0, 99.0) } else { percent };
+	format!("{percent}%")
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn percent_test()
+	{
+		assert_eq!(percent(0.0), "0%");
+		assert_eq!(percent(0.001), "1%");
+		assert_eq!(percent(0.424), "42%");
+		assert_eq!(percent(0.999), "99%");
+		assert_eq!(percent(1.0), "100%");
+	}
+
+	#[test]
+	fn heat_bands()
+	{
+		let thresholds = HeatThresholds::default();
+		let bands = [
+			(0, Heat::Boiling),
+			(10, Heat::Boiling),
+			(11, Heat::Warm),
+			(50, Heat::Warm),
+			(51, Heat::Cold),
+			(250, Heat::Cold),
+			(251, Heat::Freezing),
+			(1000, Heat::Freezing),
+		];
+		for (distance, heat) in bands
+		{
+			assert_eq!(thresholds.heat(distance, 1000), heat, "distance {distance} of 1000.");
+		}
+	}
+
+	#[test]
+	fn heat_bands_custom()
+	{
+		let thresholds = HeatThresholds { boiling: 0.1, warm: 0.2, cold: 0.5 };
+		assert_eq!(thresholds.heat(1, 10), Heat::Boiling);
+		assert_eq!(thresholds.heat(2, 10), Heat::Warm);
+		assert_eq!(thresholds.heat(3, 10), Heat::Cold);
+		assert_eq!(thresholds.heat(5, 10), Heat::Cold);
+		assert_eq!(thresholds.heat(6, 10), Heat::Freezing);
+	}
+
+	#[test]
+	fn respond_with_heat_test()
+	{
+		let thresholds = HeatThresholds::default();
+		let correct = Guess::<{ 0..=999 }>::new(500).expect("correct failed to construct.");
+		let responses = [
+			(510, "510 is too high!", Heat::Boiling),
+			(489, "489 is too low!", Heat::Warm),
+			(550, "550 is too high!", Heat::Warm),
+			(551, "551 is too high!", Heat::Cold),
+			(250, "250 is too low!", Heat::Cold),
+			(249, "249 is too low!", Heat::Freezing),
+		];
+		for (value, message, heat) in responses
+		{
+			let mut output = Vec::new();
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			assert!(respond_with_heat(guess, correct, &thresholds, &mut output).expect("respond failed.").is_continue());
+			let output = String::from_utf8(output).expect("output was not UTF-8.");
+			assert!(output.contains(message), "{output:?} does not contain {message:?}.");
+			assert!(output.contains(&heat.to_string()), "{output:?} is not {heat:?}.");
+		}
+
+		let mut output = Vec::new();
+		let action = respond_with_heat(correct, correct, &thresholds, &mut output).expect("respond failed.");
+		assert_eq!(action, ControlFlow::Break(Win { guess: correct }));
+		let mut expected = Vec::new();
+		let _ = crate::respond(correct, correct, &mut expected).expect("respond failed.");
+		assert_eq!(output, expected);
+	}
+
+	#[test]
+	fn respond_with_heat_ascii()
+	{
+		let thresholds = HeatThresholds::default();
+		let correct = Guess::<{ 0..=999 }>::new(500).expect("correct failed to construct.");
+		let mut output = Vec::new();
+		for value in [505, 0, 500]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			let action = respond_with_heat_and_style(guess, correct, &thresholds, Style::Ascii, &mut output).expect("respond failed.");
+			assert_eq!(action.is_break(), guess == correct);
+		}
+		assert!(output.iter().all(|&byte| byte <= 0x7f), "{:?} is not ASCII.", String::from_utf8_lossy(&output));
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("(fire) boiling"));
+		assert!(output.contains("(ice) freezing"));
+		assert!(output.contains("You win! :)"));
+	}
+
+	#[test]
+	fn respond_trend_test()
+	{
+		let guess = |value| Guess::<{ 0..=100 }>::new(value).expect("guess failed to construct.");
+		let correct = guess(50);
+		let respond = |value, previous: Option<i32>| {
+			let mut output = Vec::new();
+			let action = respond_trend(guess(value), previous.map(guess), correct, &mut output).expect("respond failed.");
+			(action, String::from_utf8(output).expect("output was not UTF-8."))
+		};
+
+		// The first guess only gets the directional hint.
+		let (action, output) = respond(10, None);
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooLow));
+		let mut expected = Vec::new();
+		let _ = crate::respond(guess(10), correct, &mut expected).expect("respond failed.");
+		assert_eq!(output.as_bytes(), expected);
+
+		let (action, output) = respond(60, Some(10));
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooHigh));
+		assert!(output.contains("60 is too high!"));
+		assert!(output.contains("warmer"));
+
+		let (action, output) = respond(20, Some(60));
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooLow));
+		assert!(output.contains("20 is too low!"));
+		assert!(output.contains("colder"));
+
+		// Tied on the other side of the correct value.
+		let (action, output) = respond(80, Some(20));
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooHigh));
+		assert!(output.contains("same distance"));
+
+		let (action, output) = respond(50, Some(80));
+		assert_eq!(action, ControlFlow::Break(Win { guess: correct }));
+		assert!(output.contains("You win!"));
+		assert!(!output.contains("warmer"));
+
+		assert_eq!(Trend::of(guess(49), guess(51), correct), Trend::Same);
+		assert_eq!(Trend::of(guess(49), guess(48), correct), Trend::Warmer);
+		assert_eq!(Trend::of(guess(0), guess(100), correct), Trend::Same);
+	}
+}
@@ -0,0 +1,378 @@
+use {
+	crate::{
+		is_blank,
+		options::echo,
+		read_line_limited,
+		Feedback,
+		Guess,
+		Hints,
+		InputError,
+		ParseGuessError,
+		Win,
+	},
+	std::{
+		fmt::Write as _,
+		io::{
+			self,
+			BufRead,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+	},
+};
+
+/// Respond to a user's input with one line of JSON instead of colored prose.
+/// Returns the correct action to take, the same as respond.
+///
+/// Each response is a JSON object on a line of its own, with these fields:
+///
+/// - `guess`: the guess, as a number.
+/// - `result`: `"too_high"`, `"too_low"` or `"correct"`.
+/// - `win`: `true`, only present when the result is `"correct"`.
+///
+/// For example `{"guess":512,"result":"too_high"}` or
+/// `{"guess":700,"result":"correct","win":true}`. Fields are only ever added to
+/// this schema, so readers should ignore fields they do not know.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_json,
+/// // 		Guess,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // assert!(respond_json(example_guess, correct_guess, &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_json<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_json_with_hints(guess, correct, Hints::default(), output)
+}
+
+/// Respond to a user's input with one line of JSON that tells as much as hints.
+/// Returns the correct action to take, the same as respond.
+///
+/// This is the same as respond_json, except that with Hints::None the result of
+/// a wrong guess is `"wrong"` instead of `"too_high"` or `"too_low"`, as in
+/// `{"guess":512,"result":"wrong"}`. The action returned still holds the real
+/// Feedback, since it is meant for the program rather than the player.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+pub fn respond_json_with_hints<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	hints: Hints,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_json_with_odds(guess, correct, hints, None, output)
+}
+
+/// Respond to a user's input with one line of JSON that also tells the chance
+/// of still winning. Returns the correct action to take, the same as respond.
+///
+/// This is the same as respond_json_with_hints, except that a wrong guess has
+/// one more field when odds is given:
+///
+/// - `odds`: the chance of still winning with optimal play, from 0 through 1.
+///   See win_probability.
+///
+/// For example `{"guess":512,"result":"too_high","odds":0.03125}`. With
+/// Hints::None the odds are left out, since they would tell which way the guess
+/// was off.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_json_with_odds,
+/// // 		win_probability,
+/// // 		Bounds,
+/// // 		Feedback,
+/// // 		Guess,
+/// // 		Hints,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=1024 }>::new(512_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=1024 }>::new(300_i32).expect("");
+/// // let mut bounds = Bounds::new();
+/// // bounds.update(example_guess, Feedback::TooHigh);
+/// // let odds = win_probability(&bounds, 4);
+/// // respond_json_with_odds(example_guess, correct_guess, Hints::Direction, Some(odds), &mut stdout()).expect("");
+/// ```
+pub fn respond_json_with_odds<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	hints: Hints,
+	odds: Option<f64>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	let feedback = Feedback::from(guess.cmp(&correct));
+	let result = match feedback
+	{
+		Feedback::TooHigh | Feedback::TooLow if hints == Hints::None => "wrong",
+		Feedback::TooHigh => "too_high",
+		Feedback::TooLow => "too_low",
+		Feedback::Correct => "correct",
+	};
+	let extra = match odds
+	{
+		_ if feedback.is_correct() => String::from(",\"win\":true"),
+		Some(odds) if hints != Hints::None => format!(",\"odds\":{odds}"),
+		_ => String::new(),
+	};
+	writeln!(output, "{{\"guess\":{guess},\"result\":\"{result}\"{extra}}}")?;
+	Ok(feedback.into_control_flow(guess))
+}
+
+/// Gets user input until it is valid and returns it as a Guess, explaining
+/// invalid guesses with one line of JSON each instead of colored prose.
+///
+/// This is the same as input, except that there is no prompt, since the other
+/// end of a pipe does not need one. Each invalid guess is a JSON object on a
+/// line of its own, with these fields:
+///
+/// - `input`: the rejected line, trimmed and cut short after MAX_ECHO_CHARS
+///   characters.
+/// - `error`: `"not_a_number"`, `"overflow"`, `"underflow"` or
+///   `"out_of_range"`.
+/// - `message`: the reason the line was rejected, meant for people.
+///
+/// For example `{"input":"abc","error":"not_a_number","message":"Guesses must be
+/// an integer."}`. Fields are only ever added to this schema, so readers should
+/// ignore fields they do not know.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::input_json,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let guess = input_json::<{ 0..=100000 }>(&mut stdin().lock(), &mut stdout());
+/// ```
+pub fn input_json<const RANGE: RangeInclusive<i32>>(
+	input: &mut impl BufRead,
+	output: &mut impl Write,
+) -> Result<Guess<RANGE>, InputError>
+{
+	loop
+	{
+		let mut line = Vec::new();
+		let read = read_line_limited(input, &mut line)?;
+		if read == 0
+		{
+			return Err(InputError::Eof);
+		}
+		if is_blank(&line, read)
+		{
+			continue;
+		}
+		// A line that was cut short or is not text can not be a number either.
+		let result = std::str::from_utf8(&line)
+			.ok()
+			.filter(|_| read == line.len())
+			.map_or(Err(ParseGuessError::NotANumber), Guess::parse_lenient);
+		match result
+		{
+			Ok(guess) => return Ok(guess),
+			Err(error) => write_error(&String::from_utf8_lossy(&line), &error, output)?,
+		}
+	}
+}
+
+/// Writes the JSON line that input_json explains a rejected line with.
+fn write_error(
+	line: &str,
+	error: &ParseGuessError,
+	output: &mut impl Write,
+) -> io::Result<()>
+{
+	let code = match error
+	{
+		ParseGuessError::NotANumber => "not_a_number",
+		ParseGuessError::Overflow => "overflow",
+		ParseGuessError::Underflow => "underflow",
+		ParseGuessError::OutOfRange(_) | ParseGuessError::Negative { .. } => "out_of_range",
+		ParseGuessError::NoPreviousGuess => "no_previous_guess",
+		ParseGuessError::Repeated(_) => "repeated",
+		ParseGuessError::ReversedInterval { .. } => "reversed_interval",
+		ParseGuessError::IntervalOutOfRange(_) => "interval_out_of_range",
+	};
+	writeln!(output, "{{\"input\":{},\"error\":\"{code}\",\"message\":{}}}", quote(&echo(line)), quote(&error.to_string()))
+}
+
+/// Quotes text as a JSON string, escaping the characters that JSON requires.
+fn quote(text: &str) -> String
+{
+	let mut quoted = String::with_capacity(text.len() + 2);
+	quoted.push('"');
+	for character in text.chars()
+	{
+		match character
+		{
+			'"' => quoted.push_str("\\\""),
+			'\\' => quoted.push_str("\\\\"),
+			'\n' => quoted.push_str("\\n"),
+			'\r' => quoted.push_str("\\r"),
+			'\t' => quoted.push_str("\\t"),
+			control if control < ' ' => write!(quoted, "\\u{:04x}", control as u32).expect("writing to a String can not fail."),
+			character => quoted.push(character),
+		}
+	}
+	quoted.push('"');
+	quoted
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		serde_json::Value,
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Parses each line of output as a JSON object.
+	fn parse_lines(output: &[u8]) -> Vec<Value>
+	{
+		let output = std::str::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.ends_with('\n'));
+		output.lines().map(|line| serde_json::from_str(line).expect("line was not JSON.")).collect()
+	}
+
+	#[test]
+	fn respond_json_test()
+	{
+		let correct = guess(700);
+		let mut output = Vec::new();
+		assert_eq!(respond_json(guess(512), correct, &mut output).expect("respond failed."), ControlFlow::Continue(Feedback::TooLow));
+		assert_eq!(respond_json(guess(1024), correct, &mut output).expect("respond failed."), ControlFlow::Continue(Feedback::TooHigh));
+		assert_eq!(respond_json(correct, correct, &mut output).expect("respond failed."), ControlFlow::Break(Win { guess: correct }));
+		assert!(!output.windows(2).any(|bytes| bytes == b"\x1b["));
+
+		let lines = parse_lines(&output);
+		assert_eq!(lines.len(), 3);
+		assert_eq!(lines[0]["guess"], 512);
+		assert_eq!(lines[0]["result"], "too_low");
+		assert_eq!(lines[0].get("win"), None);
+		assert_eq!(lines[1]["guess"], 1024);
+		assert_eq!(lines[1]["result"], "too_high");
+		assert_eq!(lines[2]["guess"], 700);
+		assert_eq!(lines[2]["result"], "correct");
+		assert_eq!(lines[2]["win"], true);
+
+		assert_eq!(String::from_utf8_lossy(&output).lines().last(), Some("{\"guess\":700,\"result\":\"correct\",\"win\":true}"));
+	}
+
+	#[test]
+	fn respond_json_without_hints()
+	{
+		let correct = guess(700);
+		let mut output = Vec::new();
+		for value in [512, 1024, 700]
+		{
+			let action = respond_json_with_hints(guess(value), correct, Hints::None, &mut output).expect("respond failed.");
+			assert_eq!(action, respond_json(guess(value), correct, &mut Vec::new()).expect("respond failed."));
+		}
+		let text = String::from_utf8_lossy(&output);
+		assert!(!text.contains("high") && !text.contains("low"), "{text}");
+		let lines = parse_lines(&output);
+		assert_eq!(lines[0]["result"], "wrong");
+		assert_eq!(lines[1]["result"], "wrong");
+		assert_eq!(lines[2]["result"], "correct");
+
+		let mut directional = Vec::new();
+		let _ = respond_json_with_hints(guess(512), correct, Hints::Direction, &mut directional).expect("respond failed.");
+		assert_eq!(parse_lines(&directional)[0]["result"], "too_low");
+	}
+
+	#[test]
+	fn respond_json_with_odds_test()
+	{
+		let correct = guess(300);
+		let mut output = Vec::new();
+		for (value, odds) in [(512, 0.03125), (100, 0.0), (299, 1.0), (300, 0.5)]
+		{
+			let action = respond_json_with_odds(guess(value), correct, Hints::Direction, Some(odds), &mut output).expect("respond failed.");
+			assert_eq!(action, respond_json(guess(value), correct, &mut Vec::new()).expect("respond failed."));
+		}
+		let lines = parse_lines(&output);
+		assert_eq!(lines[0]["odds"], 0.03125);
+		assert_eq!(lines[1]["odds"], 0.0);
+		assert_eq!(lines[2]["odds"], 1.0);
+		assert_eq!(lines[3].get("odds"), None);
+		assert_eq!(lines[3]["win"], true);
+		assert_eq!(String::from_utf8_lossy(&output).lines().next(), Some("{\"guess\":512,\"result\":\"too_high\",\"odds\":0.03125}"));
+
+		let mut hidden = Vec::new();
+		let _ = respond_json_with_odds(guess(512), correct, Hints::None, Some(0.5), &mut hidden).expect("respond failed.");
+		assert_eq!(parse_lines(&hidden)[0].get("odds"), None);
+	}
+
+	#[test]
+	fn input_json_test()
+	{
+		let mut output = Vec::new();
+		let lines = "abc\n\n99999999999\n2000\n\"quoted\" \\ \t\n512\n";
+		let guess: Guess<{ 0..=1024 }> = input_json(&mut lines.as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 512_i32);
+
+		let errors = parse_lines(&output);
+		assert_eq!(errors.len(), 4);
+		assert_eq!(errors[0]["input"], "abc");
+		assert_eq!(errors[0]["error"], "not_a_number");
+		assert_eq!(errors[0]["message"], "Guesses must be an integer.");
+		assert_eq!(errors[1]["error"], "overflow");
+		assert_eq!(errors[2]["input"], "2000");
+		assert_eq!(errors[2]["error"], "out_of_range");
+		assert!(errors[2]["message"].as_str().is_some_and(|message| message.contains("at most 1024")));
+		assert_eq!(errors[3]["input"], "\"quoted\" \\");
+		assert_eq!(errors[3]["error"], "not_a_number");
+
+		let result = input_json::<{ 0..=1024 }>(&mut "abc\n".as_bytes(), &mut Vec::new());
+		assert!(matches!(result, Err(InputError::Eof)));
+	}
+
+	#[test]
+	fn quote_test()
+	{
+		for text in ["", "plain", "\"", "\\", "tab\there", "new\nline", "\u{1}\u{1f}", "é 😊"]
+		{
+			let quoted = quote(text);
+			assert_eq!(serde_json::from_str::<String>(&quoted).expect("quoted text was not JSON."), text, "{quoted}");
+		}
+	}
+}
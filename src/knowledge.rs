@@ -0,0 +1,251 @@
+use {
+	crate::{
+		Bounds,
+		Feedback,
+		Guess,
+		GuessRange,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		ops::RangeInclusive,
+		result::Result,
+	},
+};
+
+/// What a player knows about the secret from the feedback so far: the interval
+/// that is still consistent with every answer, or that there is none. Every
+/// game keeps one, see Game::knowledge.
+///
+/// Feedback that contradicts itself, which only a lying game or a wrong oracle
+/// gives, leaves the state contradictory rather than panicking, and it stays
+/// that way.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	Feedback,
+/// // 	Guess,
+/// // 	GuessRange,
+/// // 	KnowledgeState,
+/// //};
+/// // let mut knowledge = KnowledgeState::<{ 0..=1024 }>::new(GuessRange::full());
+/// // knowledge.update(Guess::new(512).expect(""), Feedback::TooHigh);
+/// // knowledge.update(Guess::new(256).expect(""), Feedback::TooLow);
+/// // assert_eq!((knowledge.lo(), knowledge.hi(), knowledge.size()), (Guess::new(257), Guess::new(511), 255));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct KnowledgeState<const RANGE: RangeInclusive<i32>>
+{
+	bounds: Bounds<RANGE>,
+}
+
+impl<const RANGE: RangeInclusive<i32>> KnowledgeState<RANGE>
+{
+	/// Creates the state before the first guess, when the secret can be anywhere
+	/// in range.
+	pub const fn new(range: GuessRange<RANGE>) -> Self
+	{
+		KnowledgeState { bounds: Bounds::Within(range) }
+	}
+
+	/// Creates the state range is left in by history, as pairs of a guess and
+	/// the feedback about it, oldest first.
+	pub fn from_history(
+		range: GuessRange<RANGE>,
+		history: impl IntoIterator<Item = (Guess<RANGE>, Feedback)>,
+	) -> Self
+	{
+		history.into_iter().fold(KnowledgeState::new(range), |mut knowledge, (guess, feedback)| {
+			knowledge.update(guess, feedback);
+			knowledge
+		})
+	}
+
+	/// Narrows the interval with feedback about guess. A guess outside of the
+	/// interval that agrees with it tells nothing new, while one that disagrees
+	/// makes the state contradictory for good.
+	pub fn update(
+		&mut self,
+		guess: Guess<RANGE>,
+		feedback: Feedback,
+	)
+	{
+		self.bounds.update(guess, feedback);
+	}
+
+	/// Returns the lowest value the secret can still be, or None if the feedback
+	/// contradicts itself.
+	pub const fn lo(&self) -> Option<Guess<RANGE>>
+	{
+		match self.bounds.range()
+		{
+			Some(range) => Some(range.lo()),
+			None => None,
+		}
+	}
+
+	/// Returns the highest value the secret can still be, or None if the
+	/// feedback contradicts itself.
+	pub const fn hi(&self) -> Option<Guess<RANGE>>
+	{
+		match self.bounds.range()
+		{
+			Some(range) => Some(range.hi()),
+			None => None,
+		}
+	}
+
+	/// Returns how many values the secret can still be, which is 0 if the
+	/// feedback contradicts itself and 1 once it is known.
+	pub fn size(&self) -> u64
+	{
+		self.bounds.range().map_or(0_u64, |range| range.size())
+	}
+
+	/// Returns whether the feedback contradicts itself, so that no value is
+	/// possible.
+	pub const fn is_contradictory(&self) -> bool
+	{
+		matches!(self.bounds, Bounds::Contradiction)
+	}
+
+	/// Returns whether the secret can still be value.
+	pub fn allows(
+		&self,
+		value: Guess<RANGE>,
+	) -> bool
+	{
+		self.bounds.range().is_some_and(|range| range.contains(value))
+	}
+
+	/// Returns the interval the secret lies within, or None if the feedback
+	/// contradicts itself.
+	pub const fn range(&self) -> Option<GuessRange<RANGE>>
+	{
+		self.bounds.range()
+	}
+
+	/// Returns the state as Bounds.
+	pub const fn bounds(&self) -> Bounds<RANGE>
+	{
+		self.bounds
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Default for KnowledgeState<RANGE>
+{
+	fn default() -> Self
+	{
+		KnowledgeState::new(GuessRange::full())
+	}
+}
+
+/// Formats KnowledgeState like its Bounds, as in `257..=511`, or as `nothing`
+/// once it is contradictory.
+impl<const RANGE: RangeInclusive<i32>> Display for KnowledgeState<RANGE>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		write!(f, "{}", self.bounds)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	fn interval(knowledge: &KnowledgeState<{ 0..=1024 }>) -> Option<(i32, i32, u64)>
+	{
+		Some((knowledge.lo()?.value(), knowledge.hi()?.value(), knowledge.size()))
+	}
+
+	#[test]
+	fn update()
+	{
+		let mut knowledge = KnowledgeState::default();
+		assert_eq!(interval(&knowledge), Some((0, 1024, 1025)));
+		let steps = [
+			(512, Feedback::TooHigh, (0, 511, 512)),
+			(256, Feedback::TooLow, (257, 511, 255)),
+			// Feedback that agrees with what is known tells nothing new.
+			(600, Feedback::TooHigh, (257, 511, 255)),
+			(100, Feedback::TooLow, (257, 511, 255)),
+			(511, Feedback::TooHigh, (257, 510, 254)),
+			(257, Feedback::TooLow, (258, 510, 253)),
+			(300, Feedback::Correct, (300, 300, 1)),
+			(300, Feedback::Correct, (300, 300, 1)),
+		];
+		for (value, feedback, expected) in steps
+		{
+			knowledge.update(guess(value), feedback);
+			assert_eq!(interval(&knowledge), Some(expected), "after {value} was {feedback:?}.");
+			assert!(!knowledge.is_contradictory());
+		}
+		assert!(knowledge.allows(guess(300)) && !knowledge.allows(guess(301)));
+		assert_eq!(knowledge.to_string(), "300..=300");
+
+		// Within a narrower range, as for a difficulty.
+		let range = GuessRange::new(guess(1), guess(100)).expect("range failed to construct.");
+		let knowledge = KnowledgeState::from_history(range, [(guess(50), Feedback::TooLow), (guess(75), Feedback::TooHigh)]);
+		assert_eq!(interval(&knowledge), Some((51, 74, 24)));
+		assert_eq!(knowledge.bounds(), Bounds::Within(GuessRange::new(guess(51), guess(74)).expect("range failed to construct.")));
+	}
+
+	#[test]
+	fn size()
+	{
+		let narrow = GuessRange::new(guess(1), guess(100)).expect("range failed to construct.");
+		let cases = [
+			(KnowledgeState::default(), 1025),
+			(KnowledgeState::new(narrow), 100),
+			(KnowledgeState::from_history(narrow, [(guess(42), Feedback::Correct)]), 1),
+			(KnowledgeState::from_history(narrow, [(guess(42), Feedback::TooHigh), (guess(41), Feedback::TooLow)]), 0),
+		];
+		for (knowledge, size) in cases
+		{
+			assert_eq!(knowledge.size(), size, "{knowledge}");
+			assert_eq!(knowledge.size() == 0, knowledge.is_contradictory(), "{knowledge}");
+		}
+	}
+
+	#[test]
+	fn contradiction()
+	{
+		let sequences = [
+			vec![(10, Feedback::TooHigh), (9, Feedback::TooLow)],
+			vec![(0, Feedback::TooHigh)],
+			vec![(1024, Feedback::TooLow)],
+			vec![(100, Feedback::TooLow), (50, Feedback::Correct)],
+			vec![(100, Feedback::Correct), (200, Feedback::Correct)],
+			vec![(100, Feedback::Correct), (100, Feedback::TooHigh)],
+			vec![(100, Feedback::Correct), (100, Feedback::TooLow)],
+		];
+		for sequence in sequences
+		{
+			let mut knowledge = KnowledgeState::<{ 0..=1024 }>::from_history(GuessRange::full(), sequence.iter().map(|&(value, feedback)| (guess(value), feedback)));
+			assert!(knowledge.is_contradictory(), "{sequence:?}");
+			assert_eq!((knowledge.lo(), knowledge.hi(), knowledge.size(), knowledge.range()), (None, None, 0, None), "{sequence:?}");
+			assert_eq!(knowledge.to_string(), "nothing");
+
+			// More feedback can not resolve a contradiction.
+			for feedback in [Feedback::TooHigh, Feedback::TooLow, Feedback::Correct]
+			{
+				knowledge.update(guess(512), feedback);
+				assert!(knowledge.is_contradictory() && !knowledge.allows(guess(512)));
+			}
+		}
+	}
+}
@@ -3,41 +3,290 @@
 #![feature(adt_const_params)]
 #![allow(incomplete_features)]
 
+mod adaptive;
+mod analysis;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bounded;
+mod bounds;
+mod builder;
+mod cli;
+mod clock;
+mod clue;
+mod color;
+mod coop;
+mod daily;
+mod difficulty;
+mod duel;
+mod game;
+mod golf;
+mod grade;
+mod heat;
+mod json;
+mod knowledge;
+mod multi;
+mod observer;
+mod options;
+mod query;
+mod range_spec;
+#[cfg(feature = "save")]
+mod replay;
+mod run;
+#[cfg(feature = "save")]
+mod save;
+mod score;
+mod secret;
+mod session;
+mod simulation;
+mod stats;
+mod strategy;
+mod terminal;
+mod theme;
+#[cfg(feature = "words")]
+mod words;
+
+pub use {
+	adaptive::{
+		AdaptivePolicy,
+		Adjustment,
+		SPARE_TO_WIDEN,
+	},
+	analysis::{
+		analyze,
+		Analysis,
+	},
+	bounded::{
+		input_runtime,
+		respond_runtime,
+		BoundedGuess,
+	},
+	bounds::{
+		respond_with_bounds,
+		win_probability,
+		Bounds,
+	},
+	builder::{
+		ConfigError,
+		GameBuilder,
+	},
+	cli::{
+		flag_value,
+		ArgsError,
+		Command,
+		ExitStatus,
+		GameArgs,
+		USAGE,
+	},
+	clock::{
+		Clock,
+		SystemClock,
+	},
+	clue::{
+		Clue,
+		HintOracle,
+	},
+	color::{
+		set_color_choice,
+		ColorChoice,
+	},
+	coop::play_coop,
+	daily::{
+		daily_number,
+		today,
+	},
+	difficulty::{
+		fair_attempts,
+		Difficulty,
+		Hints,
+		DIFFICULTY_RANGE,
+	},
+	duel::DuelGame,
+	game::{
+		AlreadyFinished,
+		BonusRule,
+		Game,
+		GameOutcome,
+		HistoryEntry,
+		TurnResult,
+	},
+	golf::{
+		par,
+		play_golf,
+		render_scorecard,
+		Hole,
+		GOLF_HOLES,
+	},
+	grade::{
+		grade,
+		Grade,
+	},
+	heat::{
+		respond_trend,
+		respond_with_heat,
+		respond_with_heat_and_style,
+		Heat,
+		HeatThresholds,
+		Trend,
+	},
+	json::{
+		input_json,
+		respond_json,
+		respond_json_with_hints,
+		respond_json_with_odds,
+	},
+	knowledge::KnowledgeState,
+	multi::{
+		MultiGame,
+		MultiOutcome,
+		MultiTurn,
+	},
+	observer::{
+		GameObserver,
+		TranscriptObserver,
+	},
+	options::{
+		InputContext,
+		InputOptions,
+		InputOutput,
+		Prompt,
+		Style,
+		WithErrors,
+		MAX_ECHO_CHARS,
+	},
+	query::{
+		input_query,
+		respond_query,
+		GuessQuery,
+	},
+	range_spec::{
+		RangeSpec,
+		RangeSpecError,
+	},
+	run::Runner,
+	score::{
+		score,
+		ScoreConfig,
+	},
+	secret::{
+		respond_secret,
+		Secret,
+	},
+	session::{
+		render_summary,
+		Round,
+		Session,
+	},
+	simulation::{
+		simulate,
+		simulate_kind,
+		simulate_with,
+		SimulationReport,
+	},
+	stats::{
+		BestTimes,
+		Grades,
+		Stats,
+	},
+	strategy::{
+		BinarySearchStrategy,
+		LinearStrategy,
+		RandomStrategy,
+		Strategy,
+		StrategyKind,
+	},
+	terminal::{
+		terminal_input,
+		TerminalInput,
+		TerminalOutput,
+	},
+	theme::{
+		respond_with_attempt,
+		respond_with_theme,
+		win_banner,
+		Attempt,
+		Flourish,
+		ResponseTheme,
+		BANNER_WIDTH,
+	},
+};
+#[cfg(feature = "save")]
+pub use {
+	replay::Replay,
+	save::LoadError,
+};
+#[cfg(feature = "stats")]
+pub use stats::StatsFile;
+#[cfg(feature = "tokio")]
+pub use async_io::{
+	input_async,
+	respond_async,
+};
 use {
 	colored::{
 		ColoredString,
 		Colorize,
 	},
+	options::restyle,
+	rand::{
+		distributions::{
+			Distribution,
+			Standard,
+		},
+		Rng,
+	},
 	std::{
 		cmp::Ordering,
+		collections::HashSet,
 		fmt::{
 			Display,
 			Formatter,
 		},
+		hash::Hash,
 		io::{
-			Read,
+			self,
+			BufRead,
+			ErrorKind,
 			Write,
 		},
+		iter::FusedIterator,
+		num::{
+			IntErrorKind,
+			ParseIntError,
+		},
 		ops::{
 			ControlFlow,
 			RangeInclusive,
 		},
 		result::Result,
+		str::FromStr,
 	},
 };
 
 /// Gets user input until it is valid and returns it as a Guess. Accepts a
-/// colored string to prompt the user for input.
+/// colored string to prompt the user for input, or a closure that makes the
+/// prompt from an InputContext so that it can change after invalid guesses.
 ///
 /// This will block the program while waiting for input from stdin. It will ask
 /// for input until the input is an integer withing the range of valid values.
-/// If the input is invalid, it will display an error before repeating from the
-/// prompt.
+/// Each attempt reads exactly one line, so the rest of the input is left for
+/// later calls. If the input is invalid, it will display an error before
+/// repeating from the prompt. Blank lines just repeat the prompt, and do not
+/// count as invalid guesses.
+///
+/// # Errors
+/// Returns InputError::Eof if the input ends before a valid guess is read, and
+/// InputError::Io if reading the input, prompting or explaining an invalid
+/// guess fails. Lines that are not valid UTF-8 or are longer than
+/// MAX_LINE_BYTES are treated as invalid guesses rather than errors.
+///
+/// Prompts are written to output, and so are error messages unless output is a
+/// WithErrors that sends them somewhere else. Their colors follow
+/// set_color_choice().
 ///
 /// # Panics
-/// Panics if writing to [io::stdout] fails or if a formatting trait
-/// implementation returns an error. This indicates an incorrect implementation
-/// since fmt::Write for String never returns an error itself.
+/// Panics if a formatting trait implementation returns an error. This
+/// indicates an incorrect implementation since fmt::Write for String never
+/// returns an error itself.
 ///
 /// # Examples
 ///
@@ -52,197 +301,2441 @@ use {
 /// // 		stdout,
 /// // 	},
 /// //};
-/// // let input = input::<{ 0..=100000 }>(format!("Guess a number: ").yellow(), &mut stdin(), &mut stdout());
+/// // let input = input::<{ 0..=100000 }>(format!("Guess a number: ").yellow(), &mut stdin().lock(), &mut stdout());
 /// ```
 pub fn input<const RANGE: RangeInclusive<i32>>(
-	prompt: ColoredString,
-	input: &mut impl Read,
-	output: &mut impl Write,
-) -> Guess<RANGE>
+	prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<Guess<RANGE>, InputError>
 {
-	// Avoids counting invalid guesses as used attempts.
-	loop
-	{
-		print!("{}", prompt);
-		let mut guess_input = String::new();
-		// If no issue prompting.
-		if let Ok(_) = output.flush()
-		// Read input.
-			&& let Ok(_) = input.read_to_string(&mut guess_input)
-		// Trim and parse to integer.
-			&& let Ok(parsed) = guess_input.trim().parse::<i32>()
-		// Validate input.
-			&& let Ok(guess) = Guess::new(parsed)
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	read_guess(prompt, &InputOptions::default(), min, max, input, output, |text| parse_lenient(text, min, max).map(|value| Guess { value })).map(|result| result.guess)
+}
+
+/// Gets user input until it is valid and returns it as a Guess, using options
+/// for the prompt, the invalid guess messages and whether to use color.
+///
+/// This is the same as input, which uses InputOptions::new() with its prompt.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		input_with_options,
+/// // 		InputOptions,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let options = InputOptions {
+/// // 	invalid_message: "Pick a whole number between {min} and {max}, not {input}.".to_string(),
+/// // 	color: false,
+/// // 	..InputOptions::default()
+/// // };
+/// // let input = input_with_options::<{ 0..=100000 }>(&options, &mut stdin().lock(), &mut stdout());
+/// ```
+pub fn input_with_options<const RANGE: RangeInclusive<i32>>(
+	options: &InputOptions,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<Guess<RANGE>, InputError>
+{
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	read_guess(options.prompt.clone(), options, min, max, input, output, |text| parse_lenient(text, min, max).map(|value| Guess { value })).map(|result| result.guess)
+}
+
+/// Words that make input_or_command return PlayerAction::Quit. Case is ignored.
+pub const QUIT_COMMANDS: [&str; 3] = ["q", "quit", "exit"];
+
+/// Words that make input_or_command return PlayerAction::GiveUp. Case is
+/// ignored.
+pub const GIVE_UP_COMMANDS: [&str; 2] = ["give up", "giveup"];
+
+/// Words that make input_or_command return PlayerAction::Hint. Case is ignored.
+pub const HINT_COMMANDS: [&str; 2] = ["hint", "h"];
+
+/// Words that make input_or_command return PlayerAction::Pause. Case is
+/// ignored.
+pub const PAUSE_COMMANDS: [&str; 1] = ["pause"];
+
+/// Words that make input_or_command return PlayerAction::Abort. Case is
+/// ignored.
+pub const ABORT_COMMANDS: [&str; 1] = ["abort"];
+
+/// Answers that make ask_yes_no return true. Case is ignored.
+pub const YES_ANSWERS: [&str; 2] = ["y", "yes"];
+
+/// Answers that make ask_yes_no return false, as do the words in QUIT_COMMANDS.
+/// Case is ignored.
+pub const NO_ANSWERS: [&str; 2] = ["n", "no"];
+
+/// What the player chose to do at the prompt.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PlayerAction<const RANGE: RangeInclusive<i32>>
+{
+	/// The player entered a valid guess.
+	Guess(Guess<RANGE>),
+	/// The player wants to leave the game. See QUIT_COMMANDS.
+	Quit,
+	/// The player wants to stop guessing and see the answer. See
+	/// GIVE_UP_COMMANDS.
+	GiveUp,
+	/// The player wants a clue about the answer. See HINT_COMMANDS.
+	Hint,
+	/// The player wants to stop the clock for a while. See PAUSE_COMMANDS.
+	Pause,
+	/// The player wants to end the game and see how far they got, without the
+	/// answer. See ABORT_COMMANDS.
+	Abort,
+}
+
+/// Gets user input until it is a valid guess or a command, and returns what the
+/// player chose to do.
+///
+/// This is the same as input, except that the words in QUIT_COMMANDS,
+/// GIVE_UP_COMMANDS, HINT_COMMANDS, PAUSE_COMMANDS and ABORT_COMMANDS are
+/// recognized in any case instead of being invalid guesses.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::{
+/// // 		input_or_command,
+/// // 		PlayerAction,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // match input_or_command::<{ 0..=100 }>(format!("Guess a number: ").yellow(), &mut stdin().lock(), &mut stdout())
+/// // {
+/// // 	Ok(PlayerAction::Guess(guess)) => println!("You guessed {guess}."),
+/// // 	Ok(PlayerAction::Hint) => println!("No hints here."),
+/// // 	Ok(PlayerAction::Pause) => println!("There's no clock to stop."),
+/// // 	Ok(PlayerAction::Quit | PlayerAction::GiveUp | PlayerAction::Abort) | Err(_) => println!("Bye!"),
+/// // }
+/// ```
+pub fn input_or_command<const RANGE: RangeInclusive<i32>>(
+	prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<PlayerAction<RANGE>, InputError>
+{
+	input_or_command_with_options(prompt, &InputOptions::default(), input, output)
+}
+
+/// Gets user input until it is a valid guess or a command, using options for
+/// the invalid guess messages and how they look.
+///
+/// This is the same as input_or_command, which uses InputOptions::default().
+/// The prompt in options is not used, so that prompt can still change after
+/// invalid guesses.
+///
+/// # Errors
+/// Returns the same errors as input, or InputError::TooManyInvalid if options
+/// limits the number of invalid guesses.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::{
+/// // 		input_or_command_with_options,
+/// // 		InputOptions,
+/// // 		Style,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let options = InputOptions { style: Style::Ascii, ..InputOptions::default() };
+/// // let action = input_or_command_with_options::<{ 0..=100 }>("Guess: ".yellow(), &options, &mut stdin().lock(), &mut stdout());
+/// ```
+pub fn input_or_command_with_options<const RANGE: RangeInclusive<i32>>(
+	prompt: impl Prompt,
+	options: &InputOptions,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<PlayerAction<RANGE>, InputError>
+{
+	input_or_command_within(prompt, options, GuessRange::full(), input, output)
+}
+
+/// Shared implementation of input_or_command_with_options and Game::play.
+/// Accepts only guesses within range, which may be narrower than RANGE.
+pub(crate) fn input_or_command_within<const RANGE: RangeInclusive<i32>>(
+	prompt: impl Prompt,
+	options: &InputOptions,
+	range: GuessRange<RANGE>,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<PlayerAction<RANGE>, InputError>
+{
+	let (min, max) = (range.lo.value, range.hi.value);
+	read_guess(prompt, options, min, max, input, output, |text| {
+		let command = text.trim().to_lowercase();
+		if QUIT_COMMANDS.contains(&command.as_str())
 		{
-			// Stop looping if everything checks out.
-			break guess;
+			Ok(PlayerAction::Quit)
+		}
+		else if GIVE_UP_COMMANDS.contains(&command.as_str())
+		{
+			Ok(PlayerAction::GiveUp)
+		}
+		else if HINT_COMMANDS.contains(&command.as_str())
+		{
+			Ok(PlayerAction::Hint)
+		}
+		else if PAUSE_COMMANDS.contains(&command.as_str())
+		{
+			Ok(PlayerAction::Pause)
+		}
+		else if ABORT_COMMANDS.contains(&command.as_str())
+		{
+			Ok(PlayerAction::Abort)
 		}
 		else
 		{
-			// Show helpful error when user input is invalid.
-			output.write_all(format!("\n{}\n{}",
-				"Invalid guess. 🤕".red(),
-				format!("Guesses must be an integer from {} through {}.", RANGE.start(), RANGE.end()).yellow()).as_bytes())
-			.expect("Error erroring...");
+			parse_lenient(text, min, max).map(|value| PlayerAction::Guess(Guess { value }))
 		}
-	}
+	}).map(|result| result.guess)
 }
 
-/// Respond to a user's input with some console output. Returns the correct
-/// action to take.
+/// Asks a question until the answer is in YES_ANSWERS or NO_ANSWERS, and
+/// returns whether it was yes. The words in QUIT_COMMANDS and the end of the
+/// input count as no, so the player can always leave. Any other answer is
+/// explained on output.errors() before the question is asked again.
 ///
-/// The output will explain whether the guess was too high, too low, or if they
-/// are equal, that the user wins. The return value will be
-/// std::ops::ControlFlow::Continue(()) unless the user wins, in which case it
-/// will return std::ops::ControlFlow::Break(()).
-///
-/// # Panics
-/// Panics if writing to [io::stdout] fails.
+/// # Errors
+/// Returns an error if reading input or writing output fails.
 ///
 /// # Examples
 ///
-/// Demonstrating a guess that is too high:
-///
 /// ```
-/// // use std::{
-/// // 	io::stdout,
-/// // 	ops::ControlFlow,
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::{
+/// // 		ask_yes_no,
+/// // 		Style,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
 /// //};
-/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
-/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
-/// // let action: ControlFlow<()> = respond(example_guess, correct_guess, &mut stdout());
-/// // assert!(action.is_continue()));
+/// // if ask_yes_no(&"Play again? (y/n) ".yellow(), Style::Fancy, &mut stdin().lock(), &mut stdout()).expect("")
+/// // {
+/// // 	println!("Here we go!");
+/// // }
 /// ```
-pub fn respond<const RANGE: RangeInclusive<i32>>(
-	guess: Guess<RANGE>,
-	correct: Guess<RANGE>,
-	output: &mut impl Write,
-) -> ControlFlow<()>
+pub fn ask_yes_no(
+	question: &ColoredString,
+	style: Style,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> io::Result<bool>
 {
-	output.write_all(
-		match guess.cmp(&correct)
+	loop
+	{
+		write!(output.prompts(), "{}", style.render(question))?;
+		output.prompts().flush()?;
+		let mut line = Vec::new();
+		if read_line_limited(input, &mut line)? == 0
 		{
-			Ordering::Greater => "\n{guess} is too high! 🥵".magenta(),
-			Ordering::Less => "\n{guess} is too low! 🥶".cyan(),
-			Ordering::Equal => "\nYou win! 😊🏖".green().bold(),
+			return Ok(false);
 		}
-		.as_bytes(),
-	)
-	.expect("Error outputting response.");
-	if let Ordering::Equal = correct.cmp(&guess)
-	{
-		ControlFlow::Break(())
-	}
-	else
-	{
-		ControlFlow::Continue(())
+		let answer = String::from_utf8_lossy(&line).trim().to_lowercase();
+		if YES_ANSWERS.contains(&answer.as_str())
+		{
+			return Ok(true);
+		}
+		if NO_ANSWERS.contains(&answer.as_str()) || QUIT_COMMANDS.contains(&answer.as_str())
+		{
+			return Ok(false);
+		}
+		writeln!(output.errors(), "{}", style.render(&"Please answer y or n.".red()))?;
 	}
 }
 
-/// Tuple struct to represent a guess. A guess is a type-safe way to represent
-/// the integer that was input by the user, and the correct value to compare
-/// against.
+/// Gets user input until it is valid and returns it as a Guess, reading text
+/// that starts with `+` or `-` as relative to the previous guess.
+///
+/// This is the same as input, except that it parses with
+/// Guess::parse_relative(). Relative guesses are rejected until there is a
+/// previous guess, so the first guess must be absolute.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
 ///
-/// Guess's constructor offers input validation in the constructor itself rather
-/// than relying on other methods to obey the assumption that the guess is in a
-/// valid state. This struct must be created using Guess::new().
 /// ```
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-pub struct Guess<const RANGE: RangeInclusive<i32>>
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_relative,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let first = input_relative::<{ 0..=100 }>(None, "Guess: ".yellow(), &mut stdin().lock(), &mut stdout()).expect("");
+/// // let second = input_relative::<{ 0..=100 }>(Some(first), "Guess: ".yellow(), &mut stdin().lock(), &mut stdout()).expect("");
+/// ```
+pub fn input_relative<const RANGE: RangeInclusive<i32>>(
+	previous: Option<Guess<RANGE>>,
+	prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<Guess<RANGE>, InputError>
 {
-	value: i32,
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	read_guess(prompt, &InputOptions::default(), min, max, input, output, |text| Guess::parse_relative(text, previous)).map(|result| result.guess)
 }
 
-/// Constructor for creating a Guess.
+/// Gets user input until it is valid and not in used, and returns it as a Guess.
 ///
-/// The constructor validates that the guess' value lies within RANGE. If the
-/// value provided is valid, it returns an Ok() containing a new instance of
-/// Guess. If the value provided is invalid, the constructor returns an error.
+/// This is the same as input, except that a guess that is already in used is
+/// not returned. Instead a warning is shown and the prompt repeats, counting it
+/// as an invalid try. The caller owns used, and decides which guesses go in it.
 ///
+/// # Errors
+/// Returns the same errors as input.
 ///
 /// # Examples
 ///
-/// Demonstrating a guess that is too high:
-///
 /// ```
-/// // use guessing_game::{
-/// // 	input,
-/// // 	Guess,
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_unique,
+/// // 	std::{
+/// // 		collections::HashSet,
+/// // 		io::{
+/// // 			stdin,
+/// // 			stdout,
+/// // 		},
+/// // 	},
 /// //};
-/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32);
+/// // let mut used = HashSet::new();
+/// // let guess = input_unique::<{ 0..=100 }>(&used, "Guess: ".yellow(), &mut stdin().lock(), &mut stdout()).expect("");
+/// // used.insert(guess);
 /// ```
-impl<const RANGE: RangeInclusive<i32>> Guess<RANGE>
+pub fn input_unique<const RANGE: RangeInclusive<i32>>(
+	used: &HashSet<Guess<RANGE>>,
+	prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<Guess<RANGE>, InputError>
 {
-	pub fn new(guess: i32) -> Result<Self, i32>
-	{
-		if RANGE.contains(&guess)
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	read_guess(prompt, &InputOptions::default(), min, max, input, output, |text| {
+		let guess = Guess::parse_lenient(text)?;
+		if used.contains(&guess)
 		{
-			Ok(Guess { value: guess })
+			Err(ParseGuessError::Repeated(guess.value))
 		}
 		else
 		{
-			Err(guess)
+			Ok(guess)
 		}
-	}
+	}).map(|result| result.guess)
 }
 
-/// Formats Guess for displaying in console output.
+/// Gets user input until it is valid and returns it as a Guess, giving up once
+/// more than max_invalid invalid guesses have been entered.
 ///
-/// # Examples
+/// This is the same as input, except that it can not be kept waiting forever by
+/// input that is never valid. Up to max_invalid invalid lines are explained and
+/// skipped like input does, and the line after that is not read at all.
 ///
-/// Outputting a Guess to the console:
+/// # Errors
+/// Returns InputError::TooManyInvalid with the reason the last guess was
+/// rejected if there are more than max_invalid invalid guesses. Otherwise
+/// returns the same errors as input.
+///
+/// # Examples
 ///
 /// ```
-/// // use guessing_game::Guess;
-/// // println!("The guess displays as {}", Guess::<{ 0..=100000 }>::new(100_i32).expect(""));
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_with_retries,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let input = input_with_retries::<{ 0..=100000 }>(format!("Guess a number: ").yellow(), 3, &mut stdin().lock(), &mut stdout());
 /// ```
-impl<const RANGE: RangeInclusive<i32>> Display for Guess<RANGE>
+pub fn input_with_retries<const RANGE: RangeInclusive<i32>>(
+	prompt: impl Prompt,
+	max_invalid: usize,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<Guess<RANGE>, InputError>
 {
-	fn fmt(
-		&self,
-		f: &mut Formatter<'_>,
-	) -> Result<(), std::fmt::Error>
-	{
-		self.value.fmt(f)
-	}
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	let options = InputOptions { max_invalid: Some(max_invalid), ..InputOptions::default() };
+	read_guess(prompt, &options, min, max, input, output, |text| parse_lenient(text, min, max).map(|value| Guess { value })).map(|result| result.guess)
 }
 
-#[cfg(test)]
-mod tests
+/// Gets user input until it is valid and returns it as a Guess, together with
+/// how many invalid guesses were entered first.
+///
+/// This is the same as input, except for also returning the number of invalid
+/// tries. Blank lines are not counted.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_counted,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let result = input_counted::<{ 0..=100 }>("Guess: ".yellow(), &mut stdin().lock(), &mut stdout()).expect("");
+/// // println!("You guessed {} after {} invalid tries.", result.guess, result.invalid_tries);
+/// ```
+pub fn input_counted<const RANGE: RangeInclusive<i32>>(
+	prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<InputResult<Guess<RANGE>>, InputError>
 {
-	use {
-		super::{
-			Guess,
-			*,
-		},
-		std::io::stdout,
-	};
-	#[test]
-	fn construction()
-	{
-		Guess::<{ 0..=0 }>::new(0_i32).expect("guess 1 failed to construct.");
-		Guess::<{ 0..=1 }>::new(0_i32).expect("guess 2 failed to construct.");
-		Guess::<{ 0..=0 }>::new(1_i32).expect_err("guess 3 failed to fail to construct.");
-
-		Guess::<{ 0..=10 }>::new(0_i32).expect("guess 4 failed to construct.");
-		Guess::<{ 0..=10 }>::new(5_i32).expect("guess 5 failed to construct.");
-		Guess::<{ 0..=10 }>::new(10_i32).expect("guess 6 failed to construct.");
-
-		Guess::<{ 10..=20 }>::new(0_i32).expect_err("guess 7 failed to fail to construct.");
-		Guess::<{ 10..=20 }>::new(10_i32).expect("guess 8 failed to construct.");
-		Guess::<{ 10..=20 }>::new(15_i32).expect("guess 9 failed to construct.");
-		Guess::<{ 10..=20 }>::new(20_i32).expect("guess 10 failed to construct.");
-
-		Guess::<{ 0..=10000 }>::new(-0_i32).expect("guess 11 failed to construct.");
-		Guess::<{ 0..=10000 }>::new(-5_i32).expect_err("guess 12 failed to construct.");
-		Guess::<{ 0..=10000 }>::new(-10_i32).expect_err("guess 13 failed to construct.");
-	}
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	read_guess(prompt, &InputOptions::default(), min, max, input, output, |text| parse_lenient(text, min, max).map(|value| Guess { value }))
+}
 
-	#[test]
-	fn equality()
-	{
-		assert_eq!(
-			Guess::<{ 0..=10 }>::new(0_i32).expect("guess 1 failed to construct."),
+/// Gets user input until it is valid and returns it as a Guess, or returns
+/// default if the line is blank.
+///
+/// This is the same as input, except for blank lines, which includes lines of
+/// only whitespace. The default is added to the prompt, so that "Guess: "
+/// becomes "Guess [512]: ".
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::{
+/// // 		input_with_default,
+/// // 		Guess,
+/// // 	},
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let default = Guess::<{ 0..=1024 }>::new(512).expect("");
+/// // let guess = input_with_default(default, "Guess: ".yellow(), &mut stdin().lock(), &mut stdout()).expect("");
+/// ```
+pub fn input_with_default<const RANGE: RangeInclusive<i32>>(
+	default: Guess<RANGE>,
+	mut prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<Guess<RANGE>, InputError>
+{
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	let options = InputOptions { repeat_on_blank: false, ..InputOptions::default() };
+	let prompt = move |context: InputContext| prompt_with_default(&prompt.prompt(context), default);
+	read_guess(prompt, &options, min, max, input, output, |text| {
+		if text.trim().is_empty()
+		{
+			Ok(default)
+		}
+		else
+		{
+			parse_lenient(text, min, max).map(|value| Guess { value })
+		}
+	})
+	.map(|result| result.guess)
+}
+
+/// Adds default to prompt in brackets, before the colon if it ends with one.
+fn prompt_with_default(
+	prompt: &ColoredString,
+	default: impl Display,
+) -> ColoredString
+{
+	let text = prompt.trim_end();
+	let text = text.strip_suffix(':').unwrap_or(text);
+	restyle(&format!("{text} [{default}]: "), prompt)
+}
+
+/// A valid guess read by input_counted, and how hard it was to get.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct InputResult<G>
+{
+	/// The valid guess that was entered.
+	pub guess: G,
+	/// How many invalid guesses were entered before it.
+	pub invalid_tries: usize,
+}
+
+/// Shared implementation of input and input_runtime. Loops until parse accepts
+/// a line of input, explaining each rejected line using options, min and max.
+/// Gives up after more than options.max_invalid invalid guesses, if there is a
+/// limit. The prompt in options is not used, so that it can vary.
+fn read_guess<T: Integer, G>(
+	mut prompt: impl Prompt,
+	options: &InputOptions,
+	min: T,
+	max: T,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+	parse: impl Fn(&str) -> Result<G, ParseGuessError<T>>,
+) -> Result<InputResult<G>, InputError<T>>
+{
+	let mut invalid = 0_usize;
+	// Avoids counting invalid guesses as used attempts.
+	loop
+	{
+		options.write_prompt(&prompt.prompt(InputContext { invalid_tries: invalid }), &mut output.prompts())?;
+		let mut guess_input = Vec::new();
+		// Read a line of input.
+		let error = match read_line_limited(input, &mut guess_input)
+		{
+			// Stop looping when there is nothing left to read.
+			Ok(0) => break Err(InputError::Eof),
+			// Just ask again if nothing was entered.
+			Ok(read) if options.repeat_on_blank && is_blank(&guess_input, read) => continue,
+			// Stop looping if everything checks out.
+			Ok(read) => match check_line(options, min, max, &guess_input, read, &parse, &mut output.errors())?
+			{
+				Ok(guess) => break Ok(InputResult { guess, invalid_tries: invalid }),
+				Err(error) =>
+				{
+					let line = if options.redact { String::from("***") } else { String::from_utf8_lossy(&guess_input).trim().to_string() };
+					prompt.rejected(&line);
+					error
+				},
+			},
+			Err(error) => break Err(InputError::Io(error)),
+		};
+		invalid += 1;
+		if options.max_invalid.is_some_and(|max_invalid| invalid > max_invalid)
+		{
+			break Err(InputError::TooManyInvalid(error));
+		}
+	}
+}
+
+/// Copies available bytes up to and including the first line ending into line,
+/// keeping at most MAX_LINE_BYTES bytes in it. Returns how many bytes were used
+/// and whether the line is complete, which it also is when there are no bytes
+/// left at all.
+fn take_line_chunk(
+	available: &[u8],
+	line: &mut Vec<u8>,
+) -> (usize, bool)
+{
+	let (chunk, done) = match available.iter().position(|&byte| byte == b'\n')
+	{
+		Some(end) => (&available[..=end], true),
+		None => (available, available.is_empty()),
+	};
+	let keep = chunk.len().min(MAX_LINE_BYTES - line.len());
+	line.extend_from_slice(&chunk[..keep]);
+	(chunk.len(), done)
+}
+
+/// Checks whether a line that read_line_limited read is empty apart from
+/// whitespace. Such a line is not an invalid guess, just no guess at all.
+fn is_blank(
+	line: &[u8],
+	read: usize,
+) -> bool
+{
+	read == line.len() && line.trim_ascii().is_empty()
+}
+
+/// Parses and validates a line that read_line_limited read, or explains to the
+/// user why it was rejected. A line that was cut short or is not text can not be
+/// a number either. Only fails if the explanation can not be written.
+fn check_line<T: Integer, G>(
+	options: &InputOptions,
+	min: T,
+	max: T,
+	line: &[u8],
+	read: usize,
+	parse: &impl Fn(&str) -> Result<G, ParseGuessError<T>>,
+	output: &mut impl Write,
+) -> io::Result<Result<G, ParseGuessError<T>>>
+{
+	let result = std::str::from_utf8(line)
+		.ok()
+		.filter(|_| read == line.len())
+		.map_or(Err(ParseGuessError::NotANumber), parse);
+	match &result
+	{
+		Ok(_) => (),
+		// Errors about numbers would repeat the number.
+		Err(_) if options.redact => options.write_invalid(&options.render_invalid_message(min, max, ""), output)?,
+		// Explain why the input could not be used when it is at least a number.
+		Err(
+			error @ (ParseGuessError::Overflow
+			| ParseGuessError::Underflow
+			| ParseGuessError::OutOfRange(_)
+			| ParseGuessError::Negative { .. }
+			| ParseGuessError::NoPreviousGuess
+			| ParseGuessError::ReversedInterval { .. }
+			| ParseGuessError::IntervalOutOfRange(_)),
+		) =>
+		{
+			options.write_invalid(&error.to_string(), output)?
+		},
+		// A repeated guess is not wrong, so it only gets a warning.
+		Err(error @ ParseGuessError::Repeated(_)) => options.write_warning(&error.to_string(), output)?,
+		Err(ParseGuessError::NotANumber) =>
+		{
+			let text = String::from_utf8_lossy(line);
+			options.write_invalid(&options.render_invalid_message(min, max, &text), output)?;
+		},
+	}
+	Ok(result)
+}
+
+/// The longest line, in bytes and including its line ending, that input will
+/// try to read as a guess. Longer lines are skipped without being stored.
+pub const MAX_LINE_BYTES: usize = 1024;
+
+/// Reads one line into line, keeping at most MAX_LINE_BYTES bytes of it and
+/// skipping the rest. Returns how many bytes were read in total, so the line
+/// was cut short if that is more than line.len().
+fn read_line_limited(
+	input: &mut impl BufRead,
+	line: &mut Vec<u8>,
+) -> io::Result<usize>
+{
+	let mut read = 0_usize;
+	loop
+	{
+		let available = match input.fill_buf()
+		{
+			Ok(available) => available,
+			Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+			Err(error) => return Err(error),
+		};
+		let (len, done) = take_line_chunk(available, line);
+		input.consume(len);
+		read += len;
+		if done
+		{
+			return Ok(read);
+		}
+	}
+}
+
+/// Error returned when input can not produce a guess at all. Invalid guesses are
+/// not errors, since input keeps asking until it gets a valid one, unless there
+/// is a limit on how many it accepts.
+#[derive(Debug)]
+pub enum InputError<T = i32>
+{
+	/// The input ended before a valid guess was read.
+	Eof,
+	/// Reading the input or writing the prompt failed.
+	Io(io::Error),
+	/// Too many invalid guesses were entered. Holds the reason the last one was
+	/// rejected.
+	TooManyInvalid(ParseGuessError<T>),
+}
+
+impl<T> From<io::Error> for InputError<T>
+{
+	fn from(error: io::Error) -> Self
+	{
+		InputError::Io(error)
+	}
+}
+
+/// Formats InputError as a message that can be shown to the user.
+impl<T> Display for InputError<T>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			InputError::Eof => write!(f, "the input ended"),
+			InputError::Io(error) => write!(f, "the input could not be read ({error})"),
+			InputError::TooManyInvalid(_) => write!(f, "too many invalid guesses were entered"),
+		}
+	}
+}
+
+impl<T: Integer> std::error::Error for InputError<T>
+{
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+	{
+		match self
+		{
+			InputError::Eof => None,
+			InputError::Io(error) => Some(error),
+			InputError::TooManyInvalid(error) => Some(error),
+		}
+	}
+}
+
+/// Respond to a user's input with some console output. Returns the correct
+/// action to take.
+///
+/// The output will explain whether the guess was too high, too low, or if they
+/// are equal, that the user wins. The return value will be
+/// std::ops::ControlFlow::Continue() with the Feedback unless the user wins, in
+/// which case it will return std::ops::ControlFlow::Break() with the Win, which
+/// holds the winning guess. The colors of the output follow set_color_choice().
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// Demonstrating a guess that is too high:
+///
+/// ```
+/// // use std::{
+/// // 	io::stdout,
+/// // 	ops::ControlFlow,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // let action = respond(example_guess, correct_guess, &mut stdout()).expect("");
+/// // assert_eq!(action, ControlFlow::Continue(Feedback::TooHigh));
+/// ```
+pub fn respond<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_to(guess, guess.cmp(&correct), &ResponseTheme::default(), Style::Fancy, output)
+}
+
+/// Respond to a user's input with some console output in the given style.
+/// Returns the correct action to take.
+///
+/// This is the same as respond, which uses Style::Fancy. Use Style::Plain when
+/// output is redirected or the terminal can not show colors and emoji.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_with_style,
+/// // 		Guess,
+/// // 		Style,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // assert!(respond_with_style(example_guess, correct_guess, Style::Plain, &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_with_style<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	style: Style,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_to(guess, guess.cmp(&correct), &ResponseTheme::default(), style, output)
+}
+
+/// Shared implementation of respond and respond_runtime. Responds to a guess
+/// that compares to the correct value with the given ordering.
+fn respond_to<G: Display>(
+	guess: G,
+	ordering: Ordering,
+	theme: &ResponseTheme,
+	style: Style,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<G>, Feedback>>
+{
+	let feedback = Feedback::from(ordering);
+	// The newline stays outside of the colors so that they end with the line.
+	write!(output, "\n{}\n", style.render(&theme.render(&guess, feedback)))?;
+	if feedback.is_correct()
+	{
+		theme.write_celebration(style, output)?;
+	}
+	Ok(feedback.into_control_flow(guess))
+}
+
+/// What a guess revealed about the correct value.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond,
+/// // 		Feedback,
+/// // 		Guess,
+/// // 	},
+/// // 	std::{
+/// // 		io::stdout,
+/// // 		ops::ControlFlow,
+/// // 	},
+/// //};
+/// // let example_guess = Guess::<{ 0..=100 }>::new(42_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100 }>::new(60_i32).expect("");
+/// // match respond(example_guess, correct_guess, &mut stdout()).expect("")
+/// // {
+/// // 	ControlFlow::Continue(Feedback::TooHigh) => println!("Lower next time."),
+/// // 	ControlFlow::Continue(Feedback::TooLow) => println!("Higher next time."),
+/// // 	ControlFlow::Continue(Feedback::Correct) | ControlFlow::Break(_) => println!("Done."),
+/// // }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Feedback
+{
+	/// The guess is greater than the correct value.
+	TooHigh,
+	/// The guess is less than the correct value.
+	TooLow,
+	/// The guess is the correct value.
+	Correct,
+}
+
+impl Feedback
+{
+	/// Returns whether the guess was the correct value.
+	pub const fn is_correct(self) -> bool
+	{
+		matches!(self, Feedback::Correct)
+	}
+
+	/// Breaks with a Win holding guess if the guess was correct, and continues
+	/// with the feedback otherwise.
+	pub fn into_control_flow<G>(
+		self,
+		guess: G,
+	) -> ControlFlow<Win<G>, Feedback>
+	{
+		if self.is_correct()
+		{
+			ControlFlow::Break(Win { guess })
+		}
+		else
+		{
+			ControlFlow::Continue(self)
+		}
+	}
+}
+
+/// The end of a game that the user won, which respond breaks with.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond,
+/// // 		Guess,
+/// // 		Win,
+/// // 	},
+/// // 	std::{
+/// // 		io::stdout,
+/// // 		ops::ControlFlow,
+/// // 	},
+/// //};
+/// // let correct_guess = Guess::<{ 0..=100 }>::new(60_i32).expect("");
+/// // if let ControlFlow::Break(Win { guess }) = respond(correct_guess, correct_guess, &mut stdout()).expect("")
+/// // {
+/// // 	println!("{guess} was right.");
+/// // }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Win<G>
+{
+	/// The guess that was the correct value.
+	pub guess: G,
+}
+
+/// Converts how a guess compares to the correct value into Feedback, so that
+/// Greater means the guess is too high.
+impl From<Ordering> for Feedback
+{
+	fn from(ordering: Ordering) -> Self
+	{
+		match ordering
+		{
+			Ordering::Greater => Feedback::TooHigh,
+			Ordering::Less => Feedback::TooLow,
+			Ordering::Equal => Feedback::Correct,
+		}
+	}
+}
+
+/// Tuple struct to represent a guess. A guess is a type-safe way to represent
+/// the integer that was input by the user, and the correct value to compare
+/// against.
+///
+/// Guess's constructor offers input validation in the constructor itself rather
+/// than relying on other methods to obey the assumption that the guess is in a
+/// valid state. This struct must be created using Guess::new().
+///
+/// The value of a guess is always an i32. Making the value type generic would
+/// require a const parameter whose type depends on a type parameter, as in
+/// `Guess<T, const RANGE: RangeInclusive<T>>`, which const generics can not
+/// express. BoundedGuess is the generic alternative: its range is known at
+/// runtime instead, so it can hold any Integer such as u8 or u64, it is parsed
+/// with that type's FromStr, and it is read and answered by input_runtime and
+/// respond_runtime in place of input and respond. Its type defaults to i32.
+///
+/// RANGE must contain at least one value, otherwise no guess could ever be
+/// valid and input would never return. Using a Guess with an empty range fails
+/// to compile:
+///
+/// ```compile_fail
+/// #![feature(adt_const_params)]
+/// use guessing_game::Guess;
+///
+/// let guess = Guess::<{ 10..=0 }>::new(5_i32);
+/// ```
+///
+/// ```compile_fail
+/// #![feature(adt_const_params)]
+/// use {
+/// 	colored::Colorize,
+/// 	guessing_game::input,
+/// 	std::io::stdout,
+/// };
+///
+/// let guess = input::<{ 10..=0 }>("Guess: ".clear(), &mut "5".as_bytes(), &mut stdout()).expect("");
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct Guess<const RANGE: RangeInclusive<i32>>
+{
+	value: i32,
+}
+
+/// Constructor for creating a Guess.
+///
+/// The constructor validates that the guess' value lies within RANGE. If the
+/// value provided is valid, it returns an Ok() containing a new instance of
+/// Guess. If the value provided is invalid, the constructor returns a
+/// GuessError describing which side of RANGE the value fell on.
+///
+///
+/// # Examples
+///
+/// Demonstrating a guess that is too high:
+///
+/// ```
+/// // use guessing_game::{
+/// // 	input,
+/// // 	Guess,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32);
+/// ```
+impl<const RANGE: RangeInclusive<i32>> Guess<RANGE>
+{
+	/// Rejects empty ranges at compile time. Every use of RANGE goes through
+	/// min_value() and max_value(), which evaluate this.
+	const NON_EMPTY: () = assert!(*RANGE.start() <= *RANGE.end(), "Guess requires RANGE to contain at least one value.");
+
+	pub fn new(guess: i32) -> Result<Self, GuessError>
+	{
+		validate(guess, Self::min_value(), Self::max_value()).map(|value| Guess { value })
+	}
+
+	/// Creates a Guess from any value by clamping it to RANGE.
+	///
+	/// Unlike Guess::new(), this always succeeds: a value below RANGE becomes
+	/// the start of RANGE, and a value above RANGE becomes the end of RANGE. It
+	/// is meant for computed guesses that may overshoot slightly, not for
+	/// validating user input.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100000 }>::new_clamped(100001_i32).value(), 100000_i32);
+	/// ```
+	pub const fn new_clamped(value: i32) -> Self
+	{
+		if value < Self::min_value()
+		{
+			Guess { value: Self::min_value() }
+		}
+		else if value > Self::max_value()
+		{
+			Guess { value: Self::max_value() }
+		}
+		else
+		{
+			Guess { value }
+		}
+	}
+
+	/// Parses text into a Guess, accepting digits grouped with underscores or
+	/// commas.
+	///
+	/// This is what input uses. Surrounding whitespace is ignored, as with
+	/// FromStr. Underscores may appear between any digits, as in `1_024`.
+	/// Commas must separate groups of three digits, as in `1,024`. Separators
+	/// at the start or end, doubled separators, and mixed separators are
+	/// rejected as not being a number.
+	///
+	/// Text starting with `0x`, `0b` or `0o`, in either case and after an
+	/// optional sign, is read as hexadecimal, binary or octal, as in `0x2a` or
+	/// `-0b101`. These can not contain separators.
+	///
+	/// With the words feature, numbers written in English words are accepted
+	/// as well, as in `forty-two`, `one thousand and twenty four` or `negative
+	/// five`. A phrase that is not exactly one number is not a number at all.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("1,024").map(i32::from), Ok(1024_i32));
+	/// // assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("1_024").map(i32::from), Ok(1024_i32));
+	/// ```
+	pub fn parse_lenient(text: &str) -> Result<Self, ParseGuessError>
+	{
+		parse_lenient(text, Self::min_value(), Self::max_value()).map(|value| Guess { value })
+	}
+
+	/// Parses text into a Guess the same way as Guess::parse_lenient(), except
+	/// that text starting with `+` or `-` is added to the previous guess.
+	///
+	/// This means that negative guesses can only be reached relative to a
+	/// previous guess. A relative guess without a previous guess is rejected,
+	/// and the result of adding must still lie within RANGE.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let previous = Guess::<{ 0..=100 }>::new(40).expect("");
+	/// // assert_eq!(Guess::parse_relative("+5", Some(previous)).map(i32::from), Ok(45_i32));
+	/// // assert_eq!(Guess::parse_relative("-5", Some(previous)).map(i32::from), Ok(35_i32));
+	/// // assert_eq!(Guess::parse_relative("5", Some(previous)).map(i32::from), Ok(5_i32));
+	/// ```
+	pub fn parse_relative(
+		text: &str,
+		previous: Option<Self>,
+	) -> Result<Self, ParseGuessError>
+	{
+		let text = text.trim();
+		if !text.starts_with(['+', '-'])
+		{
+			return Self::parse_lenient(text);
+		}
+		let previous = previous.ok_or(ParseGuessError::NoPreviousGuess)?;
+		// Every i64 is in range, so the delta can only fail to parse or overflow.
+		let delta = match parse_lenient(text, i64::MIN, i64::MAX)
+		{
+			Ok(delta) => delta,
+			Err(ParseGuessError::NotANumber) => return Err(ParseGuessError::NotANumber),
+			Err(ParseGuessError::Underflow) => return Err(ParseGuessError::Underflow),
+			Err(_) => return Err(ParseGuessError::Overflow),
+		};
+		let value = i64::from(previous.value)
+			.checked_add(delta)
+			.and_then(|value| i32::try_from(value).ok())
+			.ok_or(if delta < 0_i64 { ParseGuessError::Underflow } else { ParseGuessError::Overflow })?;
+		Ok(Self::new(value)?)
+	}
+
+	/// Converts the guess into a guess over a range that contains RANGE.
+	///
+	/// This always succeeds since the value is already known to lie within
+	/// RANGE. Calling it with a range that does not contain RANGE fails to
+	/// compile; use Guess::try_narrow() for that instead.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(42_i32).expect("");
+	/// // let wide: Guess<{ 0..=100000 }> = guess.widen();
+	/// ```
+	///
+	/// Widening into a range that does not contain RANGE is rejected:
+	///
+	/// ```compile_fail
+	/// #![feature(adt_const_params)]
+	/// use guessing_game::Guess;
+	///
+	/// let guess = Guess::<{ 0..=100 }>::new(42_i32).expect("42 is in range");
+	/// let narrow: Guess<{ 0..=50 }> = guess.widen();
+	/// ```
+	pub const fn widen<const OTHER: RangeInclusive<i32>>(self) -> Guess<OTHER>
+	{
+		const {
+			assert!(
+				Guess::<OTHER>::min_value() <= Self::min_value() && Self::max_value() <= Guess::<OTHER>::max_value(),
+				"Guess::widen() requires the new range to contain the old range."
+			)
+		};
+		Guess { value: self.value }
+	}
+
+	/// Converts the guess into a guess over any other range, validating the
+	/// value again the same way as Guess::new().
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+	/// // let narrow = guess.try_narrow::<{ 0..=100 }>().expect_err("");
+	/// ```
+	pub fn try_narrow<const OTHER: RangeInclusive<i32>>(self) -> Result<Guess<OTHER>, GuessError>
+	{
+		Guess::new(self.value)
+	}
+
+	/// Returns the smallest value a guess can have.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100000 }>::min_value(), 0_i32);
+	/// ```
+	pub const fn min_value() -> i32
+	{
+		let () = Self::NON_EMPTY;
+		*RANGE.start()
+	}
+
+	/// Returns the largest value a guess can have.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100000 }>::max_value(), 100000_i32);
+	/// ```
+	pub const fn max_value() -> i32
+	{
+		let () = Self::NON_EMPTY;
+		*RANGE.end()
+	}
+
+	/// Returns how many distinct values a guess can have.
+	///
+	/// The result is a u64 so that the full i32 range, which contains one more
+	/// value than fits in a u32, can be represented.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100000 }>::span(), 100001_u64);
+	/// ```
+	pub const fn span() -> u64
+	{
+		Self::max_value().abs_diff(Self::min_value()) as u64 + 1_u64
+	}
+
+	/// Returns the guess in the middle of RANGE, rounding toward the start of
+	/// RANGE. This is the first guess of a binary search.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100 }>::midpoint().value(), 50_i32);
+	/// ```
+	pub const fn midpoint() -> Guess<RANGE>
+	{
+		GuessRange::<RANGE>::full().midpoint()
+	}
+
+	/// Creates the guess at fraction of the way through RANGE, where 0.0 is the
+	/// start of RANGE and 1.0 is the end of RANGE. The result is rounded to the
+	/// nearest value.
+	///
+	/// Like Guess::new_clamped(), this always succeeds: fractions below 0.0 are
+	/// treated as 0.0, fractions above 1.0 are treated as 1.0, and NaN is
+	/// treated as 0.0.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // assert_eq!(Guess::<{ 0..=100 }>::from_fraction(0.25_f64).value(), 25_i32);
+	/// ```
+	pub fn from_fraction(fraction: f64) -> Guess<RANGE>
+	{
+		let fraction = if fraction.is_nan() { 0.0_f64 } else { fraction.clamp(0.0_f64, 1.0_f64) };
+		let min = Self::min_value() as i64;
+		let width = Self::max_value() as i64 - min;
+		Guess { value: (min + (fraction * width as f64).round() as i64) as i32 }
+	}
+
+	/// Returns an iterator over every valid guess, from the start of RANGE
+	/// through the end of RANGE.
+	///
+	/// The iterator can be reversed and knows its length, which is the same as
+	/// Guess::span() where that fits in a usize.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // for guess in Guess::<{ 0..=10 }>::iter().rev()
+	/// // {
+	/// // 	println!("{guess}");
+	/// // }
+	/// ```
+	pub const fn iter() -> Guesses<RANGE>
+	{
+		Guesses { front: Self::min_value() as i64, back: Self::max_value() as i64 }
+	}
+
+	/// Returns the validated value of the guess.
+	///
+	/// The value is guaranteed to lie within RANGE since the only way to create
+	/// a Guess is through the validating constructor.
+	///
+	/// # Examples
+	///
+	/// Computing how far off a guess was:
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+	/// // let correct = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+	/// // let difference = guess.value() - correct.value();
+	/// ```
+	pub const fn value(&self) -> i32
+	{
+		self.value
+	}
+
+	/// Consumes the guess and returns its value. This is the same as
+	/// `i32::from(guess)`.
+	pub const fn into_inner(self) -> i32
+	{
+		self.value
+	}
+
+	/// Returns how far through RANGE the guess lies, from 0.0 at the start of
+	/// RANGE to 1.0 at the end of RANGE.
+	///
+	/// This is computed in i64 and f64, so it does not overflow for wide
+	/// ranges. When RANGE contains a single value, the result is 0.0.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(73_i32).expect("");
+	/// // println!("Your guess is at {:.0}% of the range.", guess.percent_of_range() * 100.0_f64);
+	/// ```
+	pub fn percent_of_range(&self) -> f64
+	{
+		let min = Self::min_value() as i64;
+		let width = Self::max_value() as i64 - min;
+		if width == 0_i64
+		{
+			0.0_f64
+		}
+		else
+		{
+			(self.value as i64 - min) as f64 / width as f64
+		}
+	}
+
+	/// Returns the absolute difference between two guesses.
+	///
+	/// The result is a u32 so that it can not overflow, even when the guesses
+	/// lie at opposite ends of the full i32 range.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+	/// // let correct = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+	/// // assert_eq!(guess.distance(correct), 40409_u32);
+	/// ```
+	pub const fn distance(
+		&self,
+		other: Guess<RANGE>,
+	) -> u32
+	{
+		self.value.abs_diff(other.value)
+	}
+
+	/// Adds delta to the guess, returning None if the result is outside of
+	/// RANGE or overflows i32.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(50_i32).expect("");
+	/// // assert_eq!(guess.checked_add(25_i32).map(i32::from), Some(75_i32));
+	/// // assert_eq!(guess.checked_add(51_i32), None);
+	/// ```
+	pub const fn checked_add(
+		self,
+		delta: i32,
+	) -> Option<Guess<RANGE>>
+	{
+		match self.value.checked_add(delta)
+		{
+			Some(value) if Self::min_value() <= value && value <= Self::max_value() => Some(Guess { value }),
+			_ => None,
+		}
+	}
+
+	/// Subtracts delta from the guess, returning None if the result is outside
+	/// of RANGE or overflows i32.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(50_i32).expect("");
+	/// // assert_eq!(guess.checked_sub(25_i32).map(i32::from), Some(25_i32));
+	/// // assert_eq!(guess.checked_sub(51_i32), None);
+	/// ```
+	pub const fn checked_sub(
+		self,
+		delta: i32,
+	) -> Option<Guess<RANGE>>
+	{
+		match self.value.checked_sub(delta)
+		{
+			Some(value) if Self::min_value() <= value && value <= Self::max_value() => Some(Guess { value }),
+			_ => None,
+		}
+	}
+
+	/// Returns the guess one greater than this one, or None at the end of
+	/// RANGE.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(99_i32).expect("");
+	/// // assert_eq!(guess.next().map(i32::from), Some(100_i32));
+	/// // assert_eq!(guess.next().and_then(Guess::next), None);
+	/// ```
+	pub const fn next(self) -> Option<Guess<RANGE>>
+	{
+		self.checked_add(1_i32)
+	}
+
+	/// Returns the guess one less than this one, or None at the start of
+	/// RANGE.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(1_i32).expect("");
+	/// // assert_eq!(guess.prev().map(i32::from), Some(0_i32));
+	/// // assert_eq!(guess.prev().and_then(Guess::prev), None);
+	/// ```
+	pub const fn prev(self) -> Option<Guess<RANGE>>
+	{
+		self.checked_sub(1_i32)
+	}
+
+	/// Adds delta to the guess, stopping at the end of RANGE.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(50_i32).expect("");
+	/// // assert_eq!(guess.saturating_add(51_i32).value(), 100_i32);
+	/// ```
+	pub const fn saturating_add(
+		self,
+		delta: i32,
+	) -> Guess<RANGE>
+	{
+		Self::new_clamped(self.value.saturating_add(delta))
+	}
+
+	/// Subtracts delta from the guess, stopping at the start of RANGE.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100 }>::new(50_i32).expect("");
+	/// // assert_eq!(guess.saturating_sub(51_i32).value(), 0_i32);
+	/// ```
+	pub const fn saturating_sub(
+		self,
+		delta: i32,
+	) -> Guess<RANGE>
+	{
+		Self::new_clamped(self.value.saturating_sub(delta))
+	}
+
+	/// Returns whether two guesses are at most tolerance apart.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Guess;
+	/// // let guess = Guess::<{ 0..=100000 }>::new(1670_i32).expect("");
+	/// // let correct = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+	/// // assert!(guess.is_within(correct, 10_u32));
+	/// ```
+	pub const fn is_within(
+		&self,
+		other: Guess<RANGE>,
+		tolerance: u32,
+	) -> bool
+	{
+		self.distance(other) <= tolerance
+	}
+}
+
+/// Integer types that a guess can hold.
+///
+/// Guess always holds an i32, but BoundedGuess can hold any of the primitive
+/// integer types.
+pub trait Integer: Copy + Ord + Hash + std::fmt::Debug + Display + FromStr<Err = ParseIntError> + 'static
+{
+	/// Parses text in the given radix, the same as the inherent from_str_radix of
+	/// each integer type.
+	fn from_str_radix(
+		text: &str,
+		radix: u32,
+	) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_integer {
+	($($integer:ty),*) => {
+		$(
+			impl Integer for $integer
+			{
+				fn from_str_radix(
+					text: &str,
+					radix: u32,
+				) -> Result<Self, ParseIntError>
+				{
+					<$integer>::from_str_radix(text, radix)
+				}
+			}
+		)*
+	};
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Validates that value lies within min through max. This is the validation
+/// shared by Guess and BoundedGuess.
+fn validate<T: Integer>(
+	value: T,
+	min: T,
+	max: T,
+) -> Result<T, GuessError<T>>
+{
+	if value < min
+	{
+		Err(GuessError::BelowRange { value, min })
+	}
+	else if value > max
+	{
+		Err(GuessError::AboveRange { value, max })
+	}
+	else
+	{
+		Ok(value)
+	}
+}
+
+/// Trims and parses text, then validates that it lies within min through max.
+/// This is the parsing shared by Guess and BoundedGuess.
+fn parse<T: Integer>(
+	text: &str,
+	min: T,
+	max: T,
+) -> Result<T, ParseGuessError<T>>
+{
+	Ok(validate(parse_integer(text.trim(), 10, min)?, min, max)?)
+}
+
+/// Parses text as an integer in radix. An unsigned type can not hold a negative
+/// integer, which is below any range of that type, so one is reported as
+/// ParseGuessError::Negative rather than as not a number. Only zero is accepted
+/// with a minus sign.
+fn parse_integer<T: Integer>(
+	text: &str,
+	radix: u32,
+	min: T,
+) -> Result<T, ParseGuessError<T>>
+{
+	match T::from_str_radix(text, radix)
+	{
+		Ok(value) => Ok(value),
+		Err(error) => match text.strip_prefix('-')
+		{
+			Some(digits) if *error.kind() == IntErrorKind::InvalidDigit && !digits.is_empty() && digits.chars().all(|digit| digit.is_digit(radix)) =>
+			{
+				if digits.chars().all(|digit| digit == '0')
+				{
+					Ok(T::from_str_radix(digits, radix)?)
+				}
+				else
+				{
+					Err(ParseGuessError::Negative { min })
+				}
+			},
+			_ => Err(error.into()),
+		},
+	}
+}
+
+/// Same as parse, except that digits may be grouped with underscores, as in
+/// `1_024`, or with commas, as in `1,024`, and that the prefixes `0x`, `0b` and
+/// `0o` select hexadecimal, binary and octal, as in `0x2a`. With the words
+/// feature, numbers written in English words, as in `forty-two`, are accepted
+/// too.
+fn parse_lenient<T: Integer>(
+	text: &str,
+	min: T,
+	max: T,
+) -> Result<T, ParseGuessError<T>>
+{
+	let text = text.trim();
+	#[cfg(feature = "words")]
+	if let Some(value) = words::parse_words(text)
+	{
+		return Ok(validate(parse_integer(&value.to_string(), 10, min)?, min, max)?);
+	}
+	let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+	let radix = match unsigned.get(..2)
+	{
+		Some("0x" | "0X") => 16,
+		Some("0b" | "0B") => 2,
+		Some("0o" | "0O") => 8,
+		_ => 10,
+	};
+	if radix != 10
+	{
+		// The sign goes before the prefix, so from_str_radix must not see another.
+		let digits = &unsigned[2..];
+		if digits.starts_with(['+', '-'])
+		{
+			return Err(ParseGuessError::NotANumber);
+		}
+		let sign = &text[..text.len() - unsigned.len()];
+		return Ok(validate(parse_integer(&format!("{sign}{digits}"), radix, min)?, min, max)?);
+	}
+	match remove_separators(text)
+	{
+		Some(text) => parse(&text, min, max),
+		None => Err(ParseGuessError::NotANumber),
+	}
+}
+
+/// Removes group separators from text, or returns None if they are malformed.
+///
+/// Underscores may separate any non-empty groups of digits. Commas must follow
+/// a group of one to three digits and precede groups of exactly three digits.
+/// The two separators can not be mixed. Text without separators is returned as
+/// is so that parsing can explain what is wrong with it.
+fn remove_separators(text: &str) -> Option<String>
+{
+	let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+	let sign = &text[..text.len() - digits.len()];
+	let commas = digits.contains(',');
+	let underscores = digits.contains('_');
+	if !commas && !underscores
+	{
+		return Some(text.to_owned());
+	}
+	if commas && underscores
+	{
+		return None;
+	}
+	let mut groups = digits.split([',', '_']);
+	let first = groups.next()?;
+	let valid_group = |group: &str, lengths: RangeInclusive<usize>| {
+		lengths.contains(&group.len()) && group.bytes().all(|byte| byte.is_ascii_digit())
+	};
+	let rest_lengths = if commas { 3..=3 } else { 1..=usize::MAX };
+	let first_lengths = if commas { 1..=3 } else { 1..=usize::MAX };
+	let mut number = sign.to_owned();
+	if !valid_group(first, first_lengths)
+	{
+		return None;
+	}
+	number.push_str(first);
+	for group in groups
+	{
+		if !valid_group(group, rest_lengths.clone())
+		{
+			return None;
+		}
+		number.push_str(group);
+	}
+	Some(number)
+}
+
+/// Non-empty interval of guesses from lo through hi, such as the values that
+/// are still possible during a binary search.
+///
+/// # Examples
+///
+/// Narrowing the interval after a guess that was too high:
+///
+/// ```
+/// // use guessing_game::GuessRange;
+/// // let possible = GuessRange::<{ 0..=100 }>::full();
+/// // let guess = possible.midpoint();
+/// // let possible = possible.below(guess).expect("");
+/// // assert_eq!(possible.hi().value(), 49_i32);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct GuessRange<const RANGE: RangeInclusive<i32>>
+{
+	lo: Guess<RANGE>,
+	hi: Guess<RANGE>,
+}
+
+impl<const RANGE: RangeInclusive<i32>> GuessRange<RANGE>
+{
+	/// Creates the interval of every valid guess.
+	pub const fn full() -> Self
+	{
+		GuessRange { lo: Guess { value: Guess::<RANGE>::min_value() }, hi: Guess { value: Guess::<RANGE>::max_value() } }
+	}
+
+	/// Creates the interval from lo through hi, or returns None if lo is
+	/// greater than hi.
+	pub const fn new(
+		lo: Guess<RANGE>,
+		hi: Guess<RANGE>,
+	) -> Option<Self>
+	{
+		if lo.value <= hi.value
+		{
+			Some(GuessRange { lo, hi })
+		}
+		else
+		{
+			None
+		}
+	}
+
+	/// Returns the smallest guess in the interval.
+	pub const fn lo(&self) -> Guess<RANGE>
+	{
+		self.lo
+	}
+
+	/// Returns the largest guess in the interval.
+	pub const fn hi(&self) -> Guess<RANGE>
+	{
+		self.hi
+	}
+
+	/// Returns how many guesses are in the interval.
+	pub const fn size(&self) -> u64
+	{
+		self.hi.value.abs_diff(self.lo.value) as u64 + 1_u64
+	}
+
+	/// Returns whether guess lies within the interval.
+	pub const fn contains(
+		&self,
+		guess: Guess<RANGE>,
+	) -> bool
+	{
+		self.lo.value <= guess.value && guess.value <= self.hi.value
+	}
+
+	/// Returns the guess in the middle of the interval, rounding toward lo.
+	///
+	/// This is computed as `lo + (hi - lo) / 2` in i64, so it can not overflow
+	/// even when the interval spans all of i32.
+	pub const fn midpoint(&self) -> Guess<RANGE>
+	{
+		let lo = self.lo.value as i64;
+		let hi = self.hi.value as i64;
+		Guess { value: (lo + (hi - lo) / 2_i64) as i32 }
+	}
+
+	/// Returns the part of the interval that is less than guess, or None if
+	/// there is no such part. This is what remains when guess is too high.
+	pub const fn below(
+		&self,
+		guess: Guess<RANGE>,
+	) -> Option<Self>
+	{
+		if guess.value <= self.lo.value
+		{
+			None
+		}
+		else if guess.value > self.hi.value
+		{
+			Some(*self)
+		}
+		else
+		{
+			Some(GuessRange { lo: self.lo, hi: Guess { value: guess.value - 1_i32 } })
+		}
+	}
+
+	/// Returns the part of the interval that is greater than guess, or None if
+	/// there is no such part. This is what remains when guess is too low.
+	pub const fn above(
+		&self,
+		guess: Guess<RANGE>,
+	) -> Option<Self>
+	{
+		if guess.value >= self.hi.value
+		{
+			None
+		}
+		else if guess.value < self.lo.value
+		{
+			Some(*self)
+		}
+		else
+		{
+			Some(GuessRange { lo: Guess { value: guess.value + 1_i32 }, hi: self.hi })
+		}
+	}
+}
+
+/// Iterator over every valid Guess in RANGE. This struct is created by
+/// Guess::iter().
+///
+/// The bounds are kept as i64 so that stepping past i32::MAX or i32::MIN can not
+/// overflow. Where usize has only 32 bits, the full i32 range has more values
+/// than a usize can count, so its size_hint has no upper bound there, and
+/// Guesses is only an ExactSizeIterator where usize has 64 bits.
+#[derive(Debug, Clone)]
+pub struct Guesses<const RANGE: RangeInclusive<i32>>
+{
+	front: i64,
+	back: i64,
+}
+
+impl<const RANGE: RangeInclusive<i32>> Iterator for Guesses<RANGE>
+{
+	type Item = Guess<RANGE>;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		if self.front > self.back
+		{
+			None
+		}
+		else
+		{
+			let value = self.front as i32;
+			self.front += 1_i64;
+			Some(Guess { value })
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>)
+	{
+		let len = (self.back - self.front + 1_i64).max(0_i64);
+		usize::try_from(len).ok().map_or((usize::MAX, None), |len| (len, Some(len)))
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> DoubleEndedIterator for Guesses<RANGE>
+{
+	fn next_back(&mut self) -> Option<Self::Item>
+	{
+		if self.front > self.back
+		{
+			None
+		}
+		else
+		{
+			let value = self.back as i32;
+			self.back -= 1_i64;
+			Some(Guess { value })
+		}
+	}
+}
+
+#[cfg(target_pointer_width = "64")]
+impl<const RANGE: RangeInclusive<i32>> ExactSizeIterator for Guesses<RANGE> {}
+
+impl<const RANGE: RangeInclusive<i32>> FusedIterator for Guesses<RANGE> {}
+
+/// Error returned when a value can not be made into a Guess.
+///
+/// Each variant carries the rejected value along with the bound of the range it
+/// violated, so callers can explain the problem without knowing the range.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GuessError<T = i32>
+{
+	/// The value is less than the start of the range.
+	BelowRange
+	{
+		value: T,
+		min: T,
+	},
+	/// The value is greater than the end of the range.
+	AboveRange
+	{
+		value: T,
+		max: T,
+	},
+}
+
+/// Formats GuessError as a message that can be shown to the user.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::GuessError;
+/// // println!("{}", GuessError::AboveRange { value: 101, max: 100 });
+/// ```
+impl<T: Display> Display for GuessError<T>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			GuessError::BelowRange { value, min } => write!(f, "{value} is too low to even be a guess. Guesses must be at least {min}."),
+			GuessError::AboveRange { value, max } => write!(f, "{value} is above the maximum. Guesses must be at most {max}."),
+		}
+	}
+}
+
+impl<T: std::fmt::Debug + Display> std::error::Error for GuessError<T> {}
+
+/// Error returned when text can not be made into a Guess.
+///
+/// The text is either not an integer at all, an integer too large or too small
+/// to be represented, or an integer that lies outside of the range. Relative
+/// guesses can also fail because there is nothing to count from, and intervals
+/// because their ends are backwards or out of range.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ParseGuessError<T = i32>
+{
+	/// The text is not an integer. This includes empty text.
+	NotANumber,
+	/// The text is an integer, but it is too large to fit in the integer type.
+	Overflow,
+	/// The text is an integer, but it is too small to fit in the integer type.
+	Underflow,
+	/// The text is an integer, but it is not a valid Guess.
+	OutOfRange(GuessError<T>),
+	/// The text is a negative integer, but the integer type is unsigned, so it
+	/// is below the range.
+	Negative
+	{
+		min: T,
+	},
+	/// The text is a relative guess, as in `+5`, but there is no previous guess.
+	NoPreviousGuess,
+	/// The text is a valid guess, but the same value was already guessed.
+	Repeated(T),
+	/// The text is an interval, as in `60-40`, that starts after it ends.
+	ReversedInterval
+	{
+		start: T,
+		end: T,
+	},
+	/// The text is an interval, but one of its ends is not a valid Guess.
+	IntervalOutOfRange(GuessError<T>),
+}
+
+impl<T> From<ParseIntError> for ParseGuessError<T>
+{
+	fn from(error: ParseIntError) -> Self
+	{
+		match error.kind()
+		{
+			IntErrorKind::PosOverflow => ParseGuessError::Overflow,
+			IntErrorKind::NegOverflow => ParseGuessError::Underflow,
+			_ => ParseGuessError::NotANumber,
+		}
+	}
+}
+
+impl<T> From<GuessError<T>> for ParseGuessError<T>
+{
+	fn from(error: GuessError<T>) -> Self
+	{
+		ParseGuessError::OutOfRange(error)
+	}
+}
+
+/// Formats ParseGuessError as a message that can be shown to the user.
+impl<T: Integer> Display for ParseGuessError<T>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			ParseGuessError::NotANumber => write!(f, "Guesses must be an integer."),
+			ParseGuessError::Overflow => write!(f, "That number is too large to even consider."),
+			ParseGuessError::Underflow => write!(f, "That number is too small to even consider."),
+			ParseGuessError::NoPreviousGuess => write!(f, "There is no previous guess to count from yet."),
+			ParseGuessError::Repeated(value) => write!(f, "You already guessed {value}."),
+			ParseGuessError::OutOfRange(error) => error.fmt(f),
+			ParseGuessError::Negative { min } => write!(f, "Negative numbers are too low to even be a guess. Guesses must be at least {min}."),
+			ParseGuessError::ReversedInterval { start, end } =>
+			{
+				write!(f, "The interval {start}-{end} is backwards. Put the smaller number first.")
+			},
+			ParseGuessError::IntervalOutOfRange(error) => write!(f, "Both ends of an interval must be valid guesses. {error}"),
+		}
+	}
+}
+
+impl<T: Integer> std::error::Error for ParseGuessError<T>
+{
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+	{
+		match self
+		{
+			ParseGuessError::OutOfRange(error) | ParseGuessError::IntervalOutOfRange(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+/// Validating conversion from an integer into a Guess. This is equivalent to
+/// calling Guess::new().
+///
+/// # Examples
+///
+/// ```
+/// #![feature(adt_const_params)]
+/// use guessing_game::{
+/// 	Guess,
+/// 	GuessError,
+/// };
+///
+/// let guess: Guess<{ 0..=100 }> = 42_i32.try_into().expect("42 is in range");
+/// assert_eq!(guess.value(), 42_i32);
+///
+/// let error = Guess::<{ 0..=100 }>::try_from(101_i32).expect_err("101 is out of range");
+/// assert_eq!(error, GuessError::AboveRange { value: 101_i32, max: 100_i32 });
+/// ```
+impl<const RANGE: RangeInclusive<i32>> TryFrom<i32> for Guess<RANGE>
+{
+	type Error = GuessError;
+
+	fn try_from(value: i32) -> Result<Self, Self::Error>
+	{
+		Guess::new(value)
+	}
+}
+
+/// Validating conversion from text into a Guess. Surrounding whitespace is
+/// ignored.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(adt_const_params)]
+/// use guessing_game::{
+/// 	Guess,
+/// 	GuessError,
+/// 	ParseGuessError,
+/// };
+///
+/// let guess: Guess<{ 0..=100 }> = " 42\n".try_into().expect("42 is in range");
+/// assert_eq!(guess.value(), 42_i32);
+///
+/// let error = Guess::<{ 0..=100 }>::try_from("-1").expect_err("-1 is out of range");
+/// assert_eq!(error, ParseGuessError::OutOfRange(GuessError::BelowRange { value: -1_i32, min: 0_i32 }));
+///
+/// let error = Guess::<{ 0..=100 }>::try_from("forty-two").expect_err("forty-two is not an integer");
+/// assert_eq!(error, ParseGuessError::NotANumber);
+/// ```
+impl<const RANGE: RangeInclusive<i32>> TryFrom<&str> for Guess<RANGE>
+{
+	type Error = ParseGuessError;
+
+	fn try_from(text: &str) -> Result<Self, Self::Error>
+	{
+		text.parse()
+	}
+}
+
+/// Parses text into a Guess. Surrounding whitespace is ignored, then the text
+/// is parsed as an i32 and validated against RANGE.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Guess;
+/// // let guess = "42".parse::<Guess<{ 0..=100000 }>>().expect("");
+/// ```
+impl<const RANGE: RangeInclusive<i32>> FromStr for Guess<RANGE>
+{
+	type Err = ParseGuessError;
+
+	fn from_str(text: &str) -> Result<Self, Self::Err>
+	{
+		parse(text, Self::min_value(), Self::max_value()).map(|value| Guess { value })
+	}
+}
+
+/// Formats Guess for displaying in console output.
+///
+/// The plain format is just the value. The alternate format also shows RANGE,
+/// as in `42 (in 0..=1024)`. Both respect the width, fill, and alignment flags
+/// so guesses can be lined up in a table.
+///
+/// # Examples
+///
+/// Outputting a Guess to the console:
+///
+/// ```
+/// // use guessing_game::Guess;
+/// // println!("The guess displays as {}", Guess::<{ 0..=100000 }>::new(100_i32).expect(""));
+/// // println!("The guess displays as {:#}", Guess::<{ 0..=100000 }>::new(100_i32).expect(""));
+/// // println!("The guess displays as {:>6}", Guess::<{ 0..=100000 }>::new(100_i32).expect(""));
+/// ```
+impl<const RANGE: RangeInclusive<i32>> Display for Guess<RANGE>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		if f.alternate()
+		{
+			f.pad(&format!("{} (in {}..={})", self.value, Self::min_value(), Self::max_value()))
+		}
+		else
+		{
+			f.pad_integral(self.value >= 0_i32, "", &self.value.unsigned_abs().to_string())
+		}
+	}
+}
+
+/// Converts a Guess into its underlying integer.
+///
+/// This conversion is infallible since every Guess holds a valid i32.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Guess;
+/// // let value: i32 = Guess::<{ 0..=100000 }>::new(100_i32).expect("").into();
+/// ```
+impl<const RANGE: RangeInclusive<i32>> From<Guess<RANGE>> for i32
+{
+	fn from(guess: Guess<RANGE>) -> Self
+	{
+		guess.value
+	}
+}
+
+/// Compares a Guess with a plain integer.
+///
+/// The integer is not validated, so comparing against a value outside of RANGE
+/// is allowed and simply unequal.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Guess;
+/// // assert!(Guess::<{ 0..=100000 }>::new(42_i32).expect("") == 42_i32);
+/// ```
+impl<const RANGE: RangeInclusive<i32>> PartialEq<i32> for Guess<RANGE>
+{
+	fn eq(
+		&self,
+		other: &i32,
+	) -> bool
+	{
+		self.value == *other
+	}
+}
+
+/// Compares a plain integer with a Guess.
+impl<const RANGE: RangeInclusive<i32>> PartialEq<Guess<RANGE>> for i32
+{
+	fn eq(
+		&self,
+		other: &Guess<RANGE>,
+	) -> bool
+	{
+		*self == other.value
+	}
+}
+
+/// Orders a Guess relative to a plain integer.
+///
+/// The integer is not validated, so every Guess is greater than any integer
+/// below RANGE and less than any integer above RANGE.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Guess;
+/// // assert!(Guess::<{ 0..=100000 }>::new(42_i32).expect("") < 100_i32);
+/// ```
+impl<const RANGE: RangeInclusive<i32>> PartialOrd<i32> for Guess<RANGE>
+{
+	fn partial_cmp(
+		&self,
+		other: &i32,
+	) -> Option<Ordering>
+	{
+		self.value.partial_cmp(other)
+	}
+}
+
+/// Orders a plain integer relative to a Guess.
+impl<const RANGE: RangeInclusive<i32>> PartialOrd<Guess<RANGE>> for i32
+{
+	fn partial_cmp(
+		&self,
+		other: &Guess<RANGE>,
+	) -> Option<Ordering>
+	{
+		self.partial_cmp(&other.value)
+	}
+}
+
+/// Borrows the underlying integer of a Guess.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::Guess;
+/// // let guess = Guess::<{ 0..=100000 }>::new(100_i32).expect("");
+/// // let value: &i32 = guess.as_ref();
+/// ```
+impl<const RANGE: RangeInclusive<i32>> AsRef<i32> for Guess<RANGE>
+{
+	fn as_ref(&self) -> &i32
+	{
+		&self.value
+	}
+}
+
+/// Serializes a Guess as its plain integer value.
+#[cfg(feature = "serde")]
+impl<const RANGE: RangeInclusive<i32>> serde::Serialize for Guess<RANGE>
+{
+	fn serialize<S: serde::Serializer>(
+		&self,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	{
+		serializer.serialize_i32(self.value)
+	}
+}
+
+/// Deserializes a Guess from a plain integer value.
+///
+/// The value is validated the same way as Guess::new(), so an out-of-range
+/// value is rejected with an error describing which bound it violated.
+#[cfg(feature = "serde")]
+impl<'de, const RANGE: RangeInclusive<i32>> serde::Deserialize<'de> for Guess<RANGE>
+{
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+	{
+		Guess::new(<i32 as serde::Deserialize>::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+	}
+}
+
+/// Generates uniformly distributed guesses within RANGE, so that a random
+/// correct value can be created with Rng::gen().
+///
+/// # Panics
+/// Panics if RANGE is empty.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::Guess,
+/// // 	rand::{
+/// // 		thread_rng,
+/// // 		Rng,
+/// // 	},
+/// // };
+/// // let correct: Guess<{ 0..=100000 }> = thread_rng().gen();
+/// ```
+impl<const RANGE: RangeInclusive<i32>> Distribution<Guess<RANGE>> for Standard
+{
+	fn sample<R: Rng + ?Sized>(
+		&self,
+		rng: &mut R,
+	) -> Guess<RANGE>
+	{
+		Guess { value: rng.gen_range(Guess::<RANGE>::min_value()..=Guess::<RANGE>::max_value()) }
+	}
+}
+
+/// Generates uniformly distributed guesses within RANGE for property tests.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::Guess,
+/// // 	proptest::prelude::*,
+/// // };
+/// // proptest! {
+/// // 	#[test]
+/// // 	fn in_range(guess in any::<Guess<{ 0..=100000 }>>()) {
+/// // 		prop_assert!(guess.value() <= 100000_i32);
+/// // 	}
+/// // }
+/// ```
+#[cfg(feature = "proptest")]
+impl<const RANGE: RangeInclusive<i32>> proptest::arbitrary::Arbitrary for Guess<RANGE>
+{
+	type Parameters = ();
+	type Strategy = proptest::strategy::Map<RangeInclusive<i32>, fn(i32) -> Self>;
+
+	fn arbitrary_with(_: Self::Parameters) -> Self::Strategy
+	{
+		proptest::strategy::Strategy::prop_map(Self::min_value()..=Self::max_value(), Guess::new_clamped as fn(i32) -> Self)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::{
+			Guess,
+			*,
+		},
+		colored::{
+			Color,
+			Colorize,
+			Styles,
+		},
+		rand::{
+			rngs::StdRng,
+			SeedableRng,
+		},
+		std::{
+			collections::{
+				BTreeSet,
+				HashSet,
+			},
+			io::{
+				stdout,
+				BufReader,
+				Read,
+			},
+		},
+	};
+	#[test]
+	fn construction()
+	{
+		Guess::<{ 0..=0 }>::new(0_i32).expect("guess 1 failed to construct.");
+		Guess::<{ 0..=1 }>::new(0_i32).expect("guess 2 failed to construct.");
+		Guess::<{ 0..=0 }>::new(1_i32).expect_err("guess 3 failed to fail to construct.");
+
+		Guess::<{ 0..=10 }>::new(0_i32).expect("guess 4 failed to construct.");
+		Guess::<{ 0..=10 }>::new(5_i32).expect("guess 5 failed to construct.");
+		Guess::<{ 0..=10 }>::new(10_i32).expect("guess 6 failed to construct.");
+
+		Guess::<{ 10..=20 }>::new(0_i32).expect_err("guess 7 failed to fail to construct.");
+		Guess::<{ 10..=20 }>::new(10_i32).expect("guess 8 failed to construct.");
+		Guess::<{ 10..=20 }>::new(15_i32).expect("guess 9 failed to construct.");
+		Guess::<{ 10..=20 }>::new(20_i32).expect("guess 10 failed to construct.");
+
+		Guess::<{ 0..=10000 }>::new(-0_i32).expect("guess 11 failed to construct.");
+		Guess::<{ 0..=10000 }>::new(-5_i32).expect_err("guess 12 failed to construct.");
+		Guess::<{ 0..=10000 }>::new(-10_i32).expect_err("guess 13 failed to construct.");
+	}
+
+	#[test]
+	fn construction_clamped()
+	{
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(-1_i32).value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(i32::MIN).value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(0_i32).value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(5_i32).value(), 5_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(10_i32).value(), 10_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(11_i32).value(), 10_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new_clamped(i32::MAX).value(), 10_i32);
+
+		assert_eq!(Guess::<{ -20..=-10 }>::new_clamped(0_i32).value(), -10_i32);
+		assert_eq!(Guess::<{ -20..=-10 }>::new_clamped(-30_i32).value(), -20_i32);
+
+		assert_eq!(Guess::<{ 7..=7 }>::new_clamped(6_i32).value(), 7_i32);
+		assert_eq!(Guess::<{ 7..=7 }>::new_clamped(7_i32).value(), 7_i32);
+		assert_eq!(Guess::<{ 7..=7 }>::new_clamped(8_i32).value(), 7_i32);
+	}
+
+	#[test]
+	fn construction_error()
+	{
+		assert_eq!(Guess::<{ 0..=10 }>::new(-1_i32), Err(GuessError::BelowRange { value: -1_i32, min: 0_i32 }));
+		assert_eq!(Guess::<{ 0..=10 }>::new(11_i32), Err(GuessError::AboveRange { value: 11_i32, max: 10_i32 }));
+
+		assert_eq!(Guess::<{ -20..=-10 }>::new(-21_i32), Err(GuessError::BelowRange { value: -21_i32, min: -20_i32 }));
+		assert_eq!(Guess::<{ -20..=-10 }>::new(-9_i32), Err(GuessError::AboveRange { value: -9_i32, max: -10_i32 }));
+
+		assert_eq!(Guess::<{ 5..=5 }>::new(4_i32), Err(GuessError::BelowRange { value: 4_i32, min: 5_i32 }));
+		assert_eq!(Guess::<{ 5..=5 }>::new(6_i32), Err(GuessError::AboveRange { value: 6_i32, max: 5_i32 }));
+
+		assert_eq!(
+			Guess::<{ 0..=i32::MAX }>::new(i32::MIN),
+			Err(GuessError::BelowRange { value: i32::MIN, min: 0_i32 })
+		);
+		assert_eq!(
+			Guess::<{ i32::MIN..=0 }>::new(i32::MAX),
+			Err(GuessError::AboveRange { value: i32::MAX, max: 0_i32 })
+		);
+	}
+
+	#[test]
+	fn error_message()
+	{
+		assert!(GuessError::BelowRange { value: -1_i32, min: 0_i32 }.to_string().contains("too low"));
+		assert!(GuessError::AboveRange { value: 11_i32, max: 10_i32 }.to_string().contains("above the maximum"));
+	}
+
+	#[test]
+	fn parse()
+	{
+		assert_eq!("".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::NotANumber));
+		assert_eq!(" \t\n".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::NotANumber));
+		assert_eq!("five".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::NotANumber));
+		assert_eq!("5.0".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::NotANumber));
+
+		assert_eq!("+5".parse::<Guess<{ 0..=10 }>>().map(i32::from), Ok(5_i32));
+		assert_eq!(" 5\n".parse::<Guess<{ 0..=10 }>>().map(i32::from), Ok(5_i32));
+		assert_eq!("-5".parse::<Guess<{ -10..=0 }>>().map(i32::from), Ok(-5_i32));
+
+		assert_eq!("2147483648".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::Overflow));
+		assert_eq!("-2147483649".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::Underflow));
+		assert_eq!("99999999999999999999".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::Overflow));
+		assert_eq!("-99999999999999999999".parse::<Guess<{ 0..=10 }>>(), Err(ParseGuessError::Underflow));
+
+		assert_eq!(
+			"11".parse::<Guess<{ 0..=10 }>>(),
+			Err(ParseGuessError::OutOfRange(GuessError::AboveRange { value: 11_i32, max: 10_i32 }))
+		);
+		assert_eq!(
+			"-1".parse::<Guess<{ 0..=10 }>>(),
+			Err(ParseGuessError::OutOfRange(GuessError::BelowRange { value: -1_i32, min: 0_i32 }))
+		);
+	}
+
+	#[test]
+	fn parse_lenient()
+	{
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("1,024").map(i32::from), Ok(1024_i32));
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("1_024").map(i32::from), Ok(1024_i32));
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient(" 42 ").map(i32::from), Ok(42_i32));
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("100,000\n").map(i32::from), Ok(100000_i32));
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("1_0_0").map(i32::from), Ok(100_i32));
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("+1,024").map(i32::from), Ok(1024_i32));
+		assert_eq!(Guess::<{ -10000..=0 }>::parse_lenient("-1,024").map(i32::from), Ok(-1024_i32));
+
+		assert_eq!(
+			Guess::<{ 0..=100000 }>::parse_lenient("1,000,000"),
+			Err(ParseGuessError::OutOfRange(GuessError::AboveRange { value: 1000000_i32, max: 100000_i32 }))
+		);
+		assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient("2,147,483,648"), Err(ParseGuessError::Overflow));
+	}
+
+	#[test]
+	fn parse_lenient_malformed()
+	{
+		for text in [",", "_", "1,,0", "1__0", ",1", "1,", "_1", "1_", "1,0", "1,0000", "1234,567", "1,024_000", "-,1", "+_1", "1,o24", ""]
+		{
+			assert_eq!(Guess::<{ 0..=100000 }>::parse_lenient(text), Err(ParseGuessError::NotANumber), "{text:?}");
+		}
+
+		// The strict parser does not accept separators at all.
+		assert_eq!("1,024".parse::<Guess<{ 0..=100000 }>>(), Err(ParseGuessError::NotANumber));
+		assert_eq!("1_024".parse::<Guess<{ 0..=100000 }>>(), Err(ParseGuessError::NotANumber));
+	}
+
+	#[test]
+	fn parse_lenient_radix()
+	{
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("0x2a").map(i32::from), Ok(42_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient(" 0X2A\n").map(i32::from), Ok(42_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("0b101010").map(i32::from), Ok(42_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("0B1").map(i32::from), Ok(1_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("0o52").map(i32::from), Ok(42_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("+0x10").map(i32::from), Ok(16_i32));
+		assert_eq!(Guess::<{ -1024..=0 }>::parse_lenient("-0x2a").map(i32::from), Ok(-42_i32));
+		assert_eq!(Guess::<{ -1024..=0 }>::parse_lenient("-0b1").map(i32::from), Ok(-1_i32));
+
+		// Radix literals are still validated.
+		assert_eq!(
+			Guess::<{ 0..=1024 }>::parse_lenient("0x401"),
+			Err(ParseGuessError::OutOfRange(GuessError::AboveRange { value: 1025_i32, max: 1024_i32 }))
+		);
+		assert_eq!(
+			Guess::<{ 0..=1024 }>::parse_lenient("-0x1"),
+			Err(ParseGuessError::OutOfRange(GuessError::BelowRange { value: -1_i32, min: 0_i32 }))
+		);
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("0x80000000"), Err(ParseGuessError::Overflow));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient("-0x80000001"), Err(ParseGuessError::Underflow));
+		assert_eq!(BoundedGuess::parse_lenient("0xff", 0_u8..=u8::MAX).map(|guess| guess.value()), Ok(255_u8));
+		assert_eq!(BoundedGuess::parse_lenient("0x100", 0_u8..=u8::MAX), Err(ParseGuessError::Overflow));
+
+		for text in ["x2a", "0x", "0b", "0x-5", "0x+5", "0b102", "0xg", "0x_ff", "0x1_0", "0x1,0", "00x1", "0 x1", "--0x1"]
+		{
+			assert_eq!(Guess::<{ 0..=1024 }>::parse_lenient(text), Err(ParseGuessError::NotANumber), "{text:?}");
+		}
+
+		// The strict parser only reads decimal.
+		assert_eq!("0x2a".parse::<Guess<{ 0..=1024 }>>(), Err(ParseGuessError::NotANumber));
+	}
+
+	#[test]
+	fn equality()
+	{
+		assert_eq!(
+			Guess::<{ 0..=10 }>::new(0_i32).expect("guess 1 failed to construct."),
 			Guess::<{ 0..=10 }>::new(0_i32).expect("guess 2 failed to construct.")
 		);
 		assert_eq!(
@@ -256,36 +2749,665 @@ mod tests
 	}
 
 	#[test]
-	fn nequality()
+	fn nequality()
+	{
+		assert_ne!(
+			Guess::<{ 0..=10 }>::new(5_i32).expect("guess 1 failed to construct."),
+			Guess::<{ 0..=10 }>::new(0_i32).expect("guess 2 failed to construct.")
+		);
+		assert_ne!(
+			Guess::<{ 0..=20 }>::new(10_i32).expect("guess 3 failed to construct."),
+			Guess::<{ 0..=20 }>::new(15_i32).expect("guess 4 failed to construct.")
+		);
+		assert_ne!(
+			Guess::<{ 0..=50 }>::new(50_i32).expect("guess 5 failed to construct."),
+			Guess::<{ 0..=50 }>::new(0_i32).expect("guess 6 failed to construct.")
+		);
+	}
+
+	#[test]
+	fn value()
+	{
+		assert_eq!(Guess::<{ 0..=0 }>::new(0_i32).expect("guess 1 failed to construct.").value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::new(5_i32).expect("guess 2 failed to construct.").value(), 5_i32);
+		assert_eq!(Guess::<{ 10..=20 }>::new(20_i32).expect("guess 3 failed to construct.").value(), 20_i32);
+		assert_eq!(Guess::<{ -10..=10 }>::new(-10_i32).expect("guess 4 failed to construct.").value(), -10_i32);
+
+		// Round-trip through the constructor.
+		let guess = Guess::<{ 0..=50 }>::new(42_i32).expect("guess 5 failed to construct.");
+		assert_eq!(Guess::<{ 0..=50 }>::new(guess.value()), Ok(guess));
+	}
+
+	#[test]
+	fn into_i32()
+	{
+		assert_eq!(i32::from(Guess::<{ 0..=10 }>::new(0_i32).expect("guess 1 failed to construct.")), 0_i32);
+		assert_eq!(i32::from(Guess::<{ 0..=10 }>::new(10_i32).expect("guess 2 failed to construct.")), 10_i32);
+
+		assert_eq!(i32::from(Guess::<{ -20..=-10 }>::new(-20_i32).expect("guess 3 failed to construct.")), -20_i32);
+		assert_eq!(i32::from(Guess::<{ -20..=-10 }>::new(-10_i32).expect("guess 4 failed to construct.")), -10_i32);
+
+		let min: i32 = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MIN).expect("guess 5 failed to construct.").into();
+		let max: i32 = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 6 failed to construct.").into();
+		assert_eq!(min, i32::MIN);
+		assert_eq!(max, i32::MAX);
+	}
+
+	#[test]
+	fn into_inner()
+	{
+		assert_eq!(Guess::<{ -10..=10 }>::new(-3_i32).expect("guess 1 failed to construct.").into_inner(), -3_i32);
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 2 failed to construct.").into_inner(), i32::MAX);
+	}
+
+	#[test]
+	fn compare_i32()
+	{
+		let guess = Guess::<{ 0..=100 }>::new(42_i32).expect("guess 1 failed to construct.");
+		assert!(guess == 42_i32);
+		assert!(42_i32 == guess);
+		assert!(guess != 43_i32);
+		assert!(guess < 100_i32);
+		assert!(guess > 0_i32);
+		assert!(guess <= 42_i32);
+		assert!(guess >= 42_i32);
+		assert!(100_i32 > guess);
+		assert!(0_i32 < guess);
+
+		// Integers outside of the range are compared without being validated.
+		assert!(guess != -1_i32);
+		assert!(guess > -1_i32);
+		assert!(guess < 1000_i32);
+		assert!(i32::MIN < guess);
+		assert!(i32::MAX > guess);
+
+		// Comparisons between guesses are unaffected.
+		assert!(guess < Guess::<{ 0..=100 }>::new(43_i32).expect("guess 2 failed to construct."));
+		assert_eq!(guess.cmp(&guess), Ordering::Equal);
+	}
+
+	#[test]
+	fn as_ref()
+	{
+		let guesses = [
+			Guess::<{ -5..=5 }>::new(-5_i32).expect("guess 1 failed to construct."),
+			Guess::<{ -5..=5 }>::new(0_i32).expect("guess 2 failed to construct."),
+			Guess::<{ -5..=5 }>::new(5_i32).expect("guess 3 failed to construct."),
+		];
+		let references: Vec<&i32> = guesses.iter().map(AsRef::as_ref).collect();
+		assert_eq!(references, [&-5_i32, &0_i32, &5_i32]);
+	}
+
+	#[test]
+	fn widen()
+	{
+		let guess = Guess::<{ 0..=10 }>::new(5_i32).expect("guess 1 failed to construct.");
+		assert_eq!(guess.widen::<{ 0..=1000 }>().value(), 5_i32);
+		assert_eq!(guess.widen::<{ -10..=10 }>().value(), 5_i32);
+		assert_eq!(guess.widen::<{ 0..=10 }>(), guess);
+		assert_eq!(guess.widen::<{ i32::MIN..=i32::MAX }>().value(), 5_i32);
+	}
+
+	#[test]
+	fn try_narrow()
+	{
+		let guess = Guess::<{ 0..=1000 }>::new(500_i32).expect("guess 1 failed to construct.");
+		assert_eq!(guess.try_narrow::<{ 400..=600 }>().map(i32::from), Ok(500_i32));
+		assert_eq!(guess.try_narrow::<{ 500..=500 }>().map(i32::from), Ok(500_i32));
+		assert_eq!(
+			guess.try_narrow::<{ 0..=100 }>(),
+			Err(GuessError::AboveRange { value: 500_i32, max: 100_i32 })
+		);
+		assert_eq!(
+			guess.try_narrow::<{ 501..=1000 }>(),
+			Err(GuessError::BelowRange { value: 500_i32, min: 501_i32 })
+		);
+
+		// The other range does not have to be narrower.
+		assert_eq!(guess.try_narrow::<{ 400..=2000 }>().map(i32::from), Ok(500_i32));
+	}
+
+	#[test]
+	fn range()
+	{
+		assert_eq!(Guess::<{ 0..=10 }>::min_value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::max_value(), 10_i32);
+		assert_eq!(Guess::<{ 0..=10 }>::span(), 11_u64);
+
+		assert_eq!(Guess::<{ -20..=-10 }>::min_value(), -20_i32);
+		assert_eq!(Guess::<{ -20..=-10 }>::max_value(), -10_i32);
+		assert_eq!(Guess::<{ -20..=-10 }>::span(), 11_u64);
+
+		assert_eq!(Guess::<{ 7..=7 }>::min_value(), 7_i32);
+		assert_eq!(Guess::<{ 7..=7 }>::max_value(), 7_i32);
+		assert_eq!(Guess::<{ 7..=7 }>::span(), 1_u64);
+
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::min_value(), i32::MIN);
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::max_value(), i32::MAX);
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::span(), 1_u64 << 32_u32);
+	}
+
+	#[test]
+	fn midpoint()
+	{
+		assert_eq!(Guess::<{ 0..=100 }>::midpoint().value(), 50_i32);
+		assert_eq!(Guess::<{ 0..=1024 }>::midpoint().value(), 512_i32);
+		assert_eq!(Guess::<{ 0..=1 }>::midpoint().value(), 0_i32);
+		assert_eq!(Guess::<{ 7..=7 }>::midpoint().value(), 7_i32);
+		assert_eq!(Guess::<{ -10..=10 }>::midpoint().value(), 0_i32);
+		assert_eq!(Guess::<{ -11..=10 }>::midpoint().value(), -1_i32);
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::midpoint().value(), -1_i32);
+		assert_eq!(Guess::<{ 0..=i32::MAX }>::midpoint().value(), i32::MAX / 2_i32);
+		assert_eq!(Guess::<{ 2147483646..=i32::MAX }>::midpoint().value(), 2147483646_i32);
+	}
+
+	#[test]
+	fn guess_range()
+	{
+		let guess = |value| Guess::<{ -100..=100 }>::new(value).expect("guess failed to construct.");
+
+		let single = GuessRange::new(guess(5_i32), guess(5_i32)).expect("single failed to construct.");
+		assert_eq!(single.size(), 1_u64);
+		assert_eq!(single.midpoint(), guess(5_i32));
+		assert_eq!(single.below(guess(5_i32)), None);
+		assert_eq!(single.above(guess(5_i32)), None);
+
+		let adjacent = GuessRange::new(guess(5_i32), guess(6_i32)).expect("adjacent failed to construct.");
+		assert_eq!(adjacent.size(), 2_u64);
+		assert_eq!(adjacent.midpoint(), guess(5_i32));
+		assert_eq!(adjacent.above(guess(5_i32)), GuessRange::new(guess(6_i32), guess(6_i32)));
+		assert_eq!(adjacent.below(guess(6_i32)), GuessRange::new(guess(5_i32), guess(5_i32)));
+
+		let mixed = GuessRange::new(guess(-7_i32), guess(4_i32)).expect("mixed failed to construct.");
+		assert_eq!(mixed.size(), 12_u64);
+		assert_eq!(mixed.midpoint(), guess(-2_i32));
+		assert!(mixed.contains(guess(-7_i32)));
+		assert!(mixed.contains(guess(4_i32)));
+		assert!(!mixed.contains(guess(5_i32)));
+
+		assert_eq!(GuessRange::new(guess(6_i32), guess(5_i32)), None);
+		assert_eq!(GuessRange::<{ -100..=100 }>::full().size(), 201_u64);
+	}
+
+	#[test]
+	fn binary_search()
+	{
+		for secret in Guess::<{ 0..=1024 }>::iter()
+		{
+			let mut possible = GuessRange::<{ 0..=1024 }>::full();
+			let mut attempts = 0_u32;
+			loop
+			{
+				attempts += 1_u32;
+				let guess = possible.midpoint();
+				possible = match guess.cmp(&secret)
+				{
+					Ordering::Greater => possible.below(guess).expect("secret must be below a guess that is too high."),
+					Ordering::Less => possible.above(guess).expect("secret must be above a guess that is too low."),
+					Ordering::Equal => break,
+				};
+				assert!(possible.contains(secret));
+			}
+			assert!(attempts <= 11_u32, "{secret} took {attempts} attempts.");
+		}
+	}
+
+	#[test]
+	fn percent_of_range()
+	{
+		let guess = |value| Guess::<{ 0..=100 }>::new(value).expect("guess failed to construct.");
+		assert_eq!(guess(0_i32).percent_of_range(), 0.0_f64);
+		assert_eq!(guess(50_i32).percent_of_range(), 0.5_f64);
+		assert_eq!(guess(73_i32).percent_of_range(), 0.73_f64);
+		assert_eq!(guess(100_i32).percent_of_range(), 1.0_f64);
+
+		let guess = |value| Guess::<{ -10..=10 }>::new(value).expect("guess failed to construct.");
+		assert_eq!(guess(-10_i32).percent_of_range(), 0.0_f64);
+		assert_eq!(guess(0_i32).percent_of_range(), 0.5_f64);
+		assert_eq!(guess(10_i32).percent_of_range(), 1.0_f64);
+
+		let guess = |value| Guess::<{ i32::MIN..=i32::MAX }>::new(value).expect("guess failed to construct.");
+		assert_eq!(guess(i32::MIN).percent_of_range(), 0.0_f64);
+		assert_eq!(guess(i32::MAX).percent_of_range(), 1.0_f64);
+		assert!((guess(0_i32).percent_of_range() - 0.5_f64).abs() < 1e-9_f64);
+
+		assert_eq!(Guess::<{ 7..=7 }>::new(7_i32).expect("guess failed to construct.").percent_of_range(), 0.0_f64);
+	}
+
+	#[test]
+	fn from_fraction()
+	{
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(0.0_f64).value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(0.5_f64).value(), 50_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(0.734_f64).value(), 73_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(0.736_f64).value(), 74_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(1.0_f64).value(), 100_i32);
+
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(-0.5_f64).value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(1.5_f64).value(), 100_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(f64::NAN).value(), 0_i32);
+		assert_eq!(Guess::<{ 0..=100 }>::from_fraction(f64::INFINITY).value(), 100_i32);
+
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::from_fraction(0.0_f64).value(), i32::MIN);
+		assert_eq!(Guess::<{ i32::MIN..=i32::MAX }>::from_fraction(1.0_f64).value(), i32::MAX);
+		assert_eq!(Guess::<{ 7..=7 }>::from_fraction(0.5_f64).value(), 7_i32);
+
+		// Converting back and forth lands on the same guess.
+		for guess in Guess::<{ -50..=50 }>::iter()
+		{
+			assert_eq!(Guess::from_fraction(guess.percent_of_range()), guess);
+		}
+	}
+
+	#[test]
+	fn iter()
+	{
+		let guesses: Vec<i32> = Guess::<{ 0..=10 }>::iter().map(i32::from).collect();
+		assert_eq!(guesses, (0_i32..=10_i32).collect::<Vec<_>>());
+		assert_eq!(Guess::<{ 0..=10 }>::iter().size_hint(), (11_usize, Some(11_usize)));
+
+		let reversed: Vec<i32> = Guess::<{ 0..=10 }>::iter().rev().map(i32::from).collect();
+		assert_eq!(reversed, (0_i32..=10_i32).rev().collect::<Vec<_>>());
+
+		let mut guesses = Guess::<{ -2..=2 }>::iter();
+		assert_eq!(guesses.next().map(i32::from), Some(-2_i32));
+		assert_eq!(guesses.next_back().map(i32::from), Some(2_i32));
+		assert_eq!(guesses.size_hint(), (3_usize, Some(3_usize)));
+		assert_eq!(guesses.next().map(i32::from), Some(-1_i32));
+		assert_eq!(guesses.next_back().map(i32::from), Some(1_i32));
+		assert_eq!(guesses.next().map(i32::from), Some(0_i32));
+		assert_eq!(guesses.size_hint(), (0_usize, Some(0_usize)));
+		assert_eq!(guesses.next(), None);
+		assert_eq!(guesses.next_back(), None);
+
+		assert_eq!(Guess::<{ 7..=7 }>::iter().map(i32::from).collect::<Vec<_>>(), [7_i32]);
+	}
+
+	#[test]
+	fn iter_boundaries()
+	{
+		let mut guesses = Guess::<{ 2147483645..=i32::MAX }>::iter();
+		assert_eq!(guesses.size_hint(), (3_usize, Some(3_usize)));
+		assert_eq!(guesses.by_ref().map(i32::from).collect::<Vec<_>>(), [2147483645_i32, 2147483646_i32, i32::MAX]);
+		assert_eq!(guesses.next(), None);
+
+		let mut guesses = Guess::<{ i32::MIN..=-2147483646 }>::iter().rev();
+		assert_eq!(guesses.by_ref().map(i32::from).collect::<Vec<_>>(), [-2147483646_i32, -2147483647_i32, i32::MIN]);
+		assert_eq!(guesses.next(), None);
+
+		let guesses = Guess::<{ i32::MIN..=i32::MAX }>::iter();
+		match usize::try_from(Guess::<{ i32::MIN..=i32::MAX }>::span())
+		{
+			Ok(span) => assert_eq!(guesses.size_hint(), (span, Some(span))),
+			Err(_) => assert_eq!(guesses.size_hint(), (usize::MAX, None)),
+		}
+		assert_eq!(guesses.clone().take(2).map(i32::from).collect::<Vec<_>>(), [i32::MIN, i32::MIN + 1_i32]);
+		assert_eq!(guesses.rev().take(2).map(i32::from).collect::<Vec<_>>(), [i32::MAX, i32::MAX - 1_i32]);
+	}
+
+	#[test]
+	fn distance()
 	{
-		assert_ne!(
-			Guess::<{ 0..=10 }>::new(5_i32).expect("guess 1 failed to construct."),
-			Guess::<{ 0..=10 }>::new(0_i32).expect("guess 2 failed to construct.")
-		);
-		assert_ne!(
-			Guess::<{ 0..=20 }>::new(10_i32).expect("guess 3 failed to construct."),
-			Guess::<{ 0..=20 }>::new(15_i32).expect("guess 4 failed to construct.")
-		);
-		assert_ne!(
-			Guess::<{ 0..=50 }>::new(50_i32).expect("guess 5 failed to construct."),
-			Guess::<{ 0..=50 }>::new(0_i32).expect("guess 6 failed to construct.")
-		);
+		let low = Guess::<{ 0..=10 }>::new(0_i32).expect("guess 1 failed to construct.");
+		let high = Guess::<{ 0..=10 }>::new(10_i32).expect("guess 2 failed to construct.");
+		assert_eq!(low.distance(high), 10_u32);
+		assert_eq!(high.distance(low), 10_u32);
+		assert_eq!(low.distance(low), 0_u32);
+
+		let low = Guess::<{ -20..=-10 }>::new(-20_i32).expect("guess 3 failed to construct.");
+		let high = Guess::<{ -20..=-10 }>::new(-10_i32).expect("guess 4 failed to construct.");
+		assert_eq!(low.distance(high), 10_u32);
+
+		let low = Guess::<{ -5..=5 }>::new(-5_i32).expect("guess 5 failed to construct.");
+		let high = Guess::<{ -5..=5 }>::new(5_i32).expect("guess 6 failed to construct.");
+		assert_eq!(low.distance(high), 10_u32);
+
+		let low = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MIN).expect("guess 7 failed to construct.");
+		let high = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 8 failed to construct.");
+		assert_eq!(low.distance(high), u32::MAX);
+		assert_eq!(high.distance(low), u32::MAX);
+	}
+
+	#[test]
+	fn is_within()
+	{
+		let guess = Guess::<{ -10..=10 }>::new(-3_i32).expect("guess 1 failed to construct.");
+		let correct = Guess::<{ -10..=10 }>::new(2_i32).expect("guess 2 failed to construct.");
+		assert!(guess.is_within(correct, 5_u32));
+		assert!(guess.is_within(correct, 6_u32));
+		assert!(!guess.is_within(correct, 4_u32));
+		assert!(guess.is_within(guess, 0_u32));
+
+		let low = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MIN).expect("guess 3 failed to construct.");
+		let high = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 4 failed to construct.");
+		assert!(low.is_within(high, u32::MAX));
+		assert!(!low.is_within(high, u32::MAX - 1_u32));
+	}
+
+	#[test]
+	fn display()
+	{
+		let guess = Guess::<{ 0..=1024 }>::new(42_i32).expect("guess 1 failed to construct.");
+		assert_eq!(format!("{guess}"), "42");
+		assert_eq!(format!("{guess:#}"), "42 (in 0..=1024)");
+
+		let negative = Guess::<{ -10..=10 }>::new(-7_i32).expect("guess 2 failed to construct.");
+		assert_eq!(format!("{negative}"), "-7");
+		assert_eq!(format!("{negative:#}"), "-7 (in -10..=10)");
+		assert_eq!(format!("{:+}", Guess::<{ -10..=10 }>::new(7_i32).expect("guess 3 failed to construct.")), "+7");
+	}
+
+	#[test]
+	fn display_padded()
+	{
+		let guess = Guess::<{ 0..=1024 }>::new(42_i32).expect("guess 1 failed to construct.");
+		assert_eq!(format!("{guess:>6}"), "    42");
+		assert_eq!(format!("{guess:<6}|"), "42    |");
+		assert_eq!(format!("{guess:^6}"), "  42  ");
+		assert_eq!(format!("{guess:*>6}"), "****42");
+		assert_eq!(format!("{guess:06}"), "000042");
+
+		let negative = Guess::<{ -10..=10 }>::new(-7_i32).expect("guess 2 failed to construct.");
+		assert_eq!(format!("{negative:>4}"), "  -7");
+		assert_eq!(format!("{negative:04}"), "-007");
+
+		assert_eq!(format!("{guess:>#20}"), "    42 (in 0..=1024)");
+		assert_eq!(format!("{guess:<#20}|"), "42 (in 0..=1024)    |");
+	}
+
+	#[test]
+	fn checked_arithmetic()
+	{
+		let guess = Guess::<{ -10..=10 }>::new(5_i32).expect("guess 1 failed to construct.");
+		assert_eq!(guess.checked_add(5_i32).map(i32::from), Some(10_i32));
+		assert_eq!(guess.checked_add(6_i32), None);
+		assert_eq!(guess.checked_add(-15_i32).map(i32::from), Some(-10_i32));
+		assert_eq!(guess.checked_add(-16_i32), None);
+		assert_eq!(guess.checked_add(0_i32), Some(guess));
+
+		assert_eq!(guess.checked_sub(15_i32).map(i32::from), Some(-10_i32));
+		assert_eq!(guess.checked_sub(16_i32), None);
+		assert_eq!(guess.checked_sub(-5_i32).map(i32::from), Some(10_i32));
+		assert_eq!(guess.checked_sub(-6_i32), None);
+	}
+
+	#[test]
+	fn checked_arithmetic_overflow()
+	{
+		let max = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 1 failed to construct.");
+		let min = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MIN).expect("guess 2 failed to construct.");
+		assert_eq!(max.checked_add(1_i32), None);
+		assert_eq!(max.checked_sub(-1_i32), None);
+		assert_eq!(min.checked_sub(1_i32), None);
+		assert_eq!(min.checked_add(i32::MIN), None);
+		assert_eq!(max.checked_sub(i32::MAX).map(i32::from), Some(0_i32));
+		assert_eq!(min.checked_add(i32::MAX).map(i32::from), Some(-1_i32));
+	}
+
+	#[test]
+	fn next_prev()
+	{
+		let middle = Guess::<{ -10..=10 }>::new(0_i32).expect("guess 1 failed to construct.");
+		assert_eq!(middle.next().map(i32::from), Some(1_i32));
+		assert_eq!(middle.prev().map(i32::from), Some(-1_i32));
+		assert_eq!(middle.next().and_then(Guess::prev), Some(middle));
+
+		let start = Guess::<{ -10..=10 }>::new(-10_i32).expect("guess 2 failed to construct.");
+		assert_eq!(start.prev(), None);
+		assert_eq!(start.next().map(i32::from), Some(-9_i32));
+
+		let end = Guess::<{ -10..=10 }>::new(10_i32).expect("guess 3 failed to construct.");
+		assert_eq!(end.next(), None);
+		assert_eq!(end.prev().map(i32::from), Some(9_i32));
+
+		let single = Guess::<{ 7..=7 }>::new(7_i32).expect("guess 4 failed to construct.");
+		assert_eq!(single.next(), None);
+		assert_eq!(single.prev(), None);
+
+		let max = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 5 failed to construct.");
+		let min = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MIN).expect("guess 6 failed to construct.");
+		assert_eq!(max.next(), None);
+		assert_eq!(min.prev(), None);
+		assert_eq!(max.prev().map(i32::from), Some(i32::MAX - 1_i32));
+		assert_eq!(min.next().map(i32::from), Some(i32::MIN + 1_i32));
+
+		// Stepping through the whole range visits every guess in order.
+		let stepped: Vec<i32> = std::iter::successors(Some(start), |guess| guess.next()).map(i32::from).collect();
+		assert_eq!(stepped, (-10_i32..=10_i32).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn saturating_arithmetic()
+	{
+		let guess = Guess::<{ -10..=10 }>::new(5_i32).expect("guess 1 failed to construct.");
+		assert_eq!(guess.saturating_add(3_i32).value(), 8_i32);
+		assert_eq!(guess.saturating_add(100_i32).value(), 10_i32);
+		assert_eq!(guess.saturating_add(i32::MAX).value(), 10_i32);
+		assert_eq!(guess.saturating_sub(100_i32).value(), -10_i32);
+		assert_eq!(guess.saturating_sub(i32::MIN).value(), 10_i32);
+
+		let max = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MAX).expect("guess 2 failed to construct.");
+		assert_eq!(max.saturating_add(1_i32).value(), i32::MAX);
+		assert_eq!(max.saturating_sub(-1_i32).value(), i32::MAX);
+	}
+
+	#[test]
+	fn hash_set()
+	{
+		let mut guesses = HashSet::new();
+		assert!(guesses.insert(Guess::<{ 0..=10 }>::new(5_i32).expect("guess 1 failed to construct.")));
+		assert!(guesses.insert(Guess::<{ 0..=10 }>::new(7_i32).expect("guess 2 failed to construct.")));
+		assert!(!guesses.insert(Guess::<{ 0..=10 }>::new(5_i32).expect("guess 3 failed to construct.")));
+		assert!(!guesses.insert(Guess::<{ 0..=10 }>::new(7_i32).expect("guess 4 failed to construct.")));
+		assert_eq!(guesses.len(), 2);
+		assert!(guesses.contains(&Guess::new(5_i32).expect("guess 5 failed to construct.")));
+	}
+
+	#[test]
+	fn btree_set()
+	{
+		let guesses: BTreeSet<Guess<{ -10..=10 }>> = [3_i32, -7_i32, 3_i32, 10_i32, -7_i32]
+			.into_iter()
+			.map(|value| Guess::new(value).expect("guess failed to construct."))
+			.collect();
+		assert_eq!(guesses.len(), 3);
+		assert_eq!(guesses.into_iter().map(i32::from).collect::<Vec<_>>(), [-7_i32, 3_i32, 10_i32]);
+	}
+
+	#[test]
+	fn random()
+	{
+		let mut rng = StdRng::seed_from_u64(1660_u64);
+		for _ in 0..10000
+		{
+			let guess: Guess<{ -10..=10 }> = rng.gen();
+			assert!((-10_i32..=10_i32).contains(&guess.value()));
+		}
+
+		let seen: BTreeSet<Guess<{ 0..=3 }>> = (0..1000).map(|_| rng.gen()).collect();
+		assert_eq!(seen.len(), 4);
+
+		for _ in 0..100
+		{
+			assert_eq!(rng.gen::<Guess<{ 7..=7 }>>().value(), 7_i32);
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip()
+	{
+		let guess = Guess::<{ -10..=10 }>::new(-7_i32).expect("guess 1 failed to construct.");
+		let json = serde_json::to_string(&guess).expect("guess 1 failed to serialize.");
+		assert_eq!(json, "-7");
+		assert_eq!(serde_json::from_str::<Guess<{ -10..=10 }>>(&json).expect("guess 1 failed to deserialize."), guess);
+
+		let guesses = vec![
+			Guess::<{ 0..=50 }>::new(0_i32).expect("guess 2 failed to construct."),
+			Guess::<{ 0..=50 }>::new(50_i32).expect("guess 3 failed to construct."),
+		];
+		let json = serde_json::to_string(&guesses).expect("guesses failed to serialize.");
+		assert_eq!(json, "[0,50]");
+		assert_eq!(serde_json::from_str::<Vec<Guess<{ 0..=50 }>>>(&json).expect("guesses failed to deserialize."), guesses);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_out_of_range()
+	{
+		let error = serde_json::from_str::<Guess<{ 0..=50 }>>("51").expect_err("51 failed to fail to deserialize.");
+		assert!(error.to_string().contains("above the maximum"));
+
+		let error = serde_json::from_str::<Guess<{ 0..=50 }>>("-1").expect_err("-1 failed to fail to deserialize.");
+		assert!(error.to_string().contains("too low"));
+
+		serde_json::from_str::<Guess<{ 0..=50 }>>("\"25\"").expect_err("string failed to fail to deserialize.");
+		serde_json::from_str::<Guess<{ 0..=50 }>>("4294967296").expect_err("overflow failed to fail to deserialize.");
+	}
+
+	#[cfg(feature = "proptest")]
+	proptest::proptest! {
+		#[test]
+		fn arbitrary_round_trip(guess in proptest::arbitrary::any::<Guess<{ -1000..=1000 }>>())
+		{
+			proptest::prop_assert_eq!(Guess::new(guess.value()), Ok(guess));
+		}
+
+		#[test]
+		fn arbitrary_full_range(guess in proptest::arbitrary::any::<Guess<{ i32::MIN..=i32::MAX }>>())
+		{
+			proptest::prop_assert_eq!(Guess::new(guess.value()), Ok(guess));
+		}
 	}
 
 	#[test]
 	fn respond_test()
 	{
+		let mut output = Vec::new();
 		let guess = Guess::<{ 0..=50 }>::new(40).expect("guess 1 failed to construct.");
 		let correct = Guess::<{ 0..=50 }>::new(40).expect("guess 2 failed to construct.");
-		assert_eq!(respond(guess, correct, &mut stdout()), ControlFlow::Break(()));
+		assert_eq!(respond(guess, correct, &mut output).expect("respond failed."), ControlFlow::Break(Win { guess }));
+		assert!(String::from_utf8_lossy(&output).contains("You win!"));
 
+		let mut output = Vec::new();
 		let guess = Guess::<{ 0..=50 }>::new(20).expect("guess 3 failed to construct.");
 		let correct = Guess::<{ 0..=50 }>::new(40).expect("guess 4 failed to construct.");
-		assert_eq!(respond(guess, correct, &mut stdout()), ControlFlow::Continue(()));
+		assert_eq!(respond(guess, correct, &mut output).expect("respond failed."), ControlFlow::Continue(Feedback::TooLow));
+		assert!(String::from_utf8_lossy(&output).contains("20 is too low!"));
 
-		let guess = Guess::<{ 0..=50 }>::new(40).expect("guess 5 failed to construct.");
+		let mut output = Vec::new();
+		let guess = Guess::<{ 0..=50 }>::new(37).expect("guess 5 failed to construct.");
 		let correct = Guess::<{ 0..=50 }>::new(20).expect("guess 6 failed to construct.");
-		assert_eq!(respond(guess, correct, &mut stdout()), ControlFlow::Continue(()));
+		assert_eq!(respond(guess, correct, &mut output).expect("respond failed."), ControlFlow::Continue(Feedback::TooHigh));
+		assert!(output.windows(2).any(|bytes| bytes == b"37"));
+		assert!(!String::from_utf8_lossy(&output).contains("{guess}"));
+		assert!(output.ends_with(b"\n"));
+	}
+
+	#[test]
+	fn feedback()
+	{
+		assert_eq!(Feedback::from(Ordering::Greater), Feedback::TooHigh);
+		assert_eq!(Feedback::from(Ordering::Less), Feedback::TooLow);
+		assert_eq!(Feedback::from(Ordering::Equal), Feedback::Correct);
+		assert!(Feedback::Correct.is_correct());
+		assert!(!Feedback::TooHigh.is_correct());
+		assert!(!Feedback::TooLow.is_correct());
+
+		let correct = Guess::<{ 0..=50 }>::new(25).expect("correct failed to construct.");
+		for (value, feedback) in [(26, Feedback::TooHigh), (50, Feedback::TooHigh), (24, Feedback::TooLow), (0, Feedback::TooLow), (25, Feedback::Correct)]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			assert_eq!(respond(guess, correct, &mut Vec::new()).expect("respond failed."), feedback.into_control_flow(guess));
+			assert_eq!(respond_with_style(guess, correct, Style::Plain, &mut Vec::new()).expect("respond failed."), feedback.into_control_flow(guess));
+		}
+		assert_eq!(Feedback::Correct.into_control_flow(correct), ControlFlow::Break(Win { guess: correct }));
+		assert_eq!(Feedback::TooLow.into_control_flow(correct), ControlFlow::Continue(Feedback::TooLow));
+	}
+
+	/// Writer for output that nobody reads anymore, such as a closed pipe.
+	struct Closed;
+
+	impl Write for Closed
+	{
+		fn write(
+			&mut self,
+			_buf: &[u8],
+		) -> io::Result<usize>
+		{
+			Err(io::Error::from(ErrorKind::BrokenPipe))
+		}
+
+		fn flush(&mut self) -> io::Result<()>
+		{
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn write_errors()
+	{
+		let guess = Guess::<{ 0..=50 }>::new(20).expect("guess failed to construct.");
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		let error = respond(guess, correct, &mut Closed).expect_err("respond wrote to a closed writer.");
+		assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+		let error = respond_with_style(correct, correct, Style::Plain, &mut Closed).expect_err("respond wrote to a closed writer.");
+		assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+
+		// Prompting works, but explaining why a guess is invalid does not.
+		let mut output = WithErrors { output: Vec::new(), errors: Closed };
+		let result: Result<Guess<{ 0..=50 }>, _> = input("dummy prompt: ".clear(), &mut "abc\n5\n".as_bytes(), &mut output);
+		assert!(matches!(result, Err(InputError::Io(error)) if error.kind() == ErrorKind::BrokenPipe));
+
+		let used = HashSet::from([guess]);
+		let result = input_unique(&used, "dummy prompt: ".clear(), &mut "20\n5\n".as_bytes(), &mut output);
+		assert!(matches!(result, Err(InputError::Io(error)) if error.kind() == ErrorKind::BrokenPipe));
+
+		// A valid guess needs no explanation.
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut "5\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 5_i32);
+	}
+
+	/// Each response ends its line, so the next one starts on a line of its own.
+	#[test]
+	fn respond_lines()
+	{
+		let mut output = Vec::new();
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		for value in [10, 45, 40]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			let _ = respond_with_style(guess, correct, Style::Plain, &mut output).expect("respond failed.");
+		}
+		assert_eq!(String::from_utf8_lossy(&output), "\n10 is too low! (cold)\n\n45 is too high! (hot)\n\nYou win! :)\n");
+	}
+
+	/// Plain output must be readable in a file, so it can not contain escape
+	/// sequences or emoji.
+	#[test]
+	fn plain_style()
+	{
+		let options = InputOptions { style: Style::Plain, ..InputOptions::new("Guess 🎲: ".yellow()) };
+		let mut output = Vec::new();
+		let long = "x".repeat(MAX_ECHO_CHARS * 2);
+		let lines = format!("abc\n{long}\n99\n7\n");
+		let guess: Guess<{ 0..=50 }> = input_with_options(&options, &mut lines.as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 7_i32);
+		let correct = Guess::new(7).expect("correct failed to construct.");
+		for value in [40, 3, 7]
+		{
+			let guess = Guess::<{ 0..=50 }>::new(value).expect("guess failed to construct.");
+			assert_eq!(respond_with_style(guess, correct, Style::Plain, &mut output).expect("respond failed.").is_break(), guess == correct);
+		}
+		assert!(output.is_ascii(), "{:?} is not ASCII.", String::from_utf8_lossy(&output));
+		assert!(!output.windows(2).any(|bytes| bytes == b"\x1b["));
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("Invalid guess. [!]"));
+		assert!(output.contains("Guess : "));
+		assert!(output.contains("..."));
+		assert!(output.contains("too high! (hot)"));
+		assert!(output.contains("too low! (cold)"));
+		assert!(output.contains("You win! :)"));
 	}
 
 	#[test]
@@ -293,11 +3415,438 @@ mod tests
 	{
 		let correct = Guess::<{ 0..=50 }>::new(50).expect("correct failed to construct");
 		let input1 = "50";
-		let guess1: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut input1.as_bytes(), &mut stdout());
+		let mut output = Vec::new();
+		let guess1: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut input1.as_bytes(), &mut output).expect("input failed.");
 		assert_eq!(guess1, correct);
+		assert!(String::from_utf8_lossy(&output).contains("dummy prompt: "));
 
 		let input2 = "40";
-		let guess2: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut input2.as_bytes(), &mut stdout());
+		let guess2: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut input2.as_bytes(), &mut stdout()).expect("input failed.");
 		assert_ne!(guess2, correct);
+
+		let input3 = "1,024";
+		let guess3: Guess<{ 0..=100000 }> = input("dummy prompt: ".clear(), &mut input3.as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess3.value(), 1024_i32);
+	}
+
+	#[test]
+	fn input_lines()
+	{
+		// Invalid lines are skipped within one call.
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut "abc\n50\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 50_i32);
+		// The prompt is repeated after the error, all on the same writer.
+		let output = String::from_utf8_lossy(&output);
+		assert_eq!(output.matches("dummy prompt: ").count(), 2);
+		assert!(output.contains("Invalid guess."));
+
+		// Each call consumes exactly one valid line.
+		let mut lines = "10\n20\nabc\n\n30\n".as_bytes();
+		let guesses: Vec<i32> = (0..3)
+			.map(|_| input::<{ 0..=50 }>("dummy prompt: ".clear(), &mut lines, &mut stdout()).expect("input failed.").value())
+			.collect();
+		assert_eq!(guesses, [10_i32, 20_i32, 30_i32]);
+		assert!(lines.is_empty());
+
+		// Windows line endings are trimmed like any other whitespace.
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut "42\r\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+
+		// A line that is not UTF-8 is just another invalid guess.
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut &b"4\xff2\n42\n"[..], &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+	}
+
+	#[test]
+	fn input_unique_test()
+	{
+		let mut used = HashSet::new();
+		used.insert(Guess::<{ 0..=50 }>::new(42).expect("guess 1 failed to construct."));
+		used.insert(Guess::<{ 0..=50 }>::new(7).expect("guess 2 failed to construct."));
+
+		let mut output = Vec::new();
+		let guess = input_unique(&used, "dummy prompt: ".clear(), &mut "42\n0x2a\n7\n43\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 43_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert_eq!(output.matches("You already guessed 42.").count(), 2);
+		assert!(output.contains("You already guessed 7."));
+		assert!(!output.contains("Invalid guess."));
+
+		// Repeated guesses count as invalid tries.
+		let mut output = Vec::new();
+		let prompt = |context: InputContext| format!("{} invalid: ", context.invalid_tries).normal();
+		let guess = input_unique(&used, prompt, &mut "7\n8\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 8_i32);
+		assert!(String::from_utf8_lossy(&output).contains("1 invalid: "));
+
+		// Nothing is repeated when nothing was used.
+		let guess = input_unique::<{ 0..=50 }>(&HashSet::new(), "dummy prompt: ".clear(), &mut "42\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+	}
+
+	#[test]
+	fn input_with_errors()
+	{
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut "abc\n51\n42\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+
+		let prompts = String::from_utf8(output.output).expect("output was not UTF-8.");
+		let errors = String::from_utf8(output.errors).expect("errors were not UTF-8.");
+		assert_eq!(prompts, "dummy prompt: ".repeat(3));
+		assert!(!prompts.contains("Invalid guess."));
+		assert!(!errors.contains("dummy prompt: "));
+		assert_eq!(errors.matches("Invalid guess.").count(), 2);
+		assert!(errors.contains("\"abc\" is not an integer from 0 through 50."));
+		assert!(errors.contains("51 is above the maximum."));
+	}
+
+	#[test]
+	fn input_counted_test()
+	{
+		let mut lines = "abc\n\n51\n0x2\n4o2\n99999999999999\n\n-1\n7\n".as_bytes();
+		let result: InputResult<Guess<{ 0..=50 }>> = input_counted("dummy prompt: ".clear(), &mut lines, &mut stdout()).expect("input failed.");
+		assert_eq!(result.guess.value(), 2_i32);
+		assert_eq!(result.invalid_tries, 2);
+
+		let result: InputResult<Guess<{ 0..=50 }>> = input_counted("dummy prompt: ".clear(), &mut lines, &mut stdout()).expect("input failed.");
+		assert_eq!(result.guess.value(), 7_i32);
+		assert_eq!(result.invalid_tries, 3);
+		assert!(lines.is_empty());
+	}
+
+	#[test]
+	fn input_with_default_test()
+	{
+		let default = Guess::<{ 0..=1024 }>::new(512).expect("guess 1 failed to construct.");
+
+		let mut output = Vec::new();
+		let guess = input_with_default(default, "Guess: ".clear(), &mut "\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess, default);
+		assert_eq!(String::from_utf8_lossy(&output), "Guess [512]: ");
+
+		let guess = input_with_default(default, "Guess: ".clear(), &mut " \t\r\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess, default);
+
+		// Anything else is parsed as usual.
+		let mut output = Vec::new();
+		let guess = input_with_default(default, "Pick one".clear(), &mut "abc\n7\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 7_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert_eq!(output.matches("Pick one [512]: ").count(), 2);
+		assert!(output.contains("Invalid guess."));
+
+		// The end of the input is not a blank line.
+		let result = input_with_default(default, "Guess: ".clear(), &mut "".as_bytes(), &mut stdout());
+		assert!(matches!(result, Err(InputError::Eof)));
+	}
+
+	#[test]
+	fn prompt_with_default_test()
+	{
+		assert_eq!(&*prompt_with_default(&"Guess: ".normal(), 512), "Guess [512]: ");
+		assert_eq!(&*prompt_with_default(&"Guess:".normal(), -3), "Guess [-3]: ");
+		assert_eq!(&*prompt_with_default(&"Your guess?".normal(), 1), "Your guess? [1]: ");
+
+		// Colors and styles are kept.
+		let prompt = prompt_with_default(&"Guess: ".yellow().on_blue().bold(), 512);
+		assert_eq!(prompt.fgcolor(), Some(Color::Yellow));
+		assert_eq!(prompt.bgcolor(), Some(Color::Blue));
+		assert!(prompt.style().contains(Styles::Bold));
+		assert!(!prompt.style().contains(Styles::Italic));
+	}
+
+	#[test]
+	fn input_blank_lines()
+	{
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut "\n\n42\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert_eq!(output, "dummy prompt: ".repeat(3));
+		assert!(!output.contains("Invalid guess."));
+
+		// Whitespace is blank too, and blank lines are not counted as invalid.
+		let mut lines = " \r\n\t\nabc\n \n50\n".as_bytes();
+		let guess: Guess<{ 0..=50 }> = input_with_retries("dummy prompt: ".clear(), 1, &mut lines, &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), 50_i32);
+	}
+
+	#[test]
+	fn input_eof()
+	{
+		let result = input::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "".as_bytes(), &mut stdout());
+		assert!(matches!(result, Err(InputError::Eof)));
+
+		// Invalid guesses are skipped until the input runs out.
+		let result = input::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "abc\n51\n".as_bytes(), &mut stdout());
+		assert!(matches!(result, Err(InputError::Eof)));
+
+		// A final line without a line ending is still read.
+		let result = input::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "abc\n50".as_bytes(), &mut stdout());
+		assert_eq!(result.map(i32::from).ok(), Some(50_i32));
+	}
+
+	/// Reader that fails once its data has been read.
+	struct FailAfter(&'static [u8]);
+
+	impl Read for FailAfter
+	{
+		fn read(
+			&mut self,
+			buf: &mut [u8],
+		) -> io::Result<usize>
+		{
+			if self.0.is_empty()
+			{
+				Err(io::Error::new(ErrorKind::BrokenPipe, "reader failed"))
+			}
+			else
+			{
+				let len = self.0.len().min(buf.len());
+				buf[..len].copy_from_slice(&self.0[..len]);
+				self.0 = &self.0[len..];
+				Ok(len)
+			}
+		}
+	}
+
+	#[test]
+	fn input_io_error()
+	{
+		let mut reader = BufReader::new(FailAfter(b"abc\n"));
+		let result = input::<{ 0..=50 }>("dummy prompt: ".clear(), &mut reader, &mut stdout());
+		match result
+		{
+			Err(InputError::Io(error)) => assert_eq!(error.kind(), ErrorKind::BrokenPipe),
+			_ => panic!("expected an I/O error, got {result:?}"),
+		}
+	}
+
+	#[test]
+	fn parse_relative()
+	{
+		let previous = Guess::<{ 0..=1024 }>::new(500).expect("guess 1 failed to construct.");
+		assert_eq!(Guess::parse_relative("+10", Some(previous)).map(i32::from), Ok(510_i32));
+		assert_eq!(Guess::parse_relative(" -3\n", Some(previous)).map(i32::from), Ok(497_i32));
+		assert_eq!(Guess::parse_relative("+1,000", Some(previous)), Err(ParseGuessError::OutOfRange(GuessError::AboveRange { value: 1500, max: 1024 })));
+		assert_eq!(Guess::parse_relative("-0x1f5", Some(previous)), Err(ParseGuessError::OutOfRange(GuessError::BelowRange { value: -1, min: 0 })));
+		assert_eq!(Guess::parse_relative("+9999999999", Some(previous)), Err(ParseGuessError::Overflow));
+		assert_eq!(Guess::parse_relative("+99999999999999999999", Some(previous)), Err(ParseGuessError::Overflow));
+		assert_eq!(Guess::parse_relative("-9999999999", Some(previous)), Err(ParseGuessError::Underflow));
+		assert_eq!(Guess::parse_relative("-99999999999999999999", Some(previous)), Err(ParseGuessError::Underflow));
+		assert_eq!(Guess::parse_relative("+", Some(previous)), Err(ParseGuessError::NotANumber));
+		assert_eq!(Guess::parse_relative("+-1", Some(previous)), Err(ParseGuessError::NotANumber));
+
+		// Text without a sign is absolute, with or without a previous guess.
+		assert_eq!(Guess::parse_relative("20", Some(previous)).map(i32::from), Ok(20_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_relative("20", None).map(i32::from), Ok(20_i32));
+		assert_eq!(Guess::<{ 0..=1024 }>::parse_relative("+20", None), Err(ParseGuessError::NoPreviousGuess));
+
+		// The edges of i32 are reachable without overflowing along the way.
+		let previous = Guess::<{ i32::MIN..=i32::MAX }>::new(i32::MIN).expect("guess 2 failed to construct.");
+		assert_eq!(Guess::parse_relative("+4294967295", Some(previous)).map(i32::from), Ok(i32::MAX));
+		assert_eq!(Guess::parse_relative("-1", Some(previous)), Err(ParseGuessError::Underflow));
+	}
+
+	#[test]
+	fn input_relative_test()
+	{
+		// The first guess must be absolute.
+		let mut output = Vec::new();
+		let mut lines = "+5\n100\n+5\n-10\n7\n+2000\n+0\n".as_bytes();
+		let first: Guess<{ 0..=1024 }> = input_relative(None, "dummy prompt: ".clear(), &mut lines, &mut output).expect("input failed.");
+		assert_eq!(first.value(), 100_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("There is no previous guess"));
+
+		// Absolute and relative guesses can be mixed.
+		let mut guesses = vec![first];
+		for _ in 0..4
+		{
+			let previous = guesses.last().copied();
+			guesses.push(input_relative(previous, "dummy prompt: ".clear(), &mut lines, &mut stdout()).expect("input failed."));
+		}
+		let values: Vec<i32> = guesses.into_iter().map(i32::from).collect();
+		assert_eq!(values, [100_i32, 105_i32, 95_i32, 7_i32, 7_i32]);
+		assert!(lines.is_empty());
+	}
+
+	#[test]
+	fn input_prompt_callback()
+	{
+		let mut output = Vec::new();
+		let prompt = |context: InputContext| format!("{} invalid so far: ", context.invalid_tries).normal();
+		let guess: Guess<{ 0..=50 }> = input(prompt, &mut "abc\n51\n50\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 50_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		let prompts: Vec<&str> = output.matches(" invalid so far: ").collect();
+		assert_eq!(prompts.len(), 3);
+		assert!(output.starts_with("0 invalid so far: "));
+		assert!(output.contains("1 invalid so far: "));
+		assert!(output.ends_with("2 invalid so far: "));
+
+		// The count starts over with every call, and the closure can capture state.
+		let mut calls = 0;
+		let mut lines = "x\n1\n2\n".as_bytes();
+		for expected in [1_i32, 2_i32]
+		{
+			let prompt = |context: InputContext| {
+				calls += 1;
+				format!("attempt {expected}, {} invalid: ", context.invalid_tries).normal()
+			};
+			let guess: Guess<{ 0..=50 }> = input(prompt, &mut lines, &mut stdout()).expect("input failed.");
+			assert_eq!(guess.value(), expected);
+		}
+		assert_eq!(calls, 3);
+	}
+
+	#[test]
+	fn input_or_command_test()
+	{
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "quit\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Quit);
+
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "Q\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Quit);
+
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut " ExIt \n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Quit);
+
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "Give Up\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::GiveUp);
+
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "HINT\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Hint);
+
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "Pause\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Pause);
+
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut " abort\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Abort);
+
+		// Numbers are still guesses, and other words are still invalid.
+		let action = input_or_command::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "quitt\n42\n".as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(action, PlayerAction::Guess(Guess::new(42).expect("guess 1 failed to construct.")));
+
+		// Plain input does not treat commands specially.
+		let result = input::<{ 0..=50 }>("dummy prompt: ".clear(), &mut "quit\n".as_bytes(), &mut stdout());
+		assert!(matches!(result, Err(InputError::Eof)));
+	}
+
+	#[test]
+	fn input_or_command_ascii()
+	{
+		let options = InputOptions { style: Style::Ascii, ..InputOptions::default() };
+		let mut output = Vec::new();
+		let action = input_or_command_with_options::<{ 0..=50 }>("Guess 🎲: ".yellow(), &options, &mut "abc
+99
+give up
+".as_bytes(), &mut output)
+			.expect("input failed.");
+		assert_eq!(action, PlayerAction::GiveUp);
+		assert!(output.iter().all(|&byte| byte <= 0x7f), "{:?} is not ASCII.", String::from_utf8_lossy(&output));
+		assert!(String::from_utf8_lossy(&output).contains("Invalid guess. [!]"));
+	}
+
+	#[test]
+	fn ask_yes_no_test()
+	{
+		let cases = [("y\n", true), (" YES \n", true), ("n\n", false), ("No\n", false), ("Quit\n", false), ("", false), ("maybe\n\nyes\n", true)];
+		for (lines, expected) in cases
+		{
+			let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+			assert_eq!(ask_yes_no(&"Again? ".clear(), Style::Plain, &mut lines.as_bytes(), &mut output).expect("input failed."), expected, "{lines:?}");
+			let retries = lines.lines().count().saturating_sub(1);
+			assert_eq!(String::from_utf8(output.output).expect("output was not UTF-8."), "Again? ".repeat(retries + 1), "{lines:?}");
+			assert_eq!(String::from_utf8(output.errors).expect("output was not UTF-8."), "Please answer y or n.\n".repeat(retries), "{lines:?}");
+		}
+	}
+
+	#[test]
+	fn input_with_retries_test()
+	{
+		// Gives up on the line after the last allowed invalid guess, leaving the
+		// valid guess unread.
+		let mut lines = "abc\n51\n99999999999999\n50\n".as_bytes();
+		let result = input_with_retries::<{ 0..=50 }>("dummy prompt: ".clear(), 2, &mut lines, &mut stdout());
+		assert!(matches!(result, Err(InputError::TooManyInvalid(ParseGuessError::Overflow))));
+		assert_eq!(lines, b"50\n");
+
+		// Exactly max_invalid invalid guesses are still allowed.
+		let mut lines = "abc\n51\n50\n".as_bytes();
+		let result = input_with_retries::<{ 0..=50 }>("dummy prompt: ".clear(), 2, &mut lines, &mut stdout());
+		assert_eq!(result.map(i32::from).ok(), Some(50_i32));
+
+		// The error describes the last failure.
+		let result = input_with_retries::<{ 0..=50 }>("dummy prompt: ".clear(), 0, &mut "51\n".as_bytes(), &mut stdout());
+		match result
+		{
+			Err(InputError::TooManyInvalid(error)) =>
+			{
+				assert_eq!(error, ParseGuessError::OutOfRange(GuessError::AboveRange { value: 51, max: 50 }))
+			},
+			_ => panic!("expected too many invalid guesses, got {result:?}"),
+		}
+	}
+
+	#[test]
+	fn read_line_limited_test()
+	{
+		let mut line = Vec::new();
+		let mut lines = "12\nrest".as_bytes();
+		assert_eq!(read_line_limited(&mut lines, &mut line).expect("read failed."), 3);
+		assert_eq!(line, b"12\n");
+		assert_eq!(lines, b"rest");
+
+		// Only the start of a long line is kept, but all of it is consumed.
+		let long = "1".repeat(10 * 1024 * 1024) + "\n5\n";
+		let mut lines = BufReader::new(long.as_bytes());
+		let mut line = Vec::new();
+		assert_eq!(read_line_limited(&mut lines, &mut line).expect("read failed."), 10 * 1024 * 1024 + 1);
+		assert_eq!(line.len(), MAX_LINE_BYTES);
+		assert!(line.capacity() < 2 * MAX_LINE_BYTES);
+
+		let mut line = Vec::new();
+		assert_eq!(read_line_limited(&mut lines, &mut line).expect("read failed."), 2);
+		assert_eq!(line, b"5\n");
+	}
+
+	#[test]
+	fn input_long_line()
+	{
+		// A line of 10 MB of digits is rejected, and the next line is still read.
+		let long = "1".repeat(10 * 1024 * 1024) + "\n5\n";
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=10 }> = input("dummy prompt: ".clear(), &mut BufReader::new(long.as_bytes()), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 5_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("is not an integer from 0 through 10."));
+		assert!(output.len() < 1024);
+
+		// A line of exactly MAX_LINE_BYTES is still read.
+		let padded = format!("{:>1$}\n", 5, MAX_LINE_BYTES - 1);
+		let guess: Guess<{ 0..=10 }> = input("dummy prompt: ".clear(), &mut padded.as_bytes(), &mut stdout()).expect("input failed.");
+		assert_eq!(guess.value(), 5_i32);
+	}
+
+	#[test]
+	fn input_overflow()
+	{
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=10 }> = input("dummy prompt: ".clear(), &mut "99999999999999\n5\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 5_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("too large to even consider"));
+		assert!(!output.contains("is not an integer"));
+
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=10 }> = input("dummy prompt: ".clear(), &mut "-99999999999999\nfyve\n5\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 5_i32);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("That number is too small to even consider."));
+		assert!(!output.contains("too large"));
+		assert!(output.contains("\"fyve\" is not an integer from 0 through 10."));
 	}
 }
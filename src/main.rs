@@ -1,56 +1,109 @@
 use {
-	colored::Colorize,
 	guessing_game::{
-		input,
-		respond,
-		Guess,
-	},
-	rand::{
-		thread_rng,
-		Rng,
+		set_color_choice,
+		terminal_input,
+		Command,
+		ExitStatus,
+		GameArgs,
+		Runner,
+		Style,
+		WithErrors,
 	},
 	std::{
+		env,
 		io::{
-			stdin,
+			stderr,
 			stdout,
+			IsTerminal,
 		},
-		ops::RangeInclusive,
+		process::ExitCode,
 	},
 };
+#[cfg(feature = "ctrlc")]
+use std::sync::{
+	atomic::Ordering,
+	Arc,
+};
 
-// Range of valid guesses and the correct answer.
-const GUESS_RANGE: RangeInclusive<i32> = 0_i32..=1024_i32;
+/// Runs the command in the arguments, exiting with the ExitStatus of how it
+/// went, so that scripts can tell a win from a loss.
+fn main() -> ExitCode
+{
+	let args: Vec<String> = env::args().skip(1).collect();
+	let mut plain = WithErrors { output: stdout(), errors: stderr() };
 
-// How many times does the user get to guess?
-const ATTEMPTS_ALLOWED: i32 = 10_i32;
+	// `--help` lists the commands and options.
+	if args.iter().any(|arg| arg == "--help" || arg == "-h")
+	{
+		return Runner::new(Style::Plain).help(&mut plain).into();
+	}
 
-fn main()
-{
-	// Greeting/header.
-	println!(
-		"{}",
-		format!("\n\nI'm thinking of a number somwhere from {} through {}. Guess it! 😈", GUESS_RANGE.start(), GUESS_RANGE.end()).green()
-	);
+	// The first argument picks what to do, and without a command rounds are
+	// played.
+	let (command, args) = match Command::parse(&args)
+	{
+		Ok(parsed) => parsed,
+		Err(error) =>
+		{
+			eprintln!("{error}. See --help for more.");
+			return ExitStatus::Usage.into();
+		},
+	};
+
+	// `--min 1 --max 50 --attempts 5` or `--range 1..=50 --attempts 5` plays in a
+	// range of the player's own, and `--seed 42` picks the same secrets every
+	// time, for tests and demos.
+	let game_args = match GameArgs::parse(args)
+	{
+		Ok(game_args) => game_args,
+		Err(error) =>
+		{
+			eprintln!("{error}.");
+			return ExitStatus::Usage.into();
+		},
+	};
+
+	// `--color auto` colors only when both stdout and stderr are terminals, since
+	// the choice applies to both, and `--no-color` never does. This is settled
+	// once here and handed to every game as its style.
+	let terminal = stdout().is_terminal() && stderr().is_terminal();
+	set_color_choice(game_args.color.resolve(terminal));
 
-	// Generate random Guess.
-	let correct = Guess::new(thread_rng().gen_range(GUESS_RANGE)).expect("Error generating random correct value.");
+	// Emoji only when the terminal can show them, unless `--ascii` asks for none,
+	// and neither colors nor emoji when the output is redirected.
+	let emoji = !args.iter().any(|arg| arg == "--ascii") && Style::detect() == Style::Fancy;
+	let runner = Runner::new(game_args.color.style(terminal, emoji));
 
-	// For each attempt.
-	for i in (1..=ATTEMPTS_ALLOWED).rev()
+	let status = match command
 	{
-		// Respond to prompted input
-		if respond(
-			input::<GUESS_RANGE>(format!("You have {i} attempts remaining. Guess: ").yellow(), &mut stdin(), &mut stdout()),
-			correct,
-			&mut stdout(),
-		)
-		.is_break()
+		// `--stats` shows them too, as it did before there was a command for it.
+		Command::Stats => runner.stats(args, &mut plain),
+		Command::Play if args.iter().any(|arg| arg == "--stats") => runner.stats(args, &mut plain),
+		Command::Simulate => runner.simulate(args, game_args, &mut plain),
+		Command::Replay => runner.replay(args, &mut stdout(), &mut plain),
+		Command::Play | Command::Daily | Command::Coop | Command::Golf =>
 		{
-			// Win condition: Correct guess should be end of program.
-			return
-		}
-	}
+			// Ctrl-C aborts the game once the line is in, showing how far it got
+			// rather than ending the program on the spot. With line editing Ctrl-C
+			// ends the input instead, which aborts the game too.
+			#[cfg(feature = "ctrlc")]
+			{
+				let flag = Arc::clone(&runner.interrupt);
+				let caught = ctrlc::set_handler(move || {
+					flag.store(true, Ordering::Relaxed);
+					eprintln!("\nPress Enter to abort the game.");
+				});
+				if let Err(error) = caught
+				{
+					eprintln!("Warning: Ctrl-C can not abort the game, since {error}.");
+				}
+			}
 
-	// Lose Condition: No attempts remaining.
-	println!("{}", "\nYou're out of guesses! Game over. 😢\n\n".red());
+			// Line editing when playing in a terminal. Invalid guesses are reported
+			// on stderr, so they stay visible when stdout is redirected.
+			let (mut lines, mut output) = terminal_input(plain);
+			runner.play(command, args, game_args, &mut lines, &mut output)
+		},
+	};
+	status.into()
 }
@@ -0,0 +1,517 @@
+use {
+	crate::{
+		input_or_command_within,
+		AlreadyFinished,
+		Guess,
+		GuessRange,
+		InputError,
+		InputOptions,
+		InputOutput,
+		PlayerAction,
+		Secret,
+		Style,
+	},
+	colored::{
+		ColoredString,
+		Colorize,
+	},
+	rand::Rng,
+	std::{
+		cmp::Ordering,
+		io::{
+			self,
+			BufRead,
+			ErrorKind,
+			Write,
+		},
+		ops::RangeInclusive,
+		result::Result,
+	},
+};
+
+/// A game of finding several distinct secret numbers in RANGE with one shared
+/// budget of attempts.
+///
+/// Every guess is checked against all the secrets that are still hidden. A hit
+/// finds that secret, and a miss tells whether the guess is too high or too low
+/// for the closest one that remains. The game is won once every secret is
+/// found.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		MultiGame,
+/// // 		MultiOutcome,
+/// // 	},
+/// // 	rand::thread_rng,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let mut game = MultiGame::<{ 0..=100 }>::new(3, 20, &mut thread_rng());
+/// // if let MultiOutcome::Won { attempts_used } = game.play(&mut stdin().lock(), &mut stdout())
+/// // {
+/// // 	println!("Found them all in {attempts_used}.");
+/// // }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct MultiGame<const RANGE: RangeInclusive<i32>>
+{
+	/// The secrets that were not found yet, from lowest to highest.
+	hidden: Vec<Secret<Guess<RANGE>>>,
+	found: Vec<Guess<RANGE>>,
+	attempts: u32,
+	attempts_used: u32,
+	gave_up: bool,
+	/// How the game's messages look.
+	pub style: Style,
+}
+
+/// How a MultiGame that was played ended.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum MultiOutcome<const RANGE: RangeInclusive<i32>>
+{
+	/// The player found every secret.
+	Won
+	{
+		/// How many valid guesses it took, including the last hit.
+		attempts_used: u32,
+	},
+	/// The player ran out of attempts or gave up.
+	Lost
+	{
+		/// The secrets the player did not find, from lowest to highest.
+		answers: Vec<Guess<RANGE>>,
+	},
+	/// The game stopped early, because the player quit or because the input or
+	/// output stopped working.
+	Aborted,
+}
+
+/// What happened to a guess that was submitted to a MultiGame.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum MultiTurn<const RANGE: RangeInclusive<i32>>
+{
+	/// The guess is one of the secrets, and others are still hidden.
+	Hit
+	{
+		/// How many secrets are still hidden.
+		remaining: usize,
+		/// How many guesses the player has left.
+		attempts_left: u32,
+	},
+	/// The guess is greater than the closest secret that is still hidden.
+	TooHigh
+	{
+		/// How many guesses the player has left.
+		attempts_left: u32,
+	},
+	/// The guess is less than the closest secret that is still hidden.
+	TooLow
+	{
+		/// How many guesses the player has left.
+		attempts_left: u32,
+	},
+	/// The guess found the last secret, so the game is won.
+	Win
+	{
+		/// How many guesses it took, including this one.
+		attempts_used: u32,
+	},
+	/// The guess was the last one and secrets are still hidden, so the game is
+	/// lost.
+	Loss
+	{
+		/// The secrets the player did not find, from lowest to highest.
+		answers: Vec<Guess<RANGE>>,
+	},
+}
+
+impl<const RANGE: RangeInclusive<i32>> MultiGame<RANGE>
+{
+	/// Creates a game with count distinct secrets picked by rng, where the
+	/// player gets attempts valid guesses to find them all.
+	///
+	/// If RANGE has fewer than count values, every value in it is a secret.
+	pub fn new(
+		count: usize,
+		attempts: u32,
+		rng: &mut impl Rng,
+	) -> Self
+	{
+		let count = usize::try_from(Guess::<RANGE>::span()).map_or(count, |span| count.min(span));
+		let mut secrets = Vec::with_capacity(count);
+		while secrets.len() < count
+		{
+			let secret: Guess<RANGE> = rng.gen();
+			if !secrets.contains(&secret)
+			{
+				secrets.push(secret);
+			}
+		}
+		MultiGame::with_secrets(secrets, attempts)
+	}
+
+	/// Creates a game whose secrets are secrets, where the player gets attempts
+	/// valid guesses to find them all. A secret that is given more than once
+	/// only has to be found once.
+	pub fn with_secrets(
+		secrets: impl IntoIterator<Item = Guess<RANGE>>,
+		attempts: u32,
+	) -> Self
+	{
+		let mut secrets: Vec<_> = secrets.into_iter().collect();
+		secrets.sort_unstable();
+		secrets.dedup();
+		MultiGame { hidden: secrets.into_iter().map(Secret::new).collect(), found: Vec::new(), attempts, attempts_used: 0, gave_up: false, style: Style::Fancy }
+	}
+
+	/// Returns how many secrets there are, found or not.
+	pub fn count(&self) -> usize
+	{
+		self.hidden.len() + self.found.len()
+	}
+
+	/// Returns how many secrets are still hidden.
+	pub fn remaining(&self) -> usize
+	{
+		self.hidden.len()
+	}
+
+	/// Returns the secrets the player found so far, in the order they were
+	/// found.
+	pub fn found(&self) -> &[Guess<RANGE>]
+	{
+		&self.found
+	}
+
+	/// Returns how many valid guesses the player gets.
+	pub const fn attempts(&self) -> u32
+	{
+		self.attempts
+	}
+
+	/// Returns how many guesses the player has made so far.
+	pub const fn attempts_used(&self) -> u32
+	{
+		self.attempts_used
+	}
+
+	/// Returns how many guesses the player has left.
+	pub const fn attempts_left(&self) -> u32
+	{
+		self.attempts.saturating_sub(self.attempts_used)
+	}
+
+	/// Returns whether the game is won or lost, so that it takes no more
+	/// guesses.
+	pub fn is_finished(&self) -> bool
+	{
+		self.outcome().is_some()
+	}
+
+	/// Takes the player's next guess and returns what happened to it.
+	///
+	/// A miss is compared with the closest secret that is still hidden. When the
+	/// guess lies exactly halfway between two of them, the lower one counts as
+	/// the closest.
+	///
+	/// # Errors
+	/// Returns AlreadyFinished if the game is already won or lost. The guess is
+	/// not counted then.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Guess,
+	/// // 	MultiGame,
+	/// // 	MultiTurn,
+	/// //};
+	/// // let secrets = [10, 50, 90].map(|value| Guess::<{ 0..=100 }>::new(value).expect(""));
+	/// // let mut game = MultiGame::with_secrets(secrets, 10);
+	/// // assert_eq!(game.submit(secrets[1]), Ok(MultiTurn::Hit { remaining: 2, attempts_left: 9 }));
+	/// // assert_eq!(game.submit(Guess::new(85).expect("")), Ok(MultiTurn::TooLow { attempts_left: 8 }));
+	/// ```
+	pub fn submit(
+		&mut self,
+		guess: Guess<RANGE>,
+	) -> Result<MultiTurn<RANGE>, AlreadyFinished>
+	{
+		if self.is_finished()
+		{
+			return Err(AlreadyFinished);
+		}
+		self.attempts_used += 1;
+		let attempts_left = self.attempts_left();
+		if let Some(index) = self.hidden.iter().position(|secret| secret.is(guess))
+		{
+			self.hidden.remove(index);
+			self.found.push(guess);
+		}
+		else if attempts_left > 0
+		{
+			// There is a closest secret, since the game was not won yet.
+			let closest = self.hidden.iter().min_by_key(|secret| guess.distance(secret.reveal())).expect("a secret is still hidden.");
+			return Ok(match closest.compare(guess)
+			{
+				Ordering::Greater => MultiTurn::TooHigh { attempts_left },
+				_ => MultiTurn::TooLow { attempts_left },
+			});
+		}
+		Ok(match self.hidden.len()
+		{
+			0 => MultiTurn::Win { attempts_used: self.attempts_used },
+			_ if attempts_left == 0 => MultiTurn::Loss { answers: self.answers() },
+			remaining => MultiTurn::Hit { remaining, attempts_left },
+		})
+	}
+
+	/// Plays the game, reading guesses and commands from input and writing
+	/// everything to output, until it is won, lost or aborted.
+	///
+	/// This works like Game::play, except that there are no hints to ask for.
+	pub fn play(
+		&mut self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> MultiOutcome<RANGE>
+	{
+		if let Some(outcome) = self.outcome()
+		{
+			return outcome;
+		}
+		match self.run(input, output)
+		{
+			Ok(outcome) => outcome,
+			// Nobody is reading the output anymore, as with `guessing_game | head`.
+			Err(error) if error.kind() == ErrorKind::BrokenPipe => MultiOutcome::Aborted,
+			Err(error) =>
+			{
+				// The output might be what failed, so the explanation can fail too.
+				let _ = self.say(output.errors(), format!("\n\nGame aborted because {error}. 👋\n\n").yellow());
+				MultiOutcome::Aborted
+			},
+		}
+	}
+
+	/// Plays the game, returning an error if reading or writing fails.
+	fn run(
+		&mut self,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> io::Result<MultiOutcome<RANGE>>
+	{
+		let range = GuessRange::<RANGE>::full();
+		let count = self.count();
+		self.say(output.messages(), format!("\n\nI'm thinking of {count} numbers from {} through {}. Find them all! 😈", range.lo(), range.hi()).green())?;
+		self.say(output.messages(), "Type \"give up\" to see the answers or \"quit\" to leave.".yellow())?;
+		let options = InputOptions { style: self.style, ..InputOptions::default() };
+		loop
+		{
+			let prompt = format!("You have {} attempts remaining and {} numbers to find. Guess: ", self.attempts_left(), self.remaining()).yellow();
+			let guess = match input_or_command_within(prompt, &options, range, input, output)
+			{
+				Ok(PlayerAction::Guess(guess)) => guess,
+				Ok(PlayerAction::Quit | PlayerAction::Abort) =>
+				{
+					self.say(output.messages(), "\n\nThanks for playing! 👋\n\n".yellow())?;
+					return Ok(MultiOutcome::Aborted);
+				},
+				Ok(PlayerAction::GiveUp) =>
+				{
+					self.gave_up = true;
+					self.say(output.messages(), format!("\n\nThe numbers you missed were {}. Better luck next time! 🙃\n\n", list(&self.answers())).yellow())?;
+					return Ok(MultiOutcome::Lost { answers: self.answers() });
+				},
+				Ok(PlayerAction::Hint) =>
+				{
+					self.say(output.messages(), "\nThere are no hints in this game.\n".yellow())?;
+					continue;
+				},
+				Ok(PlayerAction::Pause) =>
+				{
+					self.say(output.messages(), "\nThere is no clock to pause in this game.\n".yellow())?;
+					continue;
+				},
+				Err(InputError::Io(error)) => return Err(error),
+				Err(error) =>
+				{
+					self.say(output.messages(), format!("\n\nGame aborted because {error}. 👋\n\n").yellow())?;
+					return Ok(MultiOutcome::Aborted);
+				},
+			};
+			match self.submit(guess).expect("the game is not finished while it is played.")
+			{
+				MultiTurn::Hit { remaining, .. } => self.say(output.messages(), format!("\n{guess} is one of them! 🎯 {remaining} to go.\n").green())?,
+				MultiTurn::TooHigh { .. } => self.say(output.messages(), format!("\n{guess} is too high for the closest remaining number.\n").red())?,
+				MultiTurn::TooLow { .. } => self.say(output.messages(), format!("\n{guess} is too low for the closest remaining number.\n").cyan())?,
+				MultiTurn::Win { attempts_used } =>
+				{
+					self.say(output.messages(), format!("\n{guess} was the last one! You found all {count} numbers in {attempts_used} tries! 😊\n\n").green())?;
+					return Ok(MultiOutcome::Won { attempts_used });
+				},
+				MultiTurn::Loss { answers } =>
+				{
+					self.say(output.messages(), format!("\nYou're out of guesses! The numbers you missed were {}. Game over. 😢\n\n", list(&answers)).red())?;
+					return Ok(MultiOutcome::Lost { answers });
+				},
+			}
+		}
+	}
+
+	/// Returns how the game ended, or None if it is still going.
+	fn outcome(&self) -> Option<MultiOutcome<RANGE>>
+	{
+		if self.hidden.is_empty()
+		{
+			Some(MultiOutcome::Won { attempts_used: self.attempts_used })
+		}
+		else if self.gave_up || self.attempts_left() == 0
+		{
+			Some(MultiOutcome::Lost { answers: self.answers() })
+		}
+		else
+		{
+			None
+		}
+	}
+
+	/// Returns the secrets that are still hidden, from lowest to highest.
+	fn answers(&self) -> Vec<Guess<RANGE>>
+	{
+		self.hidden.iter().map(|secret| secret.reveal()).collect()
+	}
+
+	/// Writes text on a line of its own, in the style of the game.
+	fn say(
+		&self,
+		output: &mut dyn Write,
+		text: ColoredString,
+	) -> io::Result<()>
+	{
+		writeln!(output, "{}", self.style.render(&text))
+	}
+}
+
+/// Lists guesses as in "1, 2 and 3".
+fn list<const RANGE: RangeInclusive<i32>>(guesses: &[Guess<RANGE>]) -> String
+{
+	match guesses
+	{
+		[] => String::new(),
+		[only] => only.to_string(),
+		[rest @ .., last] =>
+		{
+			let rest: Vec<_> = rest.iter().map(ToString::to_string).collect();
+			format!("{} and {last}", rest.join(", "))
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::WithErrors,
+		rand::{
+			rngs::StdRng,
+			SeedableRng,
+		},
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=100 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Creates a game of six attempts whose secrets are 10, 50 and 90, in plain
+	/// style so that the transcript is easy to compare.
+	fn game() -> MultiGame<{ 0..=100 }>
+	{
+		MultiGame { style: Style::Plain, ..MultiGame::with_secrets([90, 10, 50].map(guess), 6) }
+	}
+
+	/// Plays game with lines as input, returning the outcome and what was
+	/// written to output.
+	fn play(
+		game: &mut MultiGame<{ 0..=100 }>,
+		lines: &str,
+	) -> (MultiOutcome<{ 0..=100 }>, String)
+	{
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = game.play(&mut lines.as_bytes(), &mut output);
+		(outcome, String::from_utf8(output.output).expect("output was not UTF-8."))
+	}
+
+	#[test]
+	fn distinct_secrets()
+	{
+		for seed in 0..50
+		{
+			let mut game = MultiGame::<{ 0..=9 }>::new(5, 100, &mut StdRng::seed_from_u64(seed));
+			assert_eq!(game.count(), 5);
+			for guess in Guess::iter()
+			{
+				if game.is_finished()
+				{
+					break;
+				}
+				game.submit(guess).expect("submit failed.");
+			}
+			assert_eq!(game.found().len(), 5);
+			assert_eq!(game.remaining(), 0);
+		}
+		// There are only 10 values to pick from.
+		assert_eq!(MultiGame::<{ 0..=9 }>::new(20, 100, &mut StdRng::seed_from_u64(1)).count(), 10);
+		assert_eq!(MultiGame::with_secrets([50, 50, 10].map(guess), 6).count(), 2);
+	}
+
+	#[test]
+	fn submit()
+	{
+		// Halfway between two secrets, the lower one is the closest.
+		assert_eq!(game().submit(guess(30)), Ok(MultiTurn::TooHigh { attempts_left: 5 }));
+
+		let mut game = game();
+		assert_eq!(game.submit(guess(50)), Ok(MultiTurn::Hit { remaining: 2, attempts_left: 5 }));
+		// 60 is closest to 90 now that 50 is found.
+		assert_eq!(game.submit(guess(60)), Ok(MultiTurn::TooLow { attempts_left: 4 }));
+		assert_eq!(game.submit(guess(5)), Ok(MultiTurn::TooLow { attempts_left: 3 }));
+		assert_eq!(game.submit(guess(50)), Ok(MultiTurn::TooHigh { attempts_left: 2 }));
+		assert_eq!(game.submit(guess(10)), Ok(MultiTurn::Hit { remaining: 1, attempts_left: 1 }));
+		assert_eq!(game.submit(guess(90)), Ok(MultiTurn::Win { attempts_used: 6 }));
+		assert_eq!(game.submit(guess(90)), Err(AlreadyFinished));
+		assert_eq!(game.found(), [50, 10, 90].map(guess));
+	}
+
+	#[test]
+	fn won()
+	{
+		let (outcome, transcript) = play(&mut game(), "50\n95\nhint\n90\n10\n");
+		assert_eq!(outcome, MultiOutcome::Won { attempts_used: 4 });
+		assert!(transcript.starts_with("\n\nI'm thinking of 3 numbers from 0 through 100. Find them all! >:)\n"));
+		assert!(transcript.contains("You have 6 attempts remaining and 3 numbers to find. Guess: \n50 is one of them! (yes) 2 to go.\n"));
+		assert!(transcript.contains("\n95 is too high for the closest remaining number.\n"));
+		assert!(transcript.contains("There are no hints in this game."));
+		assert!(transcript.ends_with("\n10 was the last one! You found all 3 numbers in 4 tries! :)\n\n\n"));
+	}
+
+	#[test]
+	fn lost()
+	{
+		let (outcome, transcript) = play(&mut game(), "10\n90\n1\n2\n3\n4\n50\n");
+		assert_eq!(outcome, MultiOutcome::Lost { answers: vec![guess(50)] });
+		assert_eq!(transcript.matches("is too low for the closest remaining number.").count(), 3);
+		assert!(transcript.ends_with("\nYou're out of guesses! The numbers you missed were 50. Game over. :(\n\n\n"));
+
+		let (outcome, transcript) = play(&mut game(), "10\ngive up\n");
+		assert_eq!(outcome, MultiOutcome::Lost { answers: vec![guess(50), guess(90)] });
+		assert!(transcript.contains("The numbers you missed were 50 and 90."));
+		assert_eq!(list(&[10, 50, 90].map(guess)), "10, 50 and 90");
+	}
+}
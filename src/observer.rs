@@ -0,0 +1,330 @@
+use {
+	crate::{
+		Feedback,
+		GameOutcome,
+		Guess,
+		GuessRange,
+	},
+	std::{
+		fmt::{
+			Debug,
+			Write as _,
+		},
+		num::NonZeroU32,
+		ops::RangeInclusive,
+		sync::Mutex,
+	},
+};
+
+/// Listens to what happens in a Game, such as for logging or achievements,
+/// without changing how it is played. See Game::add_observer.
+///
+/// Every callback does nothing unless overridden. They take &self, since a game
+/// shares its observers with whoever added them, so an observer that keeps
+/// anything uses a Mutex or the like, as TranscriptObserver does.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		Feedback,
+/// // 		Game,
+/// // 		GameObserver,
+/// // 		Guess,
+/// // 	},
+/// // 	std::sync::Arc,
+/// //};
+/// // #[derive(Debug)]
+/// // struct Cheer;
+/// // impl GameObserver<{ 0..=1024 }> for Cheer
+/// // {
+/// // 	fn on_guess(&self, _attempt: u32, _guess: Guess<{ 0..=1024 }>, feedback: Feedback)
+/// // 	{
+/// // 		if feedback.is_correct()
+/// // 		{
+/// // 			println!("Hooray!");
+/// // 		}
+/// // 	}
+/// // }
+/// // let mut game = Game::<{ 0..=1024 }>::new_random(10);
+/// // game.add_observer(Arc::new(Cheer));
+/// ```
+pub trait GameObserver<const RANGE: RangeInclusive<i32>>: Debug + Send + Sync
+{
+	/// Is told that the game started, with the guesses it accepts and how many
+	/// attempts it has, or None in practice. This happens once, when the game is
+	/// first played or given a guess or hint.
+	fn on_game_start(
+		&self,
+		_range: GuessRange<RANGE>,
+		_attempts: Option<NonZeroU32>,
+	)
+	{
+	}
+
+	/// Is told about each valid guess as it is recorded, with the attempt it
+	/// used and its feedback. A guess that came too late is not recorded.
+	fn on_guess(
+		&self,
+		_attempt: u32,
+		_guess: Guess<RANGE>,
+		_feedback: Feedback,
+	)
+	{
+	}
+
+	/// Is told about each line that play rejected as an invalid guess. See
+	/// Prompt::rejected.
+	fn on_invalid_input(
+		&self,
+		_text: &str,
+	)
+	{
+	}
+
+	/// Is told how the game ended, once it is won or lost, or each time play is
+	/// aborted.
+	fn on_game_end(
+		&self,
+		_outcome: &GameOutcome<RANGE>,
+	)
+	{
+	}
+}
+
+/// A GameObserver that writes down everything that happens as plain text, one
+/// line per event, as in:
+///
+/// ```text
+/// Game started: from 0 through 1024 with 5 attempts.
+/// Attempt 1: 512 is too high.
+/// Invalid input: "abc"
+/// Attempt 2: 300 is correct.
+/// Won in 2 attempts.
+/// ```
+#[derive(Debug, Default)]
+pub struct TranscriptObserver
+{
+	transcript: Mutex<String>,
+}
+
+impl TranscriptObserver
+{
+	/// Creates an observer that has not seen anything yet.
+	pub fn new() -> Self
+	{
+		TranscriptObserver::default()
+	}
+
+	/// Returns everything written down so far.
+	pub fn transcript(&self) -> String
+	{
+		self.transcript.lock().expect("transcript was poisoned.").clone()
+	}
+
+	/// Writes line down.
+	fn write(
+		&self,
+		line: std::fmt::Arguments<'_>,
+	)
+	{
+		let mut transcript = self.transcript.lock().expect("transcript was poisoned.");
+		let _ = writeln!(transcript, "{line}");
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> GameObserver<RANGE> for TranscriptObserver
+{
+	fn on_game_start(
+		&self,
+		range: GuessRange<RANGE>,
+		attempts: Option<NonZeroU32>,
+	)
+	{
+		match attempts
+		{
+			Some(attempts) => self.write(format_args!("Game started: from {} through {} with {attempts} attempts.", range.lo(), range.hi())),
+			None => self.write(format_args!("Game started: from {} through {} with as many attempts as it takes.", range.lo(), range.hi())),
+		}
+	}
+
+	fn on_guess(
+		&self,
+		attempt: u32,
+		guess: Guess<RANGE>,
+		feedback: Feedback,
+	)
+	{
+		let feedback = match feedback
+		{
+			Feedback::TooHigh => "too high",
+			Feedback::TooLow => "too low",
+			Feedback::Correct => "correct",
+		};
+		self.write(format_args!("Attempt {attempt}: {guess} is {feedback}."));
+	}
+
+	fn on_invalid_input(
+		&self,
+		text: &str,
+	)
+	{
+		self.write(format_args!("Invalid input: {text:?}"));
+	}
+
+	fn on_game_end(
+		&self,
+		outcome: &GameOutcome<RANGE>,
+	)
+	{
+		match outcome
+		{
+			GameOutcome::Won { attempts_used } => self.write(format_args!("Won in {attempts_used} attempts.")),
+			GameOutcome::Lost { answer } => self.write(format_args!("Lost, the number was {answer}.")),
+			GameOutcome::TimedOut { answer } => self.write(format_args!("Timed out, the number was {answer}.")),
+			GameOutcome::Aborted => self.write(format_args!("Aborted.")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Game,
+			Style,
+			WithErrors,
+		},
+		std::sync::Arc,
+	};
+
+	/// Records every callback as a line, to check their order.
+	#[derive(Debug, Default)]
+	struct Recorder
+	{
+		events: Mutex<Vec<String>>,
+	}
+
+	impl Recorder
+	{
+		fn events(&self) -> Vec<String>
+		{
+			self.events.lock().expect("events were poisoned.").clone()
+		}
+
+		fn push(
+			&self,
+			event: String,
+		)
+		{
+			self.events.lock().expect("events were poisoned.").push(event);
+		}
+	}
+
+	impl GameObserver<{ 0..=1024 }> for Recorder
+	{
+		fn on_game_start(
+			&self,
+			range: GuessRange<{ 0..=1024 }>,
+			attempts: Option<NonZeroU32>,
+		)
+		{
+			self.push(format!("start {}..={} {attempts:?}", range.lo(), range.hi()));
+		}
+
+		fn on_guess(
+			&self,
+			attempt: u32,
+			guess: Guess<{ 0..=1024 }>,
+			feedback: Feedback,
+		)
+		{
+			self.push(format!("guess {attempt} {guess} {feedback:?}"));
+		}
+
+		fn on_invalid_input(
+			&self,
+			text: &str,
+		)
+		{
+			self.push(format!("invalid {text:?}"));
+		}
+
+		fn on_game_end(
+			&self,
+			outcome: &GameOutcome<{ 0..=1024 }>,
+		)
+		{
+			self.push(format!("end {outcome:?}"));
+		}
+	}
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Creates a game of five attempts whose secret is 300, in plain style.
+	fn game() -> Game<{ 0..=1024 }>
+	{
+		Game { style: Style::Plain, ..Game::with_secret(5, guess(300)) }
+	}
+
+	#[test]
+	fn play()
+	{
+		let recorder = Arc::new(Recorder::default());
+		let transcript = Arc::new(TranscriptObserver::new());
+		let mut game = game();
+		game.add_observer(recorder.clone());
+		game.add_observer(transcript.clone());
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		game.play(&mut "512\nabc\n256\n\nquit\n".as_bytes(), &mut output);
+		game.play(&mut "300\n".as_bytes(), &mut output);
+		assert_eq!(
+			recorder.events(),
+			[
+				"start 0..=1024 Some(5)",
+				"guess 1 512 TooHigh",
+				"invalid \"abc\"",
+				"guess 2 256 TooLow",
+				"end Aborted",
+				"guess 3 300 Correct",
+				"end Won { attempts_used: 3 }",
+			]
+		);
+		assert_eq!(
+			transcript.transcript(),
+			"Game started: from 0 through 1024 with 5 attempts.\nAttempt 1: 512 is too high.\nInvalid input: \"abc\"\nAttempt 2: 256 is too low.\nAborted.\nAttempt 3: \
+			 300 is correct.\nWon in 3 attempts.\n"
+		);
+	}
+
+	#[test]
+	fn submit()
+	{
+		let recorder = Arc::new(Recorder::default());
+		let mut game = game();
+		game.add_observer(recorder.clone());
+		for value in [1, 2, 3]
+		{
+			game.submit(guess(value)).expect("submit failed.");
+		}
+		game.hint().expect("hint failed.");
+		game.submit(guess(4)).expect("submit failed.");
+		// A finished game tells nobody about guesses it does not take.
+		assert!(game.submit(guess(300)).is_err());
+		assert_eq!(
+			recorder.events(),
+			["start 0..=1024 Some(5)", "guess 1 1 TooLow", "guess 2 2 TooLow", "guess 3 3 TooLow", "guess 5 4 TooLow", "end Lost { answer: 300 }"]
+		);
+
+		let transcript = TranscriptObserver::new();
+		GameObserver::<{ 0..=1024 }>::on_game_end(&transcript, &GameOutcome::TimedOut { answer: guess(7) });
+		GameObserver::<{ 0..=1024 }>::on_game_start(&transcript, GuessRange::full(), None);
+		assert_eq!(transcript.transcript(), "Timed out, the number was 7.\nGame started: from 0 through 1024 with as many attempts as it takes.\n");
+	}
+}
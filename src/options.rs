@@ -0,0 +1,623 @@
+use {
+	colored::{
+		ColoredString,
+		Colorize,
+		Styles,
+	},
+	std::{
+		env,
+		fmt::Display,
+		io::{
+			self,
+			Write,
+		},
+	},
+};
+
+/// How many characters of rejected input are repeated back in the invalid
+/// message. Longer input is cut short and ends with an ellipsis.
+pub const MAX_ECHO_CHARS: usize = 40;
+
+/// Text that replaces each emoji, or other non-ASCII character the crate writes
+/// itself, in Style::Plain and Style::Ascii.
+const PLAIN_REPLACEMENTS: [(char, &str); 19] = [
+	('🤕', "[!]"),
+	('🥵', "(hot)"),
+	('🥶', "(cold)"),
+	('😊', ":)"),
+	('🏖', ""),
+	('😈', ">:)"),
+	('😢', ":("),
+	('🙃', "(:"),
+	('🔥', "(fire)"),
+	('🧊', "(ice)"),
+	('👋', ""),
+	('⏱', ""),
+	('🎯', "(yes)"),
+	('🙅', "(no)"),
+	('↑', "^"),
+	('↓', "v"),
+	('✔', "(ok)"),
+	('…', "..."),
+	('\u{fe0f}', ""),
+];
+
+/// How messages look.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Style
+{
+	/// Colors and emoji, for a terminal that can show them.
+	#[default]
+	Fancy,
+	/// No colors and ASCII only, for output that is redirected to a file or
+	/// shown by a dumb terminal. Emoji are replaced with text such as `[!]`.
+	Plain,
+	/// Colors, but ASCII only, for a terminal that can not show emoji, such as
+	/// the Windows console host. Emoji are replaced the same way as in
+	/// Style::Plain.
+	Ascii,
+}
+
+impl Style
+{
+	/// Picks Style::Ascii when the terminal is not expected to show emoji, and
+	/// Style::Fancy otherwise.
+	///
+	/// On Windows, only Windows Terminal is trusted with emoji, since the older
+	/// console host shows them as boxes. Elsewhere the first of LC_ALL, LC_CTYPE
+	/// and LANG that is set must name UTF-8 as its encoding.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::Style;
+	/// // println!("{}", Style::detect().render(&"You win! 😊".into()));
+	/// ```
+	pub fn detect() -> Self
+	{
+		if supports_utf8(cfg!(windows), |name| env::var(name).ok())
+		{
+			Style::Fancy
+		}
+		else
+		{
+			Style::Ascii
+		}
+	}
+
+	/// Renders text the way this style shows it.
+	pub fn render(
+		self,
+		text: &ColoredString,
+	) -> String
+	{
+		match self
+		{
+			Style::Fancy => text.to_string(),
+			Style::Plain => plain(text),
+			Style::Ascii => restyle(&plain(text), text).to_string(),
+		}
+	}
+}
+
+/// Checks whether the environment, as read by var, promises that UTF-8 output
+/// is shown correctly. See Style::detect().
+fn supports_utf8(
+	windows: bool,
+	var: impl Fn(&str) -> Option<String>,
+) -> bool
+{
+	if windows
+	{
+		return var("WT_SESSION").is_some();
+	}
+	["LC_ALL", "LC_CTYPE", "LANG"]
+		.into_iter()
+		.find_map(|name| var(name).filter(|value| !value.is_empty()))
+		.is_some_and(|locale| {
+			let locale = locale.to_lowercase();
+			locale.contains("utf-8") || locale.contains("utf8")
+		})
+}
+
+/// Text and styling used by input_with_options when asking for a guess.
+///
+/// The defaults match what input shows. The invalid message is a template where
+/// {min} and {max} are replaced with the ends of the range and {input} with the
+/// rejected line, trimmed and cut short after MAX_ECHO_CHARS characters. Write
+/// {{ or }} for a literal brace. Anything else in braces is left exactly as
+/// written.
+#[derive(Debug, Clone)]
+pub struct InputOptions
+{
+	/// Shown before every attempt.
+	pub prompt: ColoredString,
+	/// First line shown when a guess is invalid.
+	pub invalid_title: String,
+	/// Second line shown when the input is not a number. Numbers that are too
+	/// large or out of range are explained by their ParseGuessError instead.
+	pub invalid_message: String,
+	/// Whether the prompt and messages keep their colors.
+	pub color: bool,
+	/// How the prompt and messages look. Style::Plain also turns off color, and
+	/// Style::Ascii keeps it.
+	pub style: Style,
+	/// How many invalid guesses are explained and skipped before giving up, or
+	/// None to keep asking until there is a valid guess.
+	pub max_invalid: Option<usize>,
+	/// Whether a line that is empty apart from whitespace just repeats the
+	/// prompt. Otherwise it is parsed like any other line.
+	pub repeat_on_blank: bool,
+	/// Whether rejected lines are kept out of the output, for input that must
+	/// stay secret. Every invalid guess is then explained with the invalid
+	/// message, where {input} is replaced with `***`.
+	pub redact: bool,
+}
+
+impl Default for InputOptions
+{
+	fn default() -> Self
+	{
+		InputOptions {
+			prompt: "Guess: ".normal(),
+			invalid_title: "Invalid guess. 🤕".to_string(),
+			invalid_message: "\"{input}\" is not an integer from {min} through {max}.".to_string(),
+			color: true,
+			style: Style::Fancy,
+			max_invalid: None,
+			repeat_on_blank: true,
+			redact: false,
+		}
+	}
+}
+
+/// What has happened so far while input is asking for one guess.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct InputContext
+{
+	/// How many invalid guesses have been entered since input was called.
+	pub invalid_tries: usize,
+}
+
+/// Produces the prompt shown before every attempt to read a guess.
+///
+/// A ColoredString is a static prompt that is shown every time. A closure taking
+/// an InputContext can change the prompt after invalid guesses. The attempt
+/// number or anything else the caller knows can be captured by the closure.
+pub trait Prompt
+{
+	/// Returns the prompt to show for the attempt described by context.
+	fn prompt(
+		&mut self,
+		context: InputContext,
+	) -> ColoredString;
+
+	/// Is told about each line that was rejected as an invalid guess, trimmed,
+	/// before the prompt is shown again. A redacted line is given as `***`. Does
+	/// nothing unless overridden.
+	fn rejected(
+		&mut self,
+		_line: &str,
+	)
+	{
+	}
+}
+
+impl Prompt for ColoredString
+{
+	fn prompt(
+		&mut self,
+		_context: InputContext,
+	) -> ColoredString
+	{
+		self.clone()
+	}
+}
+
+impl<F: FnMut(InputContext) -> ColoredString> Prompt for F
+{
+	fn prompt(
+		&mut self,
+		context: InputContext,
+	) -> ColoredString
+	{
+		self(context)
+	}
+}
+
+impl InputOptions
+{
+	/// Creates InputOptions with the default messages and the given prompt.
+	pub fn new(prompt: ColoredString) -> Self
+	{
+		InputOptions { prompt, ..InputOptions::default() }
+	}
+
+	/// Fills in the invalid message template for a rejected line of input.
+	pub fn render_invalid_message(
+		&self,
+		min: impl Display,
+		max: impl Display,
+		input: &str,
+	) -> String
+	{
+		render_template(&self.invalid_message, |name| match name
+		{
+			"min" => Some(min.to_string()),
+			"max" => Some(max.to_string()),
+			"input" => Some(if self.redact { "***".to_string() } else { echo(input) }),
+			_ => None,
+		})
+	}
+
+	/// Renders text in the style, without its colors if color is off.
+	fn render(
+		&self,
+		text: &ColoredString,
+	) -> String
+	{
+		if self.color
+		{
+			self.style.render(text)
+		}
+		else
+		{
+			self.style.render(&(**text).normal())
+		}
+	}
+
+	/// Writes prompt, without its colors if color is off.
+	pub(crate) fn write_prompt(
+		&self,
+		prompt: &ColoredString,
+		output: &mut impl Write,
+	) -> io::Result<()>
+	{
+		write!(output, "{}", self.render(prompt))?;
+		output.flush()
+	}
+
+	/// Writes the invalid title followed by detail.
+	pub(crate) fn write_invalid(
+		&self,
+		detail: &str,
+		output: &mut impl Write,
+	) -> io::Result<()>
+	{
+		let message = format!("\n{}\n{}", self.render(&self.invalid_title.red()), self.render(&detail.yellow()));
+		output.write_all(message.as_bytes())
+	}
+
+	/// Writes a warning about a guess that is valid but not accepted.
+	pub(crate) fn write_warning(
+		&self,
+		warning: &str,
+		output: &mut impl Write,
+	) -> io::Result<()>
+	{
+		let message = format!("\n{}", self.render(&warning.yellow()));
+		output.write_all(message.as_bytes())
+	}
+}
+
+/// Where input writes its prompts and its error messages.
+///
+/// Every writer is an InputOutput that gets both. WithErrors sends error
+/// messages to a writer of their own, such as stderr.
+pub trait InputOutput
+{
+	/// Returns the writer for prompts.
+	fn prompts(&mut self) -> &mut dyn Write;
+
+	/// Returns the writer for messages about invalid guesses.
+	fn errors(&mut self) -> &mut dyn Write;
+
+	/// Returns the writer for everything else a game shows, such as responses
+	/// to guesses. This is the writer for prompts unless prompts are held back
+	/// for a line editor.
+	fn messages(&mut self) -> &mut dyn Write
+	{
+		self.prompts()
+	}
+}
+
+impl<W: Write> InputOutput for W
+{
+	fn prompts(&mut self) -> &mut dyn Write
+	{
+		self
+	}
+
+	fn errors(&mut self) -> &mut dyn Write
+	{
+		self
+	}
+}
+
+/// Output for input that writes prompts to output and error messages to errors.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::{
+/// // 		input,
+/// // 		WithErrors,
+/// // 	},
+/// // 	std::io::{
+/// // 		stderr,
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let mut output = WithErrors { output: stdout(), errors: stderr() };
+/// // let input = input::<{ 0..=100 }>("Guess: ".yellow(), &mut stdin().lock(), &mut output);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct WithErrors<O, E>
+{
+	/// Receives prompts.
+	pub output: O,
+	/// Receives messages about invalid guesses.
+	pub errors: E,
+}
+
+impl<O: Write, E: Write> InputOutput for WithErrors<O, E>
+{
+	fn prompts(&mut self) -> &mut dyn Write
+	{
+		&mut self.output
+	}
+
+	fn errors(&mut self) -> &mut dyn Write
+	{
+		&mut self.errors
+	}
+}
+
+/// Makes text into a ColoredString with the same colors and styles as like.
+pub(crate) fn restyle(
+	text: &str,
+	like: &ColoredString,
+) -> ColoredString
+{
+	let mut styled = text.normal();
+	if let Some(color) = like.fgcolor()
+	{
+		styled = styled.color(color);
+	}
+	if let Some(color) = like.bgcolor()
+	{
+		styled = styled.on_color(color);
+	}
+	type Apply = fn(ColoredString) -> ColoredString;
+	let styles: [(Styles, Apply); 8] = [
+		(Styles::Bold, Colorize::bold),
+		(Styles::Dimmed, Colorize::dimmed),
+		(Styles::Italic, Colorize::italic),
+		(Styles::Underline, Colorize::underline),
+		(Styles::Blink, Colorize::blink),
+		(Styles::Reversed, Colorize::reversed),
+		(Styles::Hidden, Colorize::hidden),
+		(Styles::Strikethrough, Colorize::strikethrough),
+	];
+	for (style, apply) in styles
+	{
+		if like.style().contains(style)
+		{
+			styled = apply(styled);
+		}
+	}
+	styled
+}
+
+/// Fills in a template, replacing each {name} with value(name). Placeholders
+/// that value returns None for are left exactly as written, and {{ and }} are
+/// written as literal braces.
+pub(crate) fn render_template(
+	template: &str,
+	value: impl Fn(&str) -> Option<String>,
+) -> String
+{
+	let mut rendered = String::new();
+	let mut rest = template;
+	while let Some(start) = rest.find(['{', '}'])
+	{
+		rendered.push_str(&rest[..start]);
+		rest = &rest[start..];
+		if rest.starts_with("{{") || rest.starts_with("}}")
+		{
+			rendered.push_str(&rest[..1]);
+			rest = &rest[2..];
+			continue;
+		}
+		// A placeholder is a name between braces with no other braces in it.
+		let end = match rest[1..].find(['{', '}'])
+		{
+			Some(end) if rest.starts_with('{') && rest[1 + end..].starts_with('}') => 1 + end,
+			_ =>
+			{
+				rendered.push_str(&rest[..1]);
+				rest = &rest[1..];
+				continue;
+			},
+		};
+		match value(&rest[1..end])
+		{
+			Some(value) => rendered.push_str(&value),
+			None => rendered.push_str(&rest[..=end]),
+		}
+		rest = &rest[end + 1..];
+	}
+	rendered.push_str(rest);
+	rendered
+}
+
+/// Removes the emoji from text, along with the spaces that separated them from
+/// the rest of it.
+pub(crate) fn strip_emoji(text: &str) -> String
+{
+	let mut stripped = String::with_capacity(text.len());
+	for character in text.chars().filter(|&character| !is_emoji(character) && character != '\u{fe0f}')
+	{
+		// Runs of spaces are collapsed, so none are left doubled where an emoji was.
+		if !(character == ' ' && (stripped.is_empty() || stripped.ends_with(' ')))
+		{
+			stripped.push(character);
+		}
+	}
+	stripped.truncate(stripped.trim_end_matches(' ').len());
+	stripped
+}
+
+/// Removes the colors from text and replaces its emoji with ASCII. Emoji without
+/// a replacement are left out.
+fn plain(text: &str) -> String
+{
+	let mut plain = String::with_capacity(text.len());
+	for character in text.chars()
+	{
+		match PLAIN_REPLACEMENTS.iter().find(|(emoji, _)| *emoji == character)
+		{
+			Some((_, replacement)) => plain.push_str(replacement),
+			None if is_emoji(character) => (),
+			None => plain.push(character),
+		}
+	}
+	plain
+}
+
+/// Checks whether character is in one of the blocks that emoji come from.
+fn is_emoji(character: char) -> bool
+{
+	matches!(character, '\u{1f000}'..='\u{1faff}' | '\u{2600}'..='\u{27bf}')
+}
+
+/// Trims input and cuts it short so it is safe to repeat back to the user.
+pub(crate) fn echo(input: &str) -> String
+{
+	let input = input.trim();
+	match input.char_indices().nth(MAX_ECHO_CHARS)
+	{
+		Some((end, _)) => format!("{}…", &input[..end]),
+		None => input.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			input_with_options,
+			Guess,
+		},
+	};
+
+	#[test]
+	fn render_invalid_message()
+	{
+		let options = InputOptions {
+			invalid_message: "Choisissez un entier de {min} à {max}, pas « {input} ».".to_string(),
+			..InputOptions::default()
+		};
+		assert_eq!(options.render_invalid_message(0, 50, "abc"), "Choisissez un entier de 0 à 50, pas « abc ».");
+
+		let options = InputOptions::default();
+		assert_eq!(options.render_invalid_message(-5, 5, " 4o2 \n"), "\"4o2\" is not an integer from -5 through 5.");
+
+		let options = InputOptions { redact: true, ..InputOptions::default() };
+		assert_eq!(options.render_invalid_message(-5, 5, "secret"), "\"***\" is not an integer from -5 through 5.");
+	}
+
+	#[test]
+	fn render_invalid_message_truncated()
+	{
+		let options = InputOptions { invalid_message: "{input}".to_string(), ..InputOptions::default() };
+		let exact = "a".repeat(MAX_ECHO_CHARS);
+		assert_eq!(options.render_invalid_message(0, 1, &exact), exact);
+
+		let long = "é".repeat(MAX_ECHO_CHARS + 1);
+		assert_eq!(options.render_invalid_message(0, 1, &long), format!("{}…", "é".repeat(MAX_ECHO_CHARS)));
+
+		let novel = "It was a dark and stormy night. ".repeat(1000);
+		let rendered = options.render_invalid_message(0, 1, &novel);
+		assert_eq!(rendered.chars().count(), MAX_ECHO_CHARS + 1);
+		assert!(rendered.starts_with("It was a dark and stormy night."));
+	}
+
+	#[test]
+	fn render_invalid_message_braces()
+	{
+		let render = |template: &str| {
+			InputOptions { invalid_message: template.to_string(), ..InputOptions::default() }.render_invalid_message(1, 9, "x")
+		};
+		assert_eq!(render("{{min}} is {min}"), "{min} is 1");
+		assert_eq!(render("{unknown} {max}"), "{unknown} 9");
+		assert_eq!(render("{ {min} }"), "{ 1 }");
+		assert_eq!(render("{max"), "{max");
+		assert_eq!(render("}{"), "}{");
+		assert_eq!(render("{}"), "{}");
+		assert_eq!(render("{{input}}}"), "{input}}");
+	}
+
+	#[test]
+	fn detect_utf8()
+	{
+		let locale = |vars: &'static [(&'static str, &'static str)]| {
+			move |name: &str| vars.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+		};
+		assert!(supports_utf8(false, locale(&[("LANG", "en_US.UTF-8")])));
+		assert!(supports_utf8(false, locale(&[("LC_CTYPE", "de_DE.utf8"), ("LANG", "C")])));
+		assert!(supports_utf8(false, locale(&[("LC_ALL", ""), ("LANG", "C.UTF-8")])));
+		assert!(!supports_utf8(false, locale(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")])));
+		assert!(!supports_utf8(false, locale(&[("LANG", "en_US.ISO-8859-1")])));
+		assert!(!supports_utf8(false, locale(&[])));
+
+		assert!(supports_utf8(true, locale(&[("WT_SESSION", "0")])));
+		assert!(!supports_utf8(true, locale(&[("LANG", "en_US.UTF-8")])));
+	}
+
+	#[test]
+	fn ascii_style()
+	{
+		let emoji = PLAIN_REPLACEMENTS.iter().map(|(emoji, _)| *emoji).collect::<String>();
+		let rendered = Style::Ascii.render(&format!("Invalid guess. {emoji}").red());
+		assert!(rendered.is_ascii(), "{rendered:?} is not ASCII.");
+		assert!(rendered.contains("Invalid guess. [!](hot)(cold):)"));
+		assert_eq!(Style::Ascii.render(&"😈 🔥 🧊 😢 🤯".normal()), ">:) (fire) (ice) :( ");
+
+		let options = InputOptions { style: Style::Ascii, ..InputOptions::new("Guess 🎲: ".yellow()) };
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=50 }> = input_with_options(&options, &mut "abc
+99
+7
+".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 7_i32);
+		assert!(output.iter().all(|&byte| byte <= 0x7f), "{:?} is not ASCII.", String::from_utf8_lossy(&output));
+	}
+
+	#[test]
+	fn input_with_options_test()
+	{
+		let options = InputOptions {
+			prompt: "Nombre : ".normal(),
+			invalid_title: "Non.".to_string(),
+			invalid_message: "Pas « {input} », de {min} à {max}.".to_string(),
+			color: false,
+			style: Style::Fancy,
+			max_invalid: None,
+			repeat_on_blank: true,
+			redact: false,
+		};
+		let mut output = Vec::new();
+		let guess: Guess<{ 0..=50 }> = input_with_options(&options, &mut "abc\n51\n50\n".as_bytes(), &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 50_i32);
+		assert_eq!(
+			String::from_utf8_lossy(&output),
+			"Nombre : \nNon.\nPas « abc », de 0 à 50.Nombre : \nNon.\n51 is above the maximum. Guesses must be at most 50.Nombre : "
+		);
+	}
+}
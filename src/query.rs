@@ -0,0 +1,344 @@
+use {
+	crate::{
+		parse_lenient,
+		read_guess,
+		respond,
+		Feedback,
+		Guess,
+		InputError,
+		InputOptions,
+		InputOutput,
+		ParseGuessError,
+		Prompt,
+		Win,
+	},
+	colored::Colorize,
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			BufRead,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+		str::FromStr,
+	},
+};
+
+/// What the player asked about with one attempt: a single number, or whether
+/// the correct value lies within an interval.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GuessQuery<const RANGE: RangeInclusive<i32>>
+{
+	/// The player guessed a single number.
+	Exact(Guess<RANGE>),
+	/// The player asked whether the correct value lies from the first guess
+	/// through the second. The first is never greater than the second.
+	Interval(Guess<RANGE>, Guess<RANGE>),
+}
+
+impl<const RANGE: RangeInclusive<i32>> GuessQuery<RANGE>
+{
+	/// Parses text into a GuessQuery.
+	///
+	/// Text of the form `A-B` or `A..=B` is an interval, where A and B are
+	/// parsed the same way as Guess::parse_lenient(). Either end may be
+	/// negative, as in `-10--5`. Anything else is parsed as a single guess.
+	///
+	/// An interval whose start is greater than its end, or whose ends are not
+	/// both valid guesses, is rejected with an error of its own.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Guess,
+	/// // 	GuessQuery,
+	/// //};
+	/// // let query = GuessQuery::<{ 0..=100 }>::parse("40-60").expect("");
+	/// // assert_eq!(query.to_string(), "40-60");
+	/// // let query = GuessQuery::<{ 0..=100 }>::parse("42").expect("");
+	/// // assert_eq!(query, GuessQuery::Exact(Guess::new(42).expect("")));
+	/// ```
+	pub fn parse(text: &str) -> Result<Self, ParseGuessError>
+	{
+		let text = text.trim();
+		// Hyphenated words like `forty-two` are one number, not an interval.
+		#[cfg(feature = "words")]
+		if crate::words::parse_words(text).is_some()
+		{
+			return Guess::parse_lenient(text).map(GuessQuery::Exact);
+		}
+		let Some((start, end)) = split_interval(text)
+		else
+		{
+			return Guess::parse_lenient(text).map(GuessQuery::Exact);
+		};
+		let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+		let endpoint = |text: &str| match parse_lenient(text, min, max)
+		{
+			Ok(value) => Ok(Guess { value }),
+			Err(ParseGuessError::OutOfRange(error)) => Err(ParseGuessError::IntervalOutOfRange(error)),
+			Err(error) => Err(error),
+		};
+		let (start, end) = (endpoint(start)?, endpoint(end)?);
+		if start > end
+		{
+			return Err(ParseGuessError::ReversedInterval { start: start.value, end: end.value });
+		}
+		Ok(GuessQuery::Interval(start, end))
+	}
+
+	/// Returns whether correct is the guess, or lies within the interval.
+	pub fn contains(
+		&self,
+		correct: Guess<RANGE>,
+	) -> bool
+	{
+		match *self
+		{
+			GuessQuery::Exact(guess) => guess == correct,
+			GuessQuery::Interval(start, end) => (start..=end).contains(&correct),
+		}
+	}
+}
+
+/// Splits text into the two ends of an interval, or returns None if it is not
+/// one. A `-` that starts either end is its sign, not the separator.
+fn split_interval(text: &str) -> Option<(&str, &str)>
+{
+	if let Some(ends) = text.split_once("..=")
+	{
+		return Some(ends);
+	}
+	text.match_indices('-')
+		.map(|(index, _)| (&text[..index], &text[index + 1..]))
+		.find(|(start, _)| !start.trim().is_empty() && !start.trim_end().ends_with(['+', '-']))
+}
+
+/// Parses text into a GuessQuery. This is equivalent to calling
+/// GuessQuery::parse().
+impl<const RANGE: RangeInclusive<i32>> FromStr for GuessQuery<RANGE>
+{
+	type Err = ParseGuessError;
+
+	fn from_str(text: &str) -> Result<Self, Self::Err>
+	{
+		GuessQuery::parse(text)
+	}
+}
+
+/// Formats GuessQuery the way it is entered, as in `42` or `40-60`.
+impl<const RANGE: RangeInclusive<i32>> Display for GuessQuery<RANGE>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			GuessQuery::Exact(guess) => guess.fmt(f),
+			GuessQuery::Interval(start, end) => write!(f, "{start}-{end}"),
+		}
+	}
+}
+
+/// Gets user input until it is a valid GuessQuery and returns it.
+///
+/// This is the same as input, except that it parses with GuessQuery::parse(),
+/// so that the player can ask about an interval instead of naming a number.
+///
+/// # Errors
+/// Returns the same errors as input.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::input_query,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let query = input_query::<{ 0..=100 }>("Guess or ask about a range: ".yellow(), &mut stdin().lock(), &mut stdout());
+/// ```
+pub fn input_query<const RANGE: RangeInclusive<i32>>(
+	prompt: impl Prompt,
+	input: &mut impl BufRead,
+	output: &mut impl InputOutput,
+) -> Result<GuessQuery<RANGE>, InputError>
+{
+	let (min, max) = (Guess::<RANGE>::min_value(), Guess::<RANGE>::max_value());
+	read_guess(prompt, &InputOptions::default(), min, max, input, output, GuessQuery::parse).map(|result| result.guess)
+}
+
+/// Respond to a user's query with some console output. Returns the correct
+/// action to take.
+///
+/// An exact guess gets the same response as respond, and continues with Some of
+/// its Feedback. An interval is answered with whether the correct value lies
+/// within it, and always continues with None, even if the interval holds only
+/// the correct value.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_query,
+/// // 		Guess,
+/// // 		GuessQuery,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let query = GuessQuery::<{ 0..=100 }>::parse("40-60").expect("");
+/// // let correct_guess = Guess::<{ 0..=100 }>::new(42_i32).expect("");
+/// // assert!(respond_query(query, correct_guess, &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_query<const RANGE: RangeInclusive<i32>>(
+	query: GuessQuery<RANGE>,
+	correct: Guess<RANGE>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Option<Feedback>>>
+{
+	match query
+	{
+		GuessQuery::Exact(guess) => match respond(guess, correct, output)?
+		{
+			ControlFlow::Break(win) => Ok(ControlFlow::Break(win)),
+			ControlFlow::Continue(feedback) => Ok(ControlFlow::Continue(Some(feedback))),
+		},
+		GuessQuery::Interval(start, end) =>
+		{
+			let answer = if query.contains(correct)
+			{
+				format!("Yes, it is from {start} through {end}. 🎯").green()
+			}
+			else
+			{
+				format!("No, it is not from {start} through {end}. 🙅").red()
+			};
+			write!(output, "\n{answer}\n")?;
+			Ok(ControlFlow::Continue(None))
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::GuessError,
+		std::io::stdout,
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=100 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	#[test]
+	fn parse()
+	{
+		assert_eq!(GuessQuery::parse("42"), Ok(GuessQuery::Exact(guess(42))));
+		assert_eq!(GuessQuery::parse("40-60"), Ok(GuessQuery::Interval(guess(40), guess(60))));
+		assert_eq!(GuessQuery::parse(" 40 - 60 \n"), Ok(GuessQuery::Interval(guess(40), guess(60))));
+		assert_eq!(GuessQuery::parse("40..=60"), Ok(GuessQuery::Interval(guess(40), guess(60))));
+		assert_eq!(GuessQuery::parse("0x0a-1_00"), Ok(GuessQuery::Interval(guess(10), guess(100))));
+		assert_eq!(GuessQuery::parse("7-7"), Ok(GuessQuery::Interval(guess(7), guess(7))));
+		assert_eq!("40-60".parse::<GuessQuery<{ 0..=100 }>>().map(|query| query.to_string()), Ok("40-60".to_string()));
+
+		// Either end may be negative.
+		let negative = |value| Guess::<{ -100..=100 }>::new(value).expect("guess failed to construct.");
+		assert_eq!(GuessQuery::parse("-10--5"), Ok(GuessQuery::Interval(negative(-10), negative(-5))));
+		assert_eq!(GuessQuery::parse("-10 - 5"), Ok(GuessQuery::Interval(negative(-10), negative(5))));
+		assert_eq!(GuessQuery::parse("-10"), Ok(GuessQuery::Exact(negative(-10))));
+	}
+
+	#[test]
+	fn parse_malformed()
+	{
+		assert_eq!(GuessQuery::<{ 0..=100 }>::parse("60-40"), Err(ParseGuessError::ReversedInterval { start: 60, end: 40 }));
+		assert_eq!(
+			GuessQuery::<{ 0..=100 }>::parse("40-101"),
+			Err(ParseGuessError::IntervalOutOfRange(GuessError::AboveRange { value: 101, max: 100 }))
+		);
+		assert_eq!(
+			GuessQuery::<{ 0..=100 }>::parse("-1..=5"),
+			Err(ParseGuessError::IntervalOutOfRange(GuessError::BelowRange { value: -1, min: 0 }))
+		);
+		assert_eq!(GuessQuery::<{ 0..=100 }>::parse("40-"), Err(ParseGuessError::NotANumber));
+		assert_eq!(GuessQuery::<{ 0..=100 }>::parse("a-b"), Err(ParseGuessError::NotANumber));
+		assert_eq!(GuessQuery::<{ 0..=100 }>::parse("1-2-3"), Err(ParseGuessError::NotANumber));
+		assert_eq!(GuessQuery::<{ 0..=100 }>::parse("101"), Err(ParseGuessError::OutOfRange(GuessError::AboveRange { value: 101, max: 100 })));
+
+		assert!(ParseGuessError::ReversedInterval { start: 60, end: 40 }.to_string().contains("60-40"));
+	}
+
+	#[cfg(feature = "words")]
+	#[test]
+	fn parse_words()
+	{
+		assert_eq!(GuessQuery::parse("forty-two"), Ok(GuessQuery::Exact(guess(42))));
+		assert_eq!(GuessQuery::parse("forty - sixty"), Ok(GuessQuery::Interval(guess(40), guess(60))));
+	}
+
+	#[test]
+	fn respond_query_test()
+	{
+		let correct = guess(42);
+
+		let mut output = Vec::new();
+		assert_eq!(respond_query(GuessQuery::Interval(guess(40), guess(60)), correct, &mut output).expect("respond failed."), ControlFlow::Continue(None));
+		assert!(String::from_utf8_lossy(&output).contains("Yes, it is from 40 through 60."));
+
+		let mut output = Vec::new();
+		assert_eq!(respond_query(GuessQuery::Interval(guess(0), guess(41)), correct, &mut output).expect("respond failed."), ControlFlow::Continue(None));
+		assert!(String::from_utf8_lossy(&output).contains("No, it is not from 0 through 41."));
+
+		// Only naming the number wins.
+		assert_eq!(respond_query(GuessQuery::Interval(guess(42), guess(42)), correct, &mut stdout()).expect("respond failed."), ControlFlow::Continue(None));
+		assert_eq!(respond_query(GuessQuery::Exact(guess(43)), correct, &mut stdout()).expect("respond failed."), ControlFlow::Continue(Some(Feedback::TooHigh)));
+		assert_eq!(respond_query(GuessQuery::Exact(correct), correct, &mut stdout()).expect("respond failed."), ControlFlow::Break(Win { guess: correct }));
+	}
+
+	/// A game where the player narrows the answer down with intervals, then wins
+	/// with an exact guess.
+	#[test]
+	fn input_query_game()
+	{
+		let correct = guess(42);
+		let mut lines = "0-50\n60-40\n0-25\n26-50\n40-45\n42\n".as_bytes();
+		let mut output = Vec::new();
+		let mut attempts = 0_usize;
+		loop
+		{
+			let query: GuessQuery<{ 0..=100 }> = input_query("dummy prompt: ".normal(), &mut lines, &mut output).expect("input failed.");
+			attempts += 1;
+			if respond_query(query, correct, &mut output).expect("respond failed.").is_break()
+			{
+				break;
+			}
+		}
+		assert_eq!(attempts, 5);
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert!(output.contains("Yes, it is from 0 through 50."));
+		assert!(output.contains("No, it is not from 0 through 25."));
+		assert!(output.contains("60-40"));
+		assert!(output.contains("You win!"));
+	}
+}
@@ -0,0 +1,222 @@
+use std::{
+	fmt::{
+		Display,
+		Formatter,
+	},
+	num::{
+		IntErrorKind,
+		ParseIntError,
+	},
+	ops::RangeInclusive,
+	result::Result,
+	str::FromStr,
+};
+
+/// A range of guesses written the way Rust writes ranges, as in `1..=100`.
+/// A half-open `1..101` is taken as the same range, and a single number such as
+/// `100` as `0..=100`.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::RangeSpec;
+/// // let spec: RangeSpec = "1..101".parse().expect("");
+/// // assert_eq!(spec.range(), 1..=100);
+/// // assert_eq!(spec.to_string(), "1..=100");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct RangeSpec
+{
+	/// The lowest value in the range.
+	pub lo: i32,
+	/// The highest value in the range.
+	pub hi: i32,
+}
+
+impl RangeSpec
+{
+	/// Returns the range as a RangeInclusive.
+	pub const fn range(self) -> RangeInclusive<i32>
+	{
+		self.lo..=self.hi
+	}
+}
+
+impl FromStr for RangeSpec
+{
+	type Err = RangeSpecError;
+
+	fn from_str(spec: &str) -> Result<Self, RangeSpecError>
+	{
+		let spec = spec.trim();
+		let (lo, hi, inclusive) = match spec.split_once("..")
+		{
+			Some((lo, rest)) =>
+			{
+				let (hi, inclusive) = match rest.strip_prefix('=')
+				{
+					Some(hi) => (hi, true),
+					None => (rest, false),
+				};
+				if hi.starts_with('.')
+				{
+					return Err(RangeSpecError::TooManyDots(spec.to_owned()));
+				}
+				let (lo, hi) = (lo.trim(), hi.trim());
+				if lo.is_empty() || hi.is_empty()
+				{
+					return Err(RangeSpecError::MissingBound(spec.to_owned()));
+				}
+				(bound(lo)?, bound(hi)?, inclusive)
+			},
+			None if spec.is_empty() => return Err(RangeSpecError::MissingBound(String::new())),
+			// A single dot is most likely meant as two.
+			None if spec.contains('.') => return Err(RangeSpecError::MissingDots(spec.to_owned())),
+			None => (0_i32, bound(spec)?, true),
+		};
+		if lo > hi
+		{
+			return Err(RangeSpecError::Reversed { lo, hi });
+		}
+		if inclusive
+		{
+			Ok(RangeSpec { lo, hi })
+		}
+		else if lo == hi
+		{
+			Err(RangeSpecError::Empty(lo))
+		}
+		else
+		{
+			Ok(RangeSpec { lo, hi: hi - 1_i32 })
+		}
+	}
+}
+
+/// Parses one end of a range.
+fn bound(text: &str) -> Result<i32, RangeSpecError>
+{
+	text.parse().map_err(|error: ParseIntError| match error.kind()
+	{
+		IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => RangeSpecError::Overflow(text.to_owned()),
+		_ => RangeSpecError::NotANumber(text.to_owned()),
+	})
+}
+
+/// Formats RangeSpec the way Rust writes an inclusive range, as in `1..=100`.
+impl Display for RangeSpec
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		write!(f, "{}..={}", self.lo, self.hi)
+	}
+}
+
+/// Error returned when a RangeSpec can not be parsed.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum RangeSpecError
+{
+	/// An end of the range is left out, as in `..=100`, or the whole range is.
+	MissingBound(String),
+	/// The ends are separated by one dot instead of two, as in `1.100`.
+	MissingDots(String),
+	/// The ends are separated by more than two dots, as in `1...100`.
+	TooManyDots(String),
+	/// An end is not a whole number, as the `x` in `1..=x`.
+	NotANumber(String),
+	/// An end does not fit in an i32, as in `1..=99999999999`.
+	Overflow(String),
+	/// The range starts above where it ends, as in `100..=1`.
+	Reversed
+	{
+		/// Where the range starts.
+		lo: i32,
+		/// Where the range ends.
+		hi: i32,
+	},
+	/// A half-open range that leaves out its only value, as in `5..5`.
+	Empty(i32),
+}
+
+/// Formats RangeSpecError as a message that can be shown to the user.
+impl Display for RangeSpecError
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			RangeSpecError::MissingBound(spec) if spec.is_empty() => write!(f, "a range is needed, like 1..=100"),
+			RangeSpecError::MissingBound(spec) => write!(f, "{spec} needs both a start and an end, like 1..=100"),
+			RangeSpecError::MissingDots(spec) => write!(f, "{spec} needs two dots between the start and the end, like 1..=100"),
+			RangeSpecError::TooManyDots(spec) => write!(f, "{spec} has too many dots, since a range is written like 1..=100 or 1..101"),
+			RangeSpecError::NotANumber(text) => write!(f, "{text} is not a whole number"),
+			RangeSpecError::Overflow(text) => write!(f, "{text} does not fit in a whole number from {} through {}", i32::MIN, i32::MAX),
+			RangeSpecError::Reversed { lo, hi } => write!(f, "the range starts at {lo}, above where it ends at {hi}, so maybe {hi}..={lo} was meant"),
+			RangeSpecError::Empty(value) => write!(f, "the range {value}..{value} is empty, since it leaves out {value}, so maybe {value}..={value} was meant"),
+		}
+	}
+}
+
+impl std::error::Error for RangeSpecError {}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn spellings()
+	{
+		let cases = [
+			("1..=100", 1..=100),
+			("1..101", 1..=100),
+			("100", 0..=100),
+			("0", 0..=0),
+			("-50..=50", -50..=50),
+			("-50..-40", -50..=-41),
+			("7..=7", 7..=7),
+			("7..8", 7..=7),
+			(" 1 ..= 100 ", 1..=100),
+			("+5..=+10", 5..=10),
+			("-2147483648..=2147483647", i32::MIN..=i32::MAX),
+			("-2147483648..2147483647", i32::MIN..=(i32::MAX - 1)),
+		];
+		for (spec, expected) in cases
+		{
+			let parsed: RangeSpec = spec.parse().expect(spec);
+			assert_eq!(parsed.range(), expected, "{spec}");
+			assert_eq!(parsed.to_string().parse(), Ok(parsed), "{spec}");
+		}
+		assert_eq!("1..101".parse::<RangeSpec>().map(|spec| spec.to_string()), Ok(String::from("1..=100")));
+	}
+
+	#[test]
+	fn malformed()
+	{
+		let cases = [
+			("", RangeSpecError::MissingBound(String::new()), "a range is needed, like 1..=100"),
+			("..=100", RangeSpecError::MissingBound(String::from("..=100")), "..=100 needs both a start and an end, like 1..=100"),
+			("1..", RangeSpecError::MissingBound(String::from("1..")), "1.. needs both a start and an end, like 1..=100"),
+			("1.100", RangeSpecError::MissingDots(String::from("1.100")), "1.100 needs two dots between the start and the end, like 1..=100"),
+			("1...100", RangeSpecError::TooManyDots(String::from("1...100")), "1...100 has too many dots, since a range is written like 1..=100 or 1..101"),
+			("1..=x", RangeSpecError::NotANumber(String::from("x")), "x is not a whole number"),
+			("1-100", RangeSpecError::NotANumber(String::from("1-100")), "1-100 is not a whole number"),
+			("1..=99999999999", RangeSpecError::Overflow(String::from("99999999999")), "99999999999 does not fit in a whole number from -2147483648 through 2147483647"),
+			("-2147483649", RangeSpecError::Overflow(String::from("-2147483649")), "-2147483649 does not fit in a whole number from -2147483648 through 2147483647"),
+			("100..=1", RangeSpecError::Reversed { lo: 100, hi: 1 }, "the range starts at 100, above where it ends at 1, so maybe 1..=100 was meant"),
+			("-5", RangeSpecError::Reversed { lo: 0, hi: -5 }, "the range starts at 0, above where it ends at -5, so maybe -5..=0 was meant"),
+			("5..5", RangeSpecError::Empty(5), "the range 5..5 is empty, since it leaves out 5, so maybe 5..=5 was meant"),
+		];
+		for (spec, error, message) in cases
+		{
+			assert_eq!(spec.parse::<RangeSpec>(), Err(error.clone()), "{spec}");
+			assert_eq!(error.to_string(), message);
+		}
+	}
+}
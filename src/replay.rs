@@ -0,0 +1,454 @@
+use {
+	crate::{
+		save::{
+			obfuscate,
+			reveal,
+		},
+		BonusRule,
+		Clock,
+		Game,
+		GameOutcome,
+		Guess,
+		GuessRange,
+		Hints,
+		InputOutput,
+		LoadError,
+		Style,
+		GIVE_UP_COMMANDS,
+		HINT_COMMANDS,
+		QUIT_COMMANDS,
+	},
+	serde::{
+		Deserialize,
+		Serialize,
+	},
+	std::{
+		io::{
+			self,
+			BufRead,
+			Read,
+			Write,
+		},
+		num::NonZeroU32,
+		ops::RangeInclusive,
+		result::Result,
+		sync::{
+			Arc,
+			Mutex,
+		},
+		thread,
+		time::{
+			Duration,
+			Instant,
+		},
+		vec,
+	},
+};
+
+/// Version of the replay format written by Replay::write. Replays of older
+/// versions can still be read.
+const REPLAY_VERSION: u32 = 1;
+
+/// A game that was played, kept so that it can be watched again. See
+/// Game::replay.
+///
+/// A replay keeps the secret, the rules and every move with the time it was
+/// made, but not the text that was shown. That is rendered again when the
+/// replay is played, with the same code that showed it the first time, so a
+/// replay looks like the game would look today, in whatever style it is played
+/// in. Invalid guesses did not count, so they are not kept.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		Game,
+/// // 		Replay,
+/// // 		Style,
+/// // 		WithErrors,
+/// // 	},
+/// // 	std::{
+/// // 		fs::File,
+/// // 		io::{
+/// // 			stderr,
+/// // 			stdin,
+/// // 			stdout,
+/// // 		},
+/// // 		time::Duration,
+/// // 	},
+/// //};
+/// // let mut game = Game::<{ 0..=1024 }>::new_random(10);
+/// // game.play(&mut stdin().lock(), &mut stdout());
+/// // game.replay().write(File::create("replay.json").expect("")).expect("");
+/// // let replay = Replay::read(File::open("replay.json").expect("")).expect("");
+/// // let mut output = WithErrors { output: stdout(), errors: stderr() };
+/// // replay.play::<{ 0..=1024 }>(Style::Fancy, Duration::from_secs(1), Some(&mut stdout()), &mut output).expect("");
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct Replay
+{
+	version: u32,
+	/// The secret, hidden as in Game::save.
+	secret: String,
+	/// How many attempts the game had, or None in practice.
+	attempts: Option<u32>,
+	lo: i32,
+	hi: i32,
+	hints: String,
+	#[serde(default)]
+	time_limit_ms: Option<u64>,
+	/// How close a guess had to be to earn the bonus attempt, or None without
+	/// one.
+	#[serde(default)]
+	bonus_within: Option<u32>,
+	/// Which guess the game lied about, counting from 1, or None if it could not
+	/// lie.
+	#[serde(default)]
+	lie: Option<u32>,
+	moves: Vec<ReplayMove>,
+	/// Whether the game ended with the player giving up.
+	#[serde(default)]
+	gave_up: bool,
+}
+
+/// A move of a Replay.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+#[serde(tag = "move", rename_all = "snake_case")]
+enum ReplayMove
+{
+	/// A valid guess, made elapsed_ms after the game started.
+	Guess
+	{
+		guess: i32,
+		elapsed_ms: u64,
+	},
+	/// A hint that was asked for.
+	Hint,
+}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Returns a replay of the game so far, which can be written to a file with
+	/// Replay::write and watched with Replay::play.
+	///
+	/// The guesses and hints are kept in the order they were made, as well as
+	/// the guess that came too late if the game timed out. A game that was
+	/// loaded with Game::load forgets which guess that was, so its replay stops
+	/// before it.
+	pub fn replay(&self) -> Replay
+	{
+		let mut moves = Vec::with_capacity(self.history.len() + self.clues.len());
+		let mut guesses = self.history.iter().chain(&self.late).peekable();
+		for attempt in 1..=self.attempts_used() + u32::from(self.late.is_some())
+		{
+			match guesses.next_if(|entry| entry.attempt == attempt)
+			{
+				Some(entry) => moves.push(ReplayMove::Guess { guess: entry.guess.value(), elapsed_ms: millis(entry.elapsed) }),
+				None => moves.push(ReplayMove::Hint),
+			}
+		}
+		Replay {
+			version: REPLAY_VERSION,
+			secret: obfuscate(self.secret().reveal().value()),
+			attempts: self.attempts().map(NonZeroU32::get),
+			lo: self.range.lo().value(),
+			hi: self.range.hi().value(),
+			hints: self.hints.name().to_string(),
+			time_limit_ms: self.time_limit.map(millis),
+			bonus_within: self.bonus.map(|bonus| bonus.within),
+			lie: self.lie,
+			moves,
+			gave_up: self.gave_up,
+		}
+	}
+}
+
+impl Replay
+{
+	/// Writes the replay to writer as JSON.
+	///
+	/// # Errors
+	/// Returns an error if writing to writer fails.
+	pub fn write(
+		&self,
+		writer: impl Write,
+	) -> Result<(), serde_json::Error>
+	{
+		serde_json::to_writer(writer, self)
+	}
+
+	/// Reads a replay that was written by Replay::write from reader, by this or
+	/// an older version of the game.
+	///
+	/// # Errors
+	/// Returns LoadError::Json if reader fails or does not hold a replay, and
+	/// LoadError::Version if the replay is too new.
+	pub fn read(reader: impl Read) -> Result<Self, LoadError>
+	{
+		let replay: Replay = serde_json::from_reader(reader)?;
+		if replay.version > REPLAY_VERSION
+		{
+			return Err(LoadError::Version(replay.version));
+		}
+		Ok(replay)
+	}
+
+	/// Plays the game again from the start, writing to output what was shown when
+	/// it was first played, and returns how it ended.
+	///
+	/// Before each move the replay waits for delay, and then writes the line that
+	/// was typed for it to echo, if there is one, since output only gets what the
+	/// game wrote. The clock of the game is moved to when each guess was made, so
+	/// the time left is shown as it was. A game that was aborted ends with the
+	/// player quitting.
+	///
+	/// # Errors
+	/// Returns LoadError::Corrupt if the replay is not of a game in RANGE, or
+	/// makes no sense, which usually means that the file was edited.
+	pub fn play<const RANGE: RangeInclusive<i32>>(
+		&self,
+		style: Style,
+		delay: Duration,
+		echo: Option<&mut dyn Write>,
+		output: &mut impl InputOutput,
+	) -> Result<GameOutcome<RANGE>, LoadError>
+	{
+		let mut game = self.game::<RANGE>()?;
+		game.style = style;
+		let clock = Arc::new(ReplayClock { start: Instant::now(), elapsed: Mutex::new(Duration::ZERO) });
+		game.set_clock(clock.clone());
+		let mut lines: Vec<_> = self
+			.moves
+			.iter()
+			.map(|&replay_move| match replay_move
+			{
+				ReplayMove::Guess { guess, elapsed_ms } => (guess.to_string(), Some(Duration::from_millis(elapsed_ms))),
+				ReplayMove::Hint => (HINT_COMMANDS[0].to_string(), None),
+			})
+			.collect();
+		// A game that was won or lost stops reading before this.
+		let last = if self.gave_up { GIVE_UP_COMMANDS[0] } else { QUIT_COMMANDS[1] };
+		lines.push((last.to_string(), None));
+		let mut input = ReplayInput { lines: lines.into_iter(), line: Vec::new(), read: 0, clock, delay, echo };
+		Ok(game.play(&mut input, output))
+	}
+
+	/// Creates the game the replay starts with, checking that it makes sense.
+	fn game<const RANGE: RangeInclusive<i32>>(&self) -> Result<Game<RANGE>, LoadError>
+	{
+		let guess = |value: i32| Guess::<RANGE>::new(value).ok();
+		let secret = reveal(&self.secret).ok_or(LoadError::Corrupt("the secret is not eight hex digits"))?;
+		let range = guess(self.lo)
+			.zip(guess(self.hi))
+			.and_then(|(lo, hi)| GuessRange::new(lo, hi))
+			.ok_or(LoadError::Corrupt("the range is not within RANGE"))?;
+		let secret = guess(secret).filter(|secret| range.contains(*secret)).ok_or(LoadError::Corrupt("the secret is not within the range"))?;
+		let hints = Hints::ALL.into_iter().find(|hints| hints.name() == self.hints).ok_or(LoadError::Corrupt("the hints are unknown"))?;
+		let attempts = match self.attempts
+		{
+			None => None,
+			Some(attempts) => Some(NonZeroU32::new(attempts).ok_or(LoadError::Corrupt("there are no attempts"))?),
+		};
+		if self.moves.iter().any(|replay_move| matches!(*replay_move, ReplayMove::Guess { guess: value, .. } if guess(value).is_none()))
+		{
+			return Err(LoadError::Corrupt("a guess is not within RANGE"));
+		}
+		if self.lie == Some(0)
+		{
+			return Err(LoadError::Corrupt("the lie is not about a guess"));
+		}
+		let mut game = Game::with_secret(1, secret);
+		game.set_attempts(attempts);
+		game.range = range;
+		game.hints = hints;
+		game.time_limit = self.time_limit_ms.map(Duration::from_millis);
+		game.bonus = self.bonus_within.map(|within| BonusRule { within });
+		game.lie = self.lie;
+		Ok(game)
+	}
+}
+
+/// Returns duration in whole milliseconds, as it is written to a file.
+fn millis(duration: Duration) -> u64
+{
+	u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// A clock that is moved to the times in a replay as its moves are read.
+#[derive(Debug)]
+struct ReplayClock
+{
+	start: Instant,
+	elapsed: Mutex<Duration>,
+}
+
+impl Clock for ReplayClock
+{
+	fn now(&self) -> Instant
+	{
+		self.start + *self.elapsed.lock().expect("clock was poisoned.")
+	}
+}
+
+/// Input that types the lines of a replay, each at its time.
+struct ReplayInput<'a>
+{
+	/// The lines that are left, with the time each was typed at, if it matters.
+	lines: vec::IntoIter<(String, Option<Duration>)>,
+	line: Vec<u8>,
+	read: usize,
+	clock: Arc<ReplayClock>,
+	delay: Duration,
+	echo: Option<&'a mut dyn Write>,
+}
+
+impl Read for ReplayInput<'_>
+{
+	fn read(
+		&mut self,
+		buf: &mut [u8],
+	) -> io::Result<usize>
+	{
+		let available = self.fill_buf()?;
+		let amount = available.len().min(buf.len());
+		buf[..amount].copy_from_slice(&available[..amount]);
+		self.consume(amount);
+		Ok(amount)
+	}
+}
+
+impl BufRead for ReplayInput<'_>
+{
+	fn fill_buf(&mut self) -> io::Result<&[u8]>
+	{
+		if self.read == self.line.len()
+			&& let Some((line, elapsed)) = self.lines.next()
+		{
+			thread::sleep(self.delay);
+			if let Some(elapsed) = elapsed
+			{
+				*self.clock.elapsed.lock().expect("clock was poisoned.") = elapsed;
+			}
+			if let Some(echo) = &mut self.echo
+			{
+				writeln!(echo, "{line}")?;
+			}
+			self.line = format!("{line}\n").into_bytes();
+			self.read = 0;
+		}
+		Ok(&self.line[self.read..])
+	}
+
+	fn consume(
+		&mut self,
+		amount: usize,
+	)
+	{
+		self.read += amount;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			clock::TestClock,
+			WithErrors,
+		},
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Creates a game of five attempts whose secret is 300, in plain style.
+	fn game() -> Game<{ 0..=1024 }>
+	{
+		Game { style: Style::Plain, ..Game::with_secret(5, guess(300)) }
+	}
+
+	/// Writes replay to JSON and reads it back.
+	fn round_trip(replay: &Replay) -> Replay
+	{
+		let mut file = Vec::new();
+		replay.write(&mut file).expect("write failed.");
+		Replay::read(file.as_slice()).expect("read failed.")
+	}
+
+	/// Plays replay in plain style, returning how it ended, the output and the
+	/// errors.
+	fn watch(replay: &Replay) -> (GameOutcome<{ 0..=1024 }>, String, String)
+	{
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = replay.play(Style::Plain, Duration::ZERO, None, &mut output).expect("play failed.");
+		let text = |bytes: Vec<u8>| String::from_utf8(bytes).expect("output was not UTF-8.");
+		(outcome, text(output.output), text(output.errors))
+	}
+
+	#[test]
+	fn matches_original()
+	{
+		for script in ["512\nhint\n256\n300\n", "512\n256\nhint\nhint\n1000\n", "512\ngive up\n", "1\n2\nquit\n", "1\n2\n3\n4\n305\n300\n"]
+		{
+			let mut original = Game { bonus: Some(BonusRule { within: 10 }), ..game() };
+			let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+			let outcome = original.play(&mut script.as_bytes(), &mut output);
+			let text = |bytes: Vec<u8>| String::from_utf8(bytes).expect("output was not UTF-8.");
+			let replay = round_trip(&original.replay());
+			assert_eq!(watch(&replay), (outcome, text(output.output), text(output.errors)), "{script:?}");
+		}
+	}
+
+	#[test]
+	fn echo()
+	{
+		let mut original = game();
+		original.play(&mut "512\nhint\n300\n".as_bytes(), &mut WithErrors { output: Vec::new(), errors: Vec::new() });
+		let mut echo = Vec::new();
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let outcome = original.replay().play::<{ 0..=1024 }>(Style::Plain, Duration::ZERO, Some(&mut echo), &mut output).expect("play failed.");
+		assert_eq!(outcome, GameOutcome::Won { attempts_used: 3 });
+		// The game stops reading once it is won, so the final quit is not typed.
+		assert_eq!(echo, b"512\nhint\n300\n");
+	}
+
+	#[test]
+	fn timed_out()
+	{
+		let clock = Arc::new(TestClock::new());
+		let mut original = game();
+		original.set_clock(clock.clone());
+		original.time_limit = Some(Duration::from_secs(10));
+		original.submit(guess(512)).expect("submit failed.");
+		clock.advance(Duration::from_secs(11));
+		original.submit(guess(400)).expect("submit failed.");
+		let replay = round_trip(&original.replay());
+		let (outcome, output, _) = watch(&replay);
+		assert_eq!(outcome, GameOutcome::TimedOut { answer: guess(300) });
+		assert!(output.contains("You have 4 attempts and 10 seconds remaining. Guess: "), "{output}");
+		assert!(output.contains("Time's up! 400 came too late, and the number was 300."), "{output}");
+	}
+
+	#[test]
+	fn versions()
+	{
+		let mut original = game();
+		original.submit(guess(512)).expect("submit failed.");
+		let mut file = Vec::new();
+		original.replay().write(&mut file).expect("write failed.");
+		let file = String::from_utf8(file).expect("replay was not UTF-8.");
+		assert!(file.contains("{\"move\":\"guess\",\"guess\":512,\"elapsed_ms\":"), "{file}");
+		assert!(!file.contains("300"), "{file}");
+
+		// Fields that were added later are optional, so older replays still work.
+		let old = file.replace(",\"time_limit_ms\":null", "").replace(",\"bonus_within\":null", "").replace(",\"lie\":null", "").replace(",\"gave_up\":false", "");
+		assert_eq!(Replay::read(old.as_bytes()).expect("read failed."), original.replay());
+		assert!(matches!(Replay::read(file.replace("\"version\":1", "\"version\":2").as_bytes()), Err(LoadError::Version(2))));
+		let corrupt = Replay::read(file.replace("\"guess\":512", "\"guess\":5000").as_bytes()).expect("read failed.");
+		assert!(matches!(corrupt.play::<{ 0..=1024 }>(Style::Plain, Duration::ZERO, None, &mut Vec::new()), Err(LoadError::Corrupt(_))));
+	}
+}
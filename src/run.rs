@@ -0,0 +1,854 @@
+use {
+	crate::{
+		cli::value,
+		daily_number,
+		flag_value,
+		play_coop,
+		play_golf,
+		simulate_kind,
+		today,
+		AdaptivePolicy,
+		ArgsError,
+		BonusRule,
+		Command,
+		ConfigError,
+		Difficulty,
+		ExitStatus,
+		Game,
+		GameArgs,
+		GameBuilder,
+		GameOutcome,
+		GuessRange,
+		Hints,
+		InputOutput,
+		Session,
+		StrategyKind,
+		Style,
+		DIFFICULTY_RANGE,
+		GOLF_HOLES,
+		USAGE,
+	},
+	rand::{
+		rngs::StdRng,
+		Rng,
+		SeedableRng,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			BufRead,
+			Write,
+		},
+		num::{
+			NonZeroU32,
+			NonZeroU64,
+		},
+		sync::{
+			atomic::AtomicBool,
+			Arc,
+		},
+		time::Duration,
+	},
+};
+#[cfg(feature = "save")]
+use {
+	crate::{
+		LoadError,
+		Replay,
+	},
+	std::{
+		fs::File,
+		io::BufReader,
+	},
+};
+#[cfg(feature = "stats")]
+use crate::{
+	Round,
+	Stats,
+	StatsFile,
+};
+
+/// Runs the commands of the binary, writing to the output it is handed and
+/// answering options that do not make sense with ExitStatus::Usage instead of
+/// exiting, so that the binary only has to pick the command.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		Command,
+/// // 		GameArgs,
+/// // 		Runner,
+/// // 		Style,
+/// // 		WithErrors,
+/// // 	},
+/// // 	std::io::{
+/// // 		stderr,
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let args = [String::from("--rounds"), String::from("3")];
+/// // let game_args = GameArgs::parse(&args).expect("");
+/// // let output = &mut WithErrors { output: stdout(), errors: stderr() };
+/// // let status = Runner::new(Style::Fancy).play(Command::Play, &args, game_args, &mut stdin().lock(), output);
+/// ```
+pub struct Runner
+{
+	/// How the games, reports and replays are written.
+	pub style: Style,
+	/// The flag that aborts the game being played, such as when Ctrl-C is
+	/// pressed. See Game::set_interrupt.
+	pub interrupt: Arc<AtomicBool>,
+	/// Where the lifetime stats are kept, or None if there is nowhere to keep
+	/// them.
+	#[cfg(feature = "stats")]
+	pub stats: Option<StatsFile>,
+}
+
+impl Runner
+{
+	/// Creates a runner that writes in style and keeps the stats in the data
+	/// directory, with an interrupt flag that nothing sets yet.
+	pub fn new(style: Style) -> Self
+	{
+		Runner {
+			style,
+			interrupt: Arc::new(AtomicBool::new(false)),
+			#[cfg(feature = "stats")]
+			stats: StatsFile::in_data_dir(),
+		}
+	}
+
+	/// `--help` lists the commands and options.
+	pub fn help(
+		&self,
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let result = write!(output.messages(), "{USAGE}");
+		written(result, "The help", output)
+	}
+
+	/// `stats` shows the lifetime stats, and `stats --json` shows them as JSON.
+	#[cfg(feature = "stats")]
+	pub fn stats(
+		&self,
+		args: &[String],
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let Some(file) = &self.stats
+		else
+		{
+			let _ = writeln!(output.errors(), "There are no stats, since this platform has no data directory to keep them in.");
+			return ExitStatus::Aborted;
+		};
+		let json = args.iter().any(|arg| arg == "--json");
+		let mut warnings = Vec::new();
+		let result = file.show(json, &mut output.messages(), &mut warnings);
+		let _ = output.errors().write_all(&warnings);
+		written(result, "The stats", output)
+	}
+
+	/// `stats` shows the lifetime stats, which this build does not keep.
+	#[cfg(not(feature = "stats"))]
+	pub fn stats(
+		&self,
+		_args: &[String],
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let _ = writeln!(output.errors(), "There are no stats, since this build leaves out the stats feature.");
+		ExitStatus::Usage
+	}
+
+	/// `simulate --games 10000 --strategy binary` lets the computer play games
+	/// of the difficulty and reports how it did, for balancing the presets.
+	pub fn simulate(
+		&self,
+		args: &[String],
+		game_args: GameArgs,
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let parsed = Setup::new(args, game_args).and_then(|setup| {
+			let games = value(args, "--games", "a whole number of at least 0", "--games 10000")?;
+			let kind = named(args, "--strategy", StrategyKind::from_name, "one of binary, linear, random", "--strategy binary")?;
+			Ok((setup, games.unwrap_or(1000), kind.unwrap_or_default()))
+		});
+		let (mut setup, games, kind) = match parsed
+		{
+			Ok(parsed) => parsed,
+			Err(error) => return usage(error, output),
+		};
+		let builder = match setup.checked_builder()
+		{
+			Ok(builder) => builder,
+			Err(error) => return usage(RunError::Config(error), output),
+		};
+		let new_game = |rng: &mut StdRng| builder.clone().build_with(rng).expect("the settings were checked.");
+		let report = simulate_kind(kind, setup.range, new_game, games, &mut setup.rng);
+		let result = write!(output.messages(), "{report}");
+		written(result, "The report", output)
+	}
+
+	/// `replay game.json --delay 500` plays back a game recorded with `--record
+	/// game.json`, waiting half a second before each move. The lines that were
+	/// typed are written to echo, as with Replay::play.
+	#[cfg(feature = "save")]
+	pub fn replay(
+		&self,
+		args: &[String],
+		echo: &mut impl Write,
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let path = args.first().map_or("", String::as_str);
+		let delay = match value(args, "--delay", "a whole number of milliseconds", "--delay 500")
+		{
+			Ok(millis) => millis.map_or(Duration::ZERO, Duration::from_millis),
+			Err(error) => return usage(error, output),
+		};
+		let played = File::open(path)
+			.map_err(LoadError::from)
+			.and_then(|file| Replay::read(BufReader::new(file)))
+			.and_then(|replay| replay.play::<DIFFICULTY_RANGE>(self.style, delay, Some(echo), output));
+		match played
+		{
+			Ok(_) => ExitStatus::Success,
+			Err(error) => usage(format_args!("{path} could not be replayed, since {error}"), output),
+		}
+	}
+
+	/// `replay` plays back a recorded game, which this build can not read.
+	#[cfg(not(feature = "save"))]
+	pub fn replay(
+		&self,
+		_args: &[String],
+		_echo: &mut impl Write,
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let _ = writeln!(output.errors(), "There is nothing to replay, since this build leaves out the save feature.");
+		ExitStatus::Usage
+	}
+
+	/// Plays the games of command, which is Command::Play, Command::Daily,
+	/// Command::Coop or Command::Golf, and returns how the last one went.
+	pub fn play(
+		&self,
+		command: Command,
+		args: &[String],
+		game_args: GameArgs,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> ExitStatus
+	{
+		let mut setup = match Setup::new(args, game_args)
+		{
+			Ok(setup) => setup,
+			Err(error) => return usage(error, output),
+		};
+		match command
+		{
+			// `daily` plays today's challenge, which is the same for everyone, and
+			// ends with a line to share.
+			Command::Daily =>
+			{
+				let day = today();
+				let mut game = Game::daily(day);
+				game.style = self.style;
+				game.set_interrupt(Arc::clone(&self.interrupt));
+				if game.play(input, output) != GameOutcome::Aborted
+				{
+					let result = writeln!(output.messages(), "{}", game.share(daily_number(day)));
+					let shared = written(result, "The line to share", output);
+					if shared != ExitStatus::Success
+					{
+						return shared;
+					}
+				}
+				return ExitStatus::of_game(&game);
+			},
+			// `coop --players Ann,Ben` lets two players take turns against one
+			// secret, sharing the attempts. Without `--players` the names are asked
+			// for.
+			Command::Coop =>
+			{
+				let names: Vec<_> = flag_value(args, "--players").map_or_else(Vec::new, |names| names.split(',').map(String::from).collect());
+				let builder = setup.builder().style(self.style).interrupt(Arc::clone(&self.interrupt));
+				let mut game = match builder.build_with(&mut setup.rng)
+				{
+					Ok(game) => game,
+					Err(error) => return usage(RunError::Config(error), output),
+				};
+				play_coop(&mut game, &names, input, output);
+				return ExitStatus::of_game(&game);
+			},
+			// `golf` plays nine holes of growing ranges, scored by strokes over par,
+			// and a round can not be lost, only left unfinished.
+			Command::Golf =>
+			{
+				let holes = play_golf(&mut setup.rng, self.style, input, output);
+				return if holes.len() == GOLF_HOLES.len() { ExitStatus::Success } else { ExitStatus::Aborted };
+			},
+			Command::Play | Command::Stats | Command::Simulate | Command::Replay => (),
+		}
+
+		let play_args = match PlayArgs::parse(args)
+		{
+			Ok(play_args) => play_args,
+			Err(error) => return usage(error, output),
+		};
+		let mut builder = match setup.checked_builder()
+		{
+			Ok(builder) => builder.liar(play_args.liar),
+			Err(error) => return usage(RunError::Config(error), output),
+		};
+		if play_args.bonus
+		{
+			builder = builder.bonus(BonusRule::percent_of_span(1, setup.range.size()));
+		}
+		let adaptive = play_args
+			.adaptive
+			.then(|| AdaptivePolicy::new(setup.range, Difficulty::Easy.span(), Difficulty::Insane.span(), StdRng::seed_from_u64(setup.rng.gen())));
+
+		// `--resume game.json` continues a game saved with `--save game.json` as the
+		// first round.
+		#[cfg(feature = "save")]
+		let mut resumed = match flag_value(args, "--resume")
+		{
+			None => None,
+			Some(path) => match File::open(path).map_err(LoadError::from).and_then(|file| Game::load(BufReader::new(file)))
+			{
+				Ok(game) if !game.is_finished() => Some(game),
+				Ok(_) => return usage(format_args!("The game in {path} is already finished"), output),
+				Err(error) => return usage(format_args!("{path} could not be resumed, since {error}"), output),
+			},
+		};
+		#[cfg(not(feature = "save"))]
+		let mut resumed = None;
+
+		// Lifetime stats are kept in the data directory. Problems saving them are
+		// written once the session is over, since it writes to output until then.
+		#[cfg(feature = "stats")]
+		let mut stats = self.stats.as_ref().map_or_else(Stats::default, |file| file.load(&mut output.errors()));
+		#[cfg(feature = "stats")]
+		let mut warnings = Vec::new();
+
+		#[cfg(feature = "stats")]
+		let difficulty = setup.difficulty;
+		let mut session = Session::new(|| {
+			// A resumed game keeps the bonus and lie it was saved with.
+			let mut game = resumed.take().unwrap_or_else(|| builder.clone().build_with(&mut setup.rng).expect("the settings were checked."));
+			game.style = self.style;
+			game.time_limit = game.time_limit.or(play_args.time_limit);
+			game.forgive_duplicates = play_args.forgive_duplicates;
+			game.show_odds = play_args.show_odds;
+			game.speedrun = play_args.speedrun;
+			game.set_interrupt(Arc::clone(&self.interrupt));
+			if play_args.practice || play_args.speedrun
+			{
+				game.set_attempts(None);
+			}
+			game
+		});
+		session.analyze = play_args.analyze;
+		session.adaptive = adaptive;
+		session.play_rounds_with(play_args.rounds, input, output, |round| {
+			#[cfg(feature = "stats")]
+			if !play_args.practice
+			{
+				return record(round, difficulty, play_args.speedrun, &mut stats, self.stats.as_ref(), &mut warnings);
+			}
+			#[cfg(not(feature = "stats"))]
+			let _ = round;
+			false
+		});
+		#[cfg(feature = "stats")]
+		let _ = output.errors().write_all(&warnings);
+
+		// The last game tells how the session went, such as a loss that ended it.
+		let status = session.last_game().map_or(ExitStatus::Aborted, ExitStatus::of_game);
+
+		// `--record game.json` keeps the last game, so it can be watched again with
+		// `replay game.json`.
+		#[cfg(feature = "save")]
+		if let Some(path) = flag_value(args, "--record")
+			&& let Some(game) = session.last_game()
+		{
+			let _ = match File::create(path).map_err(serde_json::Error::io).and_then(|file| game.replay().write(file))
+			{
+				Ok(()) => writeln!(output.errors(), "Recorded the game to {path}. Watch it with replay {path}."),
+				Err(error) => writeln!(output.errors(), "The game could not be recorded to {path}, since {error}."),
+			};
+		}
+
+		// `--save game.json` keeps a game that was quit, so it can be resumed.
+		#[cfg(feature = "save")]
+		if let Some(path) = flag_value(args, "--save")
+			&& let Some(game) = session.interrupted()
+		{
+			let _ = match File::create(path).map_err(serde_json::Error::io).and_then(|file| game.save(file))
+			{
+				Ok(()) => writeln!(output.errors(), "Saved the game to {path}. Continue it with --resume {path}."),
+				Err(error) => writeln!(output.errors(), "The game could not be saved to {path}, since {error}."),
+			};
+		}
+
+		status
+	}
+}
+
+/// The settings that every command which plays or simulates games shares.
+struct Setup
+{
+	game_args: GameArgs,
+	difficulty: Difficulty,
+	hints: Hints,
+	range: GuessRange<DIFFICULTY_RANGE>,
+	rng: StdRng,
+}
+
+impl Setup
+{
+	/// Reads the settings from args.
+	fn new(
+		args: &[String],
+		game_args: GameArgs,
+	) -> Result<Self, RunError>
+	{
+		// `--difficulty hard` picks the range, attempts and hints.
+		let difficulty = named(args, "--difficulty", Difficulty::from_name, "one of easy, normal, hard, insane", "--difficulty hard")?.unwrap_or_default();
+
+		// `--hints none` leaves out which way a wrong guess is off, and gives more
+		// attempts to make up for it.
+		let hints = named(args, "--hints", Hints::from_name, "one of none, direction, heat", "--hints none")?.unwrap_or(difficulty.hints());
+
+		// `--min` or `--max` alone keeps the other side of the difficulty's range.
+		let range = game_args.range::<DIFFICULTY_RANGE>(difficulty.range())?;
+
+		let rng = game_args.seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+		Ok(Setup { game_args, difficulty, hints, range, rng })
+	}
+
+	/// Returns a builder for a game with the settings.
+	fn builder(&self) -> GameBuilder<DIFFICULTY_RANGE>
+	{
+		self.game_args.apply(Game::builder().difficulty(self.difficulty).hints(self.hints), self.range)
+	}
+
+	/// Returns a builder like Setup::builder, after checking that its settings
+	/// fit together, so that every game it builds can be built. The check does
+	/// not use up the rng, so the secrets stay those of the seed.
+	fn checked_builder(&self) -> Result<GameBuilder<DIFFICULTY_RANGE>, ConfigError>
+	{
+		let builder = self.builder();
+		builder.clone().build_with(&mut self.rng.clone())?;
+		Ok(builder)
+	}
+}
+
+/// The options of Command::Play that neither GameArgs nor Setup reads.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+struct PlayArgs
+{
+	rounds: Option<u32>,
+	time_limit: Option<Duration>,
+	practice: bool,
+	forgive_duplicates: bool,
+	show_odds: bool,
+	speedrun: bool,
+	bonus: bool,
+	liar: bool,
+	adaptive: bool,
+	analyze: bool,
+}
+
+impl PlayArgs
+{
+	/// Reads the options from args.
+	fn parse(args: &[String]) -> Result<Self, ArgsError>
+	{
+		let flag = |name: &str| args.iter().any(|arg| arg == name);
+		Ok(PlayArgs {
+			// `--rounds 3` plays three games, and otherwise the player is asked
+			// whether to play again after each one.
+			rounds: value(args, "--rounds", "a whole number of at least 1", "--rounds 3")?.map(NonZeroU32::get),
+			// `--time-limit 60` loses a game that takes longer than a minute.
+			time_limit: value(args, "--time-limit", "a whole number of seconds of at least 1", "--time-limit 60")?.map(|seconds: NonZeroU64| Duration::from_secs(seconds.get())),
+			// `--practice` gives as many attempts as it takes, and practice games
+			// are left out of the stats.
+			practice: flag("--practice"),
+			// `--forgive-duplicates` does not count a guess that was already made.
+			forgive_duplicates: flag("--forgive-duplicates"),
+			// `--show-odds` follows each wrong guess with the chance of still
+			// winning.
+			show_odds: flag("--show-odds"),
+			// `--speedrun` gives as many guesses as it takes and times the game from
+			// the first prompt instead, keeping the best time for each difficulty.
+			speedrun: flag("--speedrun"),
+			// `--bonus` gives one extra attempt for a guess within 1% of the secret.
+			bonus: flag("--bonus"),
+			// `--liar` lets every game lie once, for a few extra attempts.
+			liar: flag("--liar"),
+			// `--adaptive` widens the range after a win with attempts to spare and
+			// narrows it after a loss.
+			adaptive: flag("--adaptive"),
+			// Won rounds are compared with optimal play, and `--analyze` compares
+			// the rest too.
+			analyze: flag("--analyze"),
+		})
+	}
+}
+
+/// Error that keeps a command from running, which makes it exit with
+/// ExitStatus::Usage.
+#[derive(Debug, PartialEq, Clone)]
+enum RunError
+{
+	/// An option does not make sense.
+	Args(ArgsError),
+	/// The options set up a game that does not fit together.
+	Config(ConfigError),
+}
+
+impl From<ArgsError> for RunError
+{
+	fn from(error: ArgsError) -> Self
+	{
+		RunError::Args(error)
+	}
+}
+
+impl From<ConfigError> for RunError
+{
+	fn from(error: ConfigError) -> Self
+	{
+		RunError::Config(error)
+	}
+}
+
+/// Formats RunError as a message that can be shown to the user.
+impl Display for RunError
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			RunError::Args(error) => write!(f, "{error}"),
+			RunError::Config(error) => write!(f, "The game could not be set up, since {error}"),
+		}
+	}
+}
+
+/// Returns the value of flag in args made into a T by from_name, or None if
+/// flag is not in args.
+fn named<T>(
+	args: &[String],
+	flag: &'static str,
+	from_name: impl FnOnce(&str) -> Option<T>,
+	expected: &'static str,
+	example: &'static str,
+) -> Result<Option<T>, ArgsError>
+{
+	flag_value(args, flag).map(|name| from_name(name).ok_or(ArgsError::Invalid { flag, expected, example })).transpose()
+}
+
+/// Writes error to output as the reason the command did not run, and returns
+/// ExitStatus::Usage.
+fn usage(
+	error: impl Display,
+	output: &mut impl InputOutput,
+) -> ExitStatus
+{
+	let _ = writeln!(output.errors(), "{error}.");
+	ExitStatus::Usage
+}
+
+/// Returns the status after a command that plays no game wrote its output,
+/// explaining what went wrong unless nobody reads the output anymore.
+fn written(
+	result: io::Result<()>,
+	what: &str,
+	output: &mut impl InputOutput,
+) -> ExitStatus
+{
+	let status = ExitStatus::of_output(&result);
+	if status != ExitStatus::Success
+		&& let Err(error) = result
+	{
+		let _ = writeln!(output.errors(), "{what} could not be shown, since {error}.");
+	}
+	status
+}
+
+/// Records round in stats and saves them to file, which keeps the best time of
+/// a speedrun of difficulty and otherwise the outcome and grade. Returns
+/// whether the time is a new personal best, and writes to warnings if the
+/// stats could not be saved.
+#[cfg(feature = "stats")]
+fn record(
+	round: &Round<DIFFICULTY_RANGE>,
+	difficulty: Difficulty,
+	speedrun: bool,
+	stats: &mut Stats,
+	file: Option<&StatsFile>,
+	warnings: &mut impl Write,
+) -> bool
+{
+	let best = match round.run_time
+	{
+		Some(time) =>
+		{
+			let (recorded, best) = stats.record_time(difficulty, time);
+			*stats = recorded;
+			best
+		},
+		// A speedrun that was not won has no time to keep.
+		None if speedrun => return false,
+		None =>
+		{
+			*stats = stats.record(&round.outcome);
+			if let Some(grade) = round.grade
+			{
+				*stats = stats.record_grade(grade);
+			}
+			false
+		},
+	};
+	if let Some(file) = file
+		&& let Err(error) = file.save(stats)
+	{
+		let _ = writeln!(warnings, "Warning: the stats could not be saved to {}, since {error}.", file.path().display());
+	}
+	best
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::WithErrors,
+		std::{
+			fs,
+			path::{
+				Path,
+				PathBuf,
+			},
+		},
+	};
+
+	/// Returns a directory of its own for the test called name to keep files in.
+	fn directory(name: &str) -> PathBuf
+	{
+		std::env::temp_dir().join(format!("guessing_game_run_{name}_{}", std::process::id()))
+	}
+
+	/// Returns a runner in plain style that keeps the stats in directory.
+	#[cfg_attr(not(feature = "stats"), expect(unused_variables))]
+	fn runner(directory: &Path) -> Runner
+	{
+		Runner {
+			style: Style::Plain,
+			interrupt: Arc::new(AtomicBool::new(false)),
+			#[cfg(feature = "stats")]
+			stats: Some(StatsFile::new(directory.join("stats.json"))),
+		}
+	}
+
+	/// Runs the command in line with runner, as the binary would, with lines as
+	/// the input, and returns the status and what was written to output and to
+	/// errors.
+	fn run(
+		runner: &Runner,
+		line: &str,
+		lines: &str,
+	) -> (ExitStatus, String, String)
+	{
+		let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+		let (command, args) = Command::parse(&args).expect("parse failed.");
+		let game_args = GameArgs::parse(args).expect("parse failed.");
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let status = match command
+		{
+			Command::Stats => runner.stats(args, &mut output),
+			Command::Simulate => runner.simulate(args, game_args, &mut output),
+			Command::Replay => runner.replay(args, &mut Vec::new(), &mut output),
+			Command::Play | Command::Daily | Command::Coop | Command::Golf => runner.play(command, args, game_args, &mut lines.as_bytes(), &mut output),
+		};
+		(status, String::from_utf8(output.output).expect("output was not UTF-8."), String::from_utf8(output.errors).expect("errors were not UTF-8."))
+	}
+
+	#[test]
+	fn play()
+	{
+		let directory = directory("play");
+		let runner = runner(&directory);
+		let (status, output, errors) = run(&runner, "--rounds 2 --min 5 --max 5", "5\n5\n");
+		assert_eq!(status, ExitStatus::Success);
+		assert!(output.contains("from 5 through 5. You have 1 attempts to guess it!"), "{output}");
+		assert!(output.contains("Round 2: "), "{output}");
+		assert!(errors.is_empty(), "{errors}");
+
+		let (status, ..) = run(&runner, "play --min 5 --max 5", "quit\n");
+		assert_eq!(status, ExitStatus::Aborted);
+
+		// Both rounds are kept in the stats, and the game that was quit is not.
+		#[cfg(feature = "stats")]
+		{
+			let (status, output, _) = run(&runner, "stats --json", "");
+			assert_eq!(status, ExitStatus::Success);
+			assert!(output.starts_with("{\n  \"played\": 2,\n  \"wins\": 2,\n"), "{output}");
+		}
+
+		let _ = fs::remove_dir_all(&directory);
+	}
+
+	#[test]
+	fn daily()
+	{
+		let runner = runner(&directory("daily"));
+		let secret = Game::daily(today()).secret().reveal();
+		let (status, output, _) = run(&runner, "daily", &format!("{secret}\n"));
+		assert_eq!(status, ExitStatus::Success);
+		assert!(output.contains(&format!("\nGuessing Game #{} — 1/", daily_number(today()))), "{output}");
+		assert!(output.ends_with(" 🎯\n"), "{output}");
+	}
+
+	#[test]
+	fn coop()
+	{
+		let runner = runner(&directory("coop"));
+		let (status, output, _) = run(&runner, "coop --players Ann,Ben --min 5 --max 5", "5\n");
+		assert_eq!(status, ExitStatus::Success);
+		assert!(output.contains("Ann and Ben, you're in this together"), "{output}");
+		assert!(output.contains("Ann and Ben win together!"), "{output}");
+	}
+
+	#[test]
+	fn golf()
+	{
+		let runner = runner(&directory("golf"));
+		// The seed picks the secret of each hole in turn.
+		let mut rng = StdRng::seed_from_u64(3);
+		let lines: String = GOLF_HOLES.iter().map(|&hi| format!("{}\n", rng.gen_range(1..=hi))).collect();
+		let (status, output, _) = run(&runner, "golf --seed 3", &lines);
+		assert_eq!(status, ExitStatus::Success);
+		assert!(output.contains("\nTotal              80        9  -71\n"), "{output}");
+
+		let (status, ..) = run(&runner, "golf --seed 3", "quit\n");
+		assert_eq!(status, ExitStatus::Aborted);
+	}
+
+	#[test]
+	fn simulate()
+	{
+		let runner = runner(&directory("simulate"));
+		let (status, output, errors) = run(&runner, "simulate --games 10 --strategy binary --seed 1", "");
+		assert_eq!(status, ExitStatus::Success);
+		assert!(output.starts_with("Games: 10\nWon: 10 (100.0%)\n"), "{output}");
+		assert!(errors.is_empty(), "{errors}");
+	}
+
+	#[cfg(feature = "stats")]
+	#[test]
+	fn stats()
+	{
+		let directory = directory("stats");
+		let runner = runner(&directory);
+		let (status, output, _) = run(&runner, "stats", "");
+		assert_eq!(status, ExitStatus::Success);
+		assert_eq!(output, Stats::default().summarize());
+
+		// Practice is left out of the stats.
+		run(&runner, "--rounds 1 --min 5 --max 5 --practice", "5\n");
+		let (_, output, _) = run(&runner, "stats", "");
+		assert_eq!(output, Stats::default().summarize());
+
+		let without = Runner { stats: None, ..runner };
+		let (status, _, errors) = run(&without, "stats", "");
+		assert_eq!(status, ExitStatus::Aborted);
+		assert_eq!(errors, "There are no stats, since this platform has no data directory to keep them in.\n");
+
+		let _ = fs::remove_dir_all(&directory);
+	}
+
+	#[cfg(feature = "save")]
+	#[test]
+	fn replay()
+	{
+		let directory = directory("replay");
+		fs::create_dir_all(&directory).expect("create failed.");
+		let runner = runner(&directory);
+		let file = directory.join("game.json");
+		let path = file.display();
+
+		// Nine attempts find any secret from 1 through 9 by counting up.
+		let every: String = (1..=9).map(|value| format!("{value}\n")).collect();
+		let (status, _, errors) = run(&runner, &format!("--rounds 1 --min 1 --max 9 --attempts 9 --record {path}"), &every);
+		assert_eq!(status, ExitStatus::Success);
+		assert_eq!(errors, format!("Recorded the game to {path}. Watch it with replay {path}.\n"));
+		let (status, replayed, errors) = run(&runner, &format!("replay {path}"), "");
+		assert_eq!(status, ExitStatus::Success, "{errors}");
+		assert!(replayed.contains("from 1 through 9. You have 9 attempts to guess it!"), "{replayed}");
+
+		// A game that was quit is saved, and resuming it picks up where it stopped.
+		let line = format!("--min 1 --max 9 --attempts 9 --seed 4 --save {path}");
+		let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+		let mut setup = Setup::new(&args, GameArgs::parse(&args).expect("parse failed.")).expect("setup failed.");
+		let secret = setup.checked_builder().expect("build failed.").build_with(&mut setup.rng).expect("build failed.").secret().reveal();
+		let miss = if secret == 1 { 2 } else { 1 };
+		let (status, _, errors) = run(&runner, &line, &format!("{miss}\nquit\n"));
+		assert_eq!(status, ExitStatus::Aborted);
+		assert_eq!(errors, format!("Saved the game to {path}. Continue it with --resume {path}.\n"));
+		let rest: String = (1..=9).filter(|&value| value != miss).map(|value| format!("{value}\n")).collect();
+		let (status, output, _) = run(&runner, &format!("--rounds 1 --resume {path}"), &rest);
+		assert_eq!(status, ExitStatus::Success);
+		assert!(output.contains(&format!("Your guesses: {miss}")), "{output}");
+
+		let (status, _, errors) = run(&runner, &format!("replay {}", directory.join("missing.json").display()), "");
+		assert_eq!(status, ExitStatus::Usage);
+		assert!(errors.contains("missing.json could not be replayed, since "), "{errors}");
+
+		let _ = fs::remove_dir_all(&directory);
+	}
+
+	#[test]
+	fn usage()
+	{
+		let runner = runner(&directory("usage"));
+		let cases = [
+			("--rounds 0", "--rounds needs a whole number of at least 1, like --rounds 3.\n"),
+			("--time-limit soon", "--time-limit needs a whole number of seconds of at least 1, like --time-limit 60.\n"),
+			("--difficulty extreme", "--difficulty needs one of easy, normal, hard, insane, like --difficulty hard.\n"),
+			("daily --hints some", "--hints needs one of none, direction, heat, like --hints none.\n"),
+			("simulate --strategy smart", "--strategy needs one of binary, linear, random, like --strategy binary.\n"),
+			("simulate --games -1", "--games needs a whole number of at least 0, like --games 10000.\n"),
+			("--min -200000 --max 5", "The game could not be set up, since the range from -200000 through 5 does not fit in -100000 through 100000.\n"),
+		];
+		for (line, message) in cases
+		{
+			let (status, output, errors) = run(&runner, line, "");
+			assert_eq!(status, ExitStatus::Usage, "{line}");
+			assert_eq!(errors, message);
+			assert!(output.is_empty(), "{output}");
+		}
+
+		// The messages list every name there is.
+		let names = |names: &[&str]| names.join(", ");
+		assert!(cases[2].1.contains(&names(&Difficulty::ALL.map(Difficulty::name))));
+		assert!(cases[3].1.contains(&names(&Hints::ALL.map(Hints::name))));
+		assert!(cases[4].1.contains(&names(&[StrategyKind::Binary.name(), StrategyKind::Linear.name(), StrategyKind::Random.name()])));
+	}
+}
@@ -0,0 +1,456 @@
+use {
+	crate::{
+		game::lie_about,
+		BonusRule,
+		Feedback,
+		Game,
+		Guess,
+		GuessRange,
+		HintOracle,
+		Hints,
+		HistoryEntry,
+	},
+	serde::{
+		Deserialize,
+		Serialize,
+	},
+	std::{
+		fmt::{
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			Read,
+			Write,
+		},
+		num::NonZeroU32,
+		ops::RangeInclusive,
+		result::Result,
+		time::{
+			Duration,
+			Instant,
+		},
+	},
+};
+
+/// Version of the save file format written by Game::save.
+const SAVE_VERSION: u32 = 1;
+
+/// Key the secret is XORed with before it is saved, so that it is not readable
+/// at a glance. This is not encryption.
+const OBFUSCATION_KEY: u32 = 0x5ec2_e7a5;
+
+/// A game as it is written to a save file.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedGame
+{
+	version: u32,
+	secret: String,
+	/// How many attempts the game has, or None in practice.
+	attempts: Option<u32>,
+	lo: i32,
+	hi: i32,
+	hints: String,
+	gave_up: bool,
+	#[serde(default)]
+	timed_out: bool,
+	#[serde(default)]
+	time_limit_ms: Option<u64>,
+	#[serde(default)]
+	clues: u32,
+	/// How close a guess must be to earn the bonus attempt, or None without one.
+	#[serde(default)]
+	bonus_within: Option<u32>,
+	#[serde(default)]
+	bonus_earned: bool,
+	/// Which guess the game lies about, counting from 1, or None if it does not.
+	#[serde(default)]
+	lie: Option<u32>,
+	history: Vec<SavedGuess>,
+}
+
+/// A guess as it is written to a save file. Its feedback follows from the
+/// secret.
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedGuess
+{
+	guess: i32,
+	elapsed_ms: u64,
+	/// Which attempt the guess was, or 0 in files from before hints cost
+	/// attempts, where it is the one after the previous guess.
+	#[serde(default)]
+	attempt: u32,
+}
+
+/// Error returned when a saved game can not be loaded.
+#[derive(Debug)]
+pub enum LoadError
+{
+	/// Reading failed, or what was read is not a saved game.
+	Json(serde_json::Error),
+	/// The saved game was written by a newer version of the game.
+	Version(u32),
+	/// The saved game is complete, but makes no sense, such as a secret outside
+	/// of RANGE. This usually means that the file was edited.
+	Corrupt(&'static str),
+}
+
+impl From<serde_json::Error> for LoadError
+{
+	fn from(error: serde_json::Error) -> Self
+	{
+		LoadError::Json(error)
+	}
+}
+
+impl From<io::Error> for LoadError
+{
+	fn from(error: io::Error) -> Self
+	{
+		LoadError::Json(serde_json::Error::io(error))
+	}
+}
+
+/// Formats LoadError as a message that can be shown to the user.
+impl Display for LoadError
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		match self
+		{
+			LoadError::Json(error) => write!(f, "the saved game could not be read ({error})"),
+			LoadError::Version(version) => write!(f, "the saved game has version {version}, but only version {SAVE_VERSION} is supported"),
+			LoadError::Corrupt(reason) => write!(f, "the saved game is corrupt, since {reason}"),
+		}
+	}
+}
+
+impl std::error::Error for LoadError
+{
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+	{
+		match self
+		{
+			LoadError::Json(error) => Some(error),
+			LoadError::Version(_) | LoadError::Corrupt(_) => None,
+		}
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Game<RANGE>
+{
+	/// Writes the game to writer as JSON, so that it can be continued later with
+	/// Game::load.
+	///
+	/// The secret, the attempts, the range, the hints, the time limit, the bonus
+	/// rule and whether it was earned, which guess is lied about, the history and
+	/// how many clues were given are saved, while the style, the heat thresholds
+	/// and the clock are left to whoever loads it. The clues themselves follow
+	/// from the secret. The secret is saved as eight hex digits of its bits XORed
+	/// with 0x5ec2e7a5, so that looking at the file does not spoil it. Anyone who
+	/// wants to can still undo that.
+	///
+	/// # Errors
+	/// Returns an error if writing to writer fails.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use {
+	/// // 	guessing_game::Game,
+	/// // 	rand::thread_rng,
+	/// // 	std::fs::File,
+	/// //};
+	/// // let game = Game::<{ 0..=1024 }>::new(10, &mut thread_rng());
+	/// // game.save(File::create("game.json").expect("")).expect("");
+	/// // let game = Game::<{ 0..=1024 }>::load(File::open("game.json").expect("")).expect("");
+	/// ```
+	pub fn save(
+		&self,
+		writer: impl Write,
+	) -> Result<(), serde_json::Error>
+	{
+		let saved = SavedGame {
+			version: SAVE_VERSION,
+			secret: obfuscate(self.secret().reveal().value()),
+			attempts: self.attempts().map(NonZeroU32::get),
+			lo: self.range.lo().value(),
+			hi: self.range.hi().value(),
+			hints: self.hints.name().to_string(),
+			gave_up: self.gave_up,
+			timed_out: self.timed_out,
+			time_limit_ms: self.time_limit.map(|limit| u64::try_from(limit.as_millis()).unwrap_or(u64::MAX)),
+			clues: self.clues.len() as u32,
+			bonus_within: self.bonus.map(|bonus| bonus.within),
+			bonus_earned: self.bonus_earned,
+			lie: self.lie,
+			history: self
+				.history
+				.iter()
+				.map(|entry| SavedGuess {
+					guess: entry.guess.value(),
+					elapsed_ms: u64::try_from(entry.elapsed.as_millis()).unwrap_or(u64::MAX),
+					attempt: entry.attempt,
+				})
+				.collect(),
+		};
+		serde_json::to_writer(writer, &saved)
+	}
+
+	/// Reads a game that was written by Game::save from reader.
+	///
+	/// The game is checked as it is loaded, so a file that was edited into a
+	/// game that could not have been played is rejected. A finished game can be
+	/// loaded, for example to look at its history, but it takes no more guesses.
+	///
+	/// # Errors
+	/// Returns LoadError::Json if reader fails or does not hold a saved game,
+	/// LoadError::Version if the file is too new, and LoadError::Corrupt if the
+	/// game makes no sense.
+	pub fn load(reader: impl Read) -> Result<Self, LoadError>
+	{
+		let saved: SavedGame = serde_json::from_reader(reader)?;
+		if saved.version != SAVE_VERSION
+		{
+			return Err(LoadError::Version(saved.version));
+		}
+		let secret = reveal(&saved.secret).ok_or(LoadError::Corrupt("the secret is not eight hex digits"))?;
+		let guess = |value: i32| Guess::<RANGE>::new(value).ok();
+		let range = guess(saved.lo)
+			.zip(guess(saved.hi))
+			.and_then(|(lo, hi)| GuessRange::new(lo, hi))
+			.ok_or(LoadError::Corrupt("the range is not within RANGE"))?;
+		let secret = guess(secret).filter(|secret| range.contains(*secret)).ok_or(LoadError::Corrupt("the secret is not within the range"))?;
+		// Only exact names are written, so anything else was edited.
+		let hints = Hints::ALL.into_iter().find(|hints| hints.name() == saved.hints).ok_or(LoadError::Corrupt("the hints are unknown"))?;
+		if saved.clues as usize > HintOracle::<RANGE>::CLUES
+		{
+			return Err(LoadError::Corrupt("there are more clues than the oracle gives"));
+		}
+		let attempts = match saved.attempts
+		{
+			None => None,
+			Some(attempts) => Some(NonZeroU32::new(attempts).ok_or(LoadError::Corrupt("there are no attempts"))?),
+		};
+		if saved.bonus_earned && saved.bonus_within.is_none()
+		{
+			return Err(LoadError::Corrupt("the bonus attempt was earned without a bonus"));
+		}
+		if saved.lie == Some(0)
+		{
+			return Err(LoadError::Corrupt("the lie is not about a guess"));
+		}
+		let limit = attempts.map_or(u32::MAX, |attempts| attempts.get().saturating_add(u32::from(saved.bonus_earned)));
+		if saved.history.len() + saved.clues as usize > limit as usize
+		{
+			return Err(LoadError::Corrupt("there are more guesses and clues than attempts"));
+		}
+		let mut history = Vec::with_capacity(saved.history.len());
+		for saved_guess in &saved.history
+		{
+			let last: Option<&HistoryEntry<RANGE>> = history.last();
+			if last.is_some_and(|entry| entry.feedback.is_correct())
+			{
+				return Err(LoadError::Corrupt("there are guesses after the winning one"));
+			}
+			let previous = last.map_or(0_u32, |entry| entry.attempt);
+			let attempt = if saved_guess.attempt == 0 { previous + 1 } else { saved_guess.attempt };
+			if attempt <= previous || attempt > limit
+			{
+				return Err(LoadError::Corrupt("the attempts of the guesses are out of order"));
+			}
+			let guess = guess(saved_guess.guess).ok_or(LoadError::Corrupt("a guess is not within RANGE"))?;
+			let elapsed = Duration::from_millis(saved_guess.elapsed_ms);
+			let mut feedback = Feedback::from(guess.cmp(&secret));
+			if saved.lie == Some(history.len() as u32 + 1)
+			{
+				feedback = lie_about(feedback);
+			}
+			history.push(HistoryEntry { attempt, guess, feedback, elapsed });
+		}
+		let mut game = Game::with_secret(limit, secret);
+		game.set_attempts(attempts);
+		// The clock picks up where the last guess left off.
+		let elapsed = history.last().map_or(Duration::ZERO, |entry| entry.elapsed);
+		game.started = Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now);
+		game.hints = hints;
+		game.gave_up = saved.gave_up;
+		game.timed_out = saved.timed_out;
+		game.time_limit = saved.time_limit_ms.map(Duration::from_millis);
+		game.bonus = saved.bonus_within.map(|within| BonusRule { within });
+		game.bonus_earned = saved.bonus_earned;
+		game.lie = saved.lie;
+		game.history = history;
+		game.set_range(range);
+		game.clues = HintOracle::new(secret, range).take(saved.clues as usize).collect();
+		Ok(game)
+	}
+}
+
+/// Hides secret as eight hex digits. See Game::save.
+pub(crate) fn obfuscate(secret: i32) -> String
+{
+	format!("{:08x}", secret.cast_unsigned() ^ OBFUSCATION_KEY)
+}
+
+/// Undoes obfuscate, or returns None if text is not eight hex digits.
+pub(crate) fn reveal(text: &str) -> Option<i32>
+{
+	let bits = u32::from_str_radix(text, 16).ok().filter(|_| text.len() == 8 && text.bytes().all(|byte| byte.is_ascii_hexdigit()))?;
+	Some((bits ^ OBFUSCATION_KEY).cast_signed())
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Difficulty,
+			GameOutcome,
+			TurnResult,
+		},
+		rand::{
+			rngs::StdRng,
+			SeedableRng,
+		},
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	fn save(game: &Game<{ 0..=1024 }>) -> String
+	{
+		let mut file = Vec::new();
+		game.save(&mut file).expect("save failed.");
+		String::from_utf8(file).expect("save file was not UTF-8.")
+	}
+
+	#[test]
+	fn round_trip()
+	{
+		let mut game = Game::with_secret(10, guess(300));
+		game.time_limit = Some(Duration::from_secs(90));
+		game.submit(guess(512)).expect("submit failed.");
+		game.submit(guess(256)).expect("submit failed.");
+		let file = save(&game);
+		assert!(!file.contains("300"), "{file}");
+
+		let mut loaded = Game::<{ 0..=1024 }>::load(file.as_bytes()).expect("load failed.");
+		assert_eq!(loaded.secret(), game.secret());
+		assert_eq!(loaded.attempts_left(), Some(8));
+		assert_eq!(loaded.time_limit, Some(Duration::from_secs(90)));
+		assert_eq!(loaded.history().len(), 2);
+		for (loaded, entry) in loaded.history().iter().zip(game.history())
+		{
+			assert_eq!((loaded.attempt, loaded.guess, loaded.feedback), (entry.attempt, entry.guess, entry.feedback));
+			assert_eq!(loaded.elapsed.as_millis(), entry.elapsed.as_millis());
+		}
+		assert_eq!(loaded.submit(guess(300)), Ok(TurnResult::Win { attempts_used: 3 }));
+
+		// Clues are saved as how many were given, and keep their attempts.
+		let mut hinted = Game::with_secret(10, guess(300));
+		hinted.submit(guess(512)).expect("submit failed.");
+		hinted.hint().expect("hint failed.");
+		hinted.submit(guess(256)).expect("submit failed.");
+		let mut loaded = Game::<{ 0..=1024 }>::load(save(&hinted).as_bytes()).expect("load failed.");
+		assert_eq!(loaded.clues(), hinted.clues());
+		assert_eq!(loaded.history()[1].attempt, 3);
+		assert_eq!(loaded.hint().expect("hint failed."), HintOracle::new(guess(300), GuessRange::full()).nth(1));
+
+		// A bonus attempt that was earned stays earned.
+		let mut bonus = Game { bonus: Some(BonusRule { within: 10 }), ..Game::with_secret(2, guess(300)) };
+		bonus.submit(guess(305)).expect("submit failed.");
+		bonus.submit(guess(512)).expect("submit failed.");
+		let loaded = Game::<{ 0..=1024 }>::load(save(&bonus).as_bytes()).expect("load failed.");
+		assert_eq!((loaded.bonus, loaded.bonus_earned(), loaded.attempts_left()), (Some(BonusRule { within: 10 }), true, Some(1)));
+
+		// A lie stays a lie.
+		let mut liar = Game { lie: Some(1), ..Game::with_secret(10, guess(300)) };
+		liar.submit(guess(512)).expect("submit failed.");
+		let loaded = Game::<{ 0..=1024 }>::load(save(&liar).as_bytes()).expect("load failed.");
+		assert_eq!((loaded.history()[0].feedback, loaded.lied_on()), (Feedback::TooLow, Some(1)));
+
+		// Practice has no attempts to save.
+		let mut practice = Game::with_secret(10, guess(300));
+		practice.set_attempts(None);
+		practice.submit(guess(512)).expect("submit failed.");
+		let file = save(&practice);
+		assert!(file.contains("\"attempts\":null"), "{file}");
+		let loaded = Game::<{ 0..=1024 }>::load(file.as_bytes()).expect("load failed.");
+		assert!(loaded.is_practice());
+		assert_eq!(loaded.attempts_used(), 1);
+
+		// A game with a difficulty keeps its range and hints.
+		let game = Game::with_difficulty_and_rng(Difficulty::Hard, &mut StdRng::seed_from_u64(5));
+		let mut file = Vec::new();
+		game.save(&mut file).expect("save failed.");
+		let loaded = Game::<{ crate::DIFFICULTY_RANGE }>::load(file.as_slice()).expect("load failed.");
+		assert_eq!(loaded.range(), game.range());
+		assert_eq!(loaded.hints, Hints::Direction);
+		assert_eq!(loaded.secret(), game.secret());
+	}
+
+	#[test]
+	fn finished()
+	{
+		let mut game = Game::with_secret(10, guess(300));
+		game.submit(guess(300)).expect("submit failed.");
+		let loaded = Game::<{ 0..=1024 }>::load(save(&game).as_bytes()).expect("load failed.");
+		assert!(loaded.is_finished());
+
+		let mut given_up = Game::with_secret(10, guess(300));
+		given_up.gave_up = true;
+		let mut loaded = Game::<{ 0..=1024 }>::load(save(&given_up).as_bytes()).expect("load failed.");
+		assert_eq!(loaded.play_with_strategy(&mut crate::LinearStrategy::default()), GameOutcome::Lost { answer: guess(300) });
+	}
+
+	#[test]
+	fn corrupt()
+	{
+		let mut game = Game::with_secret(3, guess(300));
+		game.submit(guess(512)).expect("submit failed.");
+		let file = save(&game);
+		let load = |file: &str| Game::<{ 0..=1024 }>::load(file.as_bytes());
+
+		assert!(matches!(load(""), Err(LoadError::Json(_))));
+		assert!(matches!(load(&file[..file.len() / 2]), Err(LoadError::Json(_))));
+		assert!(matches!(load("{\"version\":1}"), Err(LoadError::Json(_))));
+		assert!(matches!(load(&file.replace("\"version\":1", "\"version\":2")), Err(LoadError::Version(2))));
+
+		let secret = obfuscate(300);
+		assert!(matches!(load(&file.replace(&secret, "zzzzzzzz")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace(&secret, "5ec2")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace(&secret, &obfuscate(5000))), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"guess\":512", "\"guess\":9999")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"hints\":\"heat\"", "\"hints\":\"psychic\"")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"attempts\":3", "\"attempts\":0")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"lo\":0", "\"lo\":400")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"clues\":0", "\"clues\":3")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"attempt\":1", "\"attempt\":4")), Err(LoadError::Corrupt(_))));
+		assert!(matches!(load(&file.replace("\"bonus_earned\":false", "\"bonus_earned\":true")), Err(LoadError::Corrupt(_))));
+		assert!(load(&file.replace(",\"clues\":0", "").replace(",\"attempt\":1", "")).is_ok());
+		let won_twice = file.replace("\"guess\":512", "\"guess\":300").replace("}]", "},{\"guess\":300,\"elapsed_ms\":0}]");
+		assert!(matches!(load(&won_twice), Err(LoadError::Corrupt(_))));
+
+		let error = load(&file.replace(&secret, "zzzzzzzz")).expect_err("load succeeded.");
+		assert_eq!(error.to_string(), "the saved game is corrupt, since the secret is not eight hex digits");
+	}
+
+	#[test]
+	fn obfuscation()
+	{
+		for secret in [0, 1, -1, 300, i32::MIN, i32::MAX]
+		{
+			assert_eq!(reveal(&obfuscate(secret)), Some(secret));
+		}
+		assert_eq!(obfuscate(0), "5ec2e7a5");
+		assert_eq!(reveal("+ec2e7a5"), None);
+	}
+}
@@ -0,0 +1,150 @@
+use {
+	crate::{
+		Difficulty,
+		GameOutcome,
+	},
+	std::{
+		ops::RangeInclusive,
+		time::Duration,
+	},
+};
+
+/// The numbers that score turns a game into points with.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		score,
+/// // 		GameOutcome,
+/// // 		ScoreConfig,
+/// // 	},
+/// // 	std::time::Duration,
+/// //};
+/// // let config = ScoreConfig::new(1000, 10);
+/// // let outcome = GameOutcome::<{ 1..=1000 }>::Won { attempts_used: 3 };
+/// // assert_eq!(score(&outcome, Duration::from_secs(30), &config), 1310);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct ScoreConfig
+{
+	/// How many values the secret could have been.
+	pub span: u64,
+	/// How many valid guesses the player got.
+	pub attempts: u32,
+	/// Points for each time the span can be halved, as in span.ilog2().
+	pub points_per_halving: u32,
+	/// Points for each attempt the player did not use.
+	pub points_per_unused_attempt: u32,
+	/// Points for each whole second the player finished before par.
+	pub points_per_second: u32,
+	/// How long a game may take before it earns no time bonus.
+	pub par: Duration,
+}
+
+impl ScoreConfig
+{
+	/// Creates a config for a game of span values and attempts valid guesses,
+	/// with the default points: 100 per halving, 50 per unused attempt and 2 per
+	/// second under a par of one minute.
+	pub const fn new(
+		span: u64,
+		attempts: u32,
+	) -> Self
+	{
+		ScoreConfig {
+			span,
+			attempts,
+			points_per_halving: 100_u32,
+			points_per_unused_attempt: 50_u32,
+			points_per_second: 2_u32,
+			par: Duration::from_secs(60_u64),
+		}
+	}
+}
+
+/// The config for a game of Difficulty::Normal.
+impl Default for ScoreConfig
+{
+	fn default() -> Self
+	{
+		ScoreConfig::new(Difficulty::Normal.span(), Difficulty::Normal.attempts())
+	}
+}
+
+/// Returns how many points a game earned, so that games can be compared.
+///
+/// A game that was lost, timed out or aborted earns 0. A game that was won earns
+/// the sum of:
+///
+/// - `span.ilog2() * points_per_halving`, so that bigger ranges are worth more.
+/// - `(attempts - attempts_used) * points_per_unused_attempt`.
+/// - `(par - elapsed).as_secs() * points_per_second`, or nothing if elapsed is
+///   par or longer.
+///
+/// With the default points, winning a game of 1 through 1000 with 10 attempts on
+/// the third guess after 30 seconds earns 900 + 350 + 60 = 1310.
+pub fn score<const RANGE: RangeInclusive<i32>>(
+	outcome: &GameOutcome<RANGE>,
+	elapsed: Duration,
+	config: &ScoreConfig,
+) -> u32
+{
+	let GameOutcome::Won { attempts_used } = *outcome
+	else
+	{
+		return 0;
+	};
+	let base = config.span.checked_ilog2().unwrap_or(0).saturating_mul(config.points_per_halving);
+	let unused = config.attempts.saturating_sub(attempts_used).saturating_mul(config.points_per_unused_attempt);
+	let seconds = u32::try_from(config.par.saturating_sub(elapsed).as_secs()).unwrap_or(u32::MAX);
+	let time = seconds.saturating_mul(config.points_per_second);
+	base.saturating_add(unused).saturating_add(time)
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::Guess,
+	};
+
+	#[test]
+	fn known_scores()
+	{
+		let won = |attempts_used| GameOutcome::<{ 1..=100000 }>::Won { attempts_used };
+		let scores = [
+			(Difficulty::Normal, won(3), Duration::from_secs(30), 1310),
+			(Difficulty::Normal, won(1), Duration::ZERO, 1470),
+			(Difficulty::Normal, won(10), Duration::from_secs(120), 900),
+			(Difficulty::Easy, won(4), Duration::from_millis(59900), 700),
+			(Difficulty::Hard, won(12), Duration::from_millis(500), 1418),
+			(Difficulty::Insane, won(10), Duration::from_secs(10), 1700),
+		];
+		for (difficulty, outcome, elapsed, expected) in scores
+		{
+			let config = ScoreConfig::new(difficulty.span(), difficulty.attempts());
+			assert_eq!(score(&outcome, elapsed, &config), expected, "{difficulty:?} {outcome:?} {elapsed:?}");
+		}
+	}
+
+	#[test]
+	fn lost_scores_nothing()
+	{
+		let answer = Guess::<{ 1..=1000 }>::new(300).expect("answer failed to construct.");
+		let config = ScoreConfig::default();
+		assert_eq!(score(&GameOutcome::Lost { answer }, Duration::ZERO, &config), 0);
+		assert_eq!(score(&GameOutcome::<{ 1..=1000 }>::Aborted, Duration::ZERO, &config), 0);
+	}
+
+	#[test]
+	fn extreme_configs()
+	{
+		let outcome = GameOutcome::<{ 1..=1000 }>::Won { attempts_used: 20 };
+		assert_eq!(score(&outcome, Duration::MAX, &ScoreConfig::new(0, 10)), 0);
+		let generous = ScoreConfig { points_per_second: u32::MAX, par: Duration::MAX, ..ScoreConfig::new(u64::MAX, u32::MAX) };
+		assert_eq!(score(&outcome, Duration::ZERO, &generous), u32::MAX);
+	}
+}
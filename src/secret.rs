@@ -0,0 +1,192 @@
+use {
+	crate::{
+		respond_to,
+		Feedback,
+		Guess,
+		ResponseTheme,
+		Style,
+		Win,
+	},
+	std::{
+		cmp::Ordering,
+		fmt::{
+			Debug,
+			Display,
+			Formatter,
+		},
+		io::{
+			self,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+		result::Result,
+	},
+};
+
+/// Respond to a user's input with some console output, comparing against a
+/// correct value that is kept secret. This is the same as respond.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_secret,
+/// // 		Guess,
+/// // 		Secret,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Secret::new(Guess::<{ 0..=100000 }>::new(1660_i32).expect(""));
+/// // assert!(respond_secret(example_guess, correct_guess, &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_secret<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Secret<Guess<RANGE>>,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_to(guess, correct.compare(guess), &ResponseTheme::default(), Style::Fancy, output)
+}
+
+/// Wrapper that keeps a value, such as the correct guess, from being revealed
+/// by formatting.
+///
+/// Both Debug and Display print `***`, so a Secret can be logged as part of a
+/// larger game state without spoiling it. The value can still be compared
+/// against, or deliberately revealed with Secret::reveal().
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T>
+{
+	/// Hides value.
+	pub const fn new(value: T) -> Self
+	{
+		Secret(value)
+	}
+
+	/// Returns the hidden value, for example to show it once the game is over.
+	pub fn reveal(self) -> T
+	{
+		self.0
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Secret<Guess<RANGE>>
+{
+	/// Returns how guess compares to the hidden value: Greater if the guess is
+	/// too high, Less if it is too low, and Equal if it is correct.
+	pub fn compare(
+		&self,
+		guess: Guess<RANGE>,
+	) -> Ordering
+	{
+		guess.cmp(&self.0)
+	}
+
+	/// Returns whether guess is the hidden value.
+	pub fn is(
+		&self,
+		guess: Guess<RANGE>,
+	) -> bool
+	{
+		guess == self.0
+	}
+}
+
+impl<T> Debug for Secret<T>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		f.write_str("***")
+	}
+}
+
+impl<T> Display for Secret<T>
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		f.pad("***")
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::respond,
+		std::io::stdout,
+	};
+
+	#[test]
+	fn formatting()
+	{
+		for value in [0_i32, 7_i32, 42_i32, 1024_i32]
+		{
+			let secret = Secret::new(Guess::<{ 0..=1024 }>::new(value).expect("secret failed to construct."));
+			for text in [format!("{secret}"), format!("{secret:?}"), format!("{secret:#?}"), format!("{secret:>10}")]
+			{
+				assert!(text.contains("***"));
+				assert!(!text.bytes().any(|byte| byte.is_ascii_digit()), "{text:?} revealed {value}.");
+			}
+		}
+	}
+
+	#[test]
+	fn formatting_nested()
+	{
+		#[derive(Debug)]
+		#[allow(dead_code)]
+		struct State
+		{
+			correct: Secret<Guess<{ 0..=1024 }>>,
+			attempts: Vec<Guess<{ 0..=1024 }>>,
+		}
+
+		let state = State {
+			correct: Secret::new(Guess::new(777_i32).expect("secret failed to construct.")),
+			attempts: vec![Guess::new(512_i32).expect("guess failed to construct.")],
+		};
+		let text = format!("{state:?}");
+		assert!(text.contains("512"));
+		assert!(!text.contains('7'));
+	}
+
+	#[test]
+	fn compare()
+	{
+		let secret = Secret::new(Guess::<{ 0..=50 }>::new(40_i32).expect("secret failed to construct."));
+		assert_eq!(secret.compare(Guess::new(50_i32).expect("guess 1 failed to construct.")), Ordering::Greater);
+		assert_eq!(secret.compare(Guess::new(30_i32).expect("guess 2 failed to construct.")), Ordering::Less);
+		assert_eq!(secret.compare(Guess::new(40_i32).expect("guess 3 failed to construct.")), Ordering::Equal);
+		assert!(secret.is(Guess::new(40_i32).expect("guess 4 failed to construct.")));
+		assert!(!secret.is(Guess::new(41_i32).expect("guess 5 failed to construct.")));
+		assert_eq!(secret.reveal().value(), 40_i32);
+	}
+
+	#[test]
+	fn respond_secret_test()
+	{
+		let correct = Guess::<{ 0..=50 }>::new(40_i32).expect("correct failed to construct.");
+		for value in [20_i32, 40_i32, 50_i32]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			assert_eq!(respond_secret(guess, Secret::new(correct), &mut stdout()).expect("respond failed."), respond(guess, correct, &mut stdout()).expect("respond failed."));
+		}
+	}
+}
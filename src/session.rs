@@ -0,0 +1,453 @@
+use {
+	crate::{
+		ask_yes_no,
+		clock::seconds,
+		score,
+		AdaptivePolicy,
+		Adjustment,
+		Game,
+		GameOutcome,
+		Grade,
+		Guess,
+		InputOutput,
+	},
+	colored::Colorize,
+	std::{
+		fmt::Write as _,
+		io::{
+			self,
+			BufRead,
+		},
+		ops::RangeInclusive,
+		time::Duration,
+	},
+};
+
+/// One finished game of a Session.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Round<const RANGE: RangeInclusive<i32>>
+{
+	/// The secret of the game, revealed now that it is over.
+	pub secret: Guess<RANGE>,
+	/// How many valid guesses were made.
+	pub attempts_used: u32,
+	/// How the game ended.
+	pub outcome: GameOutcome<RANGE>,
+	/// How long the game took. See Game::elapsed.
+	pub elapsed: Duration,
+	/// The points the game earned. See score.
+	pub score: u32,
+	/// How long the game took as a speedrun, if it was one and was won. See
+	/// Game::run_time.
+	pub run_time: Option<Duration>,
+	/// How efficiently the game was won, or None if it was not. See
+	/// Game::grade.
+	pub grade: Option<Grade>,
+}
+
+/// Several games played back to back, keeping a score across all of them.
+///
+/// Each round is a new Game created by new_game, so the closure decides the
+/// range, attempts and style of every round.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		Game,
+/// // 		Session,
+/// // 	},
+/// // 	rand::thread_rng,
+/// // 	std::io::{
+/// // 		stdin,
+/// // 		stdout,
+/// // 	},
+/// //};
+/// // let mut session = Session::new(|| Game::<{ 0..=1024 }>::new(10, &mut thread_rng()));
+/// // let total = session.play_rounds(Some(3), &mut stdin().lock(), &mut stdout());
+/// ```
+pub struct Session<const RANGE: RangeInclusive<i32>, F>
+{
+	new_game: F,
+	rounds: Vec<Round<RANGE>>,
+	last: Option<Game<RANGE>>,
+	adjustments: Vec<Adjustment<RANGE>>,
+	/// Whether every round is followed by its Game::analysis, rather than only
+	/// the rounds that were won.
+	pub analyze: bool,
+	/// The policy that moves each new round to a range that suits how the player
+	/// is doing, or None to play every round as new_game creates it.
+	pub adaptive: Option<AdaptivePolicy<RANGE>>,
+}
+
+impl<const RANGE: RangeInclusive<i32>, F: FnMut() -> Game<RANGE>> Session<RANGE, F>
+{
+	/// Creates a session without any rounds, where each round is played with a
+	/// game created by new_game.
+	pub const fn new(new_game: F) -> Self
+	{
+		Session { new_game, rounds: Vec::new(), last: None, adjustments: Vec::new(), analyze: false, adaptive: None }
+	}
+
+	/// Returns the rounds played so far, oldest first.
+	pub fn rounds(&self) -> &[Round<RANGE>]
+	{
+		&self.rounds
+	}
+
+	/// Returns the changes Session::adaptive made to the range so far, oldest
+	/// first.
+	pub fn adjustments(&self) -> &[Adjustment<RANGE>]
+	{
+		&self.adjustments
+	}
+
+	/// Returns the game of the round that was aborted, if the session ended that
+	/// way. Playing it again picks up where it stopped.
+	pub fn interrupted(&self) -> Option<&Game<RANGE>>
+	{
+		self.last.as_ref().filter(|game| !game.is_finished())
+	}
+
+	/// Returns the game of the last round that was played, whether it finished or
+	/// was aborted, such as to keep its Game::replay.
+	pub const fn last_game(&self) -> Option<&Game<RANGE>>
+	{
+		self.last.as_ref()
+	}
+
+	/// Returns the sum of the scores of every round so far.
+	pub fn total_score(&self) -> u32
+	{
+		self.rounds.iter().fold(0_u32, |total, round| total.saturating_add(round.score))
+	}
+
+	/// Plays rounds games, or asks "Play again? (y/n)" after each game if rounds
+	/// is None, and returns the total score.
+	///
+	/// After each round its Game::recap, its Game::confession if it could lie, its
+	/// Game::timings, its Game::analysis if it was won or Session::analyze is set,
+	/// a line with its score and the total, and the Adjustment to the range if
+	/// Session::adaptive made one are shown, and once the session is over the
+	/// table from render_summary followed by every adjustment. A round that is
+	/// aborted, for example because the player quit, ends the session without
+	/// being recorded, but is kept as Session::interrupted. The question is asked
+	/// with ask_yes_no, so an answer of no, a quit command or the end of the input
+	/// ends the session too.
+	pub fn play_rounds(
+		&mut self,
+		rounds: Option<u32>,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+	) -> u32
+	{
+		self.play_rounds_with(rounds, input, output, |_| false)
+	}
+
+	/// Plays like Session::play_rounds, calling on_round with each round as soon
+	/// as it is recorded, for example to keep stats that survive the session
+	/// being cut off. If on_round returns true, the round set a personal best,
+	/// which is shown after its Game::timings.
+	pub fn play_rounds_with(
+		&mut self,
+		rounds: Option<u32>,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+		mut on_round: impl FnMut(&Round<RANGE>) -> bool,
+	) -> u32
+	{
+		// A session that can not be shown any longer ends early, like a game.
+		let _ = self.run(rounds, input, output, &mut on_round);
+		self.total_score()
+	}
+
+	/// Plays the session, returning an error if reading or writing fails.
+	fn run(
+		&mut self,
+		rounds: Option<u32>,
+		input: &mut impl BufRead,
+		output: &mut impl InputOutput,
+		on_round: &mut impl FnMut(&Round<RANGE>) -> bool,
+	) -> io::Result<()>
+	{
+		loop
+		{
+			let mut game = (self.new_game)();
+			if let Some(policy) = &mut self.adaptive
+			{
+				policy.apply(&mut game);
+			}
+			let outcome = game.play(input, output);
+			if outcome == GameOutcome::Aborted
+			{
+				self.last = Some(game);
+				break;
+			}
+			let elapsed = game.elapsed();
+			let round = Round {
+				secret: game.secret().reveal(),
+				attempts_used: game.attempts_used(),
+				outcome,
+				elapsed,
+				score: score(&outcome, elapsed, &game.score_config()),
+				run_time: game.run_time(),
+				grade: game.grade(),
+			};
+			self.rounds.push(round);
+			let best = on_round(&round);
+			let number = self.rounds.len();
+			let line = format!("Round {number}: {} points, {} in total.", round.score, self.total_score());
+			writeln!(output.messages(), "{}", game.style.render(&game.recap().normal()))?;
+			if let Some(confession) = game.confession()
+			{
+				writeln!(output.messages(), "{}", game.style.render(&confession.normal()))?;
+			}
+			writeln!(output.messages(), "{}", game.style.render(&game.timings().normal()))?;
+			if best
+			{
+				writeln!(output.messages(), "{}", game.style.render(&"New personal best!".yellow().bold()))?;
+			}
+			if self.analyze || matches!(outcome, GameOutcome::Won { .. })
+			{
+				write!(output.messages(), "{}", game.style.render(&game.analysis().to_string().normal()))?;
+			}
+			if let Some(grade) = round.grade
+			{
+				writeln!(output.messages(), "{}", game.style.render(&format!("Grade: {grade}. {}", grade.comment()).cyan()))?;
+			}
+			writeln!(output.messages(), "{}", game.style.render(&line.bright_green().bold()))?;
+			if let Some(adjustment) = self.adaptive.as_mut().and_then(|policy| policy.record(&outcome, game.attempts_left()))
+			{
+				self.adjustments.push(adjustment);
+				writeln!(output.messages(), "{}", game.style.render(&adjustment.to_string().yellow()))?;
+			}
+			let style = game.style;
+			self.last = Some(game);
+			let more = match rounds
+			{
+				Some(rounds) => number < rounds as usize,
+				None => ask_yes_no(&"Play again? (y/n) ".yellow(), style, input, output)?,
+			};
+			if !more
+			{
+				break;
+			}
+		}
+		if !self.rounds.is_empty()
+		{
+			write!(output.messages(), "\n{}\n", render_summary(&self.rounds))?;
+			for adjustment in &self.adjustments
+			{
+				writeln!(output.messages(), "{adjustment}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Renders rounds as a table with one row per round and the total time and
+/// score at the bottom, as in:
+///
+/// ```text
+/// Round  Secret  Attempts  Result      Time  Score
+///     1     300         3  won        30.0s   1310
+///     2     777         5  lost       12.5s      0
+/// Total                               42.5s   1310
+/// ```
+pub fn render_summary<const RANGE: RangeInclusive<i32>>(rounds: &[Round<RANGE>]) -> String
+{
+	let mut summary = String::from("Round  Secret  Attempts  Result      Time  Score\n");
+	for (number, round) in rounds.iter().enumerate()
+	{
+		let result = match round.outcome
+		{
+			GameOutcome::Won { .. } => "won",
+			GameOutcome::Lost { .. } => "lost",
+			GameOutcome::TimedOut { .. } => "timeout",
+			GameOutcome::Aborted => "aborted",
+		};
+		let time = seconds(round.elapsed);
+		writeln!(summary, "{:>5}  {:>6}  {:>8}  {result:<7}  {time:>7}  {:>5}", number + 1, round.secret, round.attempts_used, round.score).expect("writing to a String can not fail.");
+	}
+	let total = rounds.iter().fold(0_u32, |total, round| total.saturating_add(round.score));
+	let time = seconds(rounds.iter().map(|round| round.elapsed).sum());
+	writeln!(summary, "Total  {time:>34}  {total:>5}").expect("writing to a String can not fail.");
+	summary
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			GuessRange,
+			Style,
+			WithErrors,
+		},
+		rand::{
+			rngs::StdRng,
+			SeedableRng,
+		},
+	};
+
+	fn guess(value: i32) -> Guess<{ 0..=1024 }>
+	{
+		Guess::new(value).expect("guess failed to construct.")
+	}
+
+	/// Creates a game of five attempts whose secret is secret, in plain style.
+	fn game(secret: i32) -> Game<{ 0..=1024 }>
+	{
+		let mut game = Game::with_secret(5, guess(secret));
+		game.style = Style::Plain;
+		game
+	}
+
+	#[test]
+	fn render_summary_test()
+	{
+		let rounds = [
+			Round { secret: guess(300), attempts_used: 3, outcome: GameOutcome::Won { attempts_used: 3 }, elapsed: Duration::from_secs(30), score: 1310, run_time: None, grade: Some(Grade::A) },
+			Round { secret: guess(777), attempts_used: 5, outcome: GameOutcome::Lost { answer: guess(777) }, elapsed: Duration::from_millis(12500), score: 0, run_time: None, grade: None },
+			Round { secret: guess(5), attempts_used: 1, outcome: GameOutcome::TimedOut { answer: guess(5) }, elapsed: Duration::from_millis(61040), score: 0, run_time: None, grade: None },
+		];
+		assert_eq!(
+			render_summary(&rounds),
+			"Round  Secret  Attempts  Result      Time  Score\n\
+			 \x20   1     300         3  won        30.0s   1310\n\
+			 \x20   2     777         5  lost       12.5s      0\n\
+			 \x20   3       5         1  timeout    61.0s      0\n\
+			 Total                              103.5s   1310\n"
+		);
+		assert_eq!(render_summary::<{ 0..=1024 }>(&[]), "Round  Secret  Attempts  Result      Time  Score\nTotal                                0.0s      0\n");
+	}
+
+	#[test]
+	fn two_rounds()
+	{
+		let mut secrets = [300, 777].into_iter();
+		let mut session = Session::new(|| game(secrets.next().expect("too many rounds were played.")));
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let lines = "512\n256\n300\nmaybe\ny\n1\n2\n3\n4\n5\n";
+		let mut seen = Vec::new();
+		let total = session.play_rounds_with(None, &mut lines.as_bytes(), &mut output, |round| {
+			seen.push(*round);
+			false
+		});
+
+		let rounds = session.rounds();
+		assert_eq!(rounds.len(), 2);
+		assert_eq!(seen, rounds);
+		assert_eq!(rounds[0].outcome, GameOutcome::Won { attempts_used: 3 });
+		assert!(rounds[0].score > 0);
+		assert_eq!(rounds[1].outcome, GameOutcome::Lost { answer: guess(777) });
+		assert_eq!(rounds[1].score, 0);
+		assert_eq!(total, rounds[0].score);
+		assert_eq!(session.total_score(), total);
+
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert_eq!(transcript.matches("Play again? (y/n) ").count(), 3);
+		assert!(transcript.contains("Your guesses: 512^ 256v 300 (ok)\nYour times: "));
+		assert_eq!((rounds[0].grade, rounds[1].grade), (Some(Grade::A), None));
+		assert!(transcript.contains(&format!("in 10.\nDistance from the middle of what was left: 0 1 84\nGrade: A. {}\nRound 1: {total} points, {total} in total.", Grade::A.comment())));
+		assert!(transcript.contains(" in total)\nRound 2: 0 points"));
+		assert_eq!(transcript.matches("You used ").count(), 1);
+		assert_eq!(transcript.matches("Grade: ").count(), 1);
+		assert!(transcript.contains(&format!("Round 2: 0 points, {total} in total.")));
+		assert!(transcript.ends_with(&format!("\n{}\n", render_summary(rounds))));
+		assert!(transcript.contains("    2     777         5  lost "));
+		assert!(transcript.contains("\nYour times: "));
+		assert!(!transcript.contains("New personal best!"));
+		assert_eq!(String::from_utf8(output.errors).expect("output was not UTF-8."), "Please answer y or n.\n");
+	}
+
+	#[test]
+	fn personal_best()
+	{
+		let mut session = Session::new(|| game(1));
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let mut round = 0;
+		session.play_rounds_with(Some(2), &mut "1\n1\n".as_bytes(), &mut output, |_| {
+			round += 1;
+			round == 2
+		});
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		let (first, second) = transcript.split_once("Round 1: ").expect("the first round was not scored.");
+		assert!(!first.contains("New personal best!"));
+		let times = second.lines().skip_while(|line| !line.starts_with("Your times: ")).nth(1);
+		assert_eq!(times, Some("New personal best!"), "{transcript}");
+	}
+
+	#[test]
+	fn declined()
+	{
+		let mut session = Session::new(|| game(1));
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let total = session.play_rounds(None, &mut "1\nYes\n1\nN\n1\n".as_bytes(), &mut output);
+		assert_eq!(session.rounds().len(), 2);
+		assert_eq!(session.total_score(), total);
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert_eq!(transcript.matches("Play again? (y/n) ").count(), 2);
+		assert!(transcript.contains(&format!("Round 1: {} points, {} in total.", session.rounds()[0].score, session.rounds()[0].score)));
+		assert!(transcript.contains(&format!("Round 2: {} points, {total} in total.", session.rounds()[1].score)));
+		assert!(transcript.ends_with(&format!("Play again? (y/n) \n{}\n", render_summary(session.rounds()))), "{transcript}");
+		assert!(output.errors.is_empty());
+
+		// A quit command declines as well.
+		let mut session = Session::new(|| game(1));
+		session.play_rounds(None, &mut "1\nquit\n1\n".as_bytes(), &mut WithErrors { output: Vec::new(), errors: Vec::new() });
+		assert_eq!(session.rounds().len(), 1);
+	}
+
+	#[test]
+	fn adaptive()
+	{
+		let mut session = Session::new(|| game(300));
+		let range = GuessRange::new(guess(0), guess(1023)).expect("range failed to construct.");
+		session.adaptive = Some(AdaptivePolicy::new(range, 256, 2048, StdRng::seed_from_u64(1)));
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		session.play_rounds(Some(3), &mut "give up\ngive up\ngive up\n".as_bytes(), &mut output);
+		assert_eq!(session.rounds().len(), 3);
+		let game = session.last_game().expect("no game was played.");
+		assert_eq!(game.range(), GuessRange::new(guess(0), guess(255)).expect("range failed to construct."));
+		assert!(game.range().contains(game.secret().reveal()));
+
+		// The narrowest span stops the third round from narrowing the range.
+		let adjustments: Vec<_> = session.adjustments().iter().map(ToString::to_string).collect();
+		assert_eq!(adjustments, ["After round 1 the range narrowed from 0..=1023 to 0..=511.", "After round 2 the range narrowed from 0..=511 to 0..=255."]);
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(transcript.contains("Round 1: 0 points, 0 in total.\nAfter round 1 the range narrowed"), "{transcript}");
+		assert!(transcript.contains("I'm thinking of a number somwhere from 0 through 511."), "{transcript}");
+		assert!(transcript.ends_with(&format!("\n{}\n{}\n{}\n", render_summary(session.rounds()), adjustments[0], adjustments[1])), "{transcript}");
+	}
+
+	#[test]
+	fn fixed_rounds()
+	{
+		let mut session = Session::new(|| game(1));
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		session.play_rounds(Some(2), &mut "1\n1\n1\n".as_bytes(), &mut output);
+		assert_eq!(session.rounds().len(), 2);
+		assert!(!String::from_utf8(output.output).expect("output was not UTF-8.").contains("Play again?"));
+		assert!(session.interrupted().is_none());
+		assert!(session.last_game().is_some_and(Game::is_finished));
+
+		// With analyze set, lost rounds are analyzed too.
+		let mut session = Session::new(|| game(1));
+		session.analyze = true;
+		let mut output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		session.play_rounds(Some(1), &mut "9\n8\n7\n6\n5\n".as_bytes(), &mut output);
+		let transcript = String::from_utf8(output.output).expect("output was not UTF-8.");
+		assert!(transcript.contains("You used 5 guesses; optimal play needs at most 11 for this range"));
+
+		// Quitting ends the session without recording the round.
+		let mut session = Session::new(|| game(1));
+		session.play_rounds(Some(3), &mut "1\nquit\n".as_bytes(), &mut WithErrors { output: Vec::new(), errors: Vec::new() });
+		assert_eq!(session.rounds().len(), 1);
+		let interrupted = session.interrupted().expect("the session was not interrupted.");
+		assert!(!interrupted.is_finished());
+		assert_eq!(interrupted.attempts_used(), 0);
+	}
+}
@@ -0,0 +1,301 @@
+use {
+	crate::{
+		BinarySearchStrategy,
+		Game,
+		GameOutcome,
+		GuessRange,
+		LinearStrategy,
+		RandomStrategy,
+		Strategy,
+		StrategyKind,
+	},
+	rand::{
+		rngs::StdRng,
+		Rng,
+		SeedableRng,
+	},
+	std::{
+		collections::BTreeMap,
+		fmt::{
+			Display,
+			Formatter,
+		},
+		ops::RangeInclusive,
+		result::Result,
+	},
+};
+
+/// What happened when a strategy played many games. See simulate.
+///
+/// The attempt statistics are about the games that were won, since a lost game
+/// always uses every attempt.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SimulationReport
+{
+	games: u32,
+	/// How many won games took each number of attempts.
+	histogram: BTreeMap<u32, u32>,
+}
+
+impl SimulationReport
+{
+	/// Adds a game that ended with outcome to the report.
+	fn record<const RANGE: RangeInclusive<i32>>(
+		&mut self,
+		outcome: &GameOutcome<RANGE>,
+	)
+	{
+		self.games += 1;
+		if let GameOutcome::Won { attempts_used } = *outcome
+		{
+			*self.histogram.entry(attempts_used).or_insert(0_u32) += 1;
+		}
+	}
+
+	/// Returns how many games were played.
+	pub const fn games(&self) -> u32
+	{
+		self.games
+	}
+
+	/// Returns how many games were won.
+	pub fn wins(&self) -> u32
+	{
+		self.histogram.values().sum()
+	}
+
+	/// Returns the share of games that were won, from 0.0 through 1.0, or 0.0
+	/// if no games were played.
+	pub fn win_rate(&self) -> f64
+	{
+		if self.games == 0 { 0.0 } else { f64::from(self.wins()) / f64::from(self.games) }
+	}
+
+	/// Returns how many won games took each number of attempts, ordered by the
+	/// attempts. Attempts that no game took are left out.
+	pub const fn histogram(&self) -> &BTreeMap<u32, u32>
+	{
+		&self.histogram
+	}
+
+	/// Returns how many attempts a win took on average, or None if no game was
+	/// won.
+	pub fn mean(&self) -> Option<f64>
+	{
+		let total: u64 = self.histogram.iter().map(|(&attempts, &games)| u64::from(attempts) * u64::from(games)).sum();
+		(self.wins() > 0).then(|| total as f64 / f64::from(self.wins()))
+	}
+
+	/// Returns the median of the attempts the wins took, taking the lower of the
+	/// two middle ones for an even number of wins, or None if no game was won.
+	pub fn median(&self) -> Option<u32>
+	{
+		let wins = self.wins();
+		let middle = wins.checked_sub(1)? / 2;
+		let mut seen = 0_u32;
+		self.histogram.iter().find_map(|(&attempts, &games)| {
+			seen += games;
+			(seen > middle).then_some(attempts)
+		})
+	}
+
+	/// Returns the most attempts a win took, or None if no game was won.
+	pub fn max(&self) -> Option<u32>
+	{
+		self.histogram.keys().next_back().copied()
+	}
+}
+
+/// Formats SimulationReport as a small table, as in:
+///
+/// ```text
+/// Games: 5
+/// Won: 4 (80.0%)
+/// Attempts  Games
+///        1      1
+///        3      2
+///        9      1
+/// Mean: 4.0  Median: 3  Max: 9
+/// ```
+impl Display for SimulationReport
+{
+	fn fmt(
+		&self,
+		f: &mut Formatter<'_>,
+	) -> Result<(), std::fmt::Error>
+	{
+		writeln!(f, "Games: {}", self.games)?;
+		writeln!(f, "Won: {} ({:.1}%)", self.wins(), self.win_rate() * 100.0)?;
+		writeln!(f, "Attempts  Games")?;
+		for (attempts, games) in &self.histogram
+		{
+			writeln!(f, "{attempts:>8}  {games:>5}")?;
+		}
+		let mean = self.mean().map_or_else(|| String::from("-"), |mean| format!("{mean:.1}"));
+		let median = self.median().map_or_else(|| String::from("-"), |median| median.to_string());
+		let max = self.max().map_or_else(|| String::from("-"), |max| max.to_string());
+		writeln!(f, "Mean: {mean}  Median: {median}  Max: {max}")
+	}
+}
+
+/// Lets strategy play games games of Game::new_fair with secrets picked by
+/// rng, and reports how it did.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		simulate,
+/// // 		BinarySearchStrategy,
+/// // 	},
+/// // 	rand::thread_rng,
+/// //};
+/// // let report = simulate::<{ 0..=1024 }>(&mut BinarySearchStrategy::default(), 1000, &mut thread_rng());
+/// // print!("{report}");
+/// ```
+pub fn simulate<const RANGE: RangeInclusive<i32>>(
+	strategy: &mut impl Strategy<RANGE>,
+	games: u32,
+	rng: &mut impl Rng,
+) -> SimulationReport
+{
+	simulate_with(Game::new_fair, strategy, games, rng)
+}
+
+/// Lets strategy play games games created by new_game, which picks the secret
+/// with rng, and reports how it did. This is how to simulate a Difficulty, with
+/// Game::with_difficulty_and_rng as new_game.
+pub fn simulate_with<const RANGE: RangeInclusive<i32>, R: Rng>(
+	mut new_game: impl FnMut(&mut R) -> Game<RANGE>,
+	strategy: &mut impl Strategy<RANGE>,
+	games: u32,
+	rng: &mut R,
+) -> SimulationReport
+{
+	let mut report = SimulationReport::default();
+	for _ in 0..games
+	{
+		let mut game = new_game(rng);
+		report.record(&game.play_with_strategy(strategy));
+	}
+	report
+}
+
+/// Lets a strategy of kind that searches range play games games created by
+/// new_game, like simulate_with. RandomStrategy picks its guesses with an rng
+/// of its own that is seeded from rng, so a seeded rng gives the same report
+/// every time.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		simulate_kind,
+/// // 		Difficulty,
+/// // 		Game,
+/// // 		StrategyKind,
+/// // 	},
+/// // 	rand::thread_rng,
+/// //};
+/// // let range = Game::with_difficulty(Difficulty::Easy).range();
+/// // let new_game = |rng: &mut _| Game::with_difficulty_and_rng(Difficulty::Easy, rng);
+/// // print!("{}", simulate_kind(StrategyKind::Linear, range, new_game, 1000, &mut thread_rng()));
+/// ```
+pub fn simulate_kind<const RANGE: RangeInclusive<i32>, R: Rng>(
+	kind: StrategyKind,
+	range: GuessRange<RANGE>,
+	new_game: impl FnMut(&mut R) -> Game<RANGE>,
+	games: u32,
+	rng: &mut R,
+) -> SimulationReport
+{
+	match kind
+	{
+		StrategyKind::Binary => simulate_with(new_game, &mut BinarySearchStrategy::new(range), games, rng),
+		StrategyKind::Linear => simulate_with(new_game, &mut LinearStrategy::new(range), games, rng),
+		StrategyKind::Random =>
+		{
+			let mut strategy = RandomStrategy::new(range, StdRng::seed_from_u64(rng.gen()));
+			simulate_with(new_game, &mut strategy, games, rng)
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Difficulty,
+			Guess,
+		},
+	};
+
+	#[test]
+	fn binary_search()
+	{
+		let report = simulate::<{ 0..=1024 }>(&mut BinarySearchStrategy::default(), 1000, &mut StdRng::seed_from_u64(1));
+		assert_eq!(report.games(), 1000);
+		assert_eq!(report.wins(), 1000);
+		assert!(report.max().is_some_and(|max| max <= 11), "{report}");
+		assert_eq!(report.histogram().values().sum::<u32>(), 1000);
+		assert!(report.mean().is_some_and(|mean| (8.5..=9.5).contains(&mean)), "{report}");
+	}
+
+	#[test]
+	fn statistics()
+	{
+		let mut report = SimulationReport::default();
+		for attempts_used in [3, 1, 3, 9]
+		{
+			report.record(&GameOutcome::<{ 0..=1024 }>::Won { attempts_used });
+		}
+		report.record(&GameOutcome::<{ 0..=1024 }>::Lost { answer: Guess::new(5).expect("answer failed to construct.") });
+		assert_eq!((report.games(), report.wins()), (5, 4));
+		assert_eq!(report.win_rate(), 0.8);
+		assert_eq!(report.mean(), Some(4.0));
+		assert_eq!(report.median(), Some(3));
+		assert_eq!(report.max(), Some(9));
+		assert_eq!(report.to_string(), "Games: 5\nWon: 4 (80.0%)\nAttempts  Games\n       1      1\n       3      2\n       9      1\nMean: 4.0  Median: 3  Max: 9\n");
+
+		let empty = SimulationReport::default();
+		assert_eq!((empty.win_rate(), empty.mean(), empty.median(), empty.max()), (0.0, None, None, None));
+		assert!(empty.to_string().ends_with("Mean: -  Median: -  Max: -\n"));
+	}
+
+	#[test]
+	fn difficulty()
+	{
+		// Counting up from 1 only finishes hard games in 12 attempts when the
+		// secret is 12 or less.
+		let range = Game::with_difficulty(Difficulty::Hard).range();
+		let new_game = |rng: &mut StdRng| Game::with_difficulty_and_rng(Difficulty::Hard, rng);
+		let report = simulate_with(new_game, &mut LinearStrategy::new(range), 200, &mut StdRng::seed_from_u64(2));
+		assert!(report.win_rate() < 0.01, "{report}");
+	}
+
+	#[test]
+	fn kinds()
+	{
+		let range = Game::with_difficulty(Difficulty::Easy).range();
+		let new_game = |rng: &mut StdRng| Game::with_difficulty_and_rng(Difficulty::Easy, rng);
+		let reports = StrategyKind::ALL.map(|kind| simulate_kind(kind, range, new_game, 300, &mut StdRng::seed_from_u64(3)));
+		assert!(reports.iter().all(|report| report.games() == 300));
+		let [binary, random, linear] = &reports;
+		assert_eq!(binary.win_rate(), 1.0, "{binary}");
+		assert!(binary.max().is_some_and(|max| max <= 6), "{binary}");
+		assert!(random.mean() > binary.mean() && linear.win_rate() < random.win_rate(), "{random}{linear}");
+
+		// The random strategy is seeded from rng, so it plays the same again.
+		assert_eq!(simulate_kind(StrategyKind::Random, range, new_game, 300, &mut StdRng::seed_from_u64(3)), *random);
+
+		for kind in StrategyKind::ALL
+		{
+			assert_eq!(StrategyKind::from_name(&kind.name().to_uppercase()), Some(kind));
+		}
+		assert_eq!(StrategyKind::from_name("quantum"), None);
+	}
+}
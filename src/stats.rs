@@ -0,0 +1,527 @@
+#[cfg(feature = "stats")]
+use std::{
+	fs,
+	io::{
+		self,
+		ErrorKind,
+		Write,
+	},
+	path::{
+		Path,
+		PathBuf,
+	},
+};
+use {
+	crate::{
+		clock::seconds,
+		Difficulty,
+		GameOutcome,
+		Grade,
+	},
+	std::{
+		ops::RangeInclusive,
+		time::Duration,
+	},
+};
+
+/// Lifetime statistics of a player, kept across games and runs.
+///
+/// Stats is updated with Stats::record, which does not touch the filesystem.
+/// See StatsFile for keeping it in a file.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	GameOutcome,
+/// // 	Stats,
+/// //};
+/// // let stats = Stats::default().record(&GameOutcome::<{ 0..=1024 }>::Won { attempts_used: 4 });
+/// // assert_eq!(stats.current_streak, 1);
+/// // assert_eq!(stats.average_attempts(), Some(4.0));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct Stats
+{
+	/// How many games were won or lost.
+	pub played: u32,
+	/// How many games were won.
+	pub wins: u32,
+	/// How many games were lost.
+	pub losses: u32,
+	/// How many games in a row were won most recently.
+	pub current_streak: u32,
+	/// The most games in a row that were ever won.
+	pub best_streak: u32,
+	/// The attempts used by every win together.
+	pub attempts_on_wins: u64,
+	/// The fastest speedrun on each difficulty. See Stats::record_time.
+	pub best_times: BestTimes,
+	/// How many wins got each grade. See Stats::record_grade.
+	pub grades: Grades,
+}
+
+impl Stats
+{
+	/// Returns the stats after a game that ended with outcome.
+	///
+	/// A win extends the current streak, and a loss ends it, including a game
+	/// that timed out. An aborted game was never finished, so it is not counted
+	/// at all.
+	#[must_use]
+	pub const fn record<const RANGE: RangeInclusive<i32>>(
+		self,
+		outcome: &GameOutcome<RANGE>,
+	) -> Self
+	{
+		match *outcome
+		{
+			GameOutcome::Won { attempts_used } =>
+			{
+				let current_streak = self.current_streak.saturating_add(1_u32);
+				Stats {
+					played: self.played.saturating_add(1_u32),
+					wins: self.wins.saturating_add(1_u32),
+					current_streak,
+					best_streak: if current_streak > self.best_streak { current_streak } else { self.best_streak },
+					attempts_on_wins: self.attempts_on_wins.saturating_add(attempts_used as u64),
+					..self
+				}
+			},
+			GameOutcome::Lost { .. } | GameOutcome::TimedOut { .. } => Stats { played: self.played.saturating_add(1_u32), losses: self.losses.saturating_add(1_u32), current_streak: 0_u32, ..self },
+			GameOutcome::Aborted => self,
+		}
+	}
+
+	/// Returns the stats after a speedrun on difficulty that was won in time,
+	/// along with whether it was a new personal best. The first run on a
+	/// difficulty is always one, and a run only as fast as the best is not.
+	///
+	/// Speedruns are not counted as played, since any number of guesses wins.
+	#[must_use]
+	pub fn record_time(
+		self,
+		difficulty: Difficulty,
+		time: Duration,
+	) -> (Self, bool)
+	{
+		let millis = u64::try_from(time.as_millis()).unwrap_or(u64::MAX);
+		let mut best_times = self.best_times;
+		let best = best_times.slot(difficulty);
+		if best.is_some_and(|best| best <= millis)
+		{
+			return (self, false);
+		}
+		*best = Some(millis);
+		(Stats { best_times, ..self }, true)
+	}
+
+	/// Returns the stats after a win that got grade, as from Game::grade.
+	#[must_use]
+	pub fn record_grade(
+		self,
+		grade: Grade,
+	) -> Self
+	{
+		let mut grades = self.grades;
+		let count = grades.slot(grade);
+		*count = count.saturating_add(1_u32);
+		Stats { grades, ..self }
+	}
+
+	/// Returns how many attempts a win took on average, or None if no game was
+	/// won yet.
+	pub fn average_attempts(&self) -> Option<f64>
+	{
+		(self.wins > 0).then(|| self.attempts_on_wins as f64 / f64::from(self.wins))
+	}
+
+	/// Returns the stats as lines of text to show the player, as in:
+	///
+	/// ```text
+	/// Played: 12
+	/// Won: 8 (67%)
+	/// Lost: 4
+	/// Current streak: 3
+	/// Best streak: 5
+	/// Average attempts on wins: 4.2
+	/// Best time on normal: 12.3s
+	/// Grades: A 5, B 2, C 1, D 0, F 0
+	/// ```
+	///
+	/// There is a best time line for each difficulty with a speedrun, and the
+	/// grades are left out until a win is graded.
+	pub fn summarize(&self) -> String
+	{
+		let percent = if self.played == 0 { 0_u64 } else { (u64::from(self.wins) * 100_u64 + u64::from(self.played) / 2_u64) / u64::from(self.played) };
+		let average = self.average_attempts().map_or_else(|| String::from("-"), |average| format!("{average:.1}"));
+		let mut summary = format!(
+			"Played: {}\nWon: {} ({percent}%)\nLost: {}\nCurrent streak: {}\nBest streak: {}\nAverage attempts on wins: {average}\n",
+			self.played, self.wins, self.losses, self.current_streak, self.best_streak
+		);
+		for difficulty in Difficulty::ALL
+		{
+			if let Some(best) = self.best_times.get(difficulty)
+			{
+				summary.push_str(&format!("Best time on {}: {}\n", difficulty.name(), seconds(best)));
+			}
+		}
+		if self.grades != Grades::default()
+		{
+			let grades: Vec<_> = Grade::ALL.iter().map(|&grade| format!("{grade} {}", self.grades.get(grade))).collect();
+			summary.push_str(&format!("Grades: {}\n", grades.join(", ")));
+		}
+		summary
+	}
+}
+
+/// The fastest speedrun on each Difficulty in milliseconds, or None for a
+/// difficulty without a speedrun yet.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct BestTimes
+{
+	/// The best time on Difficulty::Easy.
+	pub easy_ms: Option<u64>,
+	/// The best time on Difficulty::Normal.
+	pub normal_ms: Option<u64>,
+	/// The best time on Difficulty::Hard.
+	pub hard_ms: Option<u64>,
+	/// The best time on Difficulty::Insane.
+	pub insane_ms: Option<u64>,
+}
+
+impl BestTimes
+{
+	/// Returns the best time on difficulty, or None if there is none yet.
+	pub fn get(
+		&self,
+		difficulty: Difficulty,
+	) -> Option<Duration>
+	{
+		let millis = match difficulty
+		{
+			Difficulty::Easy => self.easy_ms,
+			Difficulty::Normal => self.normal_ms,
+			Difficulty::Hard => self.hard_ms,
+			Difficulty::Insane => self.insane_ms,
+		};
+		millis.map(Duration::from_millis)
+	}
+
+	/// Returns where the best time on difficulty is kept.
+	fn slot(
+		&mut self,
+		difficulty: Difficulty,
+	) -> &mut Option<u64>
+	{
+		match difficulty
+		{
+			Difficulty::Easy => &mut self.easy_ms,
+			Difficulty::Normal => &mut self.normal_ms,
+			Difficulty::Hard => &mut self.hard_ms,
+			Difficulty::Insane => &mut self.insane_ms,
+		}
+	}
+}
+
+/// How many wins got each Grade.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct Grades
+{
+	/// The wins graded Grade::A.
+	pub a: u32,
+	/// The wins graded Grade::B.
+	pub b: u32,
+	/// The wins graded Grade::C.
+	pub c: u32,
+	/// The wins graded Grade::D.
+	pub d: u32,
+	/// The wins graded Grade::F.
+	pub f: u32,
+}
+
+impl Grades
+{
+	/// Returns how many wins got grade.
+	pub const fn get(
+		&self,
+		grade: Grade,
+	) -> u32
+	{
+		match grade
+		{
+			Grade::A => self.a,
+			Grade::B => self.b,
+			Grade::C => self.c,
+			Grade::D => self.d,
+			Grade::F => self.f,
+		}
+	}
+
+	/// Returns where the count of grade is kept.
+	fn slot(
+		&mut self,
+		grade: Grade,
+	) -> &mut u32
+	{
+		match grade
+		{
+			Grade::A => &mut self.a,
+			Grade::B => &mut self.b,
+			Grade::C => &mut self.c,
+			Grade::D => &mut self.d,
+			Grade::F => &mut self.f,
+		}
+	}
+}
+
+/// A JSON file that Stats is kept in between runs.
+///
+/// Reading the file never fails. A missing file holds fresh stats, as on the
+/// first run, and a file that can not be read or is corrupt is replaced by fresh
+/// stats with a warning.
+#[cfg(feature = "stats")]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct StatsFile
+{
+	path: PathBuf,
+}
+
+#[cfg(feature = "stats")]
+impl StatsFile
+{
+	/// Creates a store that keeps the stats at path.
+	pub fn new(path: impl Into<PathBuf>) -> Self
+	{
+		StatsFile { path: path.into() }
+	}
+
+	/// Returns the store at stats.json in the data directory of the platform,
+	/// such as ~/.local/share/guessing_game on Linux, or None if the platform
+	/// has no such directory.
+	pub fn in_data_dir() -> Option<Self>
+	{
+		directories::ProjectDirs::from("", "", "guessing_game").map(|dirs| StatsFile::new(dirs.data_dir().join("stats.json")))
+	}
+
+	/// Returns where the stats are kept.
+	pub fn path(&self) -> &Path
+	{
+		&self.path
+	}
+
+	/// Reads the stats, or returns fresh stats if there are none yet. If the file
+	/// can not be read or is corrupt, a warning is written to warnings and fresh
+	/// stats are returned.
+	pub fn load(
+		&self,
+		warnings: &mut impl Write,
+	) -> Stats
+	{
+		let error = match fs::read(&self.path)
+		{
+			Ok(bytes) => match serde_json::from_slice(&bytes)
+			{
+				Ok(stats) => return stats,
+				Err(error) => error.to_string(),
+			},
+			Err(error) if error.kind() == ErrorKind::NotFound => return Stats::default(),
+			Err(error) => error.to_string(),
+		};
+		let _ = writeln!(warnings, "Warning: starting with fresh stats, since {} could not be read ({error}).", self.path.display());
+		Stats::default()
+	}
+
+	/// Writes stats to the file, creating its directory if needed.
+	///
+	/// The stats are written to a temporary file next to it first, so a run that
+	/// is cut off while saving can not leave a half written file behind.
+	///
+	/// # Errors
+	/// Returns an error if the directory or the file can not be written.
+	pub fn save(
+		&self,
+		stats: &Stats,
+	) -> io::Result<()>
+	{
+		if let Some(directory) = self.path.parent()
+		{
+			fs::create_dir_all(directory)?;
+		}
+		let temporary = self.path.with_extension("json.tmp");
+		fs::write(&temporary, serde_json::to_vec(stats)?)?;
+		fs::rename(&temporary, &self.path)
+	}
+
+	/// Writes the stats in the file to output for the `stats` command, as the
+	/// lines of Stats::summarize, or as pretty JSON in the format of the file if
+	/// json is set. Problems reading the file are written to warnings, as for
+	/// StatsFile::load.
+	///
+	/// # Errors
+	/// Returns an error if writing to output fails.
+	pub fn show(
+		&self,
+		json: bool,
+		output: &mut impl Write,
+		warnings: &mut impl Write,
+	) -> io::Result<()>
+	{
+		let stats = self.load(warnings);
+		if json
+		{
+			serde_json::to_writer_pretty(&mut *output, &stats)?;
+			writeln!(output)
+		}
+		else
+		{
+			write!(output, "{}", stats.summarize())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::Guess,
+	};
+
+	fn won(attempts_used: u32) -> GameOutcome<{ 0..=1024 }>
+	{
+		GameOutcome::Won { attempts_used }
+	}
+
+	fn lost() -> GameOutcome<{ 0..=1024 }>
+	{
+		GameOutcome::Lost { answer: Guess::new(300).expect("answer failed to construct.") }
+	}
+
+	#[test]
+	fn streaks()
+	{
+		let timed_out = GameOutcome::TimedOut { answer: Guess::new(300).expect("answer failed to construct.") };
+		let outcomes = [won(3), won(5), lost(), won(4), GameOutcome::Aborted, won(2), won(6), timed_out];
+		let stats = outcomes.iter().fold(Stats::default(), Stats::record);
+		assert_eq!(stats, Stats { played: 7, wins: 5, losses: 2, current_streak: 0, best_streak: 3, attempts_on_wins: 20, best_times: BestTimes::default(), grades: Grades::default() });
+		assert_eq!(stats.average_attempts(), Some(4.0));
+
+		let stats = stats.record(&won(1));
+		assert_eq!((stats.current_streak, stats.best_streak), (1, 3));
+		assert_eq!(Stats::default().average_attempts(), None);
+		assert_eq!(Stats { current_streak: u32::MAX, ..Stats::default() }.record(&won(1)).best_streak, u32::MAX);
+	}
+
+	#[test]
+	fn personal_bests()
+	{
+		// The first run is a personal best.
+		let (stats, best) = Stats::default().record_time(Difficulty::Normal, Duration::from_millis(12300));
+		assert!(best);
+		assert_eq!(stats.best_times.get(Difficulty::Normal), Some(Duration::from_millis(12300)));
+		assert_eq!(stats.played, 0);
+
+		// A slower run, or one just as fast, leaves it alone.
+		assert_eq!(stats.record_time(Difficulty::Normal, Duration::from_secs(20)), (stats, false));
+		assert_eq!(stats.record_time(Difficulty::Normal, Duration::from_millis(12300)), (stats, false));
+
+		let (faster, best) = stats.record_time(Difficulty::Normal, Duration::from_millis(9800));
+		assert!(best);
+		assert_eq!(faster.best_times.get(Difficulty::Normal), Some(Duration::from_millis(9800)));
+
+		// Each difficulty has its own best.
+		let (both, best) = faster.record_time(Difficulty::Insane, Duration::from_secs(60));
+		assert!(best);
+		assert_eq!(both.best_times, BestTimes { normal_ms: Some(9800), insane_ms: Some(60000), ..BestTimes::default() });
+		assert_eq!(both.best_times.get(Difficulty::Easy), None);
+		assert!(both.summarize().ends_with("Best time on normal: 9.8s\nBest time on insane: 60.0s\n"));
+	}
+
+	#[test]
+	fn grades()
+	{
+		let stats = [Grade::A, Grade::C, Grade::A, Grade::F].into_iter().fold(Stats::default(), Stats::record_grade);
+		assert_eq!(stats.grades, Grades { a: 2, c: 1, f: 1, ..Grades::default() });
+		assert_eq!(Grade::ALL.map(|grade| stats.grades.get(grade)), [2, 0, 1, 0, 1]);
+		// Grades are kept next to the wins, not instead of them.
+		assert_eq!(stats.played, 0);
+		assert!(stats.summarize().ends_with("Average attempts on wins: -\nGrades: A 2, B 0, C 1, D 0, F 1\n"));
+	}
+
+	#[test]
+	fn summarize()
+	{
+		let stats = [won(3), won(5), lost()].iter().fold(Stats::default(), Stats::record);
+		assert_eq!(stats.summarize(), "Played: 3\nWon: 2 (67%)\nLost: 1\nCurrent streak: 0\nBest streak: 2\nAverage attempts on wins: 4.0\n");
+		assert!(Stats::default().summarize().contains("Won: 0 (0%)\n"));
+		assert!(Stats::default().summarize().ends_with("Average attempts on wins: -\n"));
+	}
+
+	#[cfg(feature = "stats")]
+	#[test]
+	fn file()
+	{
+		let directory = std::env::temp_dir().join(format!("guessing_game_stats_{}", std::process::id()));
+		let store = StatsFile::new(directory.join("nested").join("stats.json"));
+		let mut warnings = Vec::new();
+
+		// A missing file is a first run, not a problem.
+		assert_eq!(store.load(&mut warnings), Stats::default());
+		assert!(warnings.is_empty());
+
+		let stats = Stats::default().record(&won(3));
+		store.save(&stats).expect("save failed.");
+		assert_eq!(store.load(&mut warnings), stats);
+		assert!(warnings.is_empty());
+
+		// A corrupt file gives fresh stats and a warning, and is replaced on the
+		// next save.
+		fs::write(store.path(), "{\"played\": \"many\"").expect("write failed.");
+		assert_eq!(store.load(&mut warnings), Stats::default());
+		let warning = String::from_utf8(warnings).expect("warnings were not UTF-8.");
+		assert!(warning.starts_with("Warning: starting with fresh stats"), "{warning}");
+		store.save(&stats).expect("save failed.");
+		assert_eq!(store.load(&mut Vec::new()), stats);
+
+		let graded = stats.record_grade(Grade::B);
+		store.save(&graded).expect("save failed.");
+		assert_eq!(store.load(&mut Vec::new()), graded);
+
+		// Stats from before speedruns and grades have neither yet.
+		fs::write(store.path(), "{\"played\":1,\"wins\":1}").expect("write failed.");
+		assert_eq!(store.load(&mut Vec::new()), Stats { played: 1, wins: 1, ..Stats::default() });
+
+		fs::remove_dir_all(&directory).expect("cleanup failed.");
+	}
+
+	#[cfg(feature = "stats")]
+	#[test]
+	fn show()
+	{
+		let directory = std::env::temp_dir().join(format!("guessing_game_show_{}", std::process::id()));
+		let store = StatsFile::new(directory.join("stats.json"));
+		let shown = |json| {
+			let mut output = Vec::new();
+			let mut warnings = Vec::new();
+			store.show(json, &mut output, &mut warnings).expect("show failed.");
+			assert!(warnings.is_empty());
+			String::from_utf8(output).expect("output was not UTF-8.")
+		};
+
+		// Before the first game there is nothing to show but zeroes.
+		assert_eq!(shown(false), Stats::default().summarize());
+
+		let stats = Stats::default().record(&won(3)).record(&lost()).record_grade(Grade::A);
+		store.save(&stats).expect("save failed.");
+		assert_eq!(shown(false), stats.summarize());
+		let json = shown(true);
+		assert!(json.ends_with("}\n") && json.contains("\n  \"played\": 2,\n"), "{json}");
+		assert_eq!(serde_json::from_str::<Stats>(&json).expect("json was not Stats."), stats);
+
+		fs::remove_dir_all(&directory).expect("cleanup failed.");
+	}
+}
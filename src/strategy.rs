@@ -0,0 +1,293 @@
+use {
+	crate::{
+		Feedback,
+		Guess,
+		GuessRange,
+		KnowledgeState,
+	},
+	rand::Rng,
+	std::ops::RangeInclusive,
+};
+
+/// A way of picking guesses from the feedback on earlier ones, so that the
+/// computer can play a Game. See Game::play_with_strategy.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::{
+/// // 	BinarySearchStrategy,
+/// // 	Feedback,
+/// // 	Guess,
+/// // 	Strategy,
+/// //};
+/// // let mut strategy = BinarySearchStrategy::<{ 0..=100 }>::default();
+/// // let first = strategy.next_guess(&[]);
+/// // assert_eq!(first.value(), 50);
+/// // assert_eq!(strategy.next_guess(&[(first, Feedback::TooHigh)]).value(), 24);
+/// ```
+pub trait Strategy<const RANGE: RangeInclusive<i32>>
+{
+	/// Returns the next guess to make, given every guess so far with the
+	/// feedback it got, oldest first.
+	fn next_guess(
+		&mut self,
+		history: &[(Guess<RANGE>, Feedback)],
+	) -> Guess<RANGE>;
+}
+
+/// Guesses the middle of the values that are still possible, so each guess
+/// halves them. This never needs more than `span.ilog2() + 1` guesses.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct BinarySearchStrategy<const RANGE: RangeInclusive<i32>>
+{
+	range: GuessRange<RANGE>,
+}
+
+/// Guesses a random value among those that are still possible.
+#[derive(Debug, Clone)]
+pub struct RandomStrategy<R, const RANGE: RangeInclusive<i32>>
+{
+	range: GuessRange<RANGE>,
+	rng: R,
+}
+
+/// Guesses the smallest value that is still possible, counting up one at a
+/// time. This is the slowest strategy that never repeats itself.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct LinearStrategy<const RANGE: RangeInclusive<i32>>
+{
+	range: GuessRange<RANGE>,
+}
+
+/// A strategy picked by name, such as for the `simulate` command. See
+/// simulate_kind.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum StrategyKind
+{
+	/// BinarySearchStrategy.
+	#[default]
+	Binary,
+	/// LinearStrategy.
+	Linear,
+	/// RandomStrategy.
+	Random,
+}
+
+impl StrategyKind
+{
+	/// Every kind of strategy, from fastest to slowest.
+	pub const ALL: [StrategyKind; 3] = [StrategyKind::Binary, StrategyKind::Random, StrategyKind::Linear];
+
+	/// Returns the name of the kind in lowercase, as in `binary`.
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			StrategyKind::Binary => "binary",
+			StrategyKind::Linear => "linear",
+			StrategyKind::Random => "random",
+		}
+	}
+
+	/// Returns the kind called name, ignoring case and surrounding whitespace,
+	/// or None if there is none.
+	pub fn from_name(name: &str) -> Option<Self>
+	{
+		let name = name.trim().to_lowercase();
+		StrategyKind::ALL.into_iter().find(|kind| kind.name() == name)
+	}
+}
+
+/// Returns the values history leaves possible within range, or all of range if
+/// history contradicts itself.
+fn possible<const RANGE: RangeInclusive<i32>>(
+	range: GuessRange<RANGE>,
+	history: &[(Guess<RANGE>, Feedback)],
+) -> GuessRange<RANGE>
+{
+	KnowledgeState::from_history(range, history.iter().copied()).range().unwrap_or(range)
+}
+
+impl<const RANGE: RangeInclusive<i32>> BinarySearchStrategy<RANGE>
+{
+	/// Creates a strategy that searches range, such as Game::range for a game
+	/// with a difficulty.
+	pub const fn new(range: GuessRange<RANGE>) -> Self
+	{
+		BinarySearchStrategy { range }
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Default for BinarySearchStrategy<RANGE>
+{
+	fn default() -> Self
+	{
+		BinarySearchStrategy::new(GuessRange::full())
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Strategy<RANGE> for BinarySearchStrategy<RANGE>
+{
+	fn next_guess(
+		&mut self,
+		history: &[(Guess<RANGE>, Feedback)],
+	) -> Guess<RANGE>
+	{
+		possible(self.range, history).midpoint()
+	}
+}
+
+impl<R: Rng, const RANGE: RangeInclusive<i32>> RandomStrategy<R, RANGE>
+{
+	/// Creates a strategy that guesses within range, picking with rng.
+	pub const fn new(
+		range: GuessRange<RANGE>,
+		rng: R,
+	) -> Self
+	{
+		RandomStrategy { range, rng }
+	}
+}
+
+impl<R: Rng, const RANGE: RangeInclusive<i32>> Strategy<RANGE> for RandomStrategy<R, RANGE>
+{
+	fn next_guess(
+		&mut self,
+		history: &[(Guess<RANGE>, Feedback)],
+	) -> Guess<RANGE>
+	{
+		let possible = possible(self.range, history);
+		Guess { value: self.rng.gen_range(possible.lo().value()..=possible.hi().value()) }
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> LinearStrategy<RANGE>
+{
+	/// Creates a strategy that counts up through range.
+	pub const fn new(range: GuessRange<RANGE>) -> Self
+	{
+		LinearStrategy { range }
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Default for LinearStrategy<RANGE>
+{
+	fn default() -> Self
+	{
+		LinearStrategy::new(GuessRange::full())
+	}
+}
+
+impl<const RANGE: RangeInclusive<i32>> Strategy<RANGE> for LinearStrategy<RANGE>
+{
+	fn next_guess(
+		&mut self,
+		history: &[(Guess<RANGE>, Feedback)],
+	) -> Guess<RANGE>
+	{
+		possible(self.range, history).lo()
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			Difficulty,
+			Game,
+			GameOutcome,
+		},
+		rand::{
+			rngs::StdRng,
+			SeedableRng,
+		},
+	};
+
+	/// Plays a game with secret and as many attempts as it takes, returning how
+	/// many guesses strategy needed.
+	fn guesses_needed<const RANGE: RangeInclusive<i32>>(
+		secret: Guess<RANGE>,
+		strategy: &mut impl Strategy<RANGE>,
+	) -> u32
+	{
+		match Game::with_secret(u32::MAX, secret).play_with_strategy(strategy)
+		{
+			GameOutcome::Won { attempts_used } => attempts_used,
+			outcome => panic!("{outcome:?} with secret {secret}."),
+		}
+	}
+
+	/// Returns `ceil(log2(span)) + 1`, the most guesses a binary search may take.
+	fn binary_search_limit(span: u64) -> u32
+	{
+		span.next_power_of_two().ilog2() + 1
+	}
+
+	#[test]
+	fn binary_search()
+	{
+		let limit = binary_search_limit(Guess::<{ 0..=1024 }>::span());
+		for secret in Guess::<{ 0..=1024 }>::iter()
+		{
+			assert!(guesses_needed(secret, &mut BinarySearchStrategy::default()) <= limit, "secret {secret}.");
+		}
+
+		let limit = binary_search_limit(Guess::<{ -1000..=999 }>::span());
+		for secret in Guess::<{ -1000..=999 }>::iter()
+		{
+			assert!(guesses_needed(secret, &mut BinarySearchStrategy::default()) <= limit, "secret {secret}.");
+		}
+
+		// A game with a difficulty is searched within its own range.
+		let game = Game::with_difficulty_and_rng(Difficulty::Easy, &mut StdRng::seed_from_u64(3));
+		let limit = binary_search_limit(Difficulty::Easy.span());
+		for value in Difficulty::Easy.range()
+		{
+			let secret = Guess::new(value).expect("secret failed to construct.");
+			assert!(guesses_needed(secret, &mut BinarySearchStrategy::new(game.range())) <= limit, "secret {secret}.");
+		}
+	}
+
+	#[test]
+	fn linear()
+	{
+		let secret = Guess::<{ 1..=50 }>::new(37).expect("secret failed to construct.");
+		assert_eq!(guesses_needed(secret, &mut LinearStrategy::default()), 37);
+
+		let mut strategy = LinearStrategy::<{ 1..=50 }>::default();
+		let ten = Guess::new(10).expect("guess failed to construct.");
+		assert_eq!(strategy.next_guess(&[(ten, Feedback::TooLow)]).value(), 11);
+	}
+
+	#[test]
+	fn random()
+	{
+		let range = GuessRange::<{ 1..=1000 }>::full();
+		for seed in 0..20
+		{
+			let secret = Guess::new(seed * 50 + 1).expect("secret failed to construct.");
+			let first = guesses_needed(secret, &mut RandomStrategy::new(range, StdRng::seed_from_u64(seed as u64)));
+			let second = guesses_needed(secret, &mut RandomStrategy::new(range, StdRng::seed_from_u64(seed as u64)));
+			assert_eq!(first, second);
+		}
+
+		// Only values that are still possible are guessed.
+		let mut strategy = RandomStrategy::new(range, StdRng::seed_from_u64(0));
+		let history = [(Guess::new(400).expect("guess failed to construct."), Feedback::TooLow), (Guess::new(410).expect("guess failed to construct."), Feedback::TooHigh)];
+		for _ in 0..100
+		{
+			assert!((401..=409).contains(&strategy.next_guess(&history).value()));
+		}
+	}
+
+	#[test]
+	fn contradiction()
+	{
+		let mut strategy = BinarySearchStrategy::<{ 0..=100 }>::default();
+		let guess = Guess::new(10).expect("guess failed to construct.");
+		assert_eq!(strategy.next_guess(&[(guess, Feedback::TooHigh), (guess, Feedback::TooLow)]).value(), 50);
+	}
+}
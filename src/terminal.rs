@@ -0,0 +1,270 @@
+#[cfg(feature = "rustyline")]
+use {
+	rustyline::{
+		error::ReadlineError,
+		DefaultEditor,
+	},
+	std::{
+		cell::RefCell,
+		rc::Rc,
+	},
+};
+use {
+	crate::InputOutput,
+	std::io::{
+		self,
+		stdin,
+		BufRead,
+		IsTerminal,
+		Read,
+		Write,
+	},
+};
+
+/// Opens stdin for input, with line editing if it is a terminal.
+///
+/// With the rustyline feature enabled and stdin attached to a terminal, lines
+/// are read with rustyline, so the arrow keys, backspace and history work while
+/// typing a guess. Prompts written to the returned output are then shown by the
+/// line editor, and error messages still go to output. Otherwise, such as when
+/// input is piped, stdin is read as is and everything goes to output.
+///
+/// The pair works with every input function and goes through the same
+/// validation either way.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::{
+/// // 		input,
+/// // 		terminal_input,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let (mut lines, mut output) = terminal_input(stdout());
+/// // let guess = input::<{ 0..=100 }>("Guess: ".yellow(), &mut lines, &mut output);
+/// ```
+pub fn terminal_input<O: InputOutput>(output: O) -> (TerminalInput, TerminalOutput<O>)
+{
+	let terminal = stdin().is_terminal();
+	split(Box::new(stdin().lock()), terminal, output)
+}
+
+/// Shared implementation of terminal_input. Uses the line editor only if input
+/// is a terminal and it can be started, and plain otherwise.
+#[cfg_attr(not(feature = "rustyline"), expect(unused_variables))]
+fn split<O: InputOutput>(
+	plain: Box<dyn BufRead>,
+	terminal: bool,
+	output: O,
+) -> (TerminalInput, TerminalOutput<O>)
+{
+	#[cfg(feature = "rustyline")]
+	if terminal && let Ok(editor) = DefaultEditor::new()
+	{
+		let prompt = Rc::new(RefCell::new(Vec::new()));
+		let edited = Edited { editor, prompt: Rc::clone(&prompt), line: Vec::new(), position: 0 };
+		return (TerminalInput { source: Source::Edited(edited) }, TerminalOutput { prompt: Some(PromptBuffer(prompt)), output });
+	}
+	(
+		TerminalInput { source: Source::Plain(plain) },
+		TerminalOutput {
+			#[cfg(feature = "rustyline")]
+			prompt: None,
+			output,
+		},
+	)
+}
+
+/// Input returned by terminal_input. Reads lines from the line editor or from
+/// plain stdin.
+pub struct TerminalInput
+{
+	source: Source,
+}
+
+/// Where TerminalInput reads from.
+enum Source
+{
+	Plain(Box<dyn BufRead>),
+	#[cfg(feature = "rustyline")]
+	Edited(Edited),
+}
+
+impl Read for TerminalInput
+{
+	fn read(
+		&mut self,
+		buf: &mut [u8],
+	) -> io::Result<usize>
+	{
+		let available = self.fill_buf()?;
+		let len = available.len().min(buf.len());
+		buf[..len].copy_from_slice(&available[..len]);
+		self.consume(len);
+		Ok(len)
+	}
+}
+
+impl BufRead for TerminalInput
+{
+	fn fill_buf(&mut self) -> io::Result<&[u8]>
+	{
+		match &mut self.source
+		{
+			Source::Plain(input) => input.fill_buf(),
+			#[cfg(feature = "rustyline")]
+			Source::Edited(edited) => edited.fill_buf(),
+		}
+	}
+
+	fn consume(
+		&mut self,
+		amt: usize,
+	)
+	{
+		match &mut self.source
+		{
+			Source::Plain(input) => input.consume(amt),
+			#[cfg(feature = "rustyline")]
+			Source::Edited(edited) => edited.position = (edited.position + amt).min(edited.line.len()),
+		}
+	}
+}
+
+/// Output returned by terminal_input. Holds on to prompts for the line editor
+/// when there is one, and passes everything else through to output.
+pub struct TerminalOutput<O>
+{
+	#[cfg(feature = "rustyline")]
+	prompt: Option<PromptBuffer>,
+	output: O,
+}
+
+impl<O> TerminalOutput<O>
+{
+	/// Returns the output that error messages and, without a line editor,
+	/// prompts are written to.
+	pub fn into_inner(self) -> O
+	{
+		self.output
+	}
+}
+
+impl<O: InputOutput> InputOutput for TerminalOutput<O>
+{
+	fn prompts(&mut self) -> &mut dyn Write
+	{
+		#[cfg(feature = "rustyline")]
+		if let Some(prompt) = &mut self.prompt
+		{
+			return prompt;
+		}
+		self.output.prompts()
+	}
+
+	fn errors(&mut self) -> &mut dyn Write
+	{
+		self.output.errors()
+	}
+
+	fn messages(&mut self) -> &mut dyn Write
+	{
+		self.output.messages()
+	}
+}
+
+/// Collects a prompt until the line editor shows it.
+#[cfg(feature = "rustyline")]
+struct PromptBuffer(Rc<RefCell<Vec<u8>>>);
+
+#[cfg(feature = "rustyline")]
+impl Write for PromptBuffer
+{
+	fn write(
+		&mut self,
+		buf: &[u8],
+	) -> io::Result<usize>
+	{
+		self.0.borrow_mut().extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()>
+	{
+		Ok(())
+	}
+}
+
+/// Line editor and the rest of the line it last read.
+#[cfg(feature = "rustyline")]
+struct Edited
+{
+	editor: DefaultEditor,
+	prompt: Rc<RefCell<Vec<u8>>>,
+	line: Vec<u8>,
+	position: usize,
+}
+
+#[cfg(feature = "rustyline")]
+impl Edited
+{
+	/// Reads a new line with the collected prompt once the last one is used up.
+	fn fill_buf(&mut self) -> io::Result<&[u8]>
+	{
+		if self.position == self.line.len()
+		{
+			let prompt = std::mem::take(&mut *self.prompt.borrow_mut());
+			match self.editor.readline(&String::from_utf8_lossy(&prompt))
+			{
+				Ok(line) =>
+				{
+					// History is a convenience, so failing to add to it is not an error.
+					let _ = self.editor.add_history_entry(line.as_str());
+					self.line = line.into_bytes();
+					self.line.push(b'\n');
+				},
+				// Ctrl-D and Ctrl-C both end the input.
+				Err(ReadlineError::Eof | ReadlineError::Interrupted) => self.line.clear(),
+				Err(error) => return Err(io::Error::other(error)),
+			}
+			self.position = 0;
+		}
+		Ok(&self.line[self.position..])
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			input,
+			Guess,
+			WithErrors,
+		},
+		colored::Colorize,
+	};
+
+	/// Input that is not a terminal is read as is, with or without the line
+	/// editor feature.
+	#[test]
+	fn plain_when_not_terminal()
+	{
+		let output = WithErrors { output: Vec::new(), errors: Vec::new() };
+		let (mut lines, mut output) = split(Box::new(&b"abc\n42\nrest"[..]), false, output);
+		let guess: Guess<{ 0..=50 }> = input("dummy prompt: ".clear(), &mut lines, &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+
+		let mut rest = String::new();
+		lines.read_to_string(&mut rest).expect("read failed.");
+		assert_eq!(rest, "rest");
+
+		let output = output.into_inner();
+		assert_eq!(String::from_utf8_lossy(&output.output), "dummy prompt: ".repeat(2));
+		assert!(String::from_utf8_lossy(&output.errors).contains("\"abc\" is not an integer"));
+	}
+}
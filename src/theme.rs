@@ -0,0 +1,569 @@
+use {
+	crate::{
+		options::{
+			render_template,
+			restyle,
+			strip_emoji,
+		},
+		respond_to,
+		Feedback,
+		Guess,
+		Hints,
+		Style,
+		Win,
+	},
+	colored::{
+		ColoredString,
+		Colorize,
+	},
+	std::{
+		fmt::Display,
+		io::{
+			self,
+			Write,
+		},
+		ops::{
+			ControlFlow,
+			RangeInclusive,
+		},
+	},
+};
+
+/// Wording and colors of the messages that respond writes.
+///
+/// Each message is a template where {guess} is replaced with the guess. Write
+/// {{ or }} for a literal brace. The colors and styles of each template are the
+/// colors and styles of its message.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	colored::Colorize,
+/// // 	guessing_game::ResponseTheme,
+/// //};
+/// // let theme = ResponseTheme {
+/// // 	too_high: "{guess} is too big. Try a smaller number!".blue(),
+/// // 	too_low: "{guess} is too small. Try a bigger number!".blue(),
+/// // 	emoji: false,
+/// // 	..ResponseTheme::default()
+/// // };
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseTheme
+{
+	/// Shown when the guess is greater than the correct value.
+	pub too_high: ColoredString,
+	/// Shown when the guess is less than the correct value.
+	pub too_low: ColoredString,
+	/// Shown instead of too_high and too_low when hints is Hints::None.
+	pub wrong: ColoredString,
+	/// Shown when the guess is the correct value.
+	pub win: ColoredString,
+	/// Added to the win message when the first attempt wins.
+	pub lucky: ColoredString,
+	/// Added to the win message when the last attempt wins.
+	pub clutch: ColoredString,
+	/// Added to the message when a wrong guess is one away from the correct
+	/// value.
+	pub so_close: ColoredString,
+	/// How much a wrong guess is told. With Hints::None it gets wrong instead of
+	/// too_high or too_low, and never so_close. Heat is only added by
+	/// respond_with_heat, so Hints::Direction and Hints::Heat are the same here.
+	pub hints: Hints,
+	/// Whether emoji in the templates are shown. Otherwise they are left out.
+	pub emoji: bool,
+	/// Whether a win rings the terminal bell, so that a player who looked away
+	/// notices. Off by default.
+	pub bell: bool,
+	/// Whether a win is followed by the banner from win_banner(). Off by
+	/// default.
+	pub banner: bool,
+}
+
+/// How many characters wide each line of win_banner() is.
+pub const BANNER_WIDTH: usize = 40;
+
+/// Returns the lines of an ASCII-art banner with "YOU WIN" in the middle, each
+/// exactly BANNER_WIDTH characters wide.
+///
+/// # Examples
+///
+/// ```
+/// // use guessing_game::win_banner;
+/// // for line in win_banner()
+/// // {
+/// // 	println!("{line}");
+/// // }
+/// ```
+pub fn win_banner() -> Vec<String>
+{
+	let inner = BANNER_WIDTH - 2;
+	let border = format!("+{}+", "=".repeat(inner));
+	let blank = format!("|{}|", " ".repeat(inner));
+	let stars = format!("|{:^inner$}|", "*  *  *  *  *");
+	let text = format!("|{:^inner$}|", "Y O U   W I N");
+	vec![border.clone(), blank.clone(), stars.clone(), text, stars, blank, border]
+}
+
+impl Default for ResponseTheme
+{
+	fn default() -> Self
+	{
+		ResponseTheme {
+			too_high: "{guess} is too high! 🥵".magenta(),
+			too_low: "{guess} is too low! 🥶".cyan(),
+			wrong: "Nope, {guess} is not it. 🙅".red(),
+			win: "You win! 😊🏖".green().bold(),
+			lucky: "LUCKY! 🍀".bright_green().bold(),
+			clutch: "CLUTCH!".yellow().bold(),
+			so_close: "So close it hurts!".red(),
+			hints: Hints::default(),
+			emoji: true,
+			bell: false,
+			banner: false,
+		}
+	}
+}
+
+impl ResponseTheme
+{
+	/// Creates a theme with the default wording, but without colors or emoji, so
+	/// that every message is ASCII.
+	pub fn plain() -> Self
+	{
+		ResponseTheme {
+			too_high: "{guess} is too high!".normal(),
+			too_low: "{guess} is too low!".normal(),
+			wrong: "Nope, {guess} is not it.".normal(),
+			win: "You win!".normal(),
+			lucky: "LUCKY!".normal(),
+			clutch: "CLUTCH!".normal(),
+			so_close: "So close it hurts!".normal(),
+			hints: Hints::default(),
+			emoji: false,
+			bell: false,
+			banner: false,
+		}
+	}
+
+	/// Fills in the template for feedback about guess.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Feedback,
+	/// // 	ResponseTheme,
+	/// //};
+	/// // assert_eq!(&*ResponseTheme::plain().render(512, Feedback::TooHigh), "512 is too high!");
+	/// ```
+	pub fn render(
+		&self,
+		guess: impl Display,
+		feedback: Feedback,
+	) -> ColoredString
+	{
+		let template = match feedback
+		{
+			Feedback::TooHigh | Feedback::TooLow if self.hints == Hints::None => &self.wrong,
+			Feedback::TooHigh => &self.too_high,
+			Feedback::TooLow => &self.too_low,
+			Feedback::Correct => &self.win,
+		};
+		self.fill(template, guess)
+	}
+
+	/// Fills in the template for flourish about guess.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Flourish,
+	/// // 	ResponseTheme,
+	/// //};
+	/// // assert_eq!(&*ResponseTheme::plain().render_flourish(512, Flourish::Clutch), "CLUTCH!");
+	/// ```
+	pub fn render_flourish(
+		&self,
+		guess: impl Display,
+		flourish: Flourish,
+	) -> ColoredString
+	{
+		let template = match flourish
+		{
+			Flourish::Lucky => &self.lucky,
+			Flourish::Clutch => &self.clutch,
+			Flourish::SoClose => &self.so_close,
+		};
+		self.fill(template, guess)
+	}
+
+	/// Writes the banner and rings the bell after a win, if they are turned on.
+	pub(crate) fn write_celebration(
+		&self,
+		style: Style,
+		output: &mut impl Write,
+	) -> io::Result<()>
+	{
+		if self.banner
+		{
+			for line in win_banner()
+			{
+				writeln!(output, "{}", style.render(&line.green().bold()))?;
+			}
+		}
+		if self.bell
+		{
+			output.write_all(b"\x07")?;
+		}
+		Ok(())
+	}
+
+	/// Replaces {guess} in template, leaving out emoji unless they are shown.
+	fn fill(
+		&self,
+		template: &ColoredString,
+		guess: impl Display,
+	) -> ColoredString
+	{
+		let text = render_template(template, |name| (name == "guess").then(|| guess.to_string()));
+		if self.emoji
+		{
+			restyle(&text, template)
+		}
+		else
+		{
+			restyle(&strip_emoji(&text), template)
+		}
+	}
+}
+
+/// Respond to a user's input with console output worded by theme. Returns the
+/// correct action to take.
+///
+/// This is the same as respond, which uses ResponseTheme::default().
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_with_theme,
+/// // 		Guess,
+/// // 		ResponseTheme,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let example_guess = Guess::<{ 0..=100000 }>::new(42069_i32).expect("");
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // assert!(respond_with_theme(example_guess, correct_guess, &ResponseTheme::plain(), &mut stdout()).expect("").is_continue());
+/// ```
+pub fn respond_with_theme<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	theme: &ResponseTheme,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	respond_to(guess, guess.cmp(&correct), theme, Style::Fancy, output)
+}
+
+/// Which attempt a guess is, counting from 1, out of how many are allowed.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Attempt
+{
+	/// The number of the attempt, where the first attempt is 1.
+	pub number: u32,
+	/// How many attempts the player gets in total.
+	pub allowed: u32,
+}
+
+/// Something special about a guess that deserves an extra message.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Flourish
+{
+	/// The first attempt won.
+	Lucky,
+	/// The last attempt won.
+	Clutch,
+	/// The guess is wrong, but only one away from the correct value.
+	SoClose,
+}
+
+impl Flourish
+{
+	/// Returns what is special about guess as attempt, if anything. Winning on
+	/// the only attempt allowed is lucky rather than clutch.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// // use guessing_game::{
+	/// // 	Attempt,
+	/// // 	Flourish,
+	/// // 	Guess,
+	/// //};
+	/// // let correct = Guess::<{ 0..=100 }>::new(50).expect("");
+	/// // let attempt = Attempt { number: 1, allowed: 10 };
+	/// // assert_eq!(Flourish::of(correct, correct, attempt), Some(Flourish::Lucky));
+	/// ```
+	pub fn of<const RANGE: RangeInclusive<i32>>(
+		guess: Guess<RANGE>,
+		correct: Guess<RANGE>,
+		attempt: Attempt,
+	) -> Option<Self>
+	{
+		match guess.distance(correct)
+		{
+			0 if attempt.number == 1 => Some(Flourish::Lucky),
+			0 if attempt.number == attempt.allowed => Some(Flourish::Clutch),
+			1 => Some(Flourish::SoClose),
+			_ => None,
+		}
+	}
+}
+
+/// Respond to a user's input with console output worded by theme, adding a
+/// flourish for lucky, clutch and close guesses. Returns the correct action to
+/// take.
+///
+/// This is the same as respond_with_theme, except that the message is followed
+/// by the Flourish of guess as attempt, if it has one.
+///
+/// # Errors
+/// Returns an error if writing to output fails.
+///
+/// # Examples
+///
+/// ```
+/// // use {
+/// // 	guessing_game::{
+/// // 		respond_with_attempt,
+/// // 		Attempt,
+/// // 		Guess,
+/// // 		ResponseTheme,
+/// // 	},
+/// // 	std::io::stdout,
+/// //};
+/// // let correct_guess = Guess::<{ 0..=100000 }>::new(1660_i32).expect("");
+/// // let attempt = Attempt { number: 1, allowed: 10 };
+/// // assert!(respond_with_attempt(correct_guess, correct_guess, attempt, &ResponseTheme::default(), &mut stdout()).expect("").is_break());
+/// ```
+pub fn respond_with_attempt<const RANGE: RangeInclusive<i32>>(
+	guess: Guess<RANGE>,
+	correct: Guess<RANGE>,
+	attempt: Attempt,
+	theme: &ResponseTheme,
+	output: &mut impl Write,
+) -> io::Result<ControlFlow<Win<Guess<RANGE>>, Feedback>>
+{
+	let feedback = Feedback::from(guess.cmp(&correct));
+	let message = theme.render(guess, feedback);
+	// Being close is a hint too.
+	match Flourish::of(guess, correct, attempt).filter(|flourish| theme.hints != Hints::None || *flourish != Flourish::SoClose)
+	{
+		Some(flourish) => write!(output, "\n{message} {}\n", theme.render_flourish(guess, flourish)),
+		None => write!(output, "\n{message}\n"),
+	}?;
+	if feedback.is_correct()
+	{
+		theme.write_celebration(Style::Fancy, output)?;
+	}
+	Ok(feedback.into_control_flow(guess))
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::respond,
+	};
+
+	#[test]
+	fn custom_theme()
+	{
+		let theme = ResponseTheme {
+			too_high: "Too big! You said {guess}. 🐘".normal(),
+			too_low: "Too small! You said {guess}. 🐜".normal(),
+			wrong: "Not {guess}. 🙅".normal(),
+			win: "🎉 {guess} is right! 🎉 {{yay}}".normal(),
+			lucky: "Lucky {guess}! 🍀".normal(),
+			clutch: "Phew.".normal(),
+			so_close: "Argh.".normal(),
+			hints: Hints::Direction,
+			emoji: false,
+			bell: false,
+			banner: false,
+		};
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		let mut output = Vec::new();
+		for value in [45, 3, 40]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			let action = respond_with_theme(guess, correct, &theme, &mut output).expect("respond failed.");
+			assert_eq!(action.is_break(), guess == correct);
+		}
+		assert_eq!(
+			String::from_utf8_lossy(&output),
+			"\nToo big! You said 45.\n\nToo small! You said 3.\n\n40 is right! {yay}\n"
+		);
+
+		let theme = ResponseTheme { emoji: true, ..theme };
+		assert_eq!(&*theme.render(45, Feedback::TooHigh), "Too big! You said 45. 🐘");
+	}
+
+	#[test]
+	fn default_theme()
+	{
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		for value in [45, 3, 40]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			let (mut themed, mut plain) = (Vec::new(), Vec::new());
+			let action = respond_with_theme(guess, correct, &ResponseTheme::default(), &mut themed).expect("respond failed.");
+			assert_eq!(action, respond(guess, correct, &mut plain).expect("respond failed."));
+			assert_eq!(themed, plain);
+		}
+
+		let theme = ResponseTheme::default();
+		assert_eq!(theme.render(45, Feedback::TooHigh), "45 is too high! 🥵".magenta());
+		assert_eq!(theme.render(3, Feedback::TooLow), "3 is too low! 🥶".cyan());
+		assert_eq!(theme.render(40, Feedback::Correct), "You win! 😊🏖".green().bold());
+	}
+
+	#[test]
+	fn plain_theme()
+	{
+		let theme = ResponseTheme::plain();
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		let mut output = Vec::new();
+		for value in [45, 3, 40]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			let _ = respond_with_theme(guess, correct, &theme, &mut output).expect("respond failed.");
+		}
+		assert!(output.is_ascii());
+		assert!(!output.windows(2).any(|bytes| bytes == b"\x1b["));
+		assert_eq!(String::from_utf8_lossy(&output), "\n45 is too high!\n\n3 is too low!\n\nYou win!\n");
+	}
+
+	#[test]
+	fn flourish()
+	{
+		let guess = |value| Guess::<{ 0..=100 }>::new(value).expect("guess failed to construct.");
+		let correct = guess(50);
+		let respond = |value, number| {
+			let mut output = Vec::new();
+			let attempt = Attempt { number, allowed: 5 };
+			let action = respond_with_attempt(guess(value), correct, attempt, &ResponseTheme::plain(), &mut output).expect("respond failed.");
+			(action, String::from_utf8(output).expect("output was not UTF-8."))
+		};
+
+		// An ordinary win only gets the win message.
+		let (action, output) = respond(50, 3);
+		assert_eq!(action, ControlFlow::Break(Win { guess: correct }));
+		assert_eq!(output, "\nYou win!\n");
+
+		let (action, output) = respond(50, 1);
+		assert_eq!(action, ControlFlow::Break(Win { guess: correct }));
+		assert_eq!(output, "\nYou win! LUCKY!\n");
+
+		let (action, output) = respond(50, 5);
+		assert_eq!(action, ControlFlow::Break(Win { guess: correct }));
+		assert_eq!(output, "\nYou win! CLUTCH!\n");
+
+		let (action, output) = respond(51, 2);
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooHigh));
+		assert_eq!(output, "\n51 is too high! So close it hurts!\n");
+
+		let (action, output) = respond(49, 5);
+		assert_eq!(action, ControlFlow::Continue(Feedback::TooLow));
+		assert_eq!(output, "\n49 is too low! So close it hurts!\n");
+
+		// A wrong guess is only close if it is one away, even on the first or last attempt.
+		assert_eq!(respond(52, 1).1, "\n52 is too high!\n");
+		assert_eq!(respond(48, 5).1, "\n48 is too low!\n");
+
+		// The only attempt allowed is both the first and the last.
+		assert_eq!(Flourish::of(correct, correct, Attempt { number: 1, allowed: 1 }), Some(Flourish::Lucky));
+		assert_eq!(Flourish::of(guess(51), correct, Attempt { number: 1, allowed: 1 }), Some(Flourish::SoClose));
+	}
+
+	#[test]
+	fn no_hints()
+	{
+		let theme = ResponseTheme { hints: Hints::None, ..ResponseTheme::plain() };
+		let correct = Guess::<{ 0..=100 }>::new(50).expect("correct failed to construct.");
+		let mut output = Vec::new();
+		for (value, number) in [(90, 1), (10, 2), (51, 3), (49, 4), (50, 5)]
+		{
+			let guess = Guess::new(value).expect("guess failed to construct.");
+			let _ = respond_with_attempt(guess, correct, Attempt { number, allowed: 5 }, &theme, &mut output).expect("respond failed.");
+		}
+		let output = String::from_utf8(output).expect("output was not UTF-8.");
+		assert_eq!(output, "\nNope, 90 is not it.\n\nNope, 10 is not it.\n\nNope, 51 is not it.\n\nNope, 49 is not it.\n\nYou win! CLUTCH!\n");
+		assert_eq!(ResponseTheme { hints: Hints::None, ..ResponseTheme::default() }.render(90, Feedback::TooHigh), "Nope, 90 is not it. 🙅".red());
+	}
+
+	#[test]
+	fn flourish_theme()
+	{
+		let theme = ResponseTheme::default();
+		assert_eq!(theme.render_flourish(50, Flourish::Lucky), "LUCKY! 🍀".bright_green().bold());
+		assert_eq!(theme.render_flourish(50, Flourish::Clutch), "CLUTCH!".yellow().bold());
+		assert_eq!(theme.render_flourish(50, Flourish::SoClose), "So close it hurts!".red());
+
+		let theme = ResponseTheme { lucky: "{guess} on the first try! 🍀".normal(), emoji: false, ..ResponseTheme::plain() };
+		let correct = Guess::<{ 0..=100 }>::new(50).expect("correct failed to construct.");
+		let mut output = Vec::new();
+		let _ = respond_with_attempt(correct, correct, Attempt { number: 1, allowed: 5 }, &theme, &mut output).expect("respond failed.");
+		assert_eq!(String::from_utf8_lossy(&output), "\nYou win! 50 on the first try!\n");
+	}
+
+	#[test]
+	fn bell()
+	{
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		let wrong = Guess::<{ 0..=50 }>::new(41).expect("guess failed to construct.");
+		let rings = |theme: &ResponseTheme, guess| {
+			let mut output = Vec::new();
+			let _ = respond_with_theme(guess, correct, theme, &mut output).expect("respond failed.");
+			let _ = respond_with_attempt(guess, correct, Attempt { number: 2, allowed: 5 }, theme, &mut output).expect("respond failed.");
+			output.iter().filter(|&&byte| byte == b'\x07').count()
+		};
+		let theme = ResponseTheme { bell: true, ..ResponseTheme::plain() };
+		assert_eq!(rings(&theme, correct), 2);
+		assert_eq!(rings(&theme, wrong), 0);
+		assert_eq!(rings(&ResponseTheme::plain(), correct), 0);
+		assert_eq!(rings(&ResponseTheme::default(), correct), 0);
+	}
+
+	#[test]
+	fn banner()
+	{
+		let banner = win_banner();
+		assert!(banner.iter().all(|line| line.chars().count() == BANNER_WIDTH), "{banner:#?}");
+		let text = banner.iter().find(|line| line.contains("Y O U   W I N")).expect("banner has no text.");
+		let (left, right) = text.split_once("Y O U   W I N").expect("banner has no text.");
+		assert!(left.len().abs_diff(right.len()) <= 1, "{text:?} is not centered.");
+
+		let correct = Guess::<{ 0..=50 }>::new(40).expect("correct failed to construct.");
+		let theme = ResponseTheme { banner: true, ..ResponseTheme::plain() };
+		let mut output = Vec::new();
+		let _ = respond_with_theme(correct, correct, &theme, &mut output).expect("respond failed.");
+		assert_eq!(String::from_utf8_lossy(&output), format!("\nYou win!\n{}\n", banner.join("\n")));
+		assert!(!output.contains(&b'\x07'));
+
+		// Only a win gets a banner.
+		let mut output = Vec::new();
+		let guess = Guess::new(3).expect("guess failed to construct.");
+		let _ = respond_with_theme(guess, correct, &theme, &mut output).expect("respond failed.");
+		assert_eq!(String::from_utf8_lossy(&output), "\n3 is too low!\n");
+	}
+}
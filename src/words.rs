@@ -0,0 +1,209 @@
+/// Words for the numbers zero through nineteen.
+const UNITS: [&str; 20] = [
+	"zero",
+	"one",
+	"two",
+	"three",
+	"four",
+	"five",
+	"six",
+	"seven",
+	"eight",
+	"nine",
+	"ten",
+	"eleven",
+	"twelve",
+	"thirteen",
+	"fourteen",
+	"fifteen",
+	"sixteen",
+	"seventeen",
+	"eighteen",
+	"nineteen",
+];
+
+/// Words for twenty through ninety, starting with twenty.
+const TENS: [&str; 8] = ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+/// Words that multiply everything before them since the last larger one.
+const SCALES: [(&str, i128); 4] = [("thousand", 1_000), ("million", 1_000_000), ("billion", 1_000_000_000), ("trillion", 1_000_000_000_000)];
+
+/// One word of a spelled-out number.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Word
+{
+	Unit(i128),
+	Ten(i128),
+	Hundred,
+	Scale(i128),
+	And,
+}
+
+impl Word
+{
+	fn parse(word: &str) -> Option<Self>
+	{
+		if let Some(value) = UNITS.iter().position(|&unit| unit == word)
+		{
+			return Some(Word::Unit(value as i128));
+		}
+		if let Some(index) = TENS.iter().position(|&ten| ten == word)
+		{
+			return Some(Word::Ten(20 + 10 * index as i128));
+		}
+		match word
+		{
+			"hundred" => Some(Word::Hundred),
+			"and" => Some(Word::And),
+			_ => SCALES.iter().find(|(scale, _)| *scale == word).map(|&(_, value)| Word::Scale(value)),
+		}
+	}
+}
+
+/// Converts a number written in English words, as in `forty-two` or `one
+/// thousand and twenty four`, into its value. Case is ignored, and words are
+/// separated by whitespace or hyphens, optionally after a comma. The number may
+/// start with `negative` or `minus`.
+///
+/// Returns None unless the whole text is one well-formed number, so that
+/// phrases like `two forty`, `forty potato` or `one hundred and` are not
+/// guessed at.
+pub(crate) fn parse_words(text: &str) -> Option<i128>
+{
+	let text = text.to_lowercase();
+	let mut words = Vec::new();
+	for token in text.split_whitespace()
+	{
+		let token = token.strip_suffix(',').unwrap_or(token);
+		for word in token.split('-')
+		{
+			// A hyphen must join two words.
+			if word.is_empty()
+			{
+				return None;
+			}
+			words.push(word);
+		}
+	}
+	let negative = matches!(words.first(), Some(&("negative" | "minus")));
+	if negative
+	{
+		words.remove(0);
+	}
+	let words = words.into_iter().map(Word::parse).collect::<Option<Vec<_>>>()?;
+	let value = match words.as_slice()
+	{
+		[] => return None,
+		[Word::Unit(0)] => 0,
+		words => value_of(words)?,
+	};
+	Some(if negative { -value } else { value })
+}
+
+/// Adds up words that make a positive number, or returns None if they are not
+/// in an order that makes one.
+fn value_of(words: &[Word]) -> Option<i128>
+{
+	let mut total = 0_i128;
+	// Value below the last scale, such as 123 in "one hundred twenty three".
+	let mut group = 0_i128;
+	let mut last_scale = i128::MAX;
+	// What the group holds so far, so that "twenty forty" and the like are rejected.
+	let (mut hundreds, mut tens, mut ones) = (false, false, false);
+	for (index, &word) in words.iter().enumerate()
+	{
+		let previous = index.checked_sub(1).map(|index| words[index]);
+		match word
+		{
+			Word::Unit(0) => return None,
+			Word::Unit(value) if value < 10 && !ones => (group, ones) = (group + value, true),
+			Word::Unit(value) if value >= 10 && !tens && !ones => (group, tens, ones) = (group + value, true, true),
+			Word::Ten(value) if !tens && !ones => (group, tens) = (group + value, true),
+			Word::Hundred if !hundreds && !tens && ones && group < 10 => (group, hundreds, ones) = (group * 100, true, false),
+			Word::Scale(scale) if group > 0 && scale < last_scale =>
+			{
+				total += group * scale;
+				(group, last_scale) = (0, scale);
+				(hundreds, tens, ones) = (false, false, false);
+			},
+			Word::And if matches!(previous, Some(Word::Hundred | Word::Scale(_)))
+				&& matches!(words.get(index + 1), Some(Word::Unit(_) | Word::Ten(_))) => (),
+			_ => return None,
+		}
+	}
+	Some(total + group)
+}
+
+#[cfg(test)]
+mod tests
+{
+	use {
+		super::*,
+		crate::{
+			input,
+			Guess,
+			ParseGuessError,
+		},
+		colored::Colorize,
+	};
+
+	#[test]
+	fn parse_words_test()
+	{
+		assert_eq!(parse_words("forty-two"), Some(42));
+		assert_eq!(parse_words("Forty Two"), Some(42));
+		assert_eq!(parse_words("one thousand and twenty four"), Some(1024));
+		assert_eq!(parse_words("one thousand twenty-four"), Some(1024));
+		assert_eq!(parse_words("negative five"), Some(-5));
+		assert_eq!(parse_words("minus five"), Some(-5));
+		assert_eq!(parse_words("zero"), Some(0));
+		assert_eq!(parse_words("nineteen"), Some(19));
+		assert_eq!(parse_words("three hundred"), Some(300));
+		assert_eq!(parse_words("nine hundred and ninety nine"), Some(999));
+		assert_eq!(parse_words("two million, three hundred thousand and seven"), Some(2_300_007));
+		assert_eq!(parse_words("one trillion"), Some(1_000_000_000_000));
+	}
+
+	#[test]
+	fn parse_words_rejected()
+	{
+		for text in [
+			"",
+			"forty potato",
+			"potato",
+			"negative",
+			"two forty",
+			"forty twenty",
+			"twelve three",
+			"five five",
+			"one hundred and",
+			"and one",
+			"hundred",
+			"ten hundred",
+			"one thousand thousand",
+			"one thousand one million",
+			"zero zero",
+			"negative negative five",
+			"forty two 42",
+			"fifty-",
+			"forty--two",
+		]
+		{
+			assert_eq!(parse_words(text), None, "{text:?} was accepted.");
+		}
+	}
+
+	#[test]
+	fn input_words()
+	{
+		let mut lines = "forty potato\nforty-two\n".as_bytes();
+		let mut output = Vec::new();
+		let guess: Guess<{ -100..=100 }> = input("dummy prompt: ".normal(), &mut lines, &mut output).expect("input failed.");
+		assert_eq!(guess.value(), 42_i32);
+		assert!(String::from_utf8_lossy(&output).contains("\"forty potato\" is not an integer"));
+
+		assert_eq!(Guess::<{ -100..=100 }>::parse_lenient("negative five").map(i32::from), Ok(-5_i32));
+		assert!(matches!(Guess::<{ 0..=100 }>::parse_lenient("one thousand"), Err(ParseGuessError::OutOfRange(_))));
+		assert_eq!(Guess::<{ 0..=100 }>::parse_lenient("fifty-"), Err(ParseGuessError::NotANumber));
+	}
+}